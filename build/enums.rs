@@ -156,6 +156,7 @@ pub fn main() -> anyhow::Result<()> {
         "xkb_state_component",
     )?;
     write_ty(&mut f, xkbcommon::XKB_KEY_DIRECTION, "xkb_key_direction")?;
+    write_ty(&mut f, xkbcommon::XKB_KEYSYM_FLAGS, "xkb_keysym_flags")?;
 
     Ok(())
 }
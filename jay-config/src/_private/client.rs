@@ -11,8 +11,8 @@ use {
         },
         exec::Command,
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::AccelProfile, capability::Capability, pointer::PointerTrigger,
+            BindingMode, FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent,
         },
         keyboard::{
             mods::{Modifiers, RELEASE},
@@ -21,13 +21,15 @@ use {
         },
         logging::LogLevel,
         tasks::{JoinHandle, JoinSlot},
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{colors::Colorable, sized::Resizable, Color, ColorScheme},
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
-            Connector, DrmDevice, GfxApi, Mode, Transform,
+            Connector, DrmDevice, GfxApi, Mode, Transform, VrrMode,
         },
-        Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        window::{Window, WindowRule, WindowType},
+        xwayland::XScalingMode,
+        Axis, Direction, ModifiedKeySym, PciId, ResizeAmount, Workspace,
     },
     bincode::Options,
     futures_util::task::ArcWake,
@@ -58,6 +60,8 @@ fn cb<T, F: FnMut(T) + 'static>(f: F) -> Callback<T> {
     Rc::new(RefCell::new(f))
 }
 
+type IpcCommandHandler = Rc<RefCell<dyn FnMut(&str) -> Result<(), String>>>;
+
 fn run_cb<T>(name: &str, cb: &Callback<T>, t: T) {
     match cb.try_borrow_mut() {
         Ok(mut cb) => ignore_panic(name, || cb(t)),
@@ -78,12 +82,27 @@ struct KeyHandler {
     latched: Vec<Box<dyn FnOnce()>>,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct ActiveBindingMode {
+    mode: BindingMode,
+    once: bool,
+}
+
 pub(crate) struct Client {
     configure: extern "C" fn(),
     srv_data: *const u8,
     srv_unref: unsafe extern "C" fn(data: *const u8),
     srv_handler: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     key_handlers: RefCell<HashMap<(Seat, ModifiedKeySym), KeyHandler>>,
+    binds: RefCell<
+        HashMap<(Seat, Option<BindingMode>), HashMap<ModifiedKeySym, (Modifiers, Callback)>>,
+    >,
+    pointer_handlers: RefCell<HashMap<(Seat, Modifiers, PointerTrigger), Callback>>,
+    pointer_binds: RefCell<
+        HashMap<(Seat, Option<BindingMode>), HashMap<(Modifiers, PointerTrigger), Callback>>,
+    >,
+    binding_modes: RefCell<Vec<Rc<String>>>,
+    active_binding_modes: RefCell<HashMap<Seat, ActiveBindingMode>>,
     timer_handlers: RefCell<HashMap<Timer, Callback>>,
     response: RefCell<Vec<Response>>,
     on_new_seat: RefCell<Option<Callback<Seat>>>,
@@ -98,6 +117,10 @@ pub(crate) struct Client {
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_new_window: RefCell<Option<Callback<Window>>>,
+    on_window_title_changed: RefCell<Option<Callback<Window>>>,
+    on_window_closed: RefCell<Option<Callback<Window>>>,
+    on_ipc_command: RefCell<Option<IpcCommandHandler>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
     read_interests: RefCell<HashMap<PollableId, Interest>>,
@@ -214,6 +237,11 @@ pub unsafe extern "C" fn init(
         srv_unref,
         srv_handler,
         key_handlers: Default::default(),
+        binds: Default::default(),
+        pointer_handlers: Default::default(),
+        pointer_binds: Default::default(),
+        binding_modes: Default::default(),
+        active_binding_modes: Default::default(),
         timer_handlers: Default::default(),
         response: Default::default(),
         on_new_seat: Default::default(),
@@ -228,6 +256,10 @@ pub unsafe extern "C" fn init(
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
         on_switch_event: Default::default(),
+        on_new_window: Default::default(),
+        on_window_title_changed: Default::default(),
+        on_window_closed: Default::default(),
+        on_ipc_command: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
         read_interests: Default::default(),
@@ -330,8 +362,17 @@ impl Client {
         self.send(&ClientMessage::Move { seat, direction });
     }
 
-    pub fn unbind<T: Into<ModifiedKeySym>>(&self, seat: Seat, mod_sym: T) {
-        let mod_sym = mod_sym.into();
+    pub fn unbind(&self, seat: Seat, mode: Option<BindingMode>, mod_sym: ModifiedKeySym) {
+        let removed = match self.binds.borrow_mut().get_mut(&(seat, mode)) {
+            Some(binds) => binds.remove(&mod_sym).is_some(),
+            None => false,
+        };
+        if removed && self.seat_binding_mode(seat) == mode {
+            self.unregister_shortcut(seat, mod_sym);
+        }
+    }
+
+    fn unregister_shortcut(&self, seat: Seat, mod_sym: ModifiedKeySym) {
         if let Entry::Occupied(mut oe) = self.key_handlers.borrow_mut().entry((seat, mod_sym)) {
             oe.get_mut().cb = None;
             if oe.get().latched.is_empty() {
@@ -581,6 +622,37 @@ impl Client {
         self.send(&ClientMessage::FocusParent { seat });
     }
 
+    pub fn focus_last(&self, seat: Seat) {
+        self.send(&ClientMessage::FocusLast { seat });
+    }
+
+    pub fn focus_next_mru(&self, seat: Seat) {
+        self.send(&ClientMessage::FocusNextMru { seat });
+    }
+
+    pub fn focus_prev_mru(&self, seat: Seat) {
+        self.send(&ClientMessage::FocusPrevMru { seat });
+    }
+
+    pub fn show_adjacent_workspace(&self, seat: Seat, next: bool, global: bool) {
+        self.send(&ClientMessage::ShowAdjacentWorkspace { seat, next, global });
+    }
+
+    pub fn workspace_back_and_forth(&self, seat: Seat) {
+        self.send(&ClientMessage::WorkspaceBackAndForth { seat });
+    }
+
+    pub fn rename_workspace(&self, workspace: Workspace, name: &str) {
+        self.send(&ClientMessage::RenameWorkspace { workspace, name });
+    }
+
+    pub fn set_workspace_connector(&self, workspace: Workspace, connector: Option<Connector>) {
+        self.send(&ClientMessage::SetWorkspaceConnector {
+            workspace,
+            connector,
+        });
+    }
+
     pub fn get_seat(&self, name: &str) -> Seat {
         let res = self.send_with_response(&ClientMessage::GetSeat { name });
         get_response!(res, Seat(0), GetSeat { seat });
@@ -649,6 +721,211 @@ impl Client {
         self.send(&ClientMessage::ConnectorSetEnabled { connector, enabled });
     }
 
+    pub fn connector_set_tearing_enabled(&self, connector: Connector, enabled: bool) {
+        self.send(&ClientMessage::ConnectorSetTearingEnabled { connector, enabled });
+    }
+
+    pub fn connector_vrr_capable(&self, connector: Connector) -> bool {
+        let res = self.send_with_response(&ClientMessage::ConnectorVrrCapable { connector });
+        get_response!(res, false, ConnectorVrrCapable { capable });
+        capable
+    }
+
+    pub fn connector_set_vrr_mode(&self, connector: Connector, mode: VrrMode) {
+        self.send(&ClientMessage::ConnectorSetVrrMode { connector, mode });
+    }
+
+    pub fn connector_set_color_temperature(&self, connector: Connector, kelvin: u32) {
+        self.send(&ClientMessage::ConnectorSetColorTemperature { connector, kelvin });
+    }
+
+    pub fn add_window_rule(&self, rule: &WindowRule) {
+        self.send(&ClientMessage::AddWindowRule {
+            rule: rule.data.clone(),
+        });
+    }
+
+    pub fn clear_window_rules(&self) {
+        self.send(&ClientMessage::ClearWindowRules);
+    }
+
+    pub fn windows(&self) -> Vec<Window> {
+        let res = self.send_with_response(&ClientMessage::GetWindows);
+        get_response!(res, vec![], GetWindows { windows });
+        windows
+    }
+
+    pub fn seat_focused_window(&self, seat: Seat) -> Window {
+        let res = self.send_with_response(&ClientMessage::SeatFocusedWindow { seat });
+        get_response!(res, Window(0), GetWindow { window });
+        window
+    }
+
+    pub fn window_exists(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::WindowExists { window });
+        get_response!(res, false, WindowExists { exists });
+        exists
+    }
+
+    pub fn window_title(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowTitle { window });
+        get_response!(res, String::new(), GetWindowTitle { title });
+        title
+    }
+
+    pub fn window_app_id(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowAppId { window });
+        get_response!(res, String::new(), GetWindowAppId { app_id });
+        app_id
+    }
+
+    pub fn window_class(&self, window: Window) -> String {
+        let res = self.send_with_response(&ClientMessage::GetWindowClass { window });
+        get_response!(res, String::new(), GetWindowClass { class });
+        class
+    }
+
+    pub fn window_pid(&self, window: Window) -> Option<i32> {
+        let res = self.send_with_response(&ClientMessage::GetWindowPid { window });
+        get_response!(res, None, GetWindowPid { pid });
+        pid
+    }
+
+    pub fn window_type(&self, window: Window) -> Option<WindowType> {
+        let res = self.send_with_response(&ClientMessage::GetWindowType { window });
+        get_response!(res, None, GetWindowType { ty });
+        ty
+    }
+
+    pub fn window_workspace(&self, window: Window) -> Workspace {
+        let res = self.send_with_response(&ClientMessage::GetWindowWorkspace { window });
+        get_response!(res, Workspace(0), GetWorkspace { workspace });
+        workspace
+    }
+
+    pub fn focus_window(&self, window: Window, seat: Seat) {
+        self.send(&ClientMessage::FocusWindow { window, seat });
+    }
+
+    pub fn close_window(&self, window: Window) {
+        self.send(&ClientMessage::CloseWindow { window });
+    }
+
+    pub fn window_floating(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowFloating { window });
+        get_response!(res, false, GetFloating { floating });
+        floating
+    }
+
+    pub fn set_window_floating(&self, window: Window, floating: bool) {
+        self.send(&ClientMessage::SetWindowFloating { window, floating });
+    }
+
+    pub fn move_window_to_workspace(&self, window: Window, workspace: Workspace) {
+        self.send(&ClientMessage::MoveWindowToWorkspace { window, workspace });
+    }
+
+    pub fn window_fullscreen(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowFullscreen { window });
+        get_response!(res, false, GetFullscreen { fullscreen });
+        fullscreen
+    }
+
+    pub fn set_window_fullscreen(&self, window: Window, fullscreen: bool) {
+        self.send(&ClientMessage::SetWindowFullscreen { window, fullscreen });
+    }
+
+    pub fn set_smart_gaps(&self, enabled: bool) {
+        self.send(&ClientMessage::SetSmartGaps { enabled });
+    }
+
+    pub fn get_smart_gaps(&self) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetSmartGaps);
+        get_response!(res, false, GetSmartGaps { enabled });
+        enabled
+    }
+
+    pub fn set_color_scheme(&self, scheme: ColorScheme) {
+        self.send(&ClientMessage::SetColorScheme { scheme });
+    }
+
+    pub fn get_color_scheme(&self) -> ColorScheme {
+        let res = self.send_with_response(&ClientMessage::GetColorScheme);
+        get_response!(res, ColorScheme::NoPreference, GetColorScheme { scheme });
+        scheme
+    }
+
+    pub fn set_workspace_inner_gap(&self, workspace: Workspace, gap: Option<i32>) {
+        self.send(&ClientMessage::SetWorkspaceInnerGap { workspace, gap });
+    }
+
+    pub fn set_workspace_outer_gap(&self, workspace: Workspace, gap: Option<i32>) {
+        self.send(&ClientMessage::SetWorkspaceOuterGap { workspace, gap });
+    }
+
+    pub fn set_workspace_smart_gaps(&self, workspace: Workspace, enabled: Option<bool>) {
+        self.send(&ClientMessage::SetWorkspaceSmartGaps { workspace, enabled });
+    }
+
+    pub fn seat_move_to_scratchpad(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatMoveToScratchpad { seat });
+    }
+
+    pub fn seat_toggle_scratchpad(&self, seat: Seat, app_id: Option<&str>) {
+        self.send(&ClientMessage::SeatToggleScratchpad { seat, app_id });
+    }
+
+    pub fn set_x_scaling_mode(&self, mode: XScalingMode) {
+        self.send(&ClientMessage::SetXScalingMode { mode });
+    }
+
+    pub fn window_minimized(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowMinimized { window });
+        get_response!(res, false, GetMinimized { minimized });
+        minimized
+    }
+
+    pub fn set_window_minimized(&self, window: Window, minimized: bool) {
+        self.send(&ClientMessage::SetWindowMinimized { window, minimized });
+    }
+
+    pub fn workspace_minimized_windows(&self, workspace: Workspace) -> Vec<Window> {
+        let res =
+            self.send_with_response(&ClientMessage::GetWorkspaceMinimizedWindows { workspace });
+        get_response!(res, vec![], GetWindows { windows });
+        windows
+    }
+
+    pub fn seat_minimize(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatMinimize { seat });
+    }
+
+    pub fn seat_restore_minimized(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatRestoreMinimized { seat });
+    }
+
+    pub fn seat_resize(&self, seat: Seat, direction: Direction, amount: ResizeAmount) {
+        self.send(&ClientMessage::SeatResize {
+            seat,
+            direction,
+            amount,
+        });
+    }
+
+    pub fn seat_move_floating(&self, seat: Seat, dx: i32, dy: i32) {
+        self.send(&ClientMessage::SeatMoveFloating { seat, dx, dy });
+    }
+
+    pub fn seat_set_floating_geometry(&self, seat: Seat, x: i32, y: i32, width: i32, height: i32) {
+        self.send(&ClientMessage::SeatSetFloatingGeometry {
+            seat,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
     pub fn connector_set_transform(&self, connector: Connector, transform: Transform) {
         self.send(&ClientMessage::ConnectorSetTransform {
             connector,
@@ -830,6 +1107,22 @@ impl Client {
         *self.on_connector_disconnected.borrow_mut() = Some(cb(f));
     }
 
+    pub fn on_new_window<F: FnMut(Window) + 'static>(&self, f: F) {
+        *self.on_new_window.borrow_mut() = Some(cb(f));
+    }
+
+    pub fn on_window_title_changed<F: FnMut(Window) + 'static>(&self, f: F) {
+        *self.on_window_title_changed.borrow_mut() = Some(cb(f));
+    }
+
+    pub fn on_window_closed<F: FnMut(Window) + 'static>(&self, f: F) {
+        *self.on_window_closed.borrow_mut() = Some(cb(f));
+    }
+
+    pub fn on_ipc_command<F: FnMut(&str) -> Result<(), String> + 'static>(&self, f: F) {
+        *self.on_ipc_command.borrow_mut() = Some(Rc::new(RefCell::new(f)));
+    }
+
     pub fn on_graphics_initialized<F: FnOnce() + 'static>(&self, f: F) {
         self.on_graphics_initialized.set(Some(Box::new(f)));
     }
@@ -1019,14 +1312,32 @@ impl Client {
     pub fn bind_masked<F: FnMut() + 'static>(
         &self,
         seat: Seat,
+        mode: Option<BindingMode>,
         mut mod_mask: Modifiers,
         mod_sym: ModifiedKeySym,
         mut f: F,
     ) {
         mod_mask |= mod_sym.mods | RELEASE;
+        let cb = cb(move |_| f());
+        self.binds
+            .borrow_mut()
+            .entry((seat, mode))
+            .or_default()
+            .insert(mod_sym, (mod_mask, cb.clone()));
+        if self.seat_binding_mode(seat) == mode {
+            self.register_shortcut(seat, mod_mask, mod_sym, cb);
+        }
+    }
+
+    fn register_shortcut(
+        &self,
+        seat: Seat,
+        mod_mask: Modifiers,
+        mod_sym: ModifiedKeySym,
+        cb: Callback,
+    ) {
         let register = {
             let mut kh = self.key_handlers.borrow_mut();
-            let cb = cb(move |_| f());
             match kh.entry((seat, mod_sym)) {
                 Entry::Occupied(mut o) => {
                     let o = o.get_mut();
@@ -1068,6 +1379,182 @@ impl Client {
         }
     }
 
+    pub fn bind_pointer<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        mode: Option<BindingMode>,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+        mut f: F,
+    ) {
+        let cb = cb(move |_| f());
+        self.pointer_binds
+            .borrow_mut()
+            .entry((seat, mode))
+            .or_default()
+            .insert((mods, trigger), cb.clone());
+        if self.seat_binding_mode(seat) == mode {
+            self.register_pointer_shortcut(seat, mods, trigger, cb);
+        }
+    }
+
+    pub fn unbind_pointer(
+        &self,
+        seat: Seat,
+        mode: Option<BindingMode>,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    ) {
+        let removed = match self.pointer_binds.borrow_mut().get_mut(&(seat, mode)) {
+            Some(binds) => binds.remove(&(mods, trigger)).is_some(),
+            None => false,
+        };
+        if removed && self.seat_binding_mode(seat) == mode {
+            self.unregister_pointer_shortcut(seat, mods, trigger);
+        }
+    }
+
+    fn register_pointer_shortcut(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+        cb: Callback,
+    ) {
+        let prev = self
+            .pointer_handlers
+            .borrow_mut()
+            .insert((seat, mods, trigger), cb);
+        if prev.is_none() {
+            self.send(&ClientMessage::AddPointerShortcut {
+                seat,
+                mods,
+                trigger,
+            });
+        }
+    }
+
+    fn unregister_pointer_shortcut(&self, seat: Seat, mods: Modifiers, trigger: PointerTrigger) {
+        let prev = self
+            .pointer_handlers
+            .borrow_mut()
+            .remove(&(seat, mods, trigger));
+        if prev.is_some() {
+            self.send(&ClientMessage::RemovePointerShortcut {
+                seat,
+                mods,
+                trigger,
+            });
+        }
+    }
+
+    pub fn get_binding_mode(&self, name: &str) -> BindingMode {
+        let mut modes = self.binding_modes.borrow_mut();
+        let idx = match modes.iter().position(|m| **m == name) {
+            Some(idx) => idx,
+            None => {
+                modes.push(Rc::new(name.to_string()));
+                modes.len() - 1
+            }
+        };
+        BindingMode(idx as u64)
+    }
+
+    pub fn binding_mode_name(&self, mode: BindingMode) -> String {
+        match self.binding_modes.borrow().get(mode.0 as usize) {
+            Some(name) => name.to_string(),
+            None => String::new(),
+        }
+    }
+
+    pub fn seat_binding_mode(&self, seat: Seat) -> Option<BindingMode> {
+        self.active_binding_modes
+            .borrow()
+            .get(&seat)
+            .map(|m| m.mode)
+    }
+
+    pub fn set_binding_mode(&self, seat: Seat, mode: Option<BindingMode>, once: bool) {
+        let name = match mode {
+            None => None,
+            Some(mode) => match self.binding_modes.borrow().get(mode.0 as usize) {
+                Some(name) => Some(name.clone()),
+                None => {
+                    log::error!("Binding mode {} does not exist", mode.0);
+                    return;
+                }
+            },
+        };
+        let old = {
+            let mut active = self.active_binding_modes.borrow_mut();
+            let old = active.get(&seat).map(|m| m.mode);
+            match mode {
+                Some(mode) => active.insert(seat, ActiveBindingMode { mode, once }),
+                None => active.remove(&seat),
+            };
+            old
+        };
+        if old == mode {
+            self.send(&ClientMessage::SetBindingMode {
+                seat,
+                name: name.as_deref().map(|n| n.as_str()),
+                once,
+            });
+            return;
+        }
+        let (removed, added) = {
+            let binds = self.binds.borrow();
+            let new = binds.get(&(seat, mode));
+            let removed: Vec<_> = binds
+                .get(&(seat, old))
+                .into_iter()
+                .flat_map(|b| b.keys())
+                .filter(|k| new.map(|n| !n.contains_key(k)).unwrap_or(true))
+                .copied()
+                .collect();
+            let added: Vec<_> = new
+                .into_iter()
+                .flat_map(|b| b.iter())
+                .map(|(k, (mask, cb))| (*k, *mask, cb.clone()))
+                .collect();
+            (removed, added)
+        };
+        for mod_sym in removed {
+            self.unregister_shortcut(seat, mod_sym);
+        }
+        for (mod_sym, mod_mask, cb) in added {
+            self.register_shortcut(seat, mod_mask, mod_sym, cb);
+        }
+        let (removed, added) = {
+            let binds = self.pointer_binds.borrow();
+            let new = binds.get(&(seat, mode));
+            let removed: Vec<_> = binds
+                .get(&(seat, old))
+                .into_iter()
+                .flat_map(|b| b.keys())
+                .filter(|k| new.map(|n| !n.contains_key(k)).unwrap_or(true))
+                .copied()
+                .collect();
+            let added: Vec<_> = new
+                .into_iter()
+                .flat_map(|b| b.iter())
+                .map(|(k, cb)| (*k, cb.clone()))
+                .collect();
+            (removed, added)
+        };
+        for (mods, trigger) in removed {
+            self.unregister_pointer_shortcut(seat, mods, trigger);
+        }
+        for ((mods, trigger), cb) in added {
+            self.register_pointer_shortcut(seat, mods, trigger, cb);
+        }
+        self.send(&ClientMessage::SetBindingMode {
+            seat,
+            name: name.as_deref().map(|n| n.as_str()),
+            once,
+        });
+    }
+
     pub fn log(&self, level: LogLevel, msg: &str, file: Option<&str>, line: Option<u32>) {
         self.send(&ClientMessage::Log {
             level,
@@ -1206,6 +1693,27 @@ impl Client {
         self.tasks.tasks.borrow_mut().remove(&id);
     }
 
+    fn run_shortcut(&self, seat: Seat, handler: &Callback) {
+        let mode = self.active_binding_modes.borrow().get(&seat).copied();
+        run_cb("shortcut", handler, ());
+        if let Some(mode) = mode {
+            if mode.once && self.active_binding_modes.borrow().get(&seat) == Some(&mode) {
+                self.set_binding_mode(seat, None, false);
+            }
+        }
+    }
+
+    fn handle_invoke_pointer_shortcut(&self, seat: Seat, mods: Modifiers, trigger: PointerTrigger) {
+        let handler = self
+            .pointer_handlers
+            .borrow()
+            .get(&(seat, mods, trigger))
+            .cloned();
+        if let Some(handler) = handler {
+            self.run_shortcut(seat, &handler);
+        }
+    }
+
     fn handle_invoke_shortcut(
         &self,
         seat: Seat,
@@ -1237,7 +1745,7 @@ impl Client {
             ignore_panic("latch", latched);
         }
         if let Some(handler) = handler {
-            run_cb("shortcut", &handler, ());
+            self.run_shortcut(seat, &handler);
         }
         self.pressed_keysym.set(None);
         if was_latched {
@@ -1393,9 +1901,66 @@ impl Client {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::NewWindow { window } => {
+                let handler = self.on_new_window.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("new window", &handler, window);
+                }
+            }
+            ServerMessage::WindowTitleChanged { window } => {
+                let handler = self.on_window_title_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("window title changed", &handler, window);
+                }
+            }
+            ServerMessage::WindowClosed { window } => {
+                let handler = self.on_window_closed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("window closed", &handler, window);
+                }
+            }
+            ServerMessage::InvokePointerShortcut {
+                seat,
+                mods,
+                trigger,
+            } => {
+                self.handle_invoke_pointer_shortcut(seat, mods, trigger);
+            }
+            ServerMessage::IpcCommand { command } => {
+                self.handle_ipc_command(&command);
+            }
+            ServerMessage::BindingModeCancelled { seat } => {
+                self.handle_binding_mode_cancelled(seat);
+            }
+        }
+    }
+
+    fn handle_binding_mode_cancelled(&self, seat: Seat) {
+        let mode = self.active_binding_modes.borrow().get(&seat).copied();
+        if let Some(mode) = mode {
+            if mode.once {
+                self.set_binding_mode(seat, None, false);
+            }
         }
     }
 
+    fn handle_ipc_command(&self, command: &str) {
+        let handler = self.on_ipc_command.borrow().clone();
+        let res = match handler {
+            None => Err("The configuration does not handle commands".to_string()),
+            Some(handler) => match handler.try_borrow_mut() {
+                Ok(mut handler) => match catch_unwind(AssertUnwindSafe(|| handler(command))) {
+                    Ok(res) => res,
+                    Err(_) => Err("A panic occurred in the command handler".to_string()),
+                },
+                Err(_) => Err("The command handler is already running".to_string()),
+            },
+        };
+        self.send(&ClientMessage::IpcCommandResult {
+            error: res.err().as_deref(),
+        });
+    }
+
     fn handle_init_msg(&self, msg: &[u8]) {
         let init = match bincode_ops().deserialize::<InitMessage>(msg) {
             Ok(m) => m,
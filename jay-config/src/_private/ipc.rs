@@ -1,15 +1,17 @@
 use {
     crate::{
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::AccelProfile, capability::Capability, pointer::PointerTrigger,
+            FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent,
         },
         keyboard::{mods::Modifiers, syms::KeySym, Keymap},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{colors::Colorable, sized::Resizable, Color, ColorScheme},
         timer::Timer,
-        video::{connector_type::ConnectorType, Connector, DrmDevice, GfxApi, Transform},
-        Axis, Direction, PciId, Workspace,
+        video::{connector_type::ConnectorType, Connector, DrmDevice, GfxApi, Transform, VrrMode},
+        window::{Window, WindowType},
+        xwayland::XScalingMode,
+        Axis, Direction, PciId, ResizeAmount, Workspace,
         _private::{PollableId, WireMode},
     },
     serde::{Deserialize, Serialize},
@@ -88,6 +90,26 @@ pub enum ServerMessage {
         input_device: InputDevice,
         event: SwitchEvent,
     },
+    NewWindow {
+        window: Window,
+    },
+    WindowTitleChanged {
+        window: Window,
+    },
+    WindowClosed {
+        window: Window,
+    },
+    InvokePointerShortcut {
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    },
+    IpcCommand {
+        command: String,
+    },
+    BindingModeCancelled {
+        seat: Seat,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -487,6 +509,201 @@ pub enum ClientMessage<'a> {
         seat: Seat,
         enabled: bool,
     },
+    ConnectorSetTearingEnabled {
+        connector: Connector,
+        enabled: bool,
+    },
+    ConnectorVrrCapable {
+        connector: Connector,
+    },
+    ConnectorSetVrrMode {
+        connector: Connector,
+        mode: VrrMode,
+    },
+    ConnectorSetColorTemperature {
+        connector: Connector,
+        kelvin: u32,
+    },
+    AddWindowRule {
+        rule: WindowRuleData,
+    },
+    ClearWindowRules,
+    GetWindows,
+    SeatFocusedWindow {
+        seat: Seat,
+    },
+    WindowExists {
+        window: Window,
+    },
+    GetWindowTitle {
+        window: Window,
+    },
+    GetWindowAppId {
+        window: Window,
+    },
+    GetWindowClass {
+        window: Window,
+    },
+    GetWindowPid {
+        window: Window,
+    },
+    GetWindowType {
+        window: Window,
+    },
+    GetWindowWorkspace {
+        window: Window,
+    },
+    FocusWindow {
+        window: Window,
+        seat: Seat,
+    },
+    CloseWindow {
+        window: Window,
+    },
+    GetWindowFloating {
+        window: Window,
+    },
+    SetWindowFloating {
+        window: Window,
+        floating: bool,
+    },
+    MoveWindowToWorkspace {
+        window: Window,
+        workspace: Workspace,
+    },
+    GetWindowFullscreen {
+        window: Window,
+    },
+    SetWindowFullscreen {
+        window: Window,
+        fullscreen: bool,
+    },
+    SetSmartGaps {
+        enabled: bool,
+    },
+    GetSmartGaps,
+    SetWorkspaceInnerGap {
+        workspace: Workspace,
+        gap: Option<i32>,
+    },
+    SetWorkspaceOuterGap {
+        workspace: Workspace,
+        gap: Option<i32>,
+    },
+    SetWorkspaceSmartGaps {
+        workspace: Workspace,
+        enabled: Option<bool>,
+    },
+    SeatMoveToScratchpad {
+        seat: Seat,
+    },
+    SeatToggleScratchpad {
+        seat: Seat,
+        app_id: Option<&'a str>,
+    },
+    SetXScalingMode {
+        mode: XScalingMode,
+    },
+    GetWindowMinimized {
+        window: Window,
+    },
+    SetWindowMinimized {
+        window: Window,
+        minimized: bool,
+    },
+    GetWorkspaceMinimizedWindows {
+        workspace: Workspace,
+    },
+    SeatMinimize {
+        seat: Seat,
+    },
+    SeatRestoreMinimized {
+        seat: Seat,
+    },
+    SeatResize {
+        seat: Seat,
+        direction: Direction,
+        amount: ResizeAmount,
+    },
+    SeatMoveFloating {
+        seat: Seat,
+        dx: i32,
+        dy: i32,
+    },
+    SeatSetFloatingGeometry {
+        seat: Seat,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    },
+    SetBindingMode {
+        seat: Seat,
+        name: Option<&'a str>,
+        once: bool,
+    },
+    AddPointerShortcut {
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    },
+    RemovePointerShortcut {
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    },
+    FocusLast {
+        seat: Seat,
+    },
+    FocusNextMru {
+        seat: Seat,
+    },
+    FocusPrevMru {
+        seat: Seat,
+    },
+    ShowAdjacentWorkspace {
+        seat: Seat,
+        next: bool,
+        global: bool,
+    },
+    WorkspaceBackAndForth {
+        seat: Seat,
+    },
+    RenameWorkspace {
+        workspace: Workspace,
+        name: &'a str,
+    },
+    SetWorkspaceConnector {
+        workspace: Workspace,
+        connector: Option<Connector>,
+    },
+    IpcCommandResult {
+        error: Option<&'a str>,
+    },
+    SetColorScheme {
+        scheme: ColorScheme,
+    },
+    GetColorScheme,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct WindowRuleData {
+    pub app_id: Option<String>,
+    pub title: Option<String>,
+    pub class: Option<String>,
+    pub instance: Option<String>,
+    pub role: Option<String>,
+    pub pid: Option<i32>,
+    pub sandbox_engine: Option<String>,
+    pub window_type: Option<WindowType>,
+    pub workspace: Option<Workspace>,
+    pub output: Option<String>,
+    pub floating: Option<bool>,
+    pub floating_size: Option<(i32, i32)>,
+    pub floating_position: Option<(i32, i32)>,
+    pub fullscreen: Option<bool>,
+    pub opacity: Option<f32>,
+    pub reevaluate: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -635,6 +852,42 @@ pub enum Response {
     GetSocketPath {
         path: String,
     },
+    ConnectorVrrCapable {
+        capable: bool,
+    },
+    GetWindows {
+        windows: Vec<Window>,
+    },
+    GetWindow {
+        window: Window,
+    },
+    WindowExists {
+        exists: bool,
+    },
+    GetWindowTitle {
+        title: String,
+    },
+    GetWindowAppId {
+        app_id: String,
+    },
+    GetWindowClass {
+        class: String,
+    },
+    GetWindowPid {
+        pid: Option<i32>,
+    },
+    GetWindowType {
+        ty: Option<WindowType>,
+    },
+    GetSmartGaps {
+        enabled: bool,
+    },
+    GetMinimized {
+        minimized: bool,
+    },
+    GetColorScheme {
+        scheme: ColorScheme,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -2,14 +2,16 @@
 
 pub mod acceleration;
 pub mod capability;
+pub mod pointer;
 
 use {
     crate::{
-        input::{acceleration::AccelProfile, capability::Capability},
+        input::{acceleration::AccelProfile, capability::Capability, pointer::PointerTrigger},
         keyboard::{mods::Modifiers, Keymap},
-        Axis, Direction, ModifiedKeySym, Workspace,
-        _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
         video::Connector,
+        window::Window,
+        Axis, Direction, ModifiedKeySym, ResizeAmount, Workspace,
+        _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -200,6 +202,9 @@ impl Seat {
 
     /// Creates a compositor-wide hotkey.
     ///
+    /// The hotkey belongs to the default binding mode and is only active while no other
+    /// binding mode is active. See [`BindingMode`].
+    ///
     /// The closure is invoked when the user presses the last key of the modified keysym.
     /// Note that the keysym is calculated without modifiers applied. To perform an action
     /// when `SHIFT+k` is pressed, use `SHIFT | SYM_k` not `SHIFT | SYM_K`.
@@ -232,7 +237,7 @@ impl Seat {
         mod_sym: T,
         f: F,
     ) {
-        get!().bind_masked(self, mod_mask, mod_sym.into(), f)
+        get!().bind_masked(self, None, mod_mask, mod_sym.into(), f)
     }
 
     /// Registers a callback to be executed when the currently pressed key is released.
@@ -247,7 +252,39 @@ impl Seat {
 
     /// Unbinds a hotkey.
     pub fn unbind<T: Into<ModifiedKeySym>>(self, mod_sym: T) {
-        get!().unbind(self, mod_sym.into())
+        get!().unbind(self, None, mod_sym.into())
+    }
+
+    /// Creates a compositor-wide pointer binding.
+    ///
+    /// The closure is invoked when the trigger occurs while exactly the modifiers `mods`
+    /// are pressed. CapsLock and NumLock are ignored.
+    ///
+    /// Events that invoke a binding are not sent to applications. For gestures, the
+    /// whole gesture is withheld from applications if any binding exists for the kind of
+    /// gesture, the number of fingers, and the modifiers that are pressed when the
+    /// gesture begins.
+    ///
+    /// Like hotkeys, pointer bindings belong to the default binding mode.
+    pub fn bind_pointer<F: FnMut() + 'static>(
+        self,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+        f: F,
+    ) {
+        get!().bind_pointer(self, None, mods, trigger, f)
+    }
+
+    /// Removes a pointer binding.
+    pub fn unbind_pointer(self, mods: Modifiers, trigger: PointerTrigger) {
+        get!().unbind_pointer(self, None, mods, trigger)
+    }
+
+    /// Returns the active binding mode of the seat.
+    ///
+    /// Returns `None` if the default binding mode is active.
+    pub fn binding_mode(self) -> Option<BindingMode> {
+        get!(None).seat_binding_mode(self)
     }
 
     /// Moves the keyboard focus of the seat in the specified direction.
@@ -260,6 +297,32 @@ impl Seat {
         get!().move_(self, direction)
     }
 
+    /// Resizes the focused window by moving its edge in the specified direction.
+    ///
+    /// For example, `Direction::Right` moves the right edge of the window to the right.
+    /// Negative amounts move the edge in the opposite direction and shrink the window.
+    ///
+    /// Tiled windows take the space from or give the space to their neighbors. If the
+    /// window has no neighbor in that direction, the container of the window is resized
+    /// instead.
+    pub fn resize(self, direction: Direction, amount: ResizeAmount) {
+        get!().seat_resize(self, direction, amount)
+    }
+
+    /// Moves the focused window by the specified number of pixels if it is floating.
+    pub fn move_floating(self, dx: i32, dy: i32) {
+        get!().seat_move_floating(self, dx, dy)
+    }
+
+    /// Sets the position and size of the focused window if it is floating.
+    ///
+    /// The geometry describes the contents of the window without its border and title.
+    /// The position is relative to the top-left corner of the output that contains the
+    /// window.
+    pub fn set_floating_geometry(self, x: i32, y: i32, width: i32, height: i32) {
+        get!().seat_set_floating_geometry(self, x, y, width, height)
+    }
+
     /// Sets the keymap of the seat.
     pub fn set_keymap(self, keymap: Keymap) {
         get!().seat_set_keymap(self, keymap)
@@ -323,6 +386,63 @@ impl Seat {
         get!().focus_parent(self);
     }
 
+    /// Focuses the most recently focused window other than the currently focused window.
+    ///
+    /// Each seat remembers the order in which windows were focused. Repeated invocations
+    /// toggle between the two most recently focused windows.
+    pub fn focus_last(self) {
+        get!().focus_last(self);
+    }
+
+    /// Selects the next window in the window switcher of this seat.
+    ///
+    /// The window switcher shows the titles of all windows in the order in which they
+    /// were most recently focused. If it is not already open, it is opened on the output
+    /// that contains the cursor and the window before the currently focused window is
+    /// selected.
+    ///
+    /// The switcher stays open while any modifier is pressed. Once all modifiers have
+    /// been released, the selected window is focused. If no modifier is pressed when
+    /// this function is called, the window is focused immediately.
+    ///
+    /// This is usually bound to `alt-Tab`.
+    pub fn focus_next_mru(self) {
+        get!().focus_next_mru(self);
+    }
+
+    /// Selects the previous window in the window switcher of this seat.
+    ///
+    /// This is otherwise identical to [`Seat::focus_next_mru`]. This is usually bound to
+    /// `alt-shift-Tab`.
+    pub fn focus_prev_mru(self) {
+        get!().focus_prev_mru(self);
+    }
+
+    /// Shows the next workspace.
+    ///
+    /// Workspaces are ordered by their names. Names that are numbers are ordered
+    /// numerically and before all other names. If `global` is `false`, only the workspaces
+    /// of the output that contains the cursor are considered. Otherwise the workspaces of
+    /// all outputs are considered. After the last workspace, the first workspace is shown.
+    pub fn show_next_workspace(self, global: bool) {
+        get!().show_adjacent_workspace(self, true, global);
+    }
+
+    /// Shows the previous workspace.
+    ///
+    /// This is otherwise identical to [`Seat::show_next_workspace`].
+    pub fn show_prev_workspace(self, global: bool) {
+        get!().show_adjacent_workspace(self, false, global);
+    }
+
+    /// Shows the workspace that was shown before the current workspace.
+    ///
+    /// Calling this function repeatedly switches between two workspaces. If the previous
+    /// workspace no longer exists, it is re-created.
+    pub fn workspace_back_and_forth(self) {
+        get!().workspace_back_and_forth(self);
+    }
+
     /// Requests the currently focused window to be closed.
     pub fn close(self) {
         get!().close(self);
@@ -365,6 +485,48 @@ impl Seat {
         get!().set_workspace(self, workspace)
     }
 
+    /// Returns the window that has the keyboard focus of this seat.
+    ///
+    /// If no window is focused, `exists` returns `false` for the returned window.
+    pub fn focused_window(self) -> Window {
+        get!(Window(0)).seat_focused_window(self)
+    }
+
+    /// Moves the currently focused window to the scratchpad.
+    ///
+    /// Windows in the scratchpad are hidden until they are shown with
+    /// [`Seat::toggle_scratchpad`].
+    pub fn move_to_scratchpad(self) {
+        get!().seat_move_to_scratchpad(self)
+    }
+
+    /// Shows or hides a window from the scratchpad.
+    ///
+    /// If the currently focused window is in the scratchpad, it is hidden. Otherwise the
+    /// first window in the scratchpad is shown floating and centered on the output that
+    /// contains the cursor. Hidden windows are moved to the end of the scratchpad, so
+    /// repeated invocations cycle through all windows in the scratchpad.
+    ///
+    /// If `app_id` is set, only windows with this app-id are considered.
+    ///
+    /// A window is removed from the scratchpad when it is made tiled.
+    pub fn toggle_scratchpad(self, app_id: Option<&str>) {
+        get!().seat_toggle_scratchpad(self, app_id)
+    }
+
+    /// Minimizes the currently focused window.
+    ///
+    /// See [`Window::set_minimized`].
+    pub fn minimize(self) {
+        get!().seat_minimize(self)
+    }
+
+    /// Restores the most recently minimized window of the workspace on the output that
+    /// contains the cursor and focuses it.
+    pub fn restore_minimized(self) {
+        get!().seat_restore_minimized(self)
+    }
+
     /// Toggles whether the currently focused window is fullscreen.
     pub fn toggle_fullscreen(self) {
         let c = get!();
@@ -457,6 +619,112 @@ pub enum FocusFollowsMouseMode {
     False,
 }
 
+/// A binding mode.
+///
+/// A binding mode is a named set of hotkeys. Hotkeys created with [`Seat::bind`] belong
+/// to the default binding mode. While a binding mode is active on a seat, only the
+/// hotkeys of that binding mode are active.
+///
+/// For example, a binding mode can be used to resize windows with the arrow keys:
+///
+/// ```rust,ignore
+/// let resize = get_binding_mode("resize");
+/// seat.bind(MOD4 | SYM_r, move || resize.activate(seat));
+/// resize.bind(seat, SYM_Left, move || seat.resize(Left, ResizeAmount::Px(10)));
+/// resize.bind(seat, SYM_Escape, move || resize.deactivate(seat));
+/// ```
+///
+/// Binding modes activated with [`BindingMode::activate_once`] are deactivated after the
+/// first hotkey of the mode has been invoked or a non-modifier key that is not a hotkey of
+/// the mode has been pressed. This can be used to create key chords.
+///
+/// The name of the active binding mode is shown in the bar.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct BindingMode(pub u64);
+
+impl BindingMode {
+    /// Returns the name of the binding mode.
+    pub fn name(self) -> String {
+        get!(String::new()).binding_mode_name(self)
+    }
+
+    /// Creates a hotkey in this binding mode.
+    ///
+    /// This is otherwise identical to [`Seat::bind`].
+    pub fn bind<T: Into<ModifiedKeySym>, F: FnMut() + 'static>(self, seat: Seat, mod_sym: T, f: F) {
+        self.bind_masked(seat, Modifiers(!0), mod_sym, f)
+    }
+
+    /// Creates a hotkey in this binding mode while ignoring some modifiers.
+    ///
+    /// This is otherwise identical to [`Seat::bind_masked`].
+    pub fn bind_masked<T: Into<ModifiedKeySym>, F: FnMut() + 'static>(
+        self,
+        seat: Seat,
+        mod_mask: Modifiers,
+        mod_sym: T,
+        f: F,
+    ) {
+        get!().bind_masked(seat, Some(self), mod_mask, mod_sym.into(), f)
+    }
+
+    /// Unbinds a hotkey in this binding mode.
+    pub fn unbind<T: Into<ModifiedKeySym>>(self, seat: Seat, mod_sym: T) {
+        get!().unbind(seat, Some(self), mod_sym.into())
+    }
+
+    /// Creates a pointer binding in this binding mode.
+    ///
+    /// This is otherwise identical to [`Seat::bind_pointer`].
+    pub fn bind_pointer<F: FnMut() + 'static>(
+        self,
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+        f: F,
+    ) {
+        get!().bind_pointer(seat, Some(self), mods, trigger, f)
+    }
+
+    /// Removes a pointer binding in this binding mode.
+    pub fn unbind_pointer(self, seat: Seat, mods: Modifiers, trigger: PointerTrigger) {
+        get!().unbind_pointer(seat, Some(self), mods, trigger)
+    }
+
+    /// Activates the binding mode on the seat.
+    ///
+    /// The binding mode stays active until it is deactivated or another binding mode is
+    /// activated.
+    pub fn activate(self, seat: Seat) {
+        get!().set_binding_mode(seat, Some(self), false)
+    }
+
+    /// Activates the binding mode on the seat until the next hotkey has been invoked.
+    ///
+    /// Pressing a non-modifier key that is not a hotkey of the mode also deactivates the
+    /// binding mode.
+    pub fn activate_once(self, seat: Seat) {
+        get!().set_binding_mode(seat, Some(self), true)
+    }
+
+    /// Deactivates the binding mode on the seat.
+    ///
+    /// If the binding mode is active, the seat returns to the default binding mode.
+    /// Otherwise this has no effect.
+    pub fn deactivate(self, seat: Seat) {
+        if seat.binding_mode() == Some(self) {
+            get!().set_binding_mode(seat, None, false)
+        }
+    }
+}
+
+/// Returns the binding mode with the given name.
+///
+/// If no binding mode with the name exists, a new binding mode will be created.
+pub fn get_binding_mode(name: &str) -> BindingMode {
+    get!(BindingMode(0)).get_binding_mode(name)
+}
+
 /// Returns all seats.
 pub fn get_seats() -> Vec<Seat> {
     get!().seats()
@@ -0,0 +1,55 @@
+//! Tools for binding pointer buttons, scroll wheels, and touchpad gestures.
+
+use {
+    crate::Direction,
+    serde::{Deserialize, Serialize},
+};
+
+/// A pointer button.
+///
+/// The values are the evdev button codes.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Button(pub u32);
+
+pub const BTN_LEFT: Button = Button(0x110);
+pub const BTN_RIGHT: Button = Button(0x111);
+pub const BTN_MIDDLE: Button = Button(0x112);
+pub const BTN_SIDE: Button = Button(0x113);
+pub const BTN_EXTRA: Button = Button(0x114);
+pub const BTN_FORWARD: Button = Button(0x115);
+pub const BTN_BACK: Button = Button(0x116);
+pub const BTN_TASK: Button = Button(0x117);
+
+/// The direction of a pinch gesture.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PinchDirection {
+    /// The fingers move towards each other.
+    In,
+    /// The fingers move away from each other.
+    Out,
+}
+
+/// A pointer event that can be bound with [`Seat::bind_pointer`](super::Seat::bind_pointer).
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerTrigger {
+    /// A button is pressed.
+    ///
+    /// The press and the release of the button are not sent to applications.
+    Button(Button),
+    /// A scroll wheel is turned by one step in the direction.
+    ///
+    /// Only scroll wheels with discrete steps trigger this binding. The scroll event is not
+    /// sent to applications.
+    Scroll(Direction),
+    /// A touchpad swipe gesture with the number of fingers ends.
+    ///
+    /// The direction is the direction in which the fingers moved the furthest.
+    Swipe { fingers: u32, direction: Direction },
+    /// A touchpad pinch gesture with the number of fingers ends.
+    Pinch {
+        fingers: u32,
+        direction: PinchDirection,
+    },
+    /// A touchpad hold gesture with the number of fingers ends without being cancelled.
+    Hold { fingers: u32 },
+}
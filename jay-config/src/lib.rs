@@ -43,7 +43,9 @@
 )]
 
 use {
-    crate::{_private::ipc::WorkspaceSource, keyboard::ModifiedKeySym, video::Connector},
+    crate::{
+        _private::ipc::WorkspaceSource, keyboard::ModifiedKeySym, video::Connector, window::Window,
+    },
     serde::{Deserialize, Serialize},
     std::{
         fmt::{Debug, Display, Formatter},
@@ -66,9 +68,11 @@ pub mod tasks;
 pub mod theme;
 pub mod timer;
 pub mod video;
+pub mod window;
+pub mod xwayland;
 
 /// A planar direction.
-#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Direction {
     Left,
     Down,
@@ -93,6 +97,15 @@ impl Axis {
     }
 }
 
+/// The amount by which a window is resized.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum ResizeAmount {
+    /// A number of logical pixels.
+    Px(i32),
+    /// A percentage of the width or height of the output that contains the window.
+    Percent(f64),
+}
+
 /// Exits the compositor.
 pub fn quit() {
     get!().quit()
@@ -169,6 +182,55 @@ impl Workspace {
     pub fn move_to_output(self, output: Connector) {
         get!().move_to_output(WorkspaceSource::Explicit(self), output);
     }
+
+    /// Overrides the [inner gap][crate::theme::sized::INNER_GAP] of this workspace.
+    ///
+    /// `None` restores the global setting.
+    pub fn set_inner_gap(self, gap: Option<i32>) {
+        get!().set_workspace_inner_gap(self, gap)
+    }
+
+    /// Overrides the [outer gap][crate::theme::sized::OUTER_GAP] of this workspace.
+    ///
+    /// `None` restores the global setting.
+    pub fn set_outer_gap(self, gap: Option<i32>) {
+        get!().set_workspace_outer_gap(self, gap)
+    }
+
+    /// Overrides whether [smart gaps][crate::theme::set_smart_gaps] are enabled on this
+    /// workspace.
+    ///
+    /// `None` restores the global setting.
+    pub fn set_smart_gaps(self, enabled: Option<bool>) {
+        get!().set_workspace_smart_gaps(self, enabled)
+    }
+
+    /// Returns the minimized windows of this workspace.
+    ///
+    /// The windows are ordered by the time they were minimized, starting with the oldest.
+    pub fn minimized_windows(self) -> Vec<Window> {
+        get!().workspace_minimized_windows(self)
+    }
+
+    /// Renames this workspace.
+    ///
+    /// This has no effect if the workspace does not exist or if a workspace with the new
+    /// name already exists. Afterwards, this object refers to the old name. Use
+    /// [`get_workspace`] to get the renamed workspace.
+    pub fn rename(self, name: &str) {
+        get!().rename_workspace(self, name)
+    }
+
+    /// Sets the connector that this workspace is shown on.
+    ///
+    /// If the workspace exists and the connector is connected, the workspace is moved to
+    /// the connector immediately. Otherwise the workspace is created on the connector and
+    /// moved back to it whenever the connector is connected.
+    ///
+    /// `None` removes the preference.
+    pub fn set_preferred_connector(self, connector: Option<Connector>) {
+        get!().set_workspace_connector(self, connector)
+    }
 }
 
 /// Returns the workspace with the given name.
@@ -234,3 +296,12 @@ pub fn set_idle(timeout: Option<Duration>) {
 pub fn set_explicit_sync_enabled(enabled: bool) {
     get!().set_explicit_sync_enabled(enabled);
 }
+
+/// Sets the callback to be called when a command is sent via the IPC socket.
+///
+/// The callback receives the command as it was sent by the IPC client, e.g. by
+/// `jay msg command`. If the callback returns an error, the error is sent back to the
+/// client. If no callback is set, all commands fail.
+pub fn on_ipc_command<F: FnMut(&str) -> Result<(), String> + 'static>(f: F) {
+    get!().on_ipc_command(f)
+}
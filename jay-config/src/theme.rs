@@ -123,11 +123,55 @@ impl Color {
     }
 }
 
+/// The color scheme that applications should prefer.
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+pub enum ColorScheme {
+    /// Applications should use their default color scheme.
+    #[default]
+    NoPreference,
+    /// Applications should prefer a dark color scheme.
+    Dark,
+    /// Applications should prefer a light color scheme.
+    Light,
+}
+
+/// Sets the color scheme that applications should prefer.
+///
+/// Applications read this setting via the `org.freedesktop.appearance` namespace of
+/// the settings portal.
+///
+/// Default: [`ColorScheme::NoPreference`].
+pub fn set_color_scheme(scheme: ColorScheme) {
+    get!().set_color_scheme(scheme)
+}
+
+/// Returns the color scheme that applications should prefer.
+pub fn get_color_scheme() -> ColorScheme {
+    get!(ColorScheme::NoPreference).get_color_scheme()
+}
+
 /// Resets all sizes to their defaults.
 pub fn reset_sizes() {
     get!().reset_sizes();
 }
 
+/// Sets whether gaps are disabled on workspaces that contain only a single window.
+///
+/// This can be overridden per workspace with [`Workspace::set_smart_gaps`].
+///
+/// Default: `false`.
+///
+/// [`Workspace::set_smart_gaps`]: crate::Workspace::set_smart_gaps
+pub fn set_smart_gaps(enabled: bool) {
+    get!().set_smart_gaps(enabled)
+}
+
+/// Returns whether smart gaps are enabled.
+pub fn get_smart_gaps() -> bool {
+    get!(false).get_smart_gaps()
+}
+
 /// Resets all colors to their defaults.
 pub fn reset_colors() {
     get!().reset_colors();
@@ -312,5 +356,15 @@ pub mod sized {
         ///
         /// Default: 4
         const 02 => BORDER_WIDTH,
+        /// The gap between tiled windows.
+        ///
+        /// If this is not 0, the gap replaces the border between windows.
+        ///
+        /// Default: 0
+        const 03 => INNER_GAP,
+        /// The gap between tiled windows and the edges of the workspace.
+        ///
+        /// Default: 0
+        const 04 => OUTER_GAP,
     }
 }
@@ -221,6 +221,56 @@ impl Connector {
         get!().connector_set_transform(self, transform);
     }
 
+    /// Enables or disables tearing page flips on this connector.
+    ///
+    /// Tearing is only used if a fullscreen application has requested it via the
+    /// tearing-control protocol and the application's buffer is being scanned out
+    /// directly.
+    ///
+    /// By default, tearing is disabled.
+    pub fn set_tearing_enabled(self, enabled: bool) {
+        if !self.exists() {
+            log::warn!("set_tearing_enabled called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_tearing_enabled(self, enabled);
+    }
+
+    /// Returns whether the connected display supports variable refresh rate.
+    pub fn vrr_capable(self) -> bool {
+        if !self.exists() {
+            return false;
+        }
+        get!(false).connector_vrr_capable(self)
+    }
+
+    /// Sets the variable refresh rate mode of this connector.
+    ///
+    /// VRR is only enabled if the connected display supports it.
+    ///
+    /// By default, VRR is never enabled.
+    pub fn set_vrr_mode(self, mode: VrrMode) {
+        if !self.exists() {
+            log::warn!("set_vrr_mode called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_vrr_mode(self, mode);
+    }
+
+    /// Sets the color temperature of this connector in Kelvin.
+    ///
+    /// 6500 is neutral. Lower values make the colors warmer, higher values make them
+    /// colder. The value is clamped to the range `1000..=10000`.
+    ///
+    /// The default is `6500`.
+    pub fn set_color_temperature(self, kelvin: u32) {
+        if !self.exists() {
+            log::warn!("set_color_temperature called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_color_temperature(self, kelvin);
+    }
+
     pub fn name(self) -> String {
         if !self.exists() {
             return String::new();
@@ -510,6 +560,21 @@ pub fn set_direct_scanout_enabled(enabled: bool) {
     get!().set_direct_scanout_enabled(None, enabled);
 }
 
+/// The variable refresh rate mode of a connector.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum VrrMode {
+    /// VRR is never enabled.
+    #[default]
+    Never,
+    /// VRR is always enabled.
+    Always,
+    /// VRR is enabled while a window is fullscreen on the active workspace.
+    FullscreenOnly,
+    /// VRR is enabled while a window is fullscreen on the active workspace and the
+    /// window has the `game` content type.
+    FullscreenWithContentTypeGame,
+}
+
 /// A transformation.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
 pub enum Transform {
@@ -0,0 +1,318 @@
+//! Tools for inspecting and manipulating windows.
+
+use {
+    crate::{_private::ipc::WindowRuleData, input::Seat, Workspace},
+    serde::{Deserialize, Serialize},
+};
+
+/// A window.
+///
+/// Windows are toplevels created by wayland or X clients. A window stops existing when it
+/// is unmapped or destroyed by the client.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Window(pub u64);
+
+impl Window {
+    /// Returns whether the window exists.
+    pub fn exists(self) -> bool {
+        self.0 != 0 && get!(false).window_exists(self)
+    }
+
+    /// Returns the title of the window.
+    pub fn title(self) -> String {
+        get!(String::new()).window_title(self)
+    }
+
+    /// Returns the app-id of the window.
+    ///
+    /// For X windows, this is the class.
+    pub fn app_id(self) -> String {
+        get!(String::new()).window_app_id(self)
+    }
+
+    /// Returns the `WM_CLASS` class of the window.
+    ///
+    /// This is empty for windows that are not X windows.
+    pub fn class(self) -> String {
+        get!(String::new()).window_class(self)
+    }
+
+    /// Returns the PID of the process that created the window, if known.
+    pub fn pid(self) -> Option<i32> {
+        get!(None).window_pid(self)
+    }
+
+    /// Returns the type of the window.
+    pub fn window_type(self) -> Option<WindowType> {
+        get!(None).window_type(self)
+    }
+
+    /// Returns the workspace the window is on.
+    ///
+    /// If the window does not exist, `exists` returns `false` for the returned workspace.
+    pub fn workspace(self) -> Workspace {
+        get!(Workspace(0)).window_workspace(self)
+    }
+
+    /// Shows the window and sets the keyboard focus of the seat to it.
+    pub fn focus(self, seat: Seat) {
+        get!().focus_window(self, seat)
+    }
+
+    /// Requests the window to be closed.
+    pub fn close(self) {
+        get!().close_window(self)
+    }
+
+    /// Returns whether the window is floating.
+    pub fn floating(self) -> bool {
+        get!(false).window_floating(self)
+    }
+
+    /// Sets whether the window is floating.
+    pub fn set_floating(self, floating: bool) {
+        get!().set_window_floating(self, floating)
+    }
+
+    /// Toggles whether the window is floating.
+    pub fn toggle_floating(self) {
+        let get = get!();
+        get.set_window_floating(self, !get.window_floating(self));
+    }
+
+    /// Moves the window to the workspace.
+    ///
+    /// If the workspace doesn't currently exist, it is created on the output that contains
+    /// the window.
+    pub fn move_to_workspace(self, workspace: Workspace) {
+        get!().move_window_to_workspace(self, workspace)
+    }
+
+    /// Returns whether the window is fullscreen.
+    pub fn fullscreen(self) -> bool {
+        get!(false).window_fullscreen(self)
+    }
+
+    /// Sets whether the window is fullscreen.
+    pub fn set_fullscreen(self, fullscreen: bool) {
+        get!().set_window_fullscreen(self, fullscreen)
+    }
+
+    /// Toggles whether the window is fullscreen.
+    pub fn toggle_fullscreen(self) {
+        let get = get!();
+        get.set_window_fullscreen(self, !get.window_fullscreen(self));
+    }
+
+    /// Returns whether the window is minimized.
+    pub fn minimized(self) -> bool {
+        get!(false).window_minimized(self)
+    }
+
+    /// Sets whether the window is minimized.
+    ///
+    /// Minimized windows are removed from the layout of their workspace and hidden until
+    /// they are restored. Restored windows are added back to the workspace they were
+    /// minimized on.
+    pub fn set_minimized(self, minimized: bool) {
+        get!().set_window_minimized(self, minimized)
+    }
+}
+
+/// Returns all windows.
+pub fn windows() -> Vec<Window> {
+    get!().windows()
+}
+
+/// Sets a callback to run when a window is mapped.
+pub fn on_new_window<F: FnMut(Window) + 'static>(f: F) {
+    get!().on_new_window(f)
+}
+
+/// Sets a callback to run when the title of a window changes.
+pub fn on_window_title_changed<F: FnMut(Window) + 'static>(f: F) {
+    get!().on_window_title_changed(f)
+}
+
+/// Sets a callback to run when a window is unmapped or destroyed.
+///
+/// The window no longer exists when the callback runs.
+pub fn on_window_closed<F: FnMut(Window) + 'static>(f: F) {
+    get!().on_window_closed(f)
+}
+
+/// The type of a window.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum WindowType {
+    /// A window created by a wayland client via the xdg-shell protocol.
+    XdgToplevel,
+    /// A window created by an X client via Xwayland.
+    XWindow,
+}
+
+/// A window rule.
+///
+/// A window rule consists of criteria and actions. When a window is mapped, the actions of
+/// all rules whose criteria match the window are applied. If multiple matching rules set
+/// the same action, the rule that was added last takes precedence.
+///
+/// A rule without criteria matches all windows.
+///
+/// # Example
+///
+/// ```rust
+/// use jay_config::{get_workspace, window::WindowRule};
+///
+/// fn configure() {
+///     WindowRule::new()
+///         .app_id("firefox")
+///         .workspace(get_workspace("2"))
+///         .add();
+///     WindowRule::new()
+///         .app_id("org.pulseaudio.pavucontrol")
+///         .floating_size(800, 600)
+///         .add();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WindowRule {
+    pub(crate) data: WindowRuleData,
+}
+
+impl WindowRule {
+    /// Creates a new rule without criteria or actions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only matches windows with this app-id.
+    pub fn app_id(&mut self, app_id: &str) -> &mut Self {
+        self.data.app_id = Some(app_id.to_string());
+        self
+    }
+
+    /// Only matches windows whose title matches this regular expression.
+    ///
+    /// The regular expression is not anchored. Use `^` and `$` to match the whole title.
+    pub fn title(&mut self, regex: &str) -> &mut Self {
+        self.data.title = Some(regex.to_string());
+        self
+    }
+
+    /// Only matches X windows with this `WM_CLASS` class.
+    pub fn class(&mut self, class: &str) -> &mut Self {
+        self.data.class = Some(class.to_string());
+        self
+    }
+
+    /// Only matches X windows with this `WM_CLASS` instance.
+    pub fn instance(&mut self, instance: &str) -> &mut Self {
+        self.data.instance = Some(instance.to_string());
+        self
+    }
+
+    /// Only matches X windows with this `WM_WINDOW_ROLE`.
+    pub fn role(&mut self, role: &str) -> &mut Self {
+        self.data.role = Some(role.to_string());
+        self
+    }
+
+    /// Only matches windows of the process with this PID.
+    pub fn pid(&mut self, pid: i32) -> &mut Self {
+        self.data.pid = Some(pid);
+        self
+    }
+
+    /// Only matches windows of clients that connected via a security context with this
+    /// sandbox engine, for example `org.flatpak`.
+    pub fn sandbox_engine(&mut self, engine: &str) -> &mut Self {
+        self.data.sandbox_engine = Some(engine.to_string());
+        self
+    }
+
+    /// Only matches windows of this type.
+    pub fn window_type(&mut self, ty: WindowType) -> &mut Self {
+        self.data.window_type = Some(ty);
+        self
+    }
+
+    /// Moves matching windows to this workspace.
+    ///
+    /// If the workspace does not exist, it is created on the output set via
+    /// [`WindowRule::output`] or, if that is not set, on the output of the seat.
+    pub fn workspace(&mut self, workspace: Workspace) -> &mut Self {
+        self.data.workspace = Some(workspace);
+        self
+    }
+
+    /// Moves matching windows to the active workspace of the output connected to this
+    /// connector.
+    ///
+    /// `name` is the name of the connector, for example `DP-1`.
+    pub fn output(&mut self, name: &str) -> &mut Self {
+        self.data.output = Some(name.to_string());
+        self
+    }
+
+    /// Sets whether matching windows are floating.
+    pub fn floating(&mut self, floating: bool) -> &mut Self {
+        self.data.floating = Some(floating);
+        self
+    }
+
+    /// Sets the size of matching windows.
+    ///
+    /// This implies `floating(true)` unless floating is explicitly disabled.
+    pub fn floating_size(&mut self, width: i32, height: i32) -> &mut Self {
+        self.data.floating_size = Some((width, height));
+        self
+    }
+
+    /// Sets the position of matching windows relative to the top-left corner of the output.
+    ///
+    /// This implies `floating(true)` unless floating is explicitly disabled.
+    pub fn floating_position(&mut self, x: i32, y: i32) -> &mut Self {
+        self.data.floating_position = Some((x, y));
+        self
+    }
+
+    /// Sets whether matching windows are fullscreen.
+    pub fn fullscreen(&mut self, fullscreen: bool) -> &mut Self {
+        self.data.fullscreen = Some(fullscreen);
+        self
+    }
+
+    /// Sets the opacity of matching windows.
+    ///
+    /// The value is clamped to `[0, 1]`.
+    pub fn opacity(&mut self, opacity: f32) -> &mut Self {
+        self.data.opacity = Some(opacity);
+        self
+    }
+
+    /// Sets whether this rule is re-evaluated when the title or app-id of a window changes.
+    ///
+    /// By default, rules are only evaluated when a window is mapped. If this is enabled,
+    /// the rule is also applied to windows that start matching it later. Each rule is
+    /// applied at most once to each window.
+    ///
+    /// The default is `false`.
+    pub fn reevaluate(&mut self, reevaluate: bool) -> &mut Self {
+        self.data.reevaluate = reevaluate;
+        self
+    }
+
+    /// Adds the rule to the compositor.
+    ///
+    /// The rule only affects windows that are mapped afterwards.
+    pub fn add(&self) {
+        get!().add_window_rule(self);
+    }
+}
+
+/// Removes all window rules.
+///
+/// Window rules are also removed automatically when the configuration is reloaded.
+pub fn clear_window_rules() {
+    get!().clear_window_rules();
+}
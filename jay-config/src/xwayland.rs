@@ -0,0 +1,29 @@
+//! Tools for configuring Xwayland.
+
+use serde::{Deserialize, Serialize};
+
+/// The scaling mode of X windows.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum XScalingMode {
+    /// X windows are rendered at scale 1 and upscaled by the compositor.
+    ///
+    /// X windows have the correct size but are blurry on outputs with a scale larger
+    /// than 1.
+    #[default]
+    Default,
+    /// Xwayland uses the highest integer scale of all outputs and X windows are
+    /// downscaled by the compositor on outputs with a lower scale.
+    ///
+    /// X windows see their geometry and the geometry of the outputs in this scale. The
+    /// compositor advertises the scale via the `Xft.dpi` resource and via the
+    /// `Gdk/WindowScalingFactor` and `Xft/DPI` XSETTINGS. Applications that respect
+    /// these hints are crisp. Other applications appear small.
+    Downscaled,
+}
+
+/// Sets the scaling mode of X windows.
+///
+/// The default is [`XScalingMode::Default`].
+pub fn set_x_scaling_mode(mode: XScalingMode) {
+    get!().set_x_scaling_mode(mode)
+}
@@ -165,6 +165,10 @@ impl Acceptor {
         &self.socket.name
     }
 
+    pub fn path(&self) -> &Ustr {
+        self.socket.path.as_ustr()
+    }
+
     #[cfg_attr(not(feature = "it"), allow(dead_code))]
     pub fn secure_path(&self) -> &Ustr {
         self.socket.secure_path.as_ustr()
@@ -183,7 +187,7 @@ async fn accept(fd: Rc<OwnedFd>, state: Rc<State>, effective_caps: ClientCaps) {
         let id = state.clients.id();
         if let Err(e) = state
             .clients
-            .spawn(id, &state, fd, effective_caps, ClientCaps::all())
+            .spawn(id, &state, fd, effective_caps, ClientCaps::all(), None)
         {
             log::error!("Could not spawn a client: {}", ErrorFmt(e));
             break;
@@ -13,13 +13,19 @@ use {
             wl_pointer::{CONTINUOUS, FINGER, HORIZONTAL_SCROLL, VERTICAL_SCROLL, WHEEL},
         },
         libinput::consts::DeviceCapability,
+        rect::Rect,
+        utils::errorfmt::ErrorFmt,
         video::drm::{ConnectorType, DrmConnector, DrmError, DrmVersion},
     },
-    jay_config::{input::SwitchEvent, video::GfxApi},
+    jay_config::{
+        input::SwitchEvent,
+        video::{GfxApi, VrrMode},
+    },
     std::{
         any::Any,
         error::Error,
         fmt::{Debug, Display, Formatter},
+        ops::Deref,
         rc::Rc,
     },
     uapi::{c, OwnedFd},
@@ -52,6 +58,38 @@ pub trait Backend {
     fn supports_presentation_feedback(&self) -> bool {
         false
     }
+
+    /// Checks whether all connectors could be driven with this configuration at the same
+    /// time without applying it.
+    fn test_connectors(&self, configs: &[ConnectorConfig]) -> Result<(), Box<dyn Error>> {
+        for config in configs {
+            if config.new.enabled {
+                config.connector.test_mode(config.new.mode)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the configuration to all connectors.
+    ///
+    /// If this fails, the previous state of all connectors is restored.
+    fn apply_connectors(self: Rc<Self>, configs: &[ConnectorConfig]) -> Result<(), Box<dyn Error>> {
+        for (idx, config) in configs.iter().enumerate() {
+            if let Err(e) = config.apply(config.old, config.new) {
+                for config in configs[..=idx].iter().rev() {
+                    if let Err(e) = config.apply(config.new, config.old) {
+                        log::error!(
+                            "Could not restore the previous state of connector {}: {}",
+                            config.connector.kernel_id(),
+                            ErrorFmt(e.deref()),
+                        );
+                    }
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
@@ -71,6 +109,14 @@ pub struct MonitorInfo {
     pub width_mm: i32,
     pub height_mm: i32,
     pub non_desktop: bool,
+    pub vrr_capable: bool,
+}
+
+#[derive(Debug)]
+pub struct GammaLut {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -91,25 +137,80 @@ pub trait Connector {
     fn event(&self) -> Option<ConnectorEvent>;
     fn on_change(&self, cb: Rc<dyn Fn()>);
     fn damage(&self);
+    /// Damages a rectangle in output-local, logical coordinates.
+    ///
+    /// An empty rectangle schedules a present without redrawing any pixels.
+    fn damage_rect(&self, rect: Rect) {
+        let _ = rect;
+        self.damage();
+    }
     fn drm_dev(&self) -> Option<DrmDeviceId>;
     fn enabled(&self) -> bool {
         true
     }
-    fn set_enabled(&self, enabled: bool) {
+    fn set_enabled(&self, enabled: bool) -> Result<(), Box<dyn Error>> {
         let _ = enabled;
+        Ok(())
     }
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         None
     }
-    fn set_mode(&self, mode: Mode);
+    fn set_mode(&self, mode: Mode) -> Result<(), Box<dyn Error>>;
+    /// Checks whether the connector could be driven with this mode without applying it.
+    fn test_mode(&self, mode: Mode) -> Result<(), Box<dyn Error>> {
+        let _ = mode;
+        Ok(())
+    }
     fn set_non_desktop_override(&self, non_desktop: Option<bool>) {
         let _ = non_desktop;
     }
+    fn set_tearing_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+    fn set_vrr_mode(&self, mode: VrrMode) {
+        let _ = mode;
+    }
+    fn gamma_lut_size(&self) -> u32 {
+        0
+    }
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        let _ = lut;
+    }
+    fn set_color_temperature(&self, kelvin: u32) {
+        let _ = kelvin;
+    }
     fn drm_object_id(&self) -> Option<DrmConnector> {
         None
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConnectorState {
+    pub enabled: bool,
+    pub mode: Mode,
+}
+
+pub struct ConnectorConfig {
+    pub connector: Rc<dyn Connector>,
+    pub old: ConnectorState,
+    pub new: ConnectorState,
+}
+
+impl ConnectorConfig {
+    fn apply(&self, from: ConnectorState, to: ConnectorState) -> Result<(), Box<dyn Error>> {
+        if to.enabled && !from.enabled {
+            self.connector.set_enabled(true)?;
+        }
+        if to.enabled && to.mode != from.mode {
+            self.connector.set_mode(to.mode)?;
+        }
+        if !to.enabled && from.enabled {
+            self.connector.set_enabled(false)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum ConnectorEvent {
     Connected(MonitorInfo),
@@ -383,6 +484,30 @@ pub enum InputEvent {
         source: Option<TabletStripEventSource>,
         position: Option<f64>,
     },
+
+    TouchDown {
+        time_usec: u64,
+        id: i32,
+        x_normed: f64,
+        y_normed: f64,
+    },
+    TouchUp {
+        time_usec: u64,
+        id: i32,
+    },
+    TouchMotion {
+        time_usec: u64,
+        id: i32,
+        x_normed: f64,
+        y_normed: f64,
+    },
+    TouchCancel {
+        time_usec: u64,
+        id: i32,
+    },
+    TouchFrame {
+        time_usec: u64,
+    },
 }
 
 pub enum DrmEvent {
@@ -53,7 +53,7 @@ impl Connector for DummyOutput {
         None
     }
 
-    fn set_mode(&self, _mode: Mode) {
-        // nothing
+    fn set_mode(&self, _mode: Mode) -> Result<(), Box<dyn Error>> {
+        Ok(())
     }
 }
@@ -6,7 +6,7 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            Backend, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
+            Backend, ConnectorConfig, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
             InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, TransformMatrix,
         },
         backends::metal::video::{
@@ -88,6 +88,10 @@ pub enum MetalError {
     NoPrimaryPlaneForConnector,
     #[error("Cannot initialize connector because no mode is available")]
     NoModeForConnector,
+    #[error("The connector is not connected to a desktop display")]
+    NotConnected,
+    #[error("The display does not support the requested mode")]
+    UnsupportedMode,
     #[error("Could not allocate scanout buffer")]
     ScanoutBuffer(#[source] GbmError),
     #[error("addfb2 failed")]
@@ -269,6 +273,16 @@ impl Backend for MetalBackend {
     fn supports_presentation_feedback(&self) -> bool {
         true
     }
+
+    fn test_connectors(&self, configs: &[ConnectorConfig]) -> Result<(), Box<dyn Error>> {
+        self.test_connector_configs(configs)?;
+        Ok(())
+    }
+
+    fn apply_connectors(self: Rc<Self>, configs: &[ConnectorConfig]) -> Result<(), Box<dyn Error>> {
+        self.apply_connector_configs(configs)?;
+        Ok(())
+    }
 }
 
 fn dup_fd(fd: c::c_int) -> Result<Rc<OwnedFd>, MetalError> {
@@ -3,8 +3,8 @@ use {
         async_engine::{Phase, SpawnedFuture},
         backend::{
             BackendDrmDevice, BackendDrmLease, BackendDrmLessee, BackendEvent, Connector,
-            ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, HardwareCursor, Mode,
-            MonitorInfo,
+            ConnectorConfig, ConnectorEvent, ConnectorId, ConnectorKernelId, ConnectorState,
+            DrmDeviceId, GammaLut, HardwareCursor, Mode, MonitorInfo,
         },
         backends::metal::{MetalBackend, MetalError},
         drm_feedback::DrmFeedback,
@@ -14,7 +14,11 @@ use {
             AcquireSync, BufferResv, GfxApiOpt, GfxContext, GfxFramebuffer, GfxRenderPass,
             GfxTexture, ReleaseSync, SyncFile,
         },
-        ifs::wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC},
+        ifs::{
+            wp_content_type_v1::ContentType,
+            wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC},
+        },
+        rect::{Rect, Region},
         renderer::RenderResult,
         state::State,
         theme::Color,
@@ -22,19 +26,29 @@ use {
         tree::OutputNode,
         udev::UdevDevice,
         utils::{
-            asyncevent::AsyncEvent, bitflags::BitflagsExt, cell_ext::CellExt, clonecell::CloneCell,
-            copyhashmap::CopyHashMap, debug_fn::debug_fn, errorfmt::ErrorFmt, numcell::NumCell,
-            on_change::OnChange, opaque_cell::OpaqueCell, oserror::OsError,
+            asyncevent::AsyncEvent,
+            bitflags::BitflagsExt,
+            cell_ext::CellExt,
+            clonecell::CloneCell,
+            color_temperature::{white_point, NEUTRAL_COLOR_TEMPERATURE},
+            copyhashmap::CopyHashMap,
+            debug_fn::debug_fn,
+            errorfmt::ErrorFmt,
+            numcell::NumCell,
+            on_change::OnChange,
+            opaque_cell::OpaqueCell,
+            oserror::OsError,
             transform_ext::TransformExt,
         },
         video::{
             dmabuf::DmaBufId,
             drm::{
-                drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector,
-                DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmLease, DrmMaster,
-                DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
+                drm_color_lut, drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob,
+                DrmConnector, DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmLease,
+                DrmMaster, DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
                 DrmPropertyType, DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC,
-                DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_EVENT,
+                DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC,
+                DRM_MODE_PAGE_FLIP_EVENT,
             },
             gbm::{GbmBo, GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING, GBM_BO_USE_SCANOUT},
             Modifier, INVALID_MODIFIER,
@@ -45,12 +59,13 @@ use {
     bstr::{BString, ByteSlice},
     indexmap::{indexset, IndexSet},
     isnt::std_1::collections::IsntHashMap2Ext,
-    jay_config::video::GfxApi,
+    jay_config::video::{GfxApi, VrrMode},
     once_cell::sync::Lazy,
     std::{
         any::Any,
         cell::{Cell, RefCell},
         collections::hash_map::Entry,
+        error::Error,
         ffi::CString,
         fmt::{Debug, Formatter},
         mem,
@@ -96,6 +111,7 @@ pub struct MetalDrmDevice {
     pub on_change: OnChange<crate::backend::DrmEvent>,
     pub direct_scanout_enabled: Cell<Option<bool>>,
     pub is_nvidia: bool,
+    pub supports_async_commit: bool,
     pub lease_ids: MetalLeaseIds,
     pub leases: CopyHashMap<MetalLeaseId, MetalLeaseData>,
     pub leases_to_break: CopyHashMap<MetalLeaseId, MetalLeaseData>,
@@ -284,6 +300,12 @@ impl Debug for HandleEvents {
 }
 
 #[derive(Debug)]
+struct ConnectorChange {
+    connector: Rc<MetalConnector>,
+    new: ConnectorState,
+    mode: Option<DrmModeInfo>,
+}
+
 pub struct MetalDrmDeviceData {
     pub dev: Rc<MetalDrmDevice>,
     pub connectors: CopyHashMap<DrmConnector, Rc<MetalConnector>>,
@@ -300,6 +322,7 @@ pub struct ConnectorDisplayData {
     pub refresh: u32,
     pub non_desktop: bool,
     pub non_desktop_effective: bool,
+    pub vrr_capable: bool,
 
     pub monitor_manufacturer: String,
     pub monitor_name: String,
@@ -412,6 +435,13 @@ pub struct MetalConnector {
 
     pub enabled: Cell<bool>,
     pub non_desktop_override: Cell<Option<bool>>,
+    pub tearing_enabled: Cell<bool>,
+    pub vrr_mode: Cell<VrrMode>,
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub color_temperature: Cell<u32>,
+    pub gamma_lut_changed: Cell<bool>,
+    pub primary_plane_geometry: Cell<Option<PlaneGeometry>>,
+    pub pending_primary_plane_geometry: Cell<Option<PlaneGeometry>>,
 
     pub lease: Cell<Option<MetalLeaseId>>,
 
@@ -570,11 +600,15 @@ pub struct DirectScanoutPosition {
     pub crtc_height: i32,
 }
 
+// crtc_x, crtc_y, crtc_w, crtc_h, src_w, src_h
+type PlaneGeometry = (i32, i32, i32, i32, i32, i32);
+
 #[derive(Debug)]
 pub struct PresentFb {
     fb: Rc<DrmFramebuffer>,
     direct_scanout_data: Option<DirectScanoutData>,
     sync_file: Option<SyncFile>,
+    tearing: bool,
 }
 
 impl MetalConnector {
@@ -595,7 +629,7 @@ impl MetalConnector {
                     self.state.ring.timeout(next_present).await.unwrap();
                 }
             }
-            match self.present(true) {
+            match self.present(true, true) {
                 Ok(_) => self.state.set_backend_idle(false),
                 Err(e) => {
                     log::error!("Could not present: {}", ErrorFmt(e));
@@ -870,9 +904,11 @@ impl MetalConnector {
         let fb;
         match &direct_scanout_data {
             None => {
+                let region = buffer.damage_region(&buffer_fb, output);
                 let sf = buffer_fb
-                    .perform_render_pass(pass)
+                    .perform_render_pass(pass, &region)
                     .map_err(MetalError::RenderFrame)?;
+                buffer.clear_damage();
                 sync_file = buffer.copy_to_dev(sf)?;
                 output.perform_screencopies(&buffer.render_tex, !render_hw_cursor, 0, 0, None);
                 fb = buffer.drm.clone();
@@ -891,10 +927,74 @@ impl MetalConnector {
             fb,
             direct_scanout_data,
             sync_file,
+            tearing: false,
         })
     }
 
-    pub fn present(&self, try_direct_scanout: bool) -> Result<(), MetalError> {
+    fn tearing_allowed(&self, output: &OutputNode) -> bool {
+        if !self.tearing_enabled.get() || !self.dev.supports_async_commit {
+            return false;
+        }
+        let Some(ws) = output.workspace.get() else {
+            return false;
+        };
+        let Some(fullscreen) = ws.fullscreen.get() else {
+            return false;
+        };
+        match fullscreen.tl_scanout_surface() {
+            Some(surface) => surface.tearing.get(),
+            None => false,
+        }
+    }
+
+    fn vrr_wanted(&self, output: &OutputNode) -> bool {
+        if !self.display.borrow().vrr_capable {
+            return false;
+        }
+        let fullscreen = || output.workspace.get().and_then(|ws| ws.fullscreen.get());
+        match self.vrr_mode.get() {
+            VrrMode::Never => false,
+            VrrMode::Always => true,
+            VrrMode::FullscreenOnly => fullscreen().is_some(),
+            VrrMode::FullscreenWithContentTypeGame => fullscreen()
+                .and_then(|f| f.tl_scanout_surface())
+                .map(|s| s.content_type.get() == Some(ContentType::Game))
+                .unwrap_or(false),
+        }
+    }
+
+    fn create_gamma_lut_blob(&self, crtc: &MetalCrtc) -> Result<Option<PropBlob>, DrmError> {
+        let size = crtc.gamma_lut_size as usize;
+        let lut = self.gamma_lut.get().filter(|l| l.red.len() == size);
+        let temperature = self.color_temperature.get();
+        if size == 0 || (lut.is_none() && temperature == NEUTRAL_COLOR_TEMPERATURE) {
+            return Ok(None);
+        }
+        let [wr, wg, wb] = white_point(temperature);
+        let mut entries = Vec::with_capacity(size);
+        for i in 0..size {
+            let (r, g, b) = match &lut {
+                Some(lut) => (lut.red[i], lut.green[i], lut.blue[i]),
+                None => {
+                    let v = (i * 0xffff / (size - 1).max(1)) as u16;
+                    (v, v, v)
+                }
+            };
+            entries.push(drm_color_lut {
+                red: (r as f64 * wr) as u16,
+                green: (g as f64 * wg) as u16,
+                blue: (b as f64 * wb) as u16,
+                reserved: 0,
+            });
+        }
+        self.master.create_blob(&entries[..]).map(Some)
+    }
+
+    pub fn present(
+        &self,
+        try_direct_scanout: bool,
+        try_async_flip: bool,
+    ) -> Result<(), MetalError> {
         let crtc = match self.crtc.get() {
             Some(crtc) => crtc,
             _ => return Ok(()),
@@ -915,7 +1015,27 @@ impl MetalConnector {
         };
         let cursor = self.cursor_plane.get();
         let mut new_fb = None;
+        let mut tearing = false;
+        let mut vrr_enabled = None;
+        let mut gamma_lut_blob = None;
         let mut changes = self.master.change();
+        if self.gamma_lut_changed.get() {
+            if let Some(prop) = crtc.gamma_lut {
+                match self.create_gamma_lut_blob(&crtc) {
+                    Ok(blob) => {
+                        let id = blob.as_ref().map(|b| b.id().0).unwrap_or(0);
+                        changes.change_object(crtc.id, |c| {
+                            c.change(prop, id as _);
+                        });
+                        gamma_lut_blob = Some(blob);
+                    }
+                    Err(e) => {
+                        log::error!("Could not create gamma LUT blob: {}", ErrorFmt(e));
+                        self.gamma_lut_changed.set(false);
+                    }
+                }
+            }
+        }
         if self.has_damage.get() {
             if !self.backend.check_render_context(&self.dev) {
                 return Ok(());
@@ -923,10 +1043,10 @@ impl MetalConnector {
             if let Some(node) = self.state.root.outputs.get(&self.connector_id) {
                 let buffer = &buffers[self.next_buffer.get() % buffers.len()];
                 let mut rr = self.render_result.borrow_mut();
-                let fb =
+                let mut fb =
                     self.prepare_present_fb(&mut rr, buffer, &plane, &node, try_direct_scanout)?;
                 rr.dispatch_frame_requests();
-                let (crtc_x, crtc_y, crtc_w, crtc_h, src_width, src_height) =
+                let geometry @ (crtc_x, crtc_y, crtc_w, crtc_h, src_width, src_height) =
                     match &fb.direct_scanout_data {
                         None => {
                             let plane_w = plane.mode_w.get();
@@ -945,9 +1065,37 @@ impl MetalConnector {
                             )
                         }
                     };
+                let vrr = self.vrr_wanted(&node);
+                // Async commits can only change the framebuffer of the primary plane.
+                tearing = try_async_flip
+                    && fb.direct_scanout_data.is_some()
+                    && fb.sync_file.is_none()
+                    && !(self.cursor_changed.get() && cursor.is_some())
+                    && self.primary_plane_geometry.get() == Some(geometry)
+                    && crtc
+                        .vrr_enabled
+                        .as_ref()
+                        .map(|p| p.value.get())
+                        .unwrap_or(false)
+                        == vrr
+                    && gamma_lut_blob.is_none()
+                    && self.tearing_allowed(&node);
+                fb.tearing = tearing;
+                if let Some(prop) = &crtc.vrr_enabled {
+                    if prop.value.get() != vrr {
+                        changes.change_object(crtc.id, |c| {
+                            c.change(prop.id, vrr as _);
+                        });
+                        vrr_enabled = Some(vrr);
+                    }
+                }
+                self.pending_primary_plane_geometry.set(Some(geometry));
                 let in_fence = fb.sync_file.as_ref().map(|s| s.raw()).unwrap_or(-1);
                 changes.change_object(plane.id, |c| {
                     c.change(plane.fb_id, fb.fb.id().0 as _);
+                    if tearing {
+                        return;
+                    }
                     c.change(plane.src_w.id, (src_width as u64) << 16);
                     c.change(plane.src_h.id, (src_height as u64) << 16);
                     c.change(plane.crtc_x.id, crtc_x as u64);
@@ -1001,7 +1149,11 @@ impl MetalConnector {
                 });
             }
         }
-        if let Err(e) = changes.commit(DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT, 0) {
+        let mut flags = DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT;
+        if tearing {
+            flags |= DRM_MODE_PAGE_FLIP_ASYNC;
+        }
+        if let Err(e) = changes.commit(flags, 0) {
             if let DrmError::Atomic(OsError(c::EACCES)) = e {
                 log::debug!("Could not perform atomic commit, likely because we're no longer the DRM master");
                 self.render_result
@@ -1009,9 +1161,13 @@ impl MetalConnector {
                     .discard_presentation_feedback();
                 return Ok(());
             }
+            if tearing {
+                log::debug!("Could not perform async page flip: {}", ErrorFmt(&e));
+                return self.present(try_direct_scanout, false);
+            }
             if let Some(fb) = &new_fb {
                 if let Some(dsd) = &fb.direct_scanout_data {
-                    if self.present(false).is_ok() {
+                    if self.present(false, try_async_flip).is_ok() {
                         let mut cache = self.scanout_buffers.borrow_mut();
                         if let Some(buffer) = cache.remove(&dsd.dma_buf_id) {
                             cache.insert(
@@ -1035,8 +1191,17 @@ impl MetalConnector {
                 if fb.direct_scanout_data.is_none() {
                     self.next_buffer.fetch_add(1);
                 }
+                self.primary_plane_geometry
+                    .set(self.pending_primary_plane_geometry.get());
                 self.next_framebuffer.set(Some(fb));
             }
+            if let (Some(vrr), Some(prop)) = (vrr_enabled, &crtc.vrr_enabled) {
+                prop.value.set(vrr);
+            }
+            if let Some(blob) = gamma_lut_blob {
+                crtc.gamma_lut_blob.set(blob.map(Rc::new));
+                self.gamma_lut_changed.set(false);
+            }
             if cursor_swap_buffer {
                 self.cursor_swap_buffer.set(false);
                 self.cursor_front_buffer.fetch_add(1);
@@ -1049,6 +1214,14 @@ impl MetalConnector {
         }
     }
 
+    fn damage_buffers(&self, rect: Option<Rect>) {
+        if let Some(buffers) = self.buffers.get() {
+            for buffer in buffers.iter() {
+                buffer.add_damage(rect);
+            }
+        }
+    }
+
     pub fn update_drm_feedback(&self) {
         let fb = self.compute_drm_feedback();
         self.drm_feedback.set(fb);
@@ -1170,6 +1343,15 @@ impl Connector for MetalConnector {
     }
 
     fn damage(&self) {
+        self.damage_buffers(None);
+        self.has_damage.set(true);
+        if self.can_present.get() {
+            self.schedule_present();
+        }
+    }
+
+    fn damage_rect(&self, rect: Rect) {
+        self.damage_buffers(Some(rect));
         self.has_damage.set(true);
         if self.can_present.get() {
             self.schedule_present();
@@ -1184,65 +1366,137 @@ impl Connector for MetalConnector {
         self.enabled.get()
     }
 
-    fn set_enabled(&self, enabled: bool) {
+    fn set_enabled(&self, enabled: bool) -> Result<(), Box<dyn Error>> {
         if self.enabled.replace(enabled) != enabled {
             if self.display.borrow_mut().connection == ConnectorStatus::Connected {
                 if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
                     if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
                         dev.unprocessed_change.set(true);
-                        log::error!("Could not dis/enable connector: {}", ErrorFmt(e));
+                        log::error!("Could not dis/enable connector: {}", ErrorFmt(&e));
+                        return Err(Box::new(e));
                     }
                 }
             }
         }
+        Ok(())
     }
 
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         self.drm_feedback.get()
     }
 
-    fn set_mode(&self, be_mode: Mode) {
+    fn set_mode(&self, be_mode: Mode) -> Result<(), Box<dyn Error>> {
         match self.frontend_state.get() {
             FrontState::Connected { non_desktop: false } => {}
             FrontState::Connected { non_desktop: true }
             | FrontState::Removed
             | FrontState::Disconnected
-            | FrontState::Unavailable => return,
+            | FrontState::Unavailable => return Err(Box::new(MetalError::NotConnected)),
         }
         let mut dd = self.display.borrow_mut();
         let Some(mode) = dd.modes.iter().find(|m| m.to_backend() == be_mode) else {
-            log::warn!("Connector does not support mode {:?}", be_mode);
-            return;
+            return Err(Box::new(MetalError::UnsupportedMode));
         };
         let prev = dd.mode.clone();
         if prev.as_ref() == Some(mode) {
-            return;
+            return Ok(());
         }
         if dd.connection != ConnectorStatus::Connected {
-            log::warn!("Cannot change mode of connector that is not connected");
-            return;
+            return Err(Box::new(MetalError::NotConnected));
         }
         let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) else {
             log::warn!("Cannot change mode because underlying device does not exist?");
-            return;
+            return Err(Box::new(MetalError::NotConnected));
         };
         log::info!("Trying to change mode from {:?} to {:?}", prev, mode);
         dd.mode = Some(mode.clone());
         drop(dd);
         let Err(e) = self.backend.handle_drm_change_(&dev, true) else {
             self.send_event(ConnectorEvent::ModeChanged(be_mode));
-            return;
+            return Ok(());
         };
         log::warn!("Could not change mode: {}", ErrorFmt(&e));
         self.display.borrow_mut().mode = prev;
         if let MetalError::Modeset(DrmError::Atomic(OsError(c::EACCES))) = e {
             log::warn!("Failed due to access denied. Resetting in memory only.");
-            return;
+            return Err(Box::new(e));
         }
         log::warn!("Trying to re-initialize the drm device");
         if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
             log::warn!("Could not restore the previous mode: {}", ErrorFmt(e));
         };
+        Err(Box::new(e))
+    }
+
+    fn test_mode(&self, be_mode: Mode) -> Result<(), Box<dyn Error>> {
+        match self.frontend_state.get() {
+            FrontState::Connected { non_desktop: false } => {}
+            FrontState::Connected { non_desktop: true }
+            | FrontState::Removed
+            | FrontState::Disconnected
+            | FrontState::Unavailable => return Err(Box::new(MetalError::NotConnected)),
+        }
+        let dd = self.display.borrow();
+        let Some(mode) = dd.modes.iter().find(|m| m.to_backend() == be_mode) else {
+            return Err(Box::new(MetalError::UnsupportedMode));
+        };
+        if dd.connection != ConnectorStatus::Connected {
+            return Err(Box::new(MetalError::NotConnected));
+        }
+        let crtc = match self.crtc.get() {
+            Some(crtc) => crtc,
+            None => match dd
+                .crtcs
+                .values()
+                .find(|c| c.connector.is_none() && c.lease.is_none())
+            {
+                Some(crtc) => crtc.clone(),
+                None => return Err(Box::new(MetalError::NoCrtcForConnector)),
+            },
+        };
+        let mode_blob = mode.create_blob(&self.master)?;
+        let mut changes = self.master.change();
+        changes.change_object(self.id, |c| {
+            c.change(dd.crtc_id.id, crtc.id.0 as _);
+        });
+        changes.change_object(crtc.id, |c| {
+            c.change(crtc.active.id, 1);
+            c.change(crtc.mode_id.id, mode_blob.id().0 as _);
+        });
+        if let Err(e) = changes.test(DRM_MODE_ATOMIC_ALLOW_MODESET) {
+            return Err(Box::new(MetalError::Modeset(e)));
+        }
+        Ok(())
+    }
+
+    fn set_tearing_enabled(&self, enabled: bool) {
+        self.tearing_enabled.set(enabled);
+    }
+
+    fn set_vrr_mode(&self, mode: VrrMode) {
+        if self.vrr_mode.replace(mode) != mode {
+            self.damage_rect(Rect::default());
+        }
+    }
+
+    fn gamma_lut_size(&self) -> u32 {
+        match self.crtc.get() {
+            Some(crtc) if crtc.gamma_lut.is_some() => crtc.gamma_lut_size,
+            _ => 0,
+        }
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
+        self.gamma_lut_changed.set(true);
+        self.damage_rect(Rect::default());
+    }
+
+    fn set_color_temperature(&self, kelvin: u32) {
+        if self.color_temperature.replace(kelvin) != kelvin {
+            self.gamma_lut_changed.set(true);
+            self.damage_rect(Rect::default());
+        }
     }
 
     fn set_non_desktop_override(&self, non_desktop: Option<bool>) {
@@ -1283,8 +1537,12 @@ pub struct MetalCrtc {
     pub active: MutableProperty<bool>,
     pub mode_id: MutableProperty<DrmBlob>,
     pub out_fence_ptr: DrmProperty,
+    pub vrr_enabled: Option<MutableProperty<bool>>,
+    pub gamma_lut: Option<DrmProperty>,
+    pub gamma_lut_size: u32,
 
     pub mode_blob: CloneCell<Option<Rc<PropBlob>>>,
+    pub gamma_lut_blob: CloneCell<Option<Rc<PropBlob>>>,
 }
 
 impl Debug for MetalCrtc {
@@ -1389,6 +1647,13 @@ fn create_connector(
         next_buffer: Default::default(),
         enabled: Cell::new(true),
         non_desktop_override: Default::default(),
+        tearing_enabled: Cell::new(false),
+        vrr_mode: Default::default(),
+        gamma_lut: Default::default(),
+        color_temperature: Cell::new(NEUTRAL_COLOR_TEMPERATURE),
+        gamma_lut_changed: Cell::new(false),
+        primary_plane_geometry: Default::default(),
+        pending_primary_plane_geometry: Default::default(),
         lease: Cell::new(None),
         can_present: Cell::new(true),
         has_damage: Cell::new(true),
@@ -1516,6 +1781,11 @@ fn create_connector_display_data(
     let props = collect_properties(&dev.master, connector)?;
     let connector_type = ConnectorType::from_drm(info.connector_type);
     let non_desktop = props.get("non-desktop")?.value.get() != 0;
+    let vrr_capable = props
+        .get("vrr_capable")
+        .map(|p| p.value.get() != 0)
+        .unwrap_or(false)
+        && crtcs.values().all(|c| c.vrr_enabled.is_some());
     Ok(ConnectorDisplayData {
         crtc_id: props.get("CRTC_ID")?.map(|v| DrmCrtc(v as _)),
         crtcs,
@@ -1524,6 +1794,7 @@ fn create_connector_display_data(
         refresh,
         non_desktop,
         non_desktop_effective: non_desktop_override.unwrap_or(non_desktop),
+        vrr_capable,
         monitor_manufacturer: manufacturer,
         monitor_name: name,
         monitor_serial_number: serial_number,
@@ -1578,7 +1849,14 @@ fn create_crtc(
         active: props.get("ACTIVE")?.map(|v| v == 1),
         mode_id: props.get("MODE_ID")?.map(|v| DrmBlob(v as u32)),
         out_fence_ptr: props.get("OUT_FENCE_PTR")?.id,
+        vrr_enabled: props.get("VRR_ENABLED").ok().map(|p| p.map(|v| v == 1)),
+        gamma_lut: props.get("GAMMA_LUT").ok().map(|p| p.id),
+        gamma_lut_size: props
+            .get("GAMMA_LUT_SIZE")
+            .map(|p| p.value.get() as u32)
+            .unwrap_or(0),
         mode_blob: Default::default(),
+        gamma_lut_blob: Default::default(),
     })
 }
 
@@ -1911,6 +2189,183 @@ impl MetalBackend {
         Ok(())
     }
 
+    fn group_connector_configs(
+        &self,
+        configs: &[ConnectorConfig],
+    ) -> Result<Vec<(Rc<MetalDrmDeviceData>, Vec<ConnectorChange>)>, MetalError> {
+        let mut devices = Vec::<(Rc<MetalDrmDeviceData>, Vec<ConnectorChange>)>::new();
+        for config in configs {
+            let id = config.connector.id();
+            let mut found = None;
+            for dev in self.device_holder.drm_devices.lock().values() {
+                for connector in dev.connectors.lock().values() {
+                    if connector.connector_id == id {
+                        found = Some((dev.clone(), connector.clone()));
+                    }
+                }
+            }
+            let Some((dev, connector)) = found else {
+                return Err(MetalError::NotConnected);
+            };
+            let mut mode = None;
+            if config.new.enabled {
+                let dd = connector.display.borrow();
+                if dd.connection != ConnectorStatus::Connected || dd.non_desktop_effective {
+                    return Err(MetalError::NotConnected);
+                }
+                match dd.modes.iter().find(|m| m.to_backend() == config.new.mode) {
+                    Some(m) => mode = Some(m.clone()),
+                    None => return Err(MetalError::UnsupportedMode),
+                }
+            }
+            let change = ConnectorChange {
+                connector,
+                new: config.new,
+                mode,
+            };
+            match devices
+                .iter_mut()
+                .find(|(d, _)| d.dev.devnum == dev.dev.devnum)
+            {
+                Some((_, changes)) => changes.push(change),
+                None => devices.push((dev, vec![change])),
+            }
+        }
+        Ok(devices)
+    }
+
+    /// Tests the configuration with a single atomic commit per device.
+    pub fn test_connector_configs(&self, configs: &[ConnectorConfig]) -> Result<(), MetalError> {
+        for (dev, changes) in self.group_connector_configs(configs)? {
+            let mut atomic = dev.dev.master.change();
+            let mut released_crtcs = AHashSet::new();
+            for change in &changes {
+                if change.new.enabled {
+                    continue;
+                }
+                let Some(crtc) = change.connector.crtc.get() else {
+                    continue;
+                };
+                released_crtcs.insert(crtc.id);
+                let dd = change.connector.display.borrow();
+                atomic.change_object(change.connector.id, |c| {
+                    c.change(dd.crtc_id.id, 0);
+                });
+                atomic.change_object(crtc.id, |c| {
+                    c.change(crtc.active.id, 0);
+                    c.change(crtc.mode_id.id, 0);
+                });
+                for plane in crtc.possible_planes.values() {
+                    if plane.crtc_id.value.get() == crtc.id {
+                        atomic.change_object(plane.id, |c| {
+                            c.change(plane.crtc_id.id, 0);
+                            c.change(plane.fb_id, 0);
+                        });
+                    }
+                }
+            }
+            let mut used_crtcs = AHashSet::new();
+            let mut mode_blobs = vec![];
+            for change in &changes {
+                let Some(mode) = &change.mode else {
+                    continue;
+                };
+                let dd = change.connector.display.borrow();
+                let crtc = match change.connector.crtc.get() {
+                    Some(crtc) => crtc,
+                    None => match dd.crtcs.values().find(|c| {
+                        (c.connector.is_none() || released_crtcs.contains(&c.id))
+                            && c.lease.is_none()
+                            && !used_crtcs.contains(&c.id)
+                    }) {
+                        Some(crtc) => crtc.clone(),
+                        None => return Err(MetalError::NoCrtcForConnector),
+                    },
+                };
+                used_crtcs.insert(crtc.id);
+                let mode_blob = mode.create_blob(&dev.dev.master)?;
+                atomic.change_object(change.connector.id, |c| {
+                    c.change(dd.crtc_id.id, crtc.id.0 as _);
+                });
+                atomic.change_object(crtc.id, |c| {
+                    c.change(crtc.active.id, 1);
+                    c.change(crtc.mode_id.id, mode_blob.id().0 as _);
+                });
+                mode_blobs.push(mode_blob);
+            }
+            if let Err(e) = atomic.test(DRM_MODE_ATOMIC_ALLOW_MODESET) {
+                return Err(MetalError::Modeset(e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the configuration with a single modeset per device.
+    ///
+    /// If any device fails, the previous state of all connectors is restored.
+    pub fn apply_connector_configs(
+        self: &Rc<Self>,
+        configs: &[ConnectorConfig],
+    ) -> Result<(), MetalError> {
+        let devices = self.group_connector_configs(configs)?;
+        let mut prev = vec![];
+        for (_, changes) in &devices {
+            for change in changes {
+                let connector = &change.connector;
+                let mut dd = connector.display.borrow_mut();
+                prev.push((connector.clone(), connector.enabled.get(), dd.mode.clone()));
+                connector.enabled.set(change.new.enabled);
+                if change.mode.is_some() {
+                    dd.mode = change.mode.clone();
+                }
+            }
+        }
+        let mut res = Ok(());
+        for (dev, _) in &devices {
+            if let Err(e) = self.handle_drm_change_(dev, true) {
+                res = Err(e);
+                break;
+            }
+        }
+        let Err(e) = res else {
+            for (connector, enabled, mode) in prev {
+                let dd = connector.display.borrow();
+                if enabled && connector.enabled.get() && dd.mode != mode {
+                    if let FrontState::Connected { non_desktop: false } =
+                        connector.frontend_state.get()
+                    {
+                        if let Some(mode) = &dd.mode {
+                            connector.send_event(ConnectorEvent::ModeChanged(mode.to_backend()));
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        };
+        log::warn!(
+            "Could not apply the connector configuration: {}",
+            ErrorFmt(&e)
+        );
+        for (connector, enabled, mode) in prev {
+            connector.enabled.set(enabled);
+            connector.display.borrow_mut().mode = mode;
+        }
+        if let MetalError::Modeset(DrmError::Atomic(OsError(c::EACCES))) = e {
+            log::warn!("Failed due to access denied. Resetting in memory only.");
+            return Err(e);
+        }
+        for (dev, _) in &devices {
+            if let Err(e) = self.handle_drm_change_(dev, true) {
+                dev.unprocessed_change.set(true);
+                log::error!(
+                    "Could not restore the previous connector configuration: {}",
+                    ErrorFmt(e)
+                );
+            }
+        }
+        Err(e)
+    }
+
     fn send_connected(&self, connector: &Rc<MetalConnector>, dd: &ConnectorDisplayData) {
         match connector.frontend_state.get() {
             FrontState::Removed | FrontState::Connected { .. } | FrontState::Unavailable => {
@@ -1934,6 +2389,7 @@ impl MetalBackend {
             width_mm: dd.mm_width as _,
             height_mm: dd.mm_height as _,
             non_desktop: dd.non_desktop_effective,
+            vrr_capable: dd.vrr_capable,
         }));
         connector.send_hardware_cursor();
     }
@@ -2038,6 +2494,7 @@ impl MetalBackend {
             ctx: CloneCell::new(ctx),
             on_change: Default::default(),
             direct_scanout_enabled: Default::default(),
+            supports_async_commit: master.supports_async_commit(),
             is_nvidia,
             lease_ids: Default::default(),
             leases: Default::default(),
@@ -2097,6 +2554,9 @@ impl MetalBackend {
             c.mode_id
                 .value
                 .set(DrmBlob(get(&props, c.mode_id.id)? as _));
+            if let Some(vrr_enabled) = &c.vrr_enabled {
+                vrr_enabled.value.set(get(&props, vrr_enabled.id)? != 0);
+            }
         }
         for c in dev.dev.planes.values() {
             let props = collect_untyped_properties(master, c.id)?;
@@ -2113,6 +2573,7 @@ impl MetalBackend {
     ) -> Result<(), MetalError> {
         for connector in dev.connectors.lock().values() {
             connector.can_present.set(true);
+            connector.damage_buffers(None);
             connector.has_damage.set(true);
             connector.cursor_changed.set(true);
         }
@@ -2172,9 +2633,9 @@ impl MetalBackend {
             _ => return,
         };
         connector.can_present.set(true);
-        connector
-            .active_framebuffer
-            .set(connector.next_framebuffer.take());
+        let fb = connector.next_framebuffer.take();
+        let tearing = fb.as_ref().map(|fb| fb.tearing).unwrap_or(false);
+        connector.active_framebuffer.set(fb);
         if connector.has_damage.get() || connector.cursor_changed.get() {
             connector.schedule_present();
         }
@@ -2199,7 +2660,10 @@ impl MetalBackend {
                         tv_usec * 1000,
                         refresh,
                         sequence as _,
-                        KIND_VSYNC | KIND_HW_COMPLETION,
+                        match tearing {
+                            true => KIND_HW_COMPLETION,
+                            false => KIND_VSYNC | KIND_HW_COMPLETION,
+                        },
                     );
                     let _ = fb.client.remove_obj(&*fb);
                 }
@@ -2253,9 +2717,19 @@ impl MetalBackend {
             crtc.connector.set(None);
             crtc.active.value.set(false);
             crtc.mode_id.value.set(DrmBlob::NONE);
+            if let Some(vrr_enabled) = &crtc.vrr_enabled {
+                vrr_enabled.value.set(false);
+            }
+            crtc.gamma_lut_blob.take();
             changes.change_object(crtc.id, |c| {
                 c.change(crtc.active.id, 0);
                 c.change(crtc.mode_id.id, 0);
+                if let Some(vrr_enabled) = &crtc.vrr_enabled {
+                    c.change(vrr_enabled.id, 0);
+                }
+                if let Some(gamma_lut) = crtc.gamma_lut {
+                    c.change(gamma_lut, 0);
+                }
                 c.change(crtc.out_fence_ptr, 0);
             })
         }
@@ -2688,6 +3162,8 @@ impl MetalBackend {
             dev_tex,
             render_tex,
             render_fb,
+            damage: Default::default(),
+            damage_full: Cell::new(true),
         })
     }
 
@@ -2726,6 +3202,7 @@ impl MetalBackend {
         crtc.active.value.set(true);
         crtc.mode_id.value.set(mode_blob.id());
         crtc.mode_blob.set(Some(Rc::new(mode_blob)));
+        connector.gamma_lut_changed.set(true);
         Ok(())
     }
 
@@ -2886,8 +3363,13 @@ pub struct RenderBuffer {
     // ctx = render
     // buffer location = render
     render_fb: Option<Rc<dyn GfxFramebuffer>>,
+    // output-local, logical coordinates
+    damage: RefCell<Vec<Rect>>,
+    damage_full: Cell<bool>,
 }
 
+const MAX_DAMAGE_RECTS: usize = 32;
+
 impl RenderBuffer {
     fn render_fb(&self) -> Rc<dyn GfxFramebuffer> {
         self.render_fb
@@ -2895,6 +3377,43 @@ impl RenderBuffer {
             .unwrap_or_else(|| self.dev_fb.clone())
     }
 
+    fn add_damage(&self, rect: Option<Rect>) {
+        if self.damage_full.get() {
+            return;
+        }
+        let Some(rect) = rect else {
+            self.damage_full.set(true);
+            self.damage.borrow_mut().clear();
+            return;
+        };
+        if rect.is_empty() {
+            return;
+        }
+        let damage = &mut *self.damage.borrow_mut();
+        if damage.len() >= MAX_DAMAGE_RECTS {
+            self.damage_full.set(true);
+            damage.clear();
+        } else {
+            damage.push(rect);
+        }
+    }
+
+    fn clear_damage(&self) {
+        self.damage_full.set(false);
+        self.damage.borrow_mut().clear();
+    }
+
+    fn damage_region(&self, fb: &Rc<dyn GfxFramebuffer>, output: &OutputNode) -> Rc<Region> {
+        if self.damage_full.get() {
+            return fb.full_region();
+        }
+        fb.damage_region(
+            &self.damage.borrow(),
+            output.global.persistent.scale.get(),
+            output.global.persistent.transform.get(),
+        )
+    }
+
     fn copy_to_dev(&self, sync_file: Option<SyncFile>) -> Result<Option<SyncFile>, MetalError> {
         let Some(tex) = &self.dev_tex else {
             return Ok(sync_file);
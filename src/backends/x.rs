@@ -104,6 +104,8 @@ pub enum XBackendError {
     Libloading(#[from] libloading::Error),
     #[error("An unspecified X error occurred")]
     XconError(#[from] XconError),
+    #[error("The X backend does not support changing the connector mode")]
+    SetMode,
     #[error("Could not create a window")]
     CreateWindow(#[source] XconError),
     #[error("Could not set WM_CLASS")]
@@ -576,6 +578,7 @@ impl XBackend {
             width_mm: output.width.get(),
             height_mm: output.height.get(),
             non_desktop: false,
+            vrr_capable: false,
         }));
         output.changed();
         self.present(output).await;
@@ -1068,8 +1071,8 @@ impl Connector for XOutput {
         Some(self.backend.drm_device_id)
     }
 
-    fn set_mode(&self, _mode: Mode) {
-        log::warn!("X backend doesn't support changing the connector mode");
+    fn set_mode(&self, _mode: Mode) -> Result<(), Box<dyn Error>> {
+        Err(Box::new(XBackendError::SetMode))
     }
 }
 
@@ -2,17 +2,19 @@ mod generate;
 mod idle;
 mod input;
 mod log;
+mod msg;
 mod quit;
 mod randr;
 mod run_privileged;
 pub mod screenshot;
 mod seat_test;
 mod set_log_level;
+mod tree;
 mod unlock;
 
 use {
     crate::{
-        cli::{input::InputArgs, randr::RandrArgs},
+        cli::{input::InputArgs, msg::MsgArgs, randr::RandrArgs},
         compositor::start_compositor,
         portal,
     },
@@ -65,6 +67,10 @@ pub enum Cmd {
     Randr(RandrArgs),
     /// Inspect/modify input settings.
     Input(InputArgs),
+    /// Query and control the compositor via the IPC socket.
+    Msg(MsgArgs),
+    /// Print the window tree.
+    Tree(TreeArgs),
     #[cfg(feature = "it")]
     RunTests,
 }
@@ -169,6 +175,13 @@ pub struct LogArgs {
     pager_end: bool,
 }
 
+#[derive(Args, Debug)]
+pub struct TreeArgs {
+    /// Print the tree as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
 #[derive(Args, Debug)]
 pub struct SetLogArgs {
     /// The new log level.
@@ -241,6 +254,8 @@ pub fn main() {
         Cmd::Portal => portal::run_freestanding(cli.global),
         Cmd::Randr(a) => randr::main(cli.global, a),
         Cmd::Input(a) => input::main(cli.global, a),
+        Cmd::Msg(a) => msg::main(cli.global, a),
+        Cmd::Tree(a) => tree::main(cli.global, a),
         #[cfg(feature = "it")]
         Cmd::RunTests => crate::it::run_tests(),
     }
@@ -0,0 +1,133 @@
+use {
+    crate::{
+        cli::GlobalArgs,
+        compositor::{JAY_IPC_SOCKET, WAYLAND_DISPLAY},
+        json_ipc::messages::{EventType, Reply, Request},
+        logger::Logger,
+        utils::{errorfmt::ErrorFmt, xrd::xrd},
+    },
+    clap::{Args, Subcommand, ValueEnum},
+    std::{
+        io::{BufRead, BufReader, Write},
+        os::unix::net::UnixStream,
+    },
+};
+
+#[derive(Args, Debug)]
+pub struct MsgArgs {
+    #[clap(subcommand)]
+    pub command: MsgCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MsgCmd {
+    /// Print the window tree.
+    Tree,
+    /// Print the workspaces.
+    Workspaces,
+    /// Print the outputs.
+    Outputs,
+    /// Print the input devices.
+    Inputs,
+    /// Run an action.
+    ///
+    /// The action uses the syntax of actions in the TOML config, e.g. `focus-left` or
+    /// `{ type = "exec", exec = "alacritty" }`.
+    Command(CommandArgs),
+    /// Print events as they occur.
+    Subscribe(SubscribeArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// The action to run.
+    #[clap(required = true)]
+    pub command: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct SubscribeArgs {
+    /// The events to subscribe to.
+    #[clap(value_enum, required = true)]
+    pub events: Vec<MsgEvent>,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+pub enum MsgEvent {
+    /// A window was created, closed, renamed, or focused.
+    Window,
+    /// A workspace was created, destroyed, shown, renamed, or moved.
+    Workspace,
+    /// An output was connected or disconnected or its mode changed.
+    Output,
+    /// The binding mode of a seat changed.
+    Mode,
+}
+
+pub fn main(global: GlobalArgs, args: MsgArgs) {
+    Logger::install_stderr(global.log_level.into());
+    let mut subscribe = false;
+    let request = match args.command {
+        MsgCmd::Tree => Request::GetTree,
+        MsgCmd::Workspaces => Request::GetWorkspaces,
+        MsgCmd::Outputs => Request::GetOutputs,
+        MsgCmd::Inputs => Request::GetInputs,
+        MsgCmd::Command(a) => Request::Command {
+            command: a.command.join(" "),
+        },
+        MsgCmd::Subscribe(a) => {
+            subscribe = true;
+            let events = a
+                .events
+                .iter()
+                .map(|e| match e {
+                    MsgEvent::Window => EventType::Window,
+                    MsgEvent::Workspace => EventType::Workspace,
+                    MsgEvent::Output => EventType::Output,
+                    MsgEvent::Mode => EventType::Mode,
+                })
+                .collect();
+            Request::Subscribe { events }
+        }
+    };
+    let path = socket_path();
+    let stream = match UnixStream::connect(&path) {
+        Ok(s) => s,
+        Err(e) => fatal!("Could not connect to {}: {}", path, ErrorFmt(e)),
+    };
+    let mut msg = serde_json::to_vec(&request).unwrap();
+    msg.push(b'\n');
+    if let Err(e) = (&stream).write_all(&msg) {
+        fatal!("Could not send the request: {}", ErrorFmt(e));
+    }
+    let mut lines = BufReader::new(&stream).lines();
+    let mut read_line = || match lines.next() {
+        Some(Ok(line)) => line,
+        Some(Err(e)) => fatal!("Could not read the reply: {}", ErrorFmt(e)),
+        None => fatal!("The compositor closed the connection"),
+    };
+    let reply = read_line();
+    match serde_json::from_str::<Reply>(&reply) {
+        Ok(Reply::Error { message }) => fatal!("{}", message),
+        Ok(Reply::Success | Reply::Subscribed) => {}
+        _ => println!("{}", reply),
+    }
+    if subscribe {
+        loop {
+            println!("{}", read_line());
+        }
+    }
+}
+
+fn socket_path() -> String {
+    if let Ok(path) = std::env::var(JAY_IPC_SOCKET) {
+        return path;
+    }
+    let Some(xrd) = xrd() else {
+        fatal!("XDG_RUNTIME_DIR is not set");
+    };
+    let Ok(wd) = std::env::var(WAYLAND_DISPLAY) else {
+        fatal!("Neither {} nor {} is set", JAY_IPC_SOCKET, WAYLAND_DISPLAY);
+    };
+    format!("{}/{}.ipc", xrd, wd)
+}
@@ -3,12 +3,15 @@ use {
         cli::GlobalArgs,
         scale::Scale,
         tools::tool_client::{with_tool_client, Handle, ToolClient},
-        utils::transform_ext::TransformExt,
+        utils::{
+            color_temperature::NEUTRAL_COLOR_TEMPERATURE, transform_ext::TransformExt,
+            vrr_mode_ext::VrrModeExt,
+        },
         wire::{jay_compositor, jay_randr, JayRandrId},
     },
     clap::{Args, Subcommand, ValueEnum},
     isnt::std_1::vec::IsntVecExt,
-    jay_config::video::Transform,
+    jay_config::video::{Transform, VrrMode},
     std::{
         cell::RefCell,
         fmt::{Display, Formatter},
@@ -117,6 +120,34 @@ pub enum OutputCommand {
     Disable,
     /// Override the display's non-desktop setting.
     NonDesktop(NonDesktopArgs),
+    /// Modify the variable refresh rate mode of the output.
+    Vrr(VrrArgs),
+    /// Modify the color temperature of the output.
+    ColorTemperature(ColorTemperatureArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ColorTemperatureArgs {
+    /// The color temperature in Kelvin. 6500 is neutral.
+    pub kelvin: u32,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum VrrModeArg {
+    /// VRR is never enabled.
+    Never,
+    /// VRR is always enabled.
+    Always,
+    /// VRR is enabled while a window is fullscreen.
+    FullscreenOnly,
+    /// VRR is enabled while a window with the `game` content type is fullscreen.
+    FullscreenWithContentTypeGame,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct VrrArgs {
+    /// The VRR mode.
+    pub mode: VrrModeArg,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -214,6 +245,7 @@ struct Connector {
     pub drm_device: Option<u64>,
     pub name: String,
     pub enabled: bool,
+    pub color_temperature: u32,
     pub output: Option<Output>,
 }
 
@@ -233,6 +265,8 @@ struct Output {
     pub current_mode: Option<Mode>,
     pub modes: Vec<Mode>,
     pub non_desktop: bool,
+    pub vrr_capable: bool,
+    pub vrr_mode: VrrMode,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -399,6 +433,34 @@ impl Randr {
                     non_desktop: a.setting as _,
                 });
             }
+            OutputCommand::Vrr(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the VRR mode: {}", msg);
+                });
+                let mode = match a.mode {
+                    VrrModeArg::Never => VrrMode::Never,
+                    VrrModeArg::Always => VrrMode::Always,
+                    VrrModeArg::FullscreenOnly => VrrMode::FullscreenOnly,
+                    VrrModeArg::FullscreenWithContentTypeGame => {
+                        VrrMode::FullscreenWithContentTypeGame
+                    }
+                };
+                tc.send(jay_randr::SetVrrMode {
+                    self_id: randr,
+                    output: &args.output,
+                    mode: mode.to_raw(),
+                });
+            }
+            OutputCommand::ColorTemperature(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the color temperature: {}", msg);
+                });
+                tc.send(jay_randr::SetColorTemperature {
+                    self_id: randr,
+                    output: &args.output,
+                    kelvin: a.kelvin,
+                });
+            }
         }
         tc.round_trip().await;
     }
@@ -535,6 +597,16 @@ impl Randr {
             };
             println!("        transform: {}", name);
         }
+        if connector.color_temperature != NEUTRAL_COLOR_TEMPERATURE {
+            println!(
+                "        color temperature: {}K",
+                connector.color_temperature
+            );
+        }
+        if o.vrr_capable {
+            println!("        VRR capable");
+            println!("        VRR mode: {}", o.vrr_mode.to_str());
+        }
         if o.modes.is_not_empty() && modes {
             println!("        modes:");
             for mode in &o.modes {
@@ -580,9 +652,15 @@ impl Randr {
                 drm_device: (msg.drm_device != 0).then_some(msg.drm_device),
                 name: msg.name.to_string(),
                 enabled: msg.enabled != 0,
+                color_temperature: NEUTRAL_COLOR_TEMPERATURE,
                 output: None,
             });
         });
+        jay_randr::ColorTemperature::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            c.color_temperature = msg.kelvin;
+        });
         jay_randr::Output::handle(tc, randr, data.clone(), |data, msg| {
             let mut data = data.borrow_mut();
             let c = data.connectors.last_mut().unwrap();
@@ -601,6 +679,8 @@ impl Randr {
                 modes: Default::default(),
                 current_mode: None,
                 non_desktop: false,
+                vrr_capable: false,
+                vrr_mode: VrrMode::Never,
             });
         });
         jay_randr::NonDesktopOutput::handle(tc, randr, data.clone(), |data, msg| {
@@ -621,8 +701,17 @@ impl Randr {
                 modes: Default::default(),
                 current_mode: None,
                 non_desktop: true,
+                vrr_capable: false,
+                vrr_mode: VrrMode::Never,
             });
         });
+        jay_randr::VrrState::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let o = c.output.as_mut().unwrap();
+            o.vrr_capable = msg.capable != 0;
+            o.vrr_mode = VrrMode::from_raw(msg.mode).unwrap_or(VrrMode::Never);
+        });
         jay_randr::Mode::handle(tc, randr, data.clone(), |data, msg| {
             let mut data = data.borrow_mut();
             let c = data.connectors.last_mut().unwrap();
@@ -6,8 +6,8 @@ use {
         utils::{errorfmt::ErrorFmt, queue::AsyncQueue, windows::WindowsExt},
         video::{
             dmabuf::{DmaBuf, DmaBufIds, DmaBufPlane, PlaneVec},
-            drm::Drm,
-            gbm::{GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
+            drm::{Drm, DrmError},
+            gbm::{GbmDevice, GbmError, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
         },
         wire::{
             jay_compositor::TakeScreenshot,
@@ -18,6 +18,7 @@ use {
     jay_algorithms::qoi::xrgb8888_encode_qoi,
     png::{BitDepth, ColorType, Encoder, SrgbRenderingIntent},
     std::rc::Rc,
+    thiserror::Error,
 };
 
 pub fn main(global: GlobalArgs, args: ScreenshotArgs) {
@@ -74,66 +75,127 @@ async fn run(screenshot: Rc<Screenshot>) {
     }
 }
 
-pub fn buf_to_bytes(dma_buf_ids: &DmaBufIds, buf: &Dmabuf, format: ScreenshotFormat) -> Vec<u8> {
-    let drm = match Drm::reopen(buf.drm_dev.raw(), false) {
-        Ok(drm) => drm,
-        Err(e) => {
-            fatal!("Could not open the drm device: {}", ErrorFmt(e));
-        }
-    };
-    let gbm = match GbmDevice::new(&drm) {
-        Ok(g) => g,
-        Err(e) => {
-            fatal!("Could not create a gbm device: {}", ErrorFmt(e));
+#[derive(Debug, Error)]
+pub enum ScreenshotImageError {
+    #[error("Could not open the drm device")]
+    OpenDrm(#[source] DrmError),
+    #[error("Could not create a gbm device")]
+    CreateGbm(#[source] GbmError),
+    #[error("Could not import screenshot dmabuf")]
+    ImportDmabuf(#[source] GbmError),
+    #[error("Could not map dmabuf")]
+    MapDmabuf(#[source] GbmError),
+}
+
+/// An XRGB8888 image that has been copied out of a screenshot dmabuf.
+pub struct ScreenshotImage {
+    pub width: u32,
+    pub height: u32,
+    pub stride: u32,
+    pub data: Vec<u8>,
+}
+
+impl ScreenshotImage {
+    pub fn from_dmabuf(
+        dma_buf_ids: &DmaBufIds,
+        buf: &Dmabuf,
+    ) -> Result<Self, ScreenshotImageError> {
+        let drm = Drm::reopen(buf.drm_dev.raw(), false).map_err(ScreenshotImageError::OpenDrm)?;
+        let gbm = GbmDevice::new(&drm).map_err(ScreenshotImageError::CreateGbm)?;
+        let mut planes = PlaneVec::new();
+        planes.push(DmaBufPlane {
+            offset: buf.offset,
+            stride: buf.stride,
+            fd: buf.fd.clone(),
+        });
+        let dmabuf = DmaBuf {
+            id: dma_buf_ids.next(),
+            width: buf.width as _,
+            height: buf.height as _,
+            format: XRGB8888,
+            modifier: (buf.modifier_hi as u64) << 32 | (buf.modifier_lo as u64),
+            planes,
+        };
+        let bo = gbm
+            .import_dmabuf(&dmabuf, GBM_BO_USE_LINEAR | GBM_BO_USE_RENDERING)
+            .map_err(ScreenshotImageError::ImportDmabuf)?;
+        let bo = Rc::new(bo);
+        let bo_map = bo.map_read().map_err(ScreenshotImageError::MapDmabuf)?;
+        let data = unsafe { bo_map.data() };
+        let stride = bo_map.stride() as u32;
+        Ok(Self {
+            width: buf.width,
+            height: buf.height,
+            stride,
+            data: data[..(buf.height as usize * stride as usize)].to_vec(),
+        })
+    }
+
+    /// Returns the part of the image inside the rectangle.
+    ///
+    /// The rectangle is clamped to the image.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        let x = x.min(self.width);
+        let y = y.min(self.height);
+        let width = width.min(self.width - x);
+        let height = height.min(self.height - y);
+        let stride = width * 4;
+        let mut data = Vec::with_capacity((stride * height) as usize);
+        for line in self
+            .data
+            .chunks_exact(self.stride as usize)
+            .skip(y as usize)
+        {
+            if data.len() == (stride * height) as usize {
+                break;
+            }
+            data.extend_from_slice(&line[(x * 4) as usize..((x + width) * 4) as usize]);
         }
-    };
-    let mut planes = PlaneVec::new();
-    planes.push(DmaBufPlane {
-        offset: buf.offset,
-        stride: buf.stride,
-        fd: buf.fd.clone(),
-    });
-    let dmabuf = DmaBuf {
-        id: dma_buf_ids.next(),
-        width: buf.width as _,
-        height: buf.height as _,
-        format: XRGB8888,
-        modifier: (buf.modifier_hi as u64) << 32 | (buf.modifier_lo as u64),
-        planes,
-    };
-    let bo = match gbm.import_dmabuf(&dmabuf, GBM_BO_USE_LINEAR | GBM_BO_USE_RENDERING) {
-        Ok(bo) => Rc::new(bo),
-        Err(e) => {
-            fatal!("Could not import screenshot dmabuf: {}", ErrorFmt(e));
+        Self {
+            width,
+            height,
+            stride,
+            data,
         }
-    };
-    let bo_map = match bo.map_read() {
-        Ok(map) => map,
-        Err(e) => {
-            fatal!("Could not map dmabuf: {}", ErrorFmt(e));
+    }
+
+    /// Returns the red, green, and blue components of a pixel.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 3]> {
+        if x >= self.width || y >= self.height {
+            return None;
         }
-    };
-    let data = unsafe { bo_map.data() };
-    if format == ScreenshotFormat::Qoi {
-        return xrgb8888_encode_qoi(data, buf.width, buf.height, bo_map.stride() as u32);
+        let offset = (y * self.stride + x * 4) as usize;
+        let pixel = self.data.get(offset..offset + 4)?;
+        Some([pixel[2], pixel[1], pixel[0]])
     }
 
-    let mut out = vec![];
-    {
-        let mut image_data = Vec::with_capacity((buf.width * buf.height * 4) as usize);
-        let lines = data[..(buf.height as usize * bo_map.stride() as usize)]
-            .chunks_exact(bo_map.stride() as usize);
-        for line in lines {
-            for pixel in line[..(buf.width as usize * 4)].array_chunks_ext::<4>() {
-                image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
+    pub fn encode(&self, format: ScreenshotFormat) -> Vec<u8> {
+        if format == ScreenshotFormat::Qoi {
+            return xrgb8888_encode_qoi(&self.data, self.width, self.height, self.stride);
+        }
+
+        let mut out = vec![];
+        {
+            let mut image_data = Vec::with_capacity((self.width * self.height * 4) as usize);
+            for line in self.data.chunks_exact(self.stride as usize) {
+                for pixel in line[..(self.width as usize * 4)].array_chunks_ext::<4>() {
+                    image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
+                }
             }
+            let mut encoder = Encoder::new(&mut out, self.width, self.height);
+            encoder.set_color(ColorType::Rgba);
+            encoder.set_depth(BitDepth::Eight);
+            encoder.set_srgb(SrgbRenderingIntent::Perceptual);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&image_data).unwrap();
         }
-        let mut encoder = Encoder::new(&mut out, buf.width, buf.height);
-        encoder.set_color(ColorType::Rgba);
-        encoder.set_depth(BitDepth::Eight);
-        encoder.set_srgb(SrgbRenderingIntent::Perceptual);
-        let mut writer = encoder.write_header().unwrap();
-        writer.write_image_data(&image_data).unwrap();
+        out
+    }
+}
+
+pub fn buf_to_bytes(dma_buf_ids: &DmaBufIds, buf: &Dmabuf, format: ScreenshotFormat) -> Vec<u8> {
+    match ScreenshotImage::from_dmabuf(dma_buf_ids, buf) {
+        Ok(image) => image.encode(format),
+        Err(e) => fatal!("Could not read the screenshot: {}", ErrorFmt(e)),
     }
-    out
 }
@@ -0,0 +1,218 @@
+use {
+    crate::{
+        cli::{GlobalArgs, TreeArgs},
+        ifs::jay_tree::{
+            KIND_CONTAINER, KIND_DISPLAY, KIND_FLOAT, KIND_OUTPUT, KIND_PLACEHOLDER, KIND_WINDOW,
+            KIND_WORKSPACE, SPLIT_HORIZONTAL,
+        },
+        json_ipc::{
+            messages::JsonRect,
+            tree::{NodeData, Split, TreeNode, WindowData},
+        },
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        wire::{jay_compositor, jay_tree},
+    },
+    std::{cell::RefCell, rc::Rc},
+};
+
+pub fn main(global: GlobalArgs, args: TreeArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let tree = Rc::new(Tree {
+            tc: tc.clone(),
+            args,
+        });
+        tree.run().await;
+    });
+}
+
+struct Tree {
+    tc: Rc<ToolClient>,
+    args: TreeArgs,
+}
+
+#[derive(Default)]
+struct Data {
+    nodes: Vec<(u32, TreeNode)>,
+    done: bool,
+}
+
+impl Tree {
+    async fn run(&self) {
+        let tc = &self.tc;
+        let comp = tc.jay_compositor().await;
+        let id = tc.id();
+        tc.send(jay_compositor::GetTree { self_id: comp, id });
+        let data = Rc::new(RefCell::new(Data::default()));
+        jay_tree::Node::handle(tc, id, data.clone(), |data, msg| {
+            let node_data = match msg.kind {
+                KIND_DISPLAY => NodeData::Display,
+                KIND_OUTPUT => NodeData::Output {
+                    name: String::new(),
+                },
+                KIND_WORKSPACE => NodeData::Workspace {
+                    name: String::new(),
+                },
+                KIND_CONTAINER => NodeData::Container {
+                    split: Split::Horizontal,
+                    mono: false,
+                },
+                KIND_FLOAT => NodeData::Float,
+                KIND_WINDOW => NodeData::Window(empty_window()),
+                KIND_PLACEHOLDER => NodeData::Placeholder(empty_window()),
+                _ => fatal!("Unknown node kind {}", msg.kind),
+            };
+            let node = TreeNode {
+                id: msg.id,
+                rect: JsonRect {
+                    x: msg.x,
+                    y: msg.y,
+                    width: msg.width,
+                    height: msg.height,
+                },
+                visible: msg.visible != 0,
+                data: node_data,
+                nodes: vec![],
+            };
+            data.borrow_mut().nodes.push((msg.parent, node));
+        });
+        jay_tree::Name::handle(tc, id, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            match &mut data.nodes.last_mut().unwrap().1.data {
+                NodeData::Output { name } | NodeData::Workspace { name } => {
+                    *name = msg.name.to_string()
+                }
+                _ => {}
+            }
+        });
+        jay_tree::Container::handle(tc, id, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let NodeData::Container { split, mono } = &mut data.nodes.last_mut().unwrap().1.data
+            {
+                *split = match msg.split {
+                    SPLIT_HORIZONTAL => Split::Horizontal,
+                    _ => Split::Vertical,
+                };
+                *mono = msg.mono != 0;
+            }
+        });
+        jay_tree::Window::handle(tc, id, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(window) = window_data(&mut data.nodes.last_mut().unwrap().1) {
+                window.title = msg.title.to_string();
+                window.app_id = msg.app_id.to_string();
+                window.floating = msg.floating != 0;
+                window.fullscreen = msg.fullscreen != 0;
+            }
+        });
+        jay_tree::ClientInfo::handle(tc, id, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(window) = window_data(&mut data.nodes.last_mut().unwrap().1) {
+                window.client_id = Some(msg.client_id);
+                window.pid = Some(msg.pid);
+            }
+        });
+        jay_tree::Done::handle(tc, id, data.clone(), |data, _| {
+            data.borrow_mut().done = true;
+        });
+        tc.round_trip().await;
+        let data = data.take();
+        if !data.done {
+            fatal!("The compositor did not send the tree");
+        }
+        let Some(root) = build(data.nodes) else {
+            fatal!("The compositor sent an empty tree");
+        };
+        if self.args.json {
+            println!("{}", serde_json::to_string_pretty(&root).unwrap());
+        } else {
+            print_node(&root, 0);
+        }
+    }
+}
+
+fn empty_window() -> WindowData {
+    WindowData {
+        title: String::new(),
+        app_id: String::new(),
+        client_id: None,
+        pid: None,
+        floating: false,
+        fullscreen: false,
+    }
+}
+
+fn window_data(node: &mut TreeNode) -> Option<&mut WindowData> {
+    match &mut node.data {
+        NodeData::Window(w) | NodeData::Placeholder(w) => Some(w),
+        _ => None,
+    }
+}
+
+/// Assembles the nodes, which are sent parents first, into a tree.
+fn build(nodes: Vec<(u32, TreeNode)>) -> Option<TreeNode> {
+    let mut stack: Vec<TreeNode> = vec![];
+    for (parent, node) in nodes {
+        while stack.len() > 1 && stack.last().unwrap().id != parent {
+            let child = stack.pop().unwrap();
+            stack.last_mut().unwrap().nodes.push(child);
+        }
+        stack.push(node);
+    }
+    while stack.len() > 1 {
+        let child = stack.pop().unwrap();
+        stack.last_mut().unwrap().nodes.push(child);
+    }
+    stack.pop()
+}
+
+fn print_node(node: &TreeNode, depth: usize) {
+    let mut line = format!("{:1$}", "", depth * 2);
+    match &node.data {
+        NodeData::Display => line.push_str("display"),
+        NodeData::Output { name } => line.push_str(&format!("output {:?}", name)),
+        NodeData::Workspace { name } => line.push_str(&format!("workspace {:?}", name)),
+        NodeData::Container { split, .. } => {
+            let split = match split {
+                Split::Horizontal => "horizontal",
+                Split::Vertical => "vertical",
+            };
+            line.push_str(&format!("container {}", split));
+        }
+        NodeData::Float => line.push_str("float"),
+        NodeData::Window(w) => line.push_str(&format!("window {:?}", w.title)),
+        NodeData::Placeholder(w) => line.push_str(&format!("placeholder {:?}", w.title)),
+    }
+    let r = &node.rect;
+    line.push_str(&format!(
+        " #{} {}x{}+{}+{}",
+        node.id, r.width, r.height, r.x, r.y
+    ));
+    let mut flags = vec![];
+    if node.visible {
+        flags.push("visible".to_string());
+    }
+    match &node.data {
+        NodeData::Container { mono: true, .. } => flags.push("mono".to_string()),
+        NodeData::Window(w) | NodeData::Placeholder(w) => {
+            flags.push(format!("app-id={:?}", w.app_id));
+            if let (Some(client_id), Some(pid)) = (w.client_id, w.pid) {
+                flags.push(format!("client={}", client_id));
+                flags.push(format!("pid={}", pid));
+            }
+            if w.floating {
+                flags.push("floating".to_string());
+            }
+            if w.fullscreen {
+                flags.push("fullscreen".to_string());
+            }
+        }
+        _ => {}
+    }
+    if !flags.is_empty() {
+        line.push_str(&format!(" [{}]", flags.join(", ")));
+    }
+    println!("{}", line);
+    for child in &node.nodes {
+        print_node(child, depth + 1);
+    }
+}
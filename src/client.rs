@@ -57,6 +57,8 @@ bitflags! {
         CAP_SEAT_MANAGER             = 1 << 8,
         CAP_DRM_LEASE                = 1 << 9,
         CAP_INPUT_METHOD             = 1 << 10,
+        CAP_GAMMA_CONTROL_MANAGER    = 1 << 11,
+        CAP_OUTPUT_MANAGER           = 1 << 12,
 }
 
 pub const CAPS_DEFAULT: ClientCaps = ClientCaps(CAP_LAYER_SHELL.0 | CAP_DRM_LEASE.0);
@@ -122,6 +124,7 @@ impl Clients {
         socket: Rc<OwnedFd>,
         effective_caps: ClientCaps,
         bounding_caps: ClientCaps,
+        sandbox_engine: Option<String>,
     ) -> Result<(), ClientError> {
         let (uid, pid) = {
             let mut cred = c::ucred {
@@ -148,6 +151,7 @@ impl Clients {
             pid,
             effective_caps,
             bounding_caps,
+            sandbox_engine,
             false,
         )?;
         Ok(())
@@ -162,6 +166,7 @@ impl Clients {
         pid: c::pid_t,
         effective_caps: ClientCaps,
         bounding_caps: ClientCaps,
+        sandbox_engine: Option<String>,
         is_xwayland: bool,
     ) -> Result<Rc<Client>, ClientError> {
         let data = Rc::new(Client {
@@ -179,12 +184,14 @@ impl Clients {
             bounding_caps,
             last_enter_serial: Cell::new(0),
             pid_info: get_pid_info(uid, pid),
+            sandbox_engine,
             serials: Default::default(),
             symmetric_delete: Cell::new(false),
             last_xwayland_serial: Cell::new(0),
             surfaces_by_xwayland_serial: Default::default(),
             activation_tokens: Default::default(),
             commit_timelines: Rc::new(CommitTimelines::new(&global.wait_for_sync_obj)),
+            wire_scale: Default::default(),
         });
         track!(data, data);
         let display = Rc::new(WlDisplay::new(&data));
@@ -295,12 +302,15 @@ pub struct Client {
     pub bounding_caps: ClientCaps,
     pub last_enter_serial: Cell<u32>,
     pub pid_info: PidInfo,
+    pub sandbox_engine: Option<String>,
     pub serials: RefCell<VecDeque<SerialRange>>,
     pub symmetric_delete: Cell<bool>,
     pub last_xwayland_serial: Cell<u64>,
     pub surfaces_by_xwayland_serial: CopyHashMap<u64, Rc<WlSurface>>,
     pub activation_tokens: RefCell<VecDeque<ActivationToken>>,
     pub commit_timelines: Rc<CommitTimelines>,
+    /// The factor between the coordinates used on the wire and logical coordinates.
+    pub wire_scale: Cell<Option<i32>>,
 }
 
 pub const NUM_CACHED_SERIAL_RANGES: usize = 64;
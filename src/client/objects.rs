@@ -24,6 +24,8 @@ use {
             wp_linux_drm_syncobj_timeline_v1::WpLinuxDrmSyncobjTimelineV1,
             xdg_positioner::XdgPositioner,
             xdg_wm_base::XdgWmBase,
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
+            zwlr_output_mode_v1::ZwlrOutputModeV1,
         },
         object::{Object, ObjectId},
         utils::{
@@ -35,7 +37,8 @@ use {
             WlDataSourceId, WlOutputId, WlPointerId, WlRegionId, WlRegistryId, WlSeatId,
             WlSurfaceId, WpDrmLeaseConnectorV1Id, WpLinuxDrmSyncobjTimelineV1Id, XdgPopupId,
             XdgPositionerId, XdgSurfaceId, XdgToplevelId, XdgWmBaseId, ZwlrDataControlSourceV1Id,
-            ZwpPrimarySelectionSourceV1Id, ZwpTabletToolV2Id,
+            ZwlrOutputHeadV1Id, ZwlrOutputModeV1Id, ZwpPrimarySelectionSourceV1Id,
+            ZwpTabletToolV2Id,
         },
     },
     std::{cell::RefCell, mem, rc::Rc},
@@ -67,6 +70,8 @@ pub struct Objects {
     pub drm_lease_outputs: CopyHashMap<WpDrmLeaseConnectorV1Id, Rc<WpDrmLeaseConnectorV1>>,
     pub tablet_tools: CopyHashMap<ZwpTabletToolV2Id, Rc<ZwpTabletToolV2>>,
     pub xdg_popups: CopyHashMap<XdgPopupId, Rc<XdgPopup>>,
+    pub output_heads: CopyHashMap<ZwlrOutputHeadV1Id, Rc<ZwlrOutputHeadV1>>,
+    pub output_modes: CopyHashMap<ZwlrOutputModeV1Id, Rc<ZwlrOutputModeV1>>,
     ids: RefCell<Vec<usize>>,
 }
 
@@ -100,6 +105,8 @@ impl Objects {
             drm_lease_outputs: Default::default(),
             tablet_tools: Default::default(),
             xdg_popups: Default::default(),
+            output_heads: Default::default(),
+            output_modes: Default::default(),
             ids: RefCell::new(vec![]),
         }
     }
@@ -137,6 +144,8 @@ impl Objects {
         self.drm_lease_outputs.clear();
         self.tablet_tools.clear();
         self.xdg_popups.clear();
+        self.output_heads.clear();
+        self.output_modes.clear();
     }
 
     pub fn id<T>(&self, client_data: &Client) -> Result<T, ClientError>
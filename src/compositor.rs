@@ -20,8 +20,10 @@ use {
             jay_screencast::{perform_screencast_realloc, perform_toplevel_screencasts},
             wl_output::{OutputId, PersistentOutputState, WlOutputGlobal},
             wl_surface::{zwp_input_popup_surface_v2::input_popup_positioning, NoneSurfaceExt},
+            zwlr_output_manager_v1::handle_output_head_changes,
         },
         io_uring::{IoUring, IoUringError},
+        json_ipc::JsonIpc,
         leaks,
         logger::Logger,
         portal::{self, PortalStartup},
@@ -35,9 +37,9 @@ use {
         },
         user_session::import_environment,
         utils::{
-            clonecell::CloneCell, errorfmt::ErrorFmt, fdcloser::FdCloser, numcell::NumCell,
-            oserror::OsError, queue::AsyncQueue, refcounted::RefCounted, run_toplevel::RunToplevel,
-            tri::Try,
+            clonecell::CloneCell, color_temperature::NEUTRAL_COLOR_TEMPERATURE, errorfmt::ErrorFmt,
+            fdcloser::FdCloser, numcell::NumCell, oserror::OsError, queue::AsyncQueue,
+            refcounted::RefCounted, run_toplevel::RunToplevel, tri::Try,
         },
         version::VERSION,
         video::drm::wait_for_sync_obj::WaitForSyncObj,
@@ -47,7 +49,15 @@ use {
     ahash::AHashSet,
     forker::ForkerProxy,
     jay_config::{_private::DEFAULT_SEAT_NAME, video::GfxApi},
-    std::{cell::Cell, env, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    std::{
+        cell::{Cell, RefCell},
+        env,
+        future::Future,
+        ops::Deref,
+        rc::Rc,
+        sync::Arc,
+        time::Duration,
+    },
     thiserror::Error,
     uapi::c,
 };
@@ -106,6 +116,7 @@ pub enum CompositorError {
 }
 
 pub const WAYLAND_DISPLAY: &str = "WAYLAND_DISPLAY";
+pub const JAY_IPC_SOCKET: &str = "JAY_IPC_SOCKET";
 pub const DISPLAY: &str = "DISPLAY";
 
 const STATIC_VARS: &[(&str, &str)] = &[
@@ -156,6 +167,8 @@ fn start_compositor2(
         connector_ids: Default::default(),
         root: Rc::new(DisplayNode::new(node_ids.next())),
         workspaces: Default::default(),
+        workspace_gaps: Default::default(),
+        workspace_connectors: Default::default(),
         dummy_output: Default::default(),
         node_ids,
         backend_events: AsyncQueue::new(),
@@ -176,6 +189,7 @@ fn start_compositor2(
         pending_input_popup_positioning: Default::default(),
         pending_toplevel_screencasts: Default::default(),
         pending_toplevel_screencast_reallocs: Default::default(),
+        metadata_cursor_screencasts: Default::default(),
         dbus: Dbus::new(&engine, &ring, &run_toplevel),
         fdcloser: FdCloser::new(),
         logger: logger.clone(),
@@ -198,8 +212,11 @@ fn start_compositor2(
             handler: Default::default(),
             queue: Default::default(),
             ipc_device_ids: Default::default(),
+            scaling_mode: Default::default(),
+            wire_scale: Default::default(),
         },
         acceptor: Default::default(),
+        json_ipc: Default::default(),
         serial: Default::default(),
         idle_inhibitor_ids: Default::default(),
         run_toplevel,
@@ -220,6 +237,7 @@ fn start_compositor2(
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
+        appearance_watchers: Default::default(),
         default_workspace_capture: Cell::new(true),
         default_gfx_api: Cell::new(GfxApi::OpenGl),
         activation_tokens: Default::default(),
@@ -244,16 +262,33 @@ fn start_compositor2(
         tablet_ids: Default::default(),
         tablet_tool_ids: Default::default(),
         tablet_pad_ids: Default::default(),
+        output_managers: Default::default(),
+        output_heads: Default::default(),
+        output_heads_serial: Default::default(),
+        output_heads_changed: Default::default(),
+        window_rules: Default::default(),
+        scratchpad: Default::default(),
+        mapped_toplevels: Default::default(),
     });
     state.tracker.register(ClientId::from_raw(0));
     create_dummy_output(&state);
     let (acceptor, _acceptor_future) = Acceptor::install(&state)?;
+    let _json_ipc_future = match JsonIpc::install(&state, &acceptor) {
+        Ok((_, future)) => Some(future),
+        Err(e) => {
+            log::error!("Could not create the IPC socket: {}", ErrorFmt(e));
+            None
+        }
+    };
     if let Some(forker) = forker {
         forker.install(&state);
         forker.setenv(
             WAYLAND_DISPLAY.as_bytes(),
             acceptor.socket_name().as_bytes(),
         );
+        if let Some(ipc) = state.json_ipc.get() {
+            forker.setenv(JAY_IPC_SOCKET.as_bytes(), ipc.path().as_bytes());
+        }
         for (key, val) in STATIC_VARS {
             forker.setenv(key.as_bytes(), val.as_bytes());
         }
@@ -286,6 +321,10 @@ async fn start_compositor3(state: Rc<State>, test_future: Option<TestFuture>) {
         if let Some(acc) = state.acceptor.get() {
             import_environment(&state, WAYLAND_DISPLAY, acc.socket_name()).await;
         }
+        if let Some(ipc) = state.json_ipc.get() {
+            let path = ipc.path().display().to_string();
+            import_environment(&state, JAY_IPC_SOCKET, &path).await;
+        }
         for (key, val) in STATIC_VARS {
             import_environment(&state, key, val).await;
         }
@@ -343,6 +382,7 @@ fn start_global_event_handlers(
         eng.spawn2(Phase::PostLayout, input_popup_positioning(state.clone())),
         eng.spawn2(Phase::Present, perform_toplevel_screencasts(state.clone())),
         eng.spawn2(Phase::PostLayout, perform_screencast_realloc(state.clone())),
+        eng.spawn2(Phase::PostLayout, handle_output_head_changes(state.clone())),
     ]
 }
 
@@ -417,6 +457,7 @@ fn create_dummy_output(state: &Rc<State>) {
         transform: Default::default(),
         scale: Default::default(),
         pos: Default::default(),
+        vrr_mode: Default::default(),
     });
     let dummy_output = Rc::new(OutputNode {
         id: state.node_ids.next(),
@@ -432,6 +473,8 @@ fn create_dummy_output(state: &Rc<State>) {
                 name: "Dummy".to_string(),
                 drm_dev: None,
                 async_event: Default::default(),
+                gamma_control: Default::default(),
+                color_temperature: Cell::new(NEUTRAL_COLOR_TEMPERATURE),
             }),
             Vec::new(),
             &backend::Mode {
@@ -474,7 +517,7 @@ fn create_dummy_output(state: &Rc<State>) {
         container: Default::default(),
         stacked: Default::default(),
         seat_state: Default::default(),
-        name: "dummy".to_string(),
+        name: RefCell::new("dummy".to_string()),
         output_link: Default::default(),
         visible: Default::default(),
         fullscreen: Default::default(),
@@ -486,6 +529,8 @@ fn create_dummy_output(state: &Rc<State>) {
         title_texture: Cell::new(None),
         attention_requests: Default::default(),
         render_highlight: Default::default(),
+        gaps: Default::default(),
+        minimized: Default::default(),
     });
     *dummy_workspace.output_link.borrow_mut() =
         Some(dummy_output.workspaces.add_last(dummy_workspace.clone()));
@@ -8,6 +8,7 @@ use {
         config::handler::ConfigProxyHandler,
         ifs::wl_seat::SeatId,
         state::State,
+        tree::NodeId,
         utils::{
             clonecell::CloneCell, numcell::NumCell, ptr_ext::PtrExt, unlink_on_drop::UnlinkOnDrop,
             xrd::xrd,
@@ -20,9 +21,10 @@ use {
             ipc::{InitMessage, ServerFeature, ServerMessage, V1InitMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat, SwitchEvent},
+        input::{pointer::PointerTrigger, InputDevice, Seat, SwitchEvent},
         keyboard::{mods::Modifiers, syms::KeySym},
         video::{Connector, DrmDevice},
+        window::Window,
     },
     libloading::Library,
     std::{cell::Cell, io, mem, ptr, rc::Rc},
@@ -81,6 +83,20 @@ impl ConfigProxy {
         self.send(&msg);
     }
 
+    pub fn binding_mode_cancelled(&self, seat: SeatId) {
+        self.send(&ServerMessage::BindingModeCancelled {
+            seat: Seat(seat.raw() as _),
+        });
+    }
+
+    pub fn invoke_pointer_shortcut(&self, seat: SeatId, mods: Modifiers, trigger: PointerTrigger) {
+        self.send(&ServerMessage::InvokePointerShortcut {
+            seat: Seat(seat.raw() as _),
+            mods,
+            trigger,
+        });
+    }
+
     pub fn new_drm_dev(&self, dev: DrmDeviceId) {
         self.send(&ServerMessage::NewDrmDev {
             device: DrmDevice(dev.raw() as _),
@@ -152,6 +168,24 @@ impl ConfigProxy {
             event,
         });
     }
+
+    pub fn new_window(&self, window: NodeId) {
+        self.send(&ServerMessage::NewWindow {
+            window: Window(window.raw() as _),
+        });
+    }
+
+    pub fn window_title_changed(&self, window: NodeId) {
+        self.send(&ServerMessage::WindowTitleChanged {
+            window: Window(window.raw() as _),
+        });
+    }
+
+    pub fn window_closed(&self, window: NodeId) {
+        self.send(&ServerMessage::WindowClosed {
+            window: Window(window.raw() as _),
+        });
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -201,6 +235,7 @@ impl ConfigProxy {
             timers_by_id: Default::default(),
             pollable_id: Default::default(),
             pollables: Default::default(),
+            ipc_command_result: Default::default(),
         });
         let init_msg = bincode_ops()
             .serialize(&InitMessage::V1(V1InitMessage {}))
@@ -220,6 +255,21 @@ impl ConfigProxy {
         }
     }
 
+    /// Runs a command that was sent via the IPC socket.
+    pub fn run_ipc_command(&self, command: &str) -> Result<(), String> {
+        let Some(handler) = self.handler.get() else {
+            return Err("No configuration is loaded".to_string());
+        };
+        handler.ipc_command_result.take();
+        handler.send(&ServerMessage::IpcCommand {
+            command: command.to_string(),
+        });
+        match handler.ipc_command_result.take() {
+            Some(res) => res,
+            None => Err("The configuration did not handle the command".to_string()),
+        }
+    }
+
     pub fn configure(&self, reload: bool) {
         self.send(&ServerMessage::Features {
             features: vec![ServerFeature::MOD_MASK],
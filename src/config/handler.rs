@@ -7,14 +7,16 @@ use {
         },
         compositor::MAX_EXTENTS,
         config::ConfigProxy,
+        focus_history,
         ifs::wl_seat::{SeatId, WlSeatGlobal},
         io_uring::TaskResultExt,
+        minimize,
         scale::Scale,
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized, DEFAULT_FONT},
         tree::{
-            move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase,
-            OutputNode, WsMoveConfig,
+            ContainerNode, ContainerSplit, FloatNode, Node, NodeId, NodeVisitorBase, OutputNode,
+            ToplevelNode, WorkspaceGaps, WorkspaceNode,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -26,13 +28,15 @@ use {
             stack::Stack,
             timer::{TimerError, TimerFd},
         },
+        window_rules::WindowProperties,
+        workspaces,
         xkbcommon::{XkbCommonError, XkbKeymap},
     },
     bincode::Options,
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage, WorkspaceSource},
+            ipc::{ClientMessage, Response, ServerMessage, WindowRuleData, WorkspaceSource},
             PollableId, WireMode,
         },
         input::{
@@ -41,14 +45,17 @@ use {
                 Capability, CAP_GESTURE, CAP_KEYBOARD, CAP_POINTER, CAP_SWITCH, CAP_TABLET_PAD,
                 CAP_TABLET_TOOL, CAP_TOUCH,
             },
+            pointer::PointerTrigger,
             FocusFollowsMouseMode, InputDevice, Seat,
         },
         keyboard::{mods::Modifiers, syms::KeySym, Keymap},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable},
+        theme::{colors::Colorable, sized::Resizable, ColorScheme},
         timer::Timer as JayTimer,
-        video::{Connector, DrmDevice, GfxApi, Transform},
-        Axis, Direction, Workspace,
+        video::{Connector, DrmDevice, GfxApi, Transform, VrrMode},
+        window::Window,
+        xwayland::XScalingMode,
+        Axis, Direction, ResizeAmount, Workspace,
     },
     libloading::Library,
     log::Level,
@@ -80,6 +87,8 @@ pub(super) struct ConfigProxyHandler {
 
     pub pollable_id: NumCell<u64>,
     pub pollables: CopyHashMap<PollableId, Rc<Pollable>>,
+
+    pub ipc_command_result: Cell<Option<Result<(), String>>>,
 }
 
 pub struct Pollable {
@@ -105,6 +114,16 @@ impl ConfigProxyHandler {
 
         self.pollables.clear();
 
+        self.state.window_rules.clear();
+
+        if !self.state.workspace_gaps.take().is_empty() {
+            for ws in self.state.workspaces.lock().values() {
+                ws.gaps.take();
+            }
+            self.spaces_change();
+        }
+        self.state.workspace_connectors.borrow_mut().clear();
+
         if let Some(path) = &self.path {
             if let Err(e) = uapi::unlink(path.as_str()) {
                 log::error!("Could not unlink {}: {}", path, ErrorFmt(OsError(e.0)));
@@ -272,6 +291,7 @@ impl ConfigProxyHandler {
             config.destroy();
             for seat in self.state.globals.seats.lock().values() {
                 seat.clear_shortcuts();
+                seat.set_binding_mode(None, false);
             }
         }
         config.configure(true);
@@ -403,17 +423,7 @@ impl ConfigProxyHandler {
     fn handle_get_workspaces(&self) {
         let mut workspaces = vec![];
         for ws in self.state.workspaces.lock().values() {
-            let id = match self.workspaces_by_name.get(&ws.name) {
-                None => {
-                    let id = self.workspace_ids.fetch_add(1);
-                    let name = Rc::new(ws.name.clone());
-                    self.workspaces_by_name.set(name.clone(), id);
-                    self.workspaces_by_id.set(id, name);
-                    id
-                }
-                Some(id) => id,
-            };
-            workspaces.push(Workspace(id));
+            workspaces.push(self.workspace_by_name(&ws.name.borrow()));
         }
         self.respond(Response::GetWorkspaces { workspaces });
     }
@@ -491,6 +501,138 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_resize(
+        &self,
+        seat: Seat,
+        direction: Direction,
+        amount: ResizeAmount,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.resize_focused(direction.into(), amount);
+        Ok(())
+    }
+
+    fn handle_seat_move_floating(&self, seat: Seat, dx: i32, dy: i32) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.move_focused_floating(dx, dy);
+        Ok(())
+    }
+
+    fn handle_seat_set_floating_geometry(
+        &self,
+        seat: Seat,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_focused_floating_geometry(x, y, width, height);
+        Ok(())
+    }
+
+    fn handle_set_binding_mode(
+        &self,
+        seat: Seat,
+        name: Option<&str>,
+        once: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_binding_mode(name.map(|n| Rc::new(n.to_string())), once);
+        Ok(())
+    }
+
+    fn handle_add_pointer_shortcut(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_pointer_shortcut(mods, trigger);
+        Ok(())
+    }
+
+    fn handle_remove_pointer_shortcut(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_pointer_shortcut(mods, trigger);
+        Ok(())
+    }
+
+    fn handle_focus_last(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_last();
+        Ok(())
+    }
+
+    fn handle_focus_next_mru(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_next_mru();
+        Ok(())
+    }
+
+    fn handle_focus_prev_mru(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_prev_mru();
+        Ok(())
+    }
+
+    fn handle_show_adjacent_workspace(
+        &self,
+        seat: Seat,
+        next: bool,
+        global: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.show_adjacent_workspace(next, global);
+        Ok(())
+    }
+
+    fn handle_workspace_back_and_forth(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.workspace_back_and_forth();
+        Ok(())
+    }
+
+    fn handle_rename_workspace(&self, workspace: Workspace, name: &str) -> Result<(), CphError> {
+        let old = self.get_workspace(workspace)?;
+        let Some(ws) = self.state.workspaces.get(old.as_str()) else {
+            return Ok(());
+        };
+        if !workspaces::rename(&self.state, &ws, name) {
+            return Err(CphError::WorkspaceAlreadyExists(name.to_string()));
+        }
+        self.spaces_change();
+        Ok(())
+    }
+
+    fn handle_set_workspace_connector(
+        &self,
+        workspace: Workspace,
+        connector: Option<Connector>,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let connector = match connector {
+            Some(c) => Some(self.get_connector(c)?.connector.id()),
+            None => None,
+        };
+        workspaces::set_preferred_connector(&self.state, &name, connector);
+        Ok(())
+    }
+
+    fn handle_ipc_command_result(&self, error: Option<&str>) {
+        let res = match error {
+            None => Ok(()),
+            Some(e) => Err(e.to_string()),
+        };
+        self.ipc_command_result.set(Some(res));
+    }
+
     fn handle_get_repeat_rate(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         let (rate, delay) = seat.get_rate();
@@ -517,6 +659,20 @@ impl ConfigProxyHandler {
         }
     }
 
+    fn get_window(&self, window: Window) -> Result<Rc<dyn ToplevelNode>, CphError> {
+        match self.state.mapped_toplevels.get(&NodeId(window.0 as _)) {
+            Some(tl) => Ok(tl),
+            _ => Err(CphError::WindowDoesNotExist(window)),
+        }
+    }
+
+    fn get_window_properties(&self, window: Window) -> Result<WindowProperties, CphError> {
+        match self.get_window(window)?.tl_window_properties() {
+            Some(props) => Ok(props),
+            _ => Err(CphError::WindowDoesNotExist(window)),
+        }
+    }
+
     fn get_device_handler_data(
         &self,
         device: InputDevice,
@@ -685,7 +841,7 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
-    fn handle_get_workspace(&self, name: &str) {
+    fn workspace_by_name(&self, name: &str) -> Workspace {
         let name = Rc::new(name.to_owned());
         let ws = match self.workspaces_by_name.get(&name) {
             Some(w) => w,
@@ -696,8 +852,12 @@ impl ConfigProxyHandler {
                 ws
             }
         };
+        Workspace(ws)
+    }
+
+    fn handle_get_workspace(&self, name: &str) {
         self.respond(Response::GetWorkspace {
-            workspace: Workspace(ws),
+            workspace: self.workspace_by_name(name),
         });
     }
 
@@ -773,7 +933,7 @@ impl ConfigProxyHandler {
         let mut workspace = 0;
         if !output.is_dummy {
             if let Some(ws) = output.workspace.get() {
-                if let Some(ws) = self.workspaces_by_name.get(&ws.name) {
+                if let Some(ws) = self.workspaces_by_name.get(&*ws.name.borrow()) {
                     workspace = ws;
                 }
             }
@@ -796,7 +956,9 @@ impl ConfigProxyHandler {
         let name = self.get_workspace(ws)?;
         let workspace = match self.state.workspaces.get(name.deref()) {
             Some(ws) => ws,
-            _ => seat.get_output().create_workspace(name.deref()),
+            _ => workspaces::preferred_output(&self.state, &name)
+                .unwrap_or_else(|| seat.get_output())
+                .create_workspace(name.deref()),
         };
         seat.set_workspace(&workspace);
         Ok(())
@@ -846,24 +1008,7 @@ impl ConfigProxyHandler {
                 _ => return Ok(()),
             },
         };
-        if ws.is_dummy || output.is_dummy {
-            return Ok(());
-        }
-        if ws.output.get().id == output.id {
-            return Ok(());
-        }
-        let link = match &*ws.output_link.borrow() {
-            None => return Ok(()),
-            Some(l) => l.to_ref(),
-        };
-        let config = WsMoveConfig {
-            make_visible_if_empty: true,
-            source_is_destroyed: false,
-        };
-        move_ws_to_output(&link, &output, config);
-        ws.desired_output.set(output.global.output_id.clone());
-        self.state.tree_changed();
-        self.state.damage();
+        workspaces::move_to_output(&self.state, &ws, &output);
         Ok(())
     }
 
@@ -921,11 +1066,15 @@ impl ConfigProxyHandler {
         mode: WireMode,
     ) -> Result<(), CphError> {
         let connector = self.get_output(connector)?;
-        connector.connector.connector.set_mode(backend::Mode {
-            width: mode.width,
-            height: mode.height,
-            refresh_rate_millihz: mode.refresh_millihz,
-        });
+        connector
+            .connector
+            .connector
+            .set_mode(backend::Mode {
+                width: mode.width,
+                height: mode.height,
+                refresh_rate_millihz: mode.refresh_millihz,
+            })
+            .map_err(CphError::SetMode)?;
         Ok(())
     }
 
@@ -1074,7 +1223,212 @@ impl ConfigProxyHandler {
         enabled: bool,
     ) -> Result<(), CphError> {
         let connector = self.get_connector(connector)?;
-        connector.connector.set_enabled(enabled);
+        connector
+            .connector
+            .set_enabled(enabled)
+            .map_err(CphError::SetEnabled)?;
+        Ok(())
+    }
+
+    fn handle_connector_set_tearing_enabled(
+        &self,
+        connector: Connector,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.connector.set_tearing_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_connector_vrr_capable(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        self.respond(Response::ConnectorVrrCapable {
+            capable: connector.monitor_info.vrr_capable,
+        });
+        Ok(())
+    }
+
+    fn handle_connector_set_vrr_mode(
+        &self,
+        connector: Connector,
+        mode: VrrMode,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output_node(connector)?;
+        connector.set_vrr_mode(mode);
+        Ok(())
+    }
+
+    fn handle_connector_set_color_temperature(
+        &self,
+        connector: Connector,
+        kelvin: u32,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.set_color_temperature(kelvin);
+        Ok(())
+    }
+
+    fn handle_add_window_rule(&self, rule: WindowRuleData) -> Result<(), CphError> {
+        let workspace = match rule.workspace {
+            Some(ws) => Some(self.get_workspace(ws)?.to_string()),
+            None => None,
+        };
+        self.state
+            .window_rules
+            .add(rule, workspace)
+            .map_err(CphError::InvalidWindowRuleTitle)
+    }
+
+    fn handle_clear_window_rules(&self) {
+        self.state.window_rules.clear();
+    }
+
+    fn handle_get_windows(&self) {
+        let mut windows: Vec<_> = self
+            .state
+            .mapped_toplevels
+            .lock()
+            .keys()
+            .map(|id| Window(id.raw() as _))
+            .collect();
+        windows.sort_by_key(|w| w.0);
+        self.respond(Response::GetWindows { windows });
+    }
+
+    fn handle_seat_focused_window(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let mut window = Window(0);
+        if let Some(tl) = seat.focused_toplevel() {
+            if self.state.mapped_toplevels.contains(&tl.node_id()) {
+                window = Window(tl.node_id().raw() as _);
+            }
+        }
+        self.respond(Response::GetWindow { window });
+        Ok(())
+    }
+
+    fn handle_window_exists(&self, window: Window) {
+        let exists = self.get_window(window).is_ok();
+        self.respond(Response::WindowExists { exists });
+    }
+
+    fn handle_get_window_title(&self, window: Window) -> Result<(), CphError> {
+        let props = self.get_window_properties(window)?;
+        self.respond(Response::GetWindowTitle { title: props.title });
+        Ok(())
+    }
+
+    fn handle_get_window_app_id(&self, window: Window) -> Result<(), CphError> {
+        let props = self.get_window_properties(window)?;
+        self.respond(Response::GetWindowAppId {
+            app_id: props.app_id,
+        });
+        Ok(())
+    }
+
+    fn handle_get_window_class(&self, window: Window) -> Result<(), CphError> {
+        let props = self.get_window_properties(window)?;
+        self.respond(Response::GetWindowClass {
+            class: props.class.unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    fn handle_get_window_pid(&self, window: Window) -> Result<(), CphError> {
+        let props = self.get_window_properties(window)?;
+        self.respond(Response::GetWindowPid { pid: props.pid });
+        Ok(())
+    }
+
+    fn handle_get_window_type(&self, window: Window) -> Result<(), CphError> {
+        let props = self.get_window_properties(window)?;
+        self.respond(Response::GetWindowType { ty: Some(props.ty) });
+        Ok(())
+    }
+
+    fn handle_get_window_workspace(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let data = tl.tl_data();
+        let ws = match data.minimized_data.borrow().as_ref() {
+            Some(md) => Some(md.workspace.clone()),
+            _ => data.workspace.get(),
+        };
+        let workspace = match ws {
+            Some(ws) => self.workspace_by_name(&ws.name.borrow()),
+            _ => Workspace(0),
+        };
+        self.respond(Response::GetWorkspace { workspace });
+        Ok(())
+    }
+
+    fn handle_focus_window(&self, window: Window, seat: Seat) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let seat = self.get_seat(seat)?;
+        focus_history::activate(&self.state, &seat, tl);
+        Ok(())
+    }
+
+    fn handle_close_window(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        tl.tl_close();
+        Ok(())
+    }
+
+    fn handle_get_window_floating(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetFloating {
+            floating: tl.tl_data().is_floating.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_window_floating(&self, window: Window, floating: bool) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.state.set_tl_floating(tl, floating);
+        Ok(())
+    }
+
+    fn handle_move_window_to_workspace(
+        &self,
+        window: Window,
+        ws: Workspace,
+    ) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let name = self.get_workspace(ws)?;
+        let workspace = match self.state.workspaces.get(name.deref()) {
+            Some(ws) => ws,
+            _ => {
+                let output = match workspaces::preferred_output(&self.state, &name) {
+                    Some(output) => output,
+                    _ => match tl.tl_data().workspace.get() {
+                        Some(ws) => ws.output.get(),
+                        _ => self.state.float_map_ws().output.get(),
+                    },
+                };
+                output.create_workspace(name.deref())
+            }
+        };
+        self.state.set_tl_workspace(tl, &workspace);
+        Ok(())
+    }
+
+    fn handle_get_window_fullscreen(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetFullscreen {
+            fullscreen: tl.tl_data().is_fullscreen.get(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_window_fullscreen(
+        &self,
+        window: Window,
+        fullscreen: bool,
+    ) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        if tl.tl_data().is_fullscreen.get() != fullscreen {
+            tl.tl_set_fullscreen(fullscreen);
+        }
         Ok(())
     }
 
@@ -1280,6 +1634,68 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_move_to_scratchpad(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.move_to_scratchpad();
+        Ok(())
+    }
+
+    fn handle_seat_toggle_scratchpad(
+        &self,
+        seat: Seat,
+        app_id: Option<&str>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.toggle_scratchpad(app_id);
+        Ok(())
+    }
+
+    fn handle_set_x_scaling_mode(&self, mode: XScalingMode) {
+        self.state.xwayland.scaling_mode.set(mode);
+        self.state.update_xwayland_wire_scale();
+    }
+
+    fn handle_get_window_minimized(&self, window: Window) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        self.respond(Response::GetMinimized {
+            minimized: tl.tl_data().is_minimized(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_window_minimized(&self, window: Window, minimized: bool) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        match minimized {
+            true => minimize::minimize(&self.state, tl),
+            false => minimize::restore(&self.state, tl),
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_minimized_windows(&self, workspace: Workspace) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let mut windows = vec![];
+        if let Some(ws) = self.state.workspaces.get(name.as_str()) {
+            for tl in ws.minimized.iter() {
+                windows.push(Window(tl.node_id().raw() as _));
+            }
+        }
+        self.respond(Response::GetWindows { windows });
+        Ok(())
+    }
+
+    fn handle_seat_minimize(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.minimize();
+        Ok(())
+    }
+
+    fn handle_seat_restore_minimized(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.restore_minimized();
+        Ok(())
+    }
+
     fn handle_set_floating(&self, seat: Seat, floating: bool) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.set_floating(floating);
@@ -1361,6 +1777,10 @@ impl ConfigProxyHandler {
                 node.on_spaces_changed();
                 node.node_visit_children(self);
             }
+            fn visit_workspace(&mut self, node: &Rc<WorkspaceNode>) {
+                node.update_container_position();
+                node.node_visit_children(self);
+            }
             fn visit_container(&mut self, node: &Rc<ContainerNode>) {
                 node.on_spaces_changed();
                 node.node_visit_children(self);
@@ -1386,6 +1806,7 @@ impl ConfigProxyHandler {
             }
         }
         self.state.root.clone().node_visit(&mut V);
+        self.state.appearance_changed();
     }
 
     fn get_sized(&self, sized: Resizable) -> Result<ThemeSized, CphError> {
@@ -1393,11 +1814,23 @@ impl ConfigProxyHandler {
         let sized = match sized {
             TITLE_HEIGHT => ThemeSized::title_height,
             BORDER_WIDTH => ThemeSized::border_width,
+            INNER_GAP => ThemeSized::inner_gap,
+            OUTER_GAP => ThemeSized::outer_gap,
             _ => return Err(CphError::UnknownSized(sized.0)),
         };
         Ok(sized)
     }
 
+    fn check_size(&self, sized: ThemeSized, size: i32) -> Result<(), CphError> {
+        if size < sized.min() {
+            return Err(CphError::InvalidSize(size, sized));
+        }
+        if size > sized.max() {
+            return Err(CphError::InvalidSize(size, sized));
+        }
+        Ok(())
+    }
+
     fn handle_get_size(&self, sized: Resizable) -> Result<(), CphError> {
         let sized = self.get_sized(sized)?;
         let size = sized.field(&self.state.theme).get();
@@ -1407,12 +1840,7 @@ impl ConfigProxyHandler {
 
     fn handle_set_size(&self, sized: Resizable, size: i32) -> Result<(), CphError> {
         let sized = self.get_sized(sized)?;
-        if size < sized.min() {
-            return Err(CphError::InvalidSize(size, sized));
-        }
-        if size > sized.max() {
-            return Err(CphError::InvalidSize(size, sized));
-        }
+        self.check_size(sized, size)?;
         sized.field(&self.state.theme).set(size);
         self.spaces_change();
         Ok(())
@@ -1428,6 +1856,75 @@ impl ConfigProxyHandler {
         self.spaces_change();
     }
 
+    fn handle_set_smart_gaps(&self, enabled: bool) {
+        self.state.theme.smart_gaps.set(enabled);
+        self.spaces_change();
+    }
+
+    fn handle_get_smart_gaps(&self) {
+        let enabled = self.state.theme.smart_gaps.get();
+        self.respond(Response::GetSmartGaps { enabled });
+    }
+
+    fn handle_set_color_scheme(&self, scheme: ColorScheme) {
+        self.state.theme.color_scheme.set(scheme);
+        self.state.appearance_changed();
+    }
+
+    fn handle_get_color_scheme(&self) {
+        let scheme = self.state.theme.color_scheme.get();
+        self.respond(Response::GetColorScheme { scheme });
+    }
+
+    fn modify_workspace_gaps(
+        &self,
+        workspace: Workspace,
+        f: impl FnOnce(&mut WorkspaceGaps),
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let gaps = {
+            let mut map = self.state.workspace_gaps.borrow_mut();
+            let gaps = map.entry(name.to_string()).or_default();
+            f(gaps);
+            *gaps
+        };
+        if let Some(ws) = self.state.workspaces.get(name.as_str()) {
+            ws.gaps.set(gaps);
+            self.spaces_change();
+        }
+        Ok(())
+    }
+
+    fn handle_set_workspace_inner_gap(
+        &self,
+        workspace: Workspace,
+        gap: Option<i32>,
+    ) -> Result<(), CphError> {
+        if let Some(gap) = gap {
+            self.check_size(ThemeSized::inner_gap, gap)?;
+        }
+        self.modify_workspace_gaps(workspace, |g| g.inner = gap)
+    }
+
+    fn handle_set_workspace_outer_gap(
+        &self,
+        workspace: Workspace,
+        gap: Option<i32>,
+    ) -> Result<(), CphError> {
+        if let Some(gap) = gap {
+            self.check_size(ThemeSized::outer_gap, gap)?;
+        }
+        self.modify_workspace_gaps(workspace, |g| g.outer = gap)
+    }
+
+    fn handle_set_workspace_smart_gaps(
+        &self,
+        workspace: Workspace,
+        enabled: Option<bool>,
+    ) -> Result<(), CphError> {
+        self.modify_workspace_gaps(workspace, |g| g.smart = enabled)
+    }
+
     fn handle_reset_font(&self) {
         *self.state.theme.font.borrow_mut() = DEFAULT_FONT.to_string();
     }
@@ -1829,6 +2326,160 @@ impl ConfigProxyHandler {
             ClientMessage::SetWindowManagementEnabled { seat, enabled } => self
                 .handle_set_window_management_enabled(seat, enabled)
                 .wrn("set_window_management_enabled")?,
+            ClientMessage::ConnectorSetTearingEnabled { connector, enabled } => self
+                .handle_connector_set_tearing_enabled(connector, enabled)
+                .wrn("connector_set_tearing_enabled")?,
+            ClientMessage::ConnectorVrrCapable { connector } => self
+                .handle_connector_vrr_capable(connector)
+                .wrn("connector_vrr_capable")?,
+            ClientMessage::ConnectorSetVrrMode { connector, mode } => self
+                .handle_connector_set_vrr_mode(connector, mode)
+                .wrn("connector_set_vrr_mode")?,
+            ClientMessage::ConnectorSetColorTemperature { connector, kelvin } => self
+                .handle_connector_set_color_temperature(connector, kelvin)
+                .wrn("connector_set_color_temperature")?,
+            ClientMessage::AddWindowRule { rule } => {
+                self.handle_add_window_rule(rule).wrn("add_window_rule")?
+            }
+            ClientMessage::ClearWindowRules => self.handle_clear_window_rules(),
+            ClientMessage::GetWindows => self.handle_get_windows(),
+            ClientMessage::SeatFocusedWindow { seat } => self
+                .handle_seat_focused_window(seat)
+                .wrn("seat_focused_window")?,
+            ClientMessage::WindowExists { window } => self.handle_window_exists(window),
+            ClientMessage::GetWindowTitle { window } => self
+                .handle_get_window_title(window)
+                .wrn("get_window_title")?,
+            ClientMessage::GetWindowAppId { window } => self
+                .handle_get_window_app_id(window)
+                .wrn("get_window_app_id")?,
+            ClientMessage::GetWindowClass { window } => self
+                .handle_get_window_class(window)
+                .wrn("get_window_class")?,
+            ClientMessage::GetWindowPid { window } => {
+                self.handle_get_window_pid(window).wrn("get_window_pid")?
+            }
+            ClientMessage::GetWindowType { window } => {
+                self.handle_get_window_type(window).wrn("get_window_type")?
+            }
+            ClientMessage::GetWindowWorkspace { window } => self
+                .handle_get_window_workspace(window)
+                .wrn("get_window_workspace")?,
+            ClientMessage::FocusWindow { window, seat } => {
+                self.handle_focus_window(window, seat).wrn("focus_window")?
+            }
+            ClientMessage::CloseWindow { window } => {
+                self.handle_close_window(window).wrn("close_window")?
+            }
+            ClientMessage::GetWindowFloating { window } => self
+                .handle_get_window_floating(window)
+                .wrn("get_window_floating")?,
+            ClientMessage::SetWindowFloating { window, floating } => self
+                .handle_set_window_floating(window, floating)
+                .wrn("set_window_floating")?,
+            ClientMessage::MoveWindowToWorkspace { window, workspace } => self
+                .handle_move_window_to_workspace(window, workspace)
+                .wrn("move_window_to_workspace")?,
+            ClientMessage::GetWindowFullscreen { window } => self
+                .handle_get_window_fullscreen(window)
+                .wrn("get_window_fullscreen")?,
+            ClientMessage::SetWindowFullscreen { window, fullscreen } => self
+                .handle_set_window_fullscreen(window, fullscreen)
+                .wrn("set_window_fullscreen")?,
+            ClientMessage::SetSmartGaps { enabled } => self.handle_set_smart_gaps(enabled),
+            ClientMessage::GetSmartGaps => self.handle_get_smart_gaps(),
+            ClientMessage::SetWorkspaceInnerGap { workspace, gap } => self
+                .handle_set_workspace_inner_gap(workspace, gap)
+                .wrn("set_workspace_inner_gap")?,
+            ClientMessage::SetWorkspaceOuterGap { workspace, gap } => self
+                .handle_set_workspace_outer_gap(workspace, gap)
+                .wrn("set_workspace_outer_gap")?,
+            ClientMessage::SetWorkspaceSmartGaps { workspace, enabled } => self
+                .handle_set_workspace_smart_gaps(workspace, enabled)
+                .wrn("set_workspace_smart_gaps")?,
+            ClientMessage::SeatMoveToScratchpad { seat } => self
+                .handle_seat_move_to_scratchpad(seat)
+                .wrn("seat_move_to_scratchpad")?,
+            ClientMessage::SeatToggleScratchpad { seat, app_id } => self
+                .handle_seat_toggle_scratchpad(seat, app_id)
+                .wrn("seat_toggle_scratchpad")?,
+            ClientMessage::SetXScalingMode { mode } => self.handle_set_x_scaling_mode(mode),
+            ClientMessage::GetWindowMinimized { window } => self
+                .handle_get_window_minimized(window)
+                .wrn("get_window_minimized")?,
+            ClientMessage::SetWindowMinimized { window, minimized } => self
+                .handle_set_window_minimized(window, minimized)
+                .wrn("set_window_minimized")?,
+            ClientMessage::GetWorkspaceMinimizedWindows { workspace } => self
+                .handle_get_workspace_minimized_windows(workspace)
+                .wrn("get_workspace_minimized_windows")?,
+            ClientMessage::SeatMinimize { seat } => {
+                self.handle_seat_minimize(seat).wrn("seat_minimize")?
+            }
+            ClientMessage::SeatRestoreMinimized { seat } => self
+                .handle_seat_restore_minimized(seat)
+                .wrn("seat_restore_minimized")?,
+            ClientMessage::SeatResize {
+                seat,
+                direction,
+                amount,
+            } => self
+                .handle_seat_resize(seat, direction, amount)
+                .wrn("seat_resize")?,
+            ClientMessage::SeatMoveFloating { seat, dx, dy } => self
+                .handle_seat_move_floating(seat, dx, dy)
+                .wrn("seat_move_floating")?,
+            ClientMessage::SeatSetFloatingGeometry {
+                seat,
+                x,
+                y,
+                width,
+                height,
+            } => self
+                .handle_seat_set_floating_geometry(seat, x, y, width, height)
+                .wrn("seat_set_floating_geometry")?,
+            ClientMessage::SetBindingMode { seat, name, once } => self
+                .handle_set_binding_mode(seat, name, once)
+                .wrn("set_binding_mode")?,
+            ClientMessage::AddPointerShortcut {
+                seat,
+                mods,
+                trigger,
+            } => self
+                .handle_add_pointer_shortcut(seat, mods, trigger)
+                .wrn("add_pointer_shortcut")?,
+            ClientMessage::RemovePointerShortcut {
+                seat,
+                mods,
+                trigger,
+            } => self
+                .handle_remove_pointer_shortcut(seat, mods, trigger)
+                .wrn("remove_pointer_shortcut")?,
+            ClientMessage::FocusLast { seat } => self.handle_focus_last(seat).wrn("focus_last")?,
+            ClientMessage::FocusNextMru { seat } => {
+                self.handle_focus_next_mru(seat).wrn("focus_next_mru")?
+            }
+            ClientMessage::FocusPrevMru { seat } => {
+                self.handle_focus_prev_mru(seat).wrn("focus_prev_mru")?
+            }
+            ClientMessage::ShowAdjacentWorkspace { seat, next, global } => self
+                .handle_show_adjacent_workspace(seat, next, global)
+                .wrn("show_adjacent_workspace")?,
+            ClientMessage::WorkspaceBackAndForth { seat } => self
+                .handle_workspace_back_and_forth(seat)
+                .wrn("workspace_back_and_forth")?,
+            ClientMessage::RenameWorkspace { workspace, name } => self
+                .handle_rename_workspace(workspace, name)
+                .wrn("rename_workspace")?,
+            ClientMessage::SetWorkspaceConnector {
+                workspace,
+                connector,
+            } => self
+                .handle_set_workspace_connector(workspace, connector)
+                .wrn("set_workspace_connector")?,
+            ClientMessage::IpcCommandResult { error } => self.handle_ipc_command_result(error),
+            ClientMessage::SetColorScheme { scheme } => self.handle_set_color_scheme(scheme),
+            ClientMessage::GetColorScheme => self.handle_get_color_scheme(),
         }
         Ok(())
     }
@@ -1870,8 +2521,12 @@ enum CphError {
     DrmDeviceDoesNotExist(DrmDevice),
     #[error("Workspace {0:?} does not exist")]
     WorkspaceDoesNotExist(Workspace),
+    #[error("A workspace with the name {0} already exists")]
+    WorkspaceAlreadyExists(String),
     #[error("Keyboard {0:?} does not exist")]
     KeyboardDoesNotExist(InputDevice),
+    #[error("Window {0:?} does not exist")]
+    WindowDoesNotExist(Window),
     #[error("Colorable element {0} is not known")]
     UnknownColor(u32),
     #[error("Sized element {0} is not known")]
@@ -1890,6 +2545,12 @@ enum CphError {
     NegativeCursorSize,
     #[error("Config referred to a pollable that does not exist")]
     PollableDoesNotExist,
+    #[error("Could not change the mode of the connector")]
+    SetMode(#[source] Box<dyn std::error::Error>),
+    #[error("Could not change the enabled state of the connector")]
+    SetEnabled(#[source] Box<dyn std::error::Error>),
+    #[error("The title of the window rule is not a valid regular expression")]
+    InvalidWindowRuleTitle(#[source] regex::Error),
 }
 
 trait WithRequestName {
@@ -363,6 +363,11 @@ impl CursorUser {
         }
         self.pos.set((x, y));
         self.update_hardware_cursor_(false);
+        if self.is_active() && self.group.state.metadata_cursor_screencasts.get() > 0 {
+            // Screencasts that send the cursor as metadata need a new frame even if the
+            // cursor is a hardware cursor.
+            self.output.get().global.connector.connector.damage();
+        }
         (x, y)
     }
 
@@ -1,7 +1,7 @@
 use std::{
     cmp::Ordering,
     fmt::{Debug, Display, Formatter},
-    ops::{Add, AddAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, MulAssign, Sub, SubAssign},
 };
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
@@ -111,3 +111,9 @@ impl SubAssign for Fixed {
         self.0 -= rhs.0;
     }
 }
+
+impl MulAssign<i32> for Fixed {
+    fn mul_assign(&mut self, rhs: i32) {
+        self.0 *= rhs;
+    }
+}
@@ -0,0 +1,157 @@
+use {
+    crate::{
+        ifs::wl_seat::WlSeatGlobal,
+        minimize,
+        state::State,
+        tree::{Node, OutputNode, ToplevelNode},
+        utils::linkedlist::LinkedList,
+    },
+    std::{cell::RefCell, rc::Rc},
+};
+
+/// The windows that have been focused by a seat.
+///
+/// The most recently focused window is the last element of the list. Windows are removed
+/// from the list when they are unmapped.
+#[derive(Default)]
+pub struct FocusHistory {
+    windows: LinkedList<Rc<dyn ToplevelNode>>,
+    switcher: RefCell<Option<WindowSwitcher>>,
+}
+
+/// A window switcher that is shown while the modifiers of the shortcut that opened it are
+/// held.
+pub struct WindowSwitcher {
+    pub output: Rc<OutputNode>,
+    pub windows: Vec<Rc<dyn ToplevelNode>>,
+    pub selected: usize,
+}
+
+impl FocusHistory {
+    pub fn clear(&self) {
+        self.switcher.take();
+        let windows: Vec<_> = self.windows.iter().map(|tl| (*tl).clone()).collect();
+        for tl in windows {
+            tl.tl_data().focus_history.clear();
+        }
+    }
+
+    pub fn switcher(&self) -> &RefCell<Option<WindowSwitcher>> {
+        &self.switcher
+    }
+
+    /// Returns the windows in most-recently-used order.
+    ///
+    /// Windows that are neither attached to a workspace nor minimized are skipped.
+    fn windows(&self) -> Vec<Rc<dyn ToplevelNode>> {
+        self.windows
+            .rev_iter()
+            .map(|tl| (*tl).clone())
+            .filter(|tl| {
+                let data = tl.tl_data();
+                data.workspace.is_some() || data.is_minimized()
+            })
+            .collect()
+    }
+}
+
+/// Moves the toplevel that contains the node to the front of the focus history of the
+/// seat.
+pub fn add_to_focus_history(seat: &WlSeatGlobal, node: Rc<dyn Node>) {
+    let Some(tl) = node.node_toplevel() else {
+        return;
+    };
+    if tl.tl_admits_children() || tl.node_is_placeholder() {
+        return;
+    }
+    let link = seat.focus_history().windows.add_last(tl.clone());
+    tl.tl_data().focus_history.insert(seat.id(), link);
+}
+
+/// Restores the toplevel if it is minimized, shows its workspace, and focuses it.
+pub fn activate(state: &Rc<State>, seat: &Rc<WlSeatGlobal>, tl: Rc<dyn ToplevelNode>) {
+    minimize::restore(state, tl.clone());
+    if let Some(ws) = tl.tl_data().workspace.get() {
+        if !ws.visible.get() {
+            state.show_workspace(seat, &ws.name.borrow());
+        }
+    }
+    seat.focus_toplevel(tl);
+}
+
+/// Focuses the most recently focused window other than the focused window.
+pub fn focus_last(state: &Rc<State>, seat: &Rc<WlSeatGlobal>) {
+    let focused = seat.focused_toplevel().map(|tl| tl.node_id());
+    let tl = seat
+        .focus_history()
+        .windows()
+        .into_iter()
+        .find(|tl| Some(tl.node_id()) != focused);
+    if let Some(tl) = tl {
+        activate(state, seat, tl);
+    }
+}
+
+/// Selects the next or previous window in the window switcher of the seat.
+///
+/// If the window switcher is not open, it is opened on the output that contains the
+/// cursor. The selected window is focused once all modifiers have been released.
+pub fn cycle_focus_history(state: &Rc<State>, seat: &Rc<WlSeatGlobal>, forward: bool) {
+    let history = seat.focus_history();
+    let output = {
+        let switcher = &mut *history.switcher.borrow_mut();
+        match switcher {
+            Some(switcher) => {
+                let len = switcher.windows.len();
+                switcher.selected = match forward {
+                    true => (switcher.selected + 1) % len,
+                    false => (switcher.selected + len - 1) % len,
+                };
+                switcher.output.clone()
+            }
+            None => {
+                let windows = history.windows();
+                if windows.is_empty() {
+                    return;
+                }
+                let len = windows.len();
+                let focused = seat.focused_toplevel().map(|tl| tl.node_id());
+                let selected = match forward {
+                    true if Some(windows[0].node_id()) == focused => 1 % len,
+                    true => 0,
+                    false => len - 1,
+                };
+                let output = seat.get_output();
+                *switcher = Some(WindowSwitcher {
+                    output: output.clone(),
+                    windows,
+                    selected,
+                });
+                output
+            }
+        }
+    };
+    output.schedule_update_render_data();
+    on_mods_changed(state, seat);
+}
+
+/// Focuses the selected window of the window switcher if all modifiers have been
+/// released.
+pub fn on_mods_changed(state: &Rc<State>, seat: &Rc<WlSeatGlobal>) {
+    let history = seat.focus_history();
+    if history.switcher.borrow().is_none() {
+        return;
+    }
+    if seat.shortcut_mods().0 != 0 {
+        return;
+    }
+    let Some(switcher) = history.switcher.take() else {
+        return;
+    };
+    switcher.output.schedule_update_render_data();
+    let tl = switcher.windows[switcher.selected].clone();
+    let data = tl.tl_data();
+    if data.workspace.is_some() || data.is_minimized() {
+        activate(state, seat, tl);
+    }
+}
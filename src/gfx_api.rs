@@ -3,7 +3,7 @@ use {
         cursor::Cursor,
         fixed::Fixed,
         format::Format,
-        rect::Rect,
+        rect::{Rect, Region},
         renderer::{renderer_base::RendererBase, RenderResult, Renderer},
         scale::Scale,
         state::State,
@@ -226,10 +226,14 @@ pub trait GfxFramebuffer: Debug {
 
     fn physical_size(&self) -> (i32, i32);
 
+    /// Executes the ops. Only the pixels inside `region` are modified.
+    ///
+    /// The region is given in physical framebuffer coordinates.
     fn render(
         &self,
         ops: Vec<GfxApiOpt>,
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<Option<SyncFile>, GfxError>;
 
     fn copy_to_shm(
@@ -253,7 +257,36 @@ impl dyn GfxFramebuffer {
 
     pub fn clear_with(&self, r: f32, g: f32, b: f32, a: f32) -> Result<Option<SyncFile>, GfxError> {
         let ops = self.take_render_ops();
-        self.render(ops, Some(&Color { r, g, b, a }))
+        self.render(ops, Some(&Color { r, g, b, a }), &self.full_region())
+    }
+
+    pub fn full_region(&self) -> Rc<Region> {
+        let (width, height) = self.physical_size();
+        Region::new(Rect::new_sized_unchecked(0, 0, width, height))
+    }
+
+    /// Converts damage given in output-local, logical coordinates into a region in
+    /// physical framebuffer coordinates.
+    pub fn damage_region(&self, damage: &[Rect], scale: Scale, transform: Transform) -> Rc<Region> {
+        let (width, height) = self.physical_size();
+        let bounds = Rect::new_sized_unchecked(0, 0, width, height);
+        let scale = scale.to_f64();
+        let rects: Vec<_> = damage
+            .iter()
+            .filter_map(|rect| {
+                // Expand by one pixel to account for filtering of scaled textures.
+                let x1 = (rect.x1() as f64 * scale).floor() as i32 - 1;
+                let y1 = (rect.y1() as f64 * scale).floor() as i32 - 1;
+                let x2 = (rect.x2() as f64 * scale).ceil() as i32 + 1;
+                let y2 = (rect.y2() as f64 * scale).ceil() as i32 + 1;
+                let (x1, y1) = transform.apply_point(width, height, (x1, y1));
+                let (x2, y2) = transform.apply_point(width, height, (x2, y2));
+                let rect = Rect::new(x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2))?;
+                let rect = rect.intersect(bounds);
+                (!rect.is_empty()).then_some(rect)
+            })
+            .collect();
+        Region::from_rects(&rects)
     }
 
     pub fn logical_size(&self, transform: Transform) -> (i32, i32) {
@@ -303,7 +336,7 @@ impl dyn GfxFramebuffer {
             release_sync,
         );
         let clear = self.format().has_alpha.then_some(&Color::TRANSPARENT);
-        self.render(ops, clear)
+        self.render(ops, clear, &self.full_region())
     }
 
     pub fn render_custom(
@@ -315,7 +348,7 @@ impl dyn GfxFramebuffer {
         let mut ops = self.take_render_ops();
         let mut renderer = self.renderer_base(&mut ops, scale, Transform::None);
         f(&mut renderer);
-        self.render(ops, clear)
+        self.render(ops, clear, &self.full_region())
     }
 
     pub fn create_render_pass(
@@ -340,6 +373,7 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            opacity: 1.0,
         };
         node.node_render(&mut renderer, 0, 0, None);
         if let Some(rect) = cursor_rect {
@@ -405,8 +439,12 @@ impl dyn GfxFramebuffer {
         }
     }
 
-    pub fn perform_render_pass(&self, pass: GfxRenderPass) -> Result<Option<SyncFile>, GfxError> {
-        self.render(pass.ops, pass.clear.as_ref())
+    pub fn perform_render_pass(
+        &self,
+        pass: GfxRenderPass,
+        region: &Region,
+    ) -> Result<Option<SyncFile>, GfxError> {
+        self.render(pass.ops, pass.clear.as_ref(), region)
     }
 
     pub fn render_output(
@@ -454,7 +492,7 @@ impl dyn GfxFramebuffer {
             black_background,
             transform,
         );
-        self.perform_render_pass(pass)
+        self.perform_render_pass(pass, &self.full_region())
     }
 
     pub fn render_hardware_cursor(
@@ -474,9 +512,10 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            opacity: 1.0,
         };
         cursor.render_hardware_cursor(&mut renderer);
-        self.render(ops, Some(&Color::TRANSPARENT))
+        self.render(ops, Some(&Color::TRANSPARENT), &self.full_region())
     }
 }
 
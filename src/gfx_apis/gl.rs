@@ -79,10 +79,11 @@ use {
                 texture::Texture,
             },
             sys::{
-                GL_BLEND, GL_FALSE, GL_FLOAT, GL_LINEAR, GL_TEXTURE0, GL_TEXTURE_MIN_FILTER,
-                GL_TRIANGLES, GL_TRIANGLE_STRIP,
+                GL_BLEND, GL_COLOR_BUFFER_BIT, GL_FALSE, GL_FLOAT, GL_LINEAR, GL_SCISSOR_TEST,
+                GL_TEXTURE0, GL_TEXTURE_MIN_FILTER, GL_TRIANGLES, GL_TRIANGLE_STRIP,
             },
         },
+        rect::Region,
         theme::Color,
         utils::{errorfmt::ErrorFmt, rc_eq::rc_eq, vecstorage::VecStorage},
         video::{
@@ -205,7 +206,58 @@ struct GfxGlState {
     copy_tex: VecStorage<&'static CopyTexture>,
 }
 
-fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
+fn run_ops(
+    fb: &Framebuffer,
+    ops: &[GfxApiOpt],
+    clear: Option<&Color>,
+    region: &Region,
+) -> Option<SyncFile> {
+    for op in ops {
+        if let GfxApiOpt::CopyTexture(ct) = op {
+            handle_explicit_sync(&fb.ctx, ct.tex.as_gl(), &ct.acquire_sync);
+        }
+    }
+    let gles = fb.ctx.ctx.dpy.gles;
+    unsafe {
+        (gles.glEnable)(GL_SCISSOR_TEST);
+    }
+    for rect in region.iter() {
+        unsafe {
+            (gles.glScissor)(rect.x1(), rect.y1(), rect.width(), rect.height());
+            if let Some(c) = clear {
+                (gles.glClearColor)(c.r, c.g, c.b, c.a);
+                (gles.glClear)(GL_COLOR_BUFFER_BIT);
+            }
+        }
+        draw_ops(fb, ops);
+    }
+    unsafe {
+        (gles.glDisable)(GL_SCISSOR_TEST);
+    }
+    if fb.ctx.ctx.dpy.explicit_sync {
+        let file = match fb.ctx.ctx.export_sync_file() {
+            Ok(f) => SyncFile(Rc::new(f)),
+            Err(e) => {
+                log::error!("Could not create sync file: {}", ErrorFmt(e));
+                return None;
+            }
+        };
+        let user = fb.ctx.buffer_resv_user;
+        for op in ops {
+            if let GfxApiOpt::CopyTexture(ct) = op {
+                if ct.release_sync == ReleaseSync::Explicit {
+                    if let Some(resv) = &ct.buffer_resv {
+                        resv.set_sync_file(user, &file);
+                    }
+                }
+            }
+        }
+        return Some(file);
+    }
+    None
+}
+
+fn draw_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) {
     let mut state = fb.ctx.gl_state.borrow_mut();
     let state = &mut *state;
     let mut fill_rect = state.fill_rect.take();
@@ -274,27 +326,6 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
             render_texture(&fb.ctx, tex);
         }
     }
-    if fb.ctx.ctx.dpy.explicit_sync {
-        let file = match fb.ctx.ctx.export_sync_file() {
-            Ok(f) => SyncFile(Rc::new(f)),
-            Err(e) => {
-                log::error!("Could not create sync file: {}", ErrorFmt(e));
-                return None;
-            }
-        };
-        let user = fb.ctx.buffer_resv_user;
-        for op in ops {
-            if let GfxApiOpt::CopyTexture(ct) = op {
-                if ct.release_sync == ReleaseSync::Explicit {
-                    if let Some(resv) = &ct.buffer_resv {
-                        resv.set_sync_file(user, &file);
-                    }
-                }
-            }
-        }
-        return Some(file);
-    }
-    None
 }
 
 fn fill_boxes3(ctx: &GlRenderContext, boxes: &[[f32; 2]], color: &Color) {
@@ -321,8 +352,6 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
     assert!(rc_eq(&ctx.ctx, &texture.ctx.ctx));
     let gles = ctx.ctx.dpy.gles;
     unsafe {
-        handle_explicit_sync(ctx, texture, &tex.acquire_sync);
-
         (gles.glActiveTexture)(GL_TEXTURE0);
 
         let target = image_target(texture.gl.external_only);
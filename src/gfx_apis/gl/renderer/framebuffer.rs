@@ -3,15 +3,13 @@ use {
         format::Format,
         gfx_api::{GfxApiOpt, GfxError, GfxFramebuffer, SyncFile},
         gfx_apis::gl::{
-            gl::{
-                frame_buffer::GlFrameBuffer,
-                sys::{GL_COLOR_BUFFER_BIT, GL_FRAMEBUFFER},
-            },
+            gl::{frame_buffer::GlFrameBuffer, sys::GL_FRAMEBUFFER},
             renderer::context::GlRenderContext,
             run_ops,
             sys::{GL_ONE, GL_ONE_MINUS_SRC_ALPHA},
             RenderError,
         },
+        rect::Region,
         theme::Color,
     },
     std::{
@@ -72,19 +70,16 @@ impl Framebuffer {
         &self,
         mut ops: Vec<GfxApiOpt>,
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<Option<SyncFile>, RenderError> {
         let gles = self.ctx.ctx.dpy.gles;
         let res = self.ctx.ctx.with_current(|| {
             unsafe {
                 (gles.glBindFramebuffer)(GL_FRAMEBUFFER, self.gl.fbo);
                 (gles.glViewport)(0, 0, self.gl.width, self.gl.height);
-                if let Some(c) = clear {
-                    (gles.glClearColor)(c.r, c.g, c.b, c.a);
-                    (gles.glClear)(GL_COLOR_BUFFER_BIT);
-                }
                 (gles.glBlendFunc)(GL_ONE, GL_ONE_MINUS_SRC_ALPHA);
             }
-            let fd = run_ops(self, &ops);
+            let fd = run_ops(self, &ops, clear, region);
             if fd.is_none() {
                 unsafe {
                     (gles.glFlush)();
@@ -111,8 +106,9 @@ impl GfxFramebuffer for Framebuffer {
         &self,
         ops: Vec<GfxApiOpt>,
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<Option<SyncFile>, GfxError> {
-        self.render(ops, clear).map_err(|e| e.into())
+        self.render(ops, clear, region).map_err(|e| e.into())
     }
 
     fn copy_to_shm(
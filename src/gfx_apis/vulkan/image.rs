@@ -6,6 +6,7 @@ use {
             allocator::VulkanAllocation, device::VulkanDevice, format::VulkanMaxExtents,
             renderer::VulkanRenderer, shm_image::VulkanShmImage, util::OnDrop, VulkanError,
         },
+        rect::Region,
         theme::Color,
         utils::clonecell::CloneCell,
         video::dmabuf::{DmaBuf, PlaneVec},
@@ -470,9 +471,10 @@ impl GfxFramebuffer for VulkanImage {
         &self,
         ops: Vec<GfxApiOpt>,
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<Option<SyncFile>, GfxError> {
         self.renderer
-            .execute(self, &ops, clear)
+            .execute(self, &ops, clear, region)
             .map_err(|e| e.into())
     }
 
@@ -22,6 +22,7 @@ use {
             VulkanError,
         },
         io_uring::IoUring,
+        rect::{Rect, Region},
         theme::Color,
         utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell, stack::Stack},
         video::dmabuf::{dma_buf_export_sync_file, DMA_BUF_SYNC_READ, DMA_BUF_SYNC_WRITE},
@@ -30,14 +31,15 @@ use {
     ash::{
         vk::{
             AccessFlags2, AttachmentLoadOp, AttachmentStoreOp, BufferImageCopy,
-            BufferMemoryBarrier2, ClearColorValue, ClearValue, CommandBuffer,
-            CommandBufferBeginInfo, CommandBufferSubmitInfo, CommandBufferUsageFlags,
-            CopyImageInfo2, DependencyInfo, DependencyInfoKHR, DescriptorImageInfo, DescriptorType,
-            Extent2D, Extent3D, Fence, ImageAspectFlags, ImageCopy2, ImageLayout,
-            ImageMemoryBarrier2, ImageMemoryBarrier2Builder, ImageSubresourceLayers,
-            ImageSubresourceRange, PipelineBindPoint, PipelineStageFlags2, Rect2D,
-            RenderingAttachmentInfo, RenderingInfo, SemaphoreSubmitInfo, SemaphoreSubmitInfoKHR,
-            ShaderStageFlags, SubmitInfo2, Viewport, WriteDescriptorSet, QUEUE_FAMILY_FOREIGN_EXT,
+            BufferMemoryBarrier2, ClearAttachment, ClearColorValue, ClearRect, ClearValue,
+            CommandBuffer, CommandBufferBeginInfo, CommandBufferSubmitInfo,
+            CommandBufferUsageFlags, CopyImageInfo2, DependencyInfo, DependencyInfoKHR,
+            DescriptorImageInfo, DescriptorType, Extent2D, Extent3D, Fence, ImageAspectFlags,
+            ImageCopy2, ImageLayout, ImageMemoryBarrier2, ImageMemoryBarrier2Builder,
+            ImageSubresourceLayers, ImageSubresourceRange, Offset2D, PipelineBindPoint,
+            PipelineStageFlags2, Rect2D, RenderingAttachmentInfo, RenderingInfo,
+            SemaphoreSubmitInfo, SemaphoreSubmitInfoKHR, ShaderStageFlags, SubmitInfo2, Viewport,
+            WriteDescriptorSet, QUEUE_FAMILY_FOREIGN_EXT,
         },
         Device,
     },
@@ -285,14 +287,29 @@ impl VulkanRenderer {
         }
     }
 
-    fn begin_rendering(&self, buf: CommandBuffer, fb: &VulkanImage, clear: Option<&Color>) {
+    fn begin_rendering(
+        &self,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        clear: Option<&Color>,
+        region: &Region,
+    ) {
+        let full = match &region[..] {
+            [rect] => rect.contains_rect(&Rect::new_sized_unchecked(
+                0,
+                0,
+                fb.width as _,
+                fb.height as _,
+            )),
+            _ => false,
+        };
         let rendering_attachment_info = {
             let mut rai = RenderingAttachmentInfo::builder()
                 .image_view(fb.render_view.unwrap_or(fb.texture_view))
                 .image_layout(ImageLayout::GENERAL)
                 .load_op(AttachmentLoadOp::LOAD)
                 .store_op(AttachmentStoreOp::STORE);
-            if let Some(clear) = clear {
+            if let (Some(clear), true) = (clear, full) {
                 rai = rai
                     .clear_value(ClearValue {
                         color: ClearColorValue {
@@ -316,6 +333,34 @@ impl VulkanRenderer {
         unsafe {
             self.device.device.cmd_begin_rendering(buf, &rendering_info);
         }
+        if let (Some(clear), false) = (clear, full) {
+            let attachment = ClearAttachment {
+                aspect_mask: ImageAspectFlags::COLOR,
+                color_attachment: 0,
+                clear_value: ClearValue {
+                    color: ClearColorValue {
+                        float32: clear.to_array_srgb(),
+                    },
+                },
+            };
+            let rects: Vec<_> = region
+                .iter()
+                .map(|rect| ClearRect {
+                    rect: rect_2d(rect),
+                    base_array_layer: 0,
+                    layer_count: 1,
+                })
+                .collect();
+            if !rects.is_empty() {
+                unsafe {
+                    self.device.device.cmd_clear_attachments(
+                        buf,
+                        slice::from_ref(&attachment),
+                        &rects,
+                    );
+                }
+            }
+        }
     }
 
     fn set_viewport(&self, buf: CommandBuffer, fb: &VulkanImage) {
@@ -327,20 +372,18 @@ impl VulkanRenderer {
             min_depth: 0.0,
             max_depth: 1.0,
         };
-        let scissor = Rect2D {
-            offset: Default::default(),
-            extent: Extent2D {
-                width: fb.width,
-                height: fb.height,
-            },
-        };
         unsafe {
             self.device
                 .device
                 .cmd_set_viewport(buf, 0, slice::from_ref(&viewport));
+        }
+    }
+
+    fn set_scissor(&self, buf: CommandBuffer, rect: &Rect) {
+        unsafe {
             self.device
                 .device
-                .cmd_set_scissor(buf, 0, slice::from_ref(&scissor));
+                .cmd_set_scissor(buf, 0, slice::from_ref(&rect_2d(rect)));
         }
     }
 
@@ -906,8 +949,9 @@ impl VulkanRenderer {
         fb: &VulkanImage,
         opts: &[GfxApiOpt],
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<Option<SyncFile>, VulkanError> {
-        let res = self.try_execute(fb, opts, clear);
+        let res = self.try_execute(fb, opts, clear, region);
         let sync_file = {
             let mut memory = self.memory.borrow_mut();
             memory.textures.clear();
@@ -943,14 +987,18 @@ impl VulkanRenderer {
         fb: &VulkanImage,
         opts: &[GfxApiOpt],
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<(), VulkanError> {
         let buf = self.allocate_command_buffer()?;
         self.collect_memory(opts);
         self.begin_command_buffer(buf.buffer)?;
         self.initial_barriers(buf.buffer, fb);
-        self.begin_rendering(buf.buffer, fb, clear);
+        self.begin_rendering(buf.buffer, fb, clear, region);
         self.set_viewport(buf.buffer, fb);
-        self.record_draws(buf.buffer, opts)?;
+        for rect in region.iter() {
+            self.set_scissor(buf.buffer, rect);
+            self.record_draws(buf.buffer, opts)?;
+        }
         self.end_rendering(buf.buffer);
         self.copy_bridge_to_dmabuf(buf.buffer, fb);
         self.final_barriers(buf.buffer, fb);
@@ -1061,3 +1109,16 @@ async fn await_release(
     }
     renderer.pending_frames.remove(&frame.point);
 }
+
+fn rect_2d(rect: &Rect) -> Rect2D {
+    Rect2D {
+        offset: Offset2D {
+            x: rect.x1(),
+            y: rect.y1(),
+        },
+        extent: Extent2D {
+            width: rect.width() as _,
+            height: rect.height() as _,
+        },
+    }
+}
@@ -45,7 +45,9 @@ use {
             xdg_toplevel_drag_manager_v1::XdgToplevelDragManagerV1Global,
             xdg_wm_base::XdgWmBaseGlobal,
             xdg_wm_dialog_v1::XdgWmDialogV1Global,
+            zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1Global,
             zwlr_layer_shell_v1::ZwlrLayerShellV1Global,
+            zwlr_output_manager_v1::ZwlrOutputManagerV1Global,
             zwlr_screencopy_manager_v1::ZwlrScreencopyManagerV1Global,
             zwp_idle_inhibit_manager_v1::ZwpIdleInhibitManagerV1Global,
             zxdg_decoration_manager_v1::ZxdgDecorationManagerV1Global,
@@ -195,6 +197,8 @@ impl Globals {
         add_singleton!(ExtTransientSeatManagerV1Global);
         add_singleton!(ZwpPointerGesturesV1Global);
         add_singleton!(ZwpTabletManagerV2Global);
+        add_singleton!(ZwlrGammaControlManagerV1Global);
+        add_singleton!(ZwlrOutputManagerV1Global);
     }
 
     pub fn add_backend_singletons(&self, backend: &Rc<dyn Backend>) {
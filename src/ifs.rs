@@ -5,9 +5,12 @@ pub mod ext_idle_notifier_v1;
 pub mod ext_session_lock_manager_v1;
 pub mod ext_session_lock_v1;
 pub mod ipc;
+pub mod jay_appearance;
 pub mod jay_compositor;
+pub mod jay_global_shortcuts;
 pub mod jay_idle;
 pub mod jay_input;
+pub mod jay_input_injector;
 pub mod jay_log_file;
 pub mod jay_output;
 pub mod jay_pointer;
@@ -19,6 +22,7 @@ pub mod jay_seat_events;
 pub mod jay_select_toplevel;
 pub mod jay_select_workspace;
 pub mod jay_toplevel;
+pub mod jay_tree;
 pub mod jay_workspace;
 pub mod jay_workspace_watcher;
 pub mod org_kde_kwin_server_decoration;
@@ -62,7 +66,14 @@ pub mod xdg_toplevel_drag_manager_v1;
 pub mod xdg_toplevel_drag_v1;
 pub mod xdg_wm_base;
 pub mod xdg_wm_dialog_v1;
+pub mod zwlr_gamma_control_manager_v1;
+pub mod zwlr_gamma_control_v1;
 pub mod zwlr_layer_shell_v1;
+pub mod zwlr_output_configuration_head_v1;
+pub mod zwlr_output_configuration_v1;
+pub mod zwlr_output_head_v1;
+pub mod zwlr_output_manager_v1;
+pub mod zwlr_output_mode_v1;
 pub mod zwlr_screencopy_frame_v1;
 pub mod zwlr_screencopy_manager_v1;
 pub mod zwp_idle_inhibit_manager_v1;
@@ -322,6 +322,37 @@ pub fn detach_seat<S: DataSource>(src: &S, seat: &Rc<WlSeatGlobal>) {
     // data.client.flush();
 }
 
+/// Recomputes the action selected for a drag-and-drop operation and sends it to all offers.
+///
+/// Returns the new action if it has changed.
+fn update_selected_action<S: DynDataSource>(src: &S) -> Option<u32> {
+    let data = src.source_data();
+    let shared = data.shared.get();
+    let server_actions = match data.actions.get() {
+        Some(n) => n,
+        _ => {
+            log::error!("Server actions not set");
+            return None;
+        }
+    };
+    let actions = server_actions & shared.receiver_actions.get();
+    let action = if actions.contains(shared.receiver_preferred_action.get()) {
+        shared.receiver_preferred_action.get()
+    } else if actions != 0 {
+        1 << actions.trailing_zeros()
+    } else {
+        0
+    };
+    if shared.selected_action.replace(action) == action {
+        return None;
+    }
+    for (_, offer) in &data.offers {
+        offer.send_action(action);
+        // offer.client.flush();
+    }
+    Some(action)
+}
+
 fn offer_source_to_device<T: IpcVtable, S: DynDataSource>(
     src: &Rc<S>,
     dd: &Rc<T::Device>,
@@ -70,11 +70,12 @@ impl WlDataDevice {
     pub fn send_enter(
         &self,
         surface: WlSurfaceId,
-        x: Fixed,
-        y: Fixed,
+        mut x: Fixed,
+        mut y: Fixed,
         offer: WlDataOfferId,
         serial: u32,
     ) {
+        logical_to_client_wire_scale!(self.client, x, y);
         self.client.event(Enter {
             self_id: self.id,
             serial,
@@ -85,7 +86,8 @@ impl WlDataDevice {
         })
     }
 
-    pub fn send_motion(&self, time_usec: u64, x: Fixed, y: Fixed) {
+    pub fn send_motion(&self, time_usec: u64, mut x: Fixed, mut y: Fixed) {
+        logical_to_client_wire_scale!(self.client, x, y);
         self.client.event(Motion {
             self_id: self.id,
             time: (time_usec / 1000) as _,
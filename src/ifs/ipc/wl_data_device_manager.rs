@@ -11,13 +11,10 @@ use {
     thiserror::Error,
 };
 
-pub(super) const DND_NONE: u32 = 0;
-#[allow(dead_code)]
-pub(super) const DND_COPY: u32 = 1;
-#[allow(dead_code)]
-pub(super) const DND_MOVE: u32 = 2;
-#[allow(dead_code)]
-pub(super) const DND_ASK: u32 = 4;
+pub const DND_NONE: u32 = 0;
+pub const DND_COPY: u32 = 1;
+pub const DND_MOVE: u32 = 2;
+pub const DND_ASK: u32 = 4;
 pub(super) const DND_ALL: u32 = 7;
 
 pub struct WlDataDeviceManagerGlobal {
@@ -1,6 +1,7 @@
 use {
     crate::{
         client::{Client, ClientError},
+        fixed::Fixed,
         ifs::{
             ipc::{
                 x_data_offer::XDataOffer, x_data_source::XDataSource, DeviceData, IpcLocation,
@@ -12,7 +13,7 @@ use {
         xwayland::XWaylandEvent,
     },
     std::rc::Rc,
-    XWaylandEvent::IpcSetOffer,
+    XWaylandEvent::{DndDrop, DndLeave, DndMotion, IpcSetOffer},
 };
 
 linear_ids!(XIpcDeviceIds, XIpcDeviceId, u64);
@@ -26,6 +27,28 @@ pub struct XIpcDevice {
     pub client: Rc<Client>,
 }
 
+impl XIpcDevice {
+    pub fn send_dnd_motion(&self, x: Fixed, y: Fixed) {
+        self.state.xwayland.queue.push(DndMotion {
+            seat: self.seat.id(),
+            x,
+            y,
+        });
+    }
+
+    pub fn send_dnd_leave(&self) {
+        self.state.xwayland.queue.push(DndLeave {
+            seat: self.seat.id(),
+        });
+    }
+
+    pub fn send_dnd_drop(&self) {
+        self.state.xwayland.queue.push(DndDrop {
+            seat: self.seat.id(),
+        });
+    }
+}
+
 #[derive(Default)]
 pub struct XClipboardIpc;
 
@@ -100,6 +123,10 @@ impl<T: XIpc> IpcVtable for T {
             data,
             tracker: Default::default(),
             location: T::LOCATION,
+            mime_types: Default::default(),
+            dnd_accepted: Default::default(),
+            dnd_action: Default::default(),
+            dnd_mime_type: Default::default(),
         });
         track!(dd.client, rc);
         Ok(rc)
@@ -124,8 +151,11 @@ impl<T: XIpc> IpcVtable for T {
         });
     }
 
-    fn unset(seat: &Rc<WlSeatGlobal>, _role: Role) {
-        T::x_unset(seat)
+    fn unset(seat: &Rc<WlSeatGlobal>, role: Role) {
+        match role {
+            Role::Selection => T::x_unset(seat),
+            Role::Dnd => seat.cancel_dnd(),
+        }
     }
 
     fn device_client(dd: &Rc<Self::Device>) -> &Rc<Client> {
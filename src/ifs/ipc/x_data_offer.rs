@@ -1,19 +1,28 @@
 use {
     crate::{
         client::ClientId,
+        fixed::Fixed,
         ifs::{
             ipc::{
                 cancel_offer,
+                wl_data_device_manager::DND_NONE,
                 x_data_device::{XClipboardIpc, XIpcDevice, XPrimarySelectionIpc},
-                DataOffer, DataOfferId, DynDataOffer, IpcLocation, OfferData,
+                DataOffer, DataOfferId, DynDataOffer, IpcLocation, OfferData, Role,
+                OFFER_STATE_ACCEPTED, OFFER_STATE_DROPPED, OFFER_STATE_FINISHED,
+                SOURCE_STATE_FINISHED,
             },
             wl_seat::WlSeatGlobal,
         },
         leaks::Tracker,
+        utils::bitflags::BitflagsExt,
+        wire::WlSurfaceId,
         xwayland::XWaylandEvent,
     },
-    std::rc::Rc,
-    XWaylandEvent::IpcAddOfferMimeType,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    XWaylandEvent::{DndEnter, IpcAddOfferMimeType},
 };
 
 pub struct XDataOffer {
@@ -22,6 +31,14 @@ pub struct XDataOffer {
     pub data: OfferData<XIpcDevice>,
     pub tracker: Tracker<Self>,
     pub location: IpcLocation,
+    /// The mime types in the order in which they were offered.
+    pub mime_types: RefCell<Vec<String>>,
+    /// Whether the last `XdndStatus` of the X window accepted the drop.
+    pub dnd_accepted: Cell<bool>,
+    /// The action selected by the last `XdndStatus` of the X window.
+    pub dnd_action: Cell<u32>,
+    /// The mime type that the X window has requested from the `XdndSelection`.
+    pub dnd_mime_type: RefCell<Option<String>>,
 }
 
 impl DataOffer for XDataOffer {
@@ -42,6 +59,7 @@ impl DynDataOffer for XDataOffer {
     }
 
     fn send_offer(&self, mime_type: &str) {
+        self.mime_types.borrow_mut().push(mime_type.to_string());
         self.device.state.xwayland.queue.push(IpcAddOfferMimeType {
             location: self.location,
             seat: self.device.seat.id(),
@@ -60,4 +78,108 @@ impl DynDataOffer for XDataOffer {
     fn get_seat(&self) -> Rc<WlSeatGlobal> {
         self.device.seat.clone()
     }
+
+    fn send_action(&self, _action: u32) {
+        // X clients learn about the selected action from the XdndPosition messages
+    }
+
+    fn send_enter(&self, surface: WlSurfaceId, x: Fixed, y: Fixed, _serial: u32) {
+        self.device.state.xwayland.queue.push(DndEnter {
+            seat: self.device.seat.id(),
+            offer: self.offer_id,
+            surface,
+            x,
+            y,
+        })
+    }
+
+    fn send_source_actions(&self) {
+        // The source actions are sent with every XdndPosition message
+    }
+}
+
+impl XDataOffer {
+    pub fn is_dnd(&self) -> bool {
+        self.data.shared.role.get() == Role::Dnd
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.data.source.is_none()
+    }
+
+    pub fn source_actions(&self) -> u32 {
+        match self.data.source.get() {
+            Some(src) => src.source_data().actions.get().unwrap_or(DND_NONE),
+            _ => DND_NONE,
+        }
+    }
+
+    /// Handles an `XdndStatus` message of the X window under the pointer.
+    pub fn dnd_status(&self, accepted: bool, action: u32) {
+        self.dnd_accepted.set(accepted);
+        self.dnd_action.set(action);
+        self.update_dnd_status();
+    }
+
+    /// Handles a request of the X window under the pointer to convert the `XdndSelection`.
+    ///
+    /// `XdndStatus` does not contain a mime type. The mime type that the X window accepts
+    /// is only known once it requests the data.
+    pub fn dnd_request_mime_type(&self, mime_type: &str) {
+        if !self.mime_types.borrow().iter().any(|mt| mt == mime_type) {
+            return;
+        }
+        let prev = self
+            .dnd_mime_type
+            .borrow_mut()
+            .replace(mime_type.to_string());
+        if prev.as_deref() != Some(mime_type) && self.dnd_accepted.get() {
+            self.update_dnd_status();
+        }
+    }
+
+    fn update_dnd_status(&self) {
+        let mime_type = match self.dnd_accepted.get() {
+            // Until the X window requests a mime type, the first one is accepted so that
+            // the source allows the drop.
+            true => self
+                .dnd_mime_type
+                .borrow()
+                .clone()
+                .or_else(|| self.mime_types.borrow().first().cloned()),
+            false => None,
+        };
+        let action = self.dnd_action.get();
+        let shared = &self.data.shared;
+        let mut state = shared.state.get();
+        if state.contains(OFFER_STATE_FINISHED) {
+            return;
+        }
+        if mime_type.is_some() {
+            state |= OFFER_STATE_ACCEPTED;
+        } else {
+            state &= !OFFER_STATE_ACCEPTED;
+        }
+        shared.state.set(state);
+        shared.receiver_actions.set(action);
+        shared.receiver_preferred_action.set(action);
+        if let Some(src) = self.data.source.get() {
+            src.send_target(mime_type.as_deref());
+            src.update_selected_action();
+        }
+    }
+
+    /// Handles an `XdndFinished` message of the X window that accepted the drop.
+    pub fn dnd_finished(&self) {
+        let shared = &self.data.shared;
+        let state = shared.state.get();
+        if state.contains(OFFER_STATE_FINISHED) || !state.contains(OFFER_STATE_DROPPED) {
+            return;
+        }
+        shared.state.set(state | OFFER_STATE_FINISHED);
+        if let Some(src) = self.data.source.get() {
+            src.source_data().state.or_assign(SOURCE_STATE_FINISHED);
+            src.send_dnd_finished();
+        }
+    }
 }
@@ -1,22 +1,30 @@
 use {
     crate::{
         client::Client,
+        fixed::Fixed,
         ifs::{
             ipc::{
-                cancel_offers, detach_seat, offer_source_to_regular_client,
-                offer_source_to_wlr_device,
+                attach_seat, cancel_offers, detach_seat, offer_source_to_regular_client,
+                offer_source_to_wlr_device, update_selected_action,
                 wl_data_device::ClipboardIpc,
                 x_data_device::XIpcDevice,
                 zwlr_data_control_device_v1::{
                     WlrClipboardIpc, WlrPrimarySelectionIpc, ZwlrDataControlDeviceV1,
                 },
                 zwp_primary_selection_device_v1::PrimarySelectionIpc,
-                DataSource, DynDataSource, IpcLocation, SourceData,
+                DataSource, DynDataSource, IpcError, IpcLocation, Role, SharedState, SourceData,
+                OFFER_STATE_ACCEPTED, OFFER_STATE_DROPPED, SOURCE_STATE_DROPPED,
             },
             wl_seat::WlSeatGlobal,
+            wl_surface::WlSurface,
         },
+        object::Version,
         state::State,
-        xwayland::XWaylandEvent::{IpcCancelSource, IpcSendSource, IpcSetSelection},
+        time::now_usec,
+        utils::bitflags::BitflagsExt,
+        xwayland::XWaylandEvent::{
+            DndFinished, DndStatus, IpcCancelSource, IpcSendSource, IpcSetSelection,
+        },
     },
     std::rc::Rc,
     uapi::OwnedFd,
@@ -92,4 +100,110 @@ impl DynDataSource for XDataSource {
     fn cancel_unprivileged_offers(&self) {
         cancel_offers(self, false)
     }
+
+    fn send_target(&self, _mime_type: Option<&str>) {
+        self.send_dnd_status();
+    }
+
+    fn send_dnd_finished(&self) {
+        self.state.xwayland.queue.push(DndFinished {
+            seat: self.device.seat.id(),
+            source: self.data.id,
+        });
+    }
+
+    fn update_selected_action(&self) {
+        if update_selected_action(self).is_some() {
+            self.send_dnd_status();
+        }
+    }
+}
+
+impl XDataSource {
+    fn send_dnd_status(&self) {
+        self.state.xwayland.queue.push(DndStatus {
+            seat: self.device.seat.id(),
+            source: self.data.id,
+        });
+    }
+
+    /// Attaches the source to the seat as the source of a drag-and-drop operation.
+    pub fn start_dnd(&self, actions: u32) -> Result<(), IpcError> {
+        self.data.actions.set(Some(actions));
+        attach_seat(self, &self.device.seat, Role::Dnd)
+    }
+
+    /// Detaches the source from the seat once the drag-and-drop operation has ended.
+    pub fn end_dnd(&self) {
+        if let Some(seat) = self.data.seat.get() {
+            detach_seat(self, &seat);
+        }
+    }
+
+    pub fn can_drop(&self) -> bool {
+        let shared = self.data.shared.get();
+        shared.selected_action.get() != 0 && shared.state.get().contains(OFFER_STATE_ACCEPTED)
+    }
+
+    pub fn selected_action(&self) -> u32 {
+        self.data.shared.get().selected_action.get()
+    }
+
+    pub fn dnd_enter(self: &Rc<Self>, surface: &WlSurface, x: Fixed, y: Fixed) {
+        self.clone().offer_to_regular_client(&surface.client);
+        let serial = surface.client.next_serial();
+        for (_, offer) in &self.data.offers {
+            offer.send_enter(surface.id, x, y, serial);
+            offer.send_source_actions();
+        }
+    }
+
+    pub fn dnd_motion(&self, surface: &WlSurface, x: Fixed, y: Fixed) {
+        let time_usec = now_usec();
+        self.device
+            .seat
+            .for_each_data_device(Version::ALL, surface.client.id, |dd| {
+                dd.send_motion(time_usec, x, y);
+            });
+    }
+
+    pub fn dnd_leave(&self, surface: &WlSurface) {
+        self.device
+            .seat
+            .for_each_data_device(Version::ALL, surface.client.id, |dd| {
+                dd.send_leave();
+            });
+        if self
+            .data
+            .shared
+            .get()
+            .state
+            .get()
+            .contains(OFFER_STATE_DROPPED)
+        {
+            return;
+        }
+        self.data.shared.set(Rc::new(SharedState::default()));
+        cancel_offers(self, false);
+    }
+
+    /// Drops the data on the surface.
+    ///
+    /// Returns whether the surface accepted the drop. Otherwise the source has been
+    /// detached from the seat.
+    pub fn dnd_drop(&self, surface: &WlSurface) -> bool {
+        self.device
+            .seat
+            .for_each_data_device(Version::ALL, surface.client.id, |dd| {
+                dd.send_drop();
+            });
+        self.data.state.or_assign(SOURCE_STATE_DROPPED);
+        self.data.shared.get().state.or_assign(OFFER_STATE_DROPPED);
+        let accepted = self.can_drop();
+        self.dnd_leave(surface);
+        if !accepted {
+            detach_seat(self, &self.device.seat);
+        }
+        accepted
+    }
 }
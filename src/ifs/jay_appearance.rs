@@ -0,0 +1,86 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        theme::Theme,
+        wire::{
+            jay_appearance::{self, *},
+            JayAppearanceId,
+        },
+    },
+    jay_config::theme::ColorScheme,
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const COLOR_SCHEME_NO_PREFERENCE: u32 = 0;
+pub const COLOR_SCHEME_DARK: u32 = 1;
+pub const COLOR_SCHEME_LIGHT: u32 = 2;
+
+pub struct JayAppearance {
+    pub id: JayAppearanceId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl JayAppearance {
+    pub fn send_appearance(&self, theme: &Theme) {
+        let scheme = match theme.color_scheme.get() {
+            ColorScheme::Dark => COLOR_SCHEME_DARK,
+            ColorScheme::Light => COLOR_SCHEME_LIGHT,
+            _ => COLOR_SCHEME_NO_PREFERENCE,
+        };
+        self.client.event(jay_appearance::ColorScheme {
+            self_id: self.id,
+            scheme,
+        });
+        let accent = theme.colors.focused_title_background.get();
+        let to_u32 = |c: f32| (c as f64 * u32::MAX as f64) as u32;
+        self.client.event(AccentColor {
+            self_id: self.id,
+            r: to_u32(accent.r),
+            g: to_u32(accent.g),
+            b: to_u32(accent.b),
+            a: to_u32(accent.a),
+        });
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .appearance_watchers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayAppearanceRequestHandler for JayAppearance {
+    type Error = JayAppearanceError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayAppearance;
+    version = Version(1);
+}
+
+impl Object for JayAppearance {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayAppearance);
+
+#[derive(Debug, Error)]
+pub enum JayAppearanceError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayAppearanceError, ClientError);
@@ -4,8 +4,11 @@ use {
         client::{Client, ClientCaps, ClientError, CAP_JAY_COMPOSITOR},
         globals::{Global, GlobalName},
         ifs::{
+            jay_appearance::JayAppearance,
+            jay_global_shortcuts::JayGlobalShortcuts,
             jay_idle::JayIdle,
             jay_input::JayInput,
+            jay_input_injector::JayInputInjector,
             jay_log_file::JayLogFile,
             jay_output::JayOutput,
             jay_pointer::JayPointer,
@@ -16,12 +19,14 @@ use {
             jay_seat_events::JaySeatEvents,
             jay_select_toplevel::{JaySelectToplevel, JayToplevelSelector},
             jay_select_workspace::{JaySelectWorkspace, JayWorkspaceSelector},
+            jay_tree::JayTree,
             jay_workspace_watcher::JayWorkspaceWatcher,
         },
         leaks::Tracker,
         object::{Object, Version},
-        screenshoter::take_screenshot,
-        utils::errorfmt::ErrorFmt,
+        scale::Scale,
+        screenshoter::{take_node_screenshot, take_screenshot, ScreenshooterError, Screenshot},
+        utils::{clonecell::CloneCell, errorfmt::ErrorFmt},
         wire::{jay_compositor::*, JayCompositorId, JayScreenshotId},
     },
     bstr::ByteSlice,
@@ -43,12 +48,13 @@ impl JayCompositorGlobal {
         self: Rc<Self>,
         id: JayCompositorId,
         client: &Rc<Client>,
-        _version: Version,
+        version: Version,
     ) -> Result<(), JayCompositorError> {
         let obj = Rc::new(JayCompositor {
             id,
             client: client.clone(),
             tracker: Default::default(),
+            version,
         });
         track!(client, obj);
         client.add_client_obj(&obj)?;
@@ -65,7 +71,7 @@ impl Global for JayCompositorGlobal {
     }
 
     fn version(&self) -> u32 {
-        1
+        2
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -79,6 +85,7 @@ pub struct JayCompositor {
     id: JayCompositorId,
     client: Rc<Client>,
     tracker: Tracker<Self>,
+    version: Version,
 }
 
 pub struct Cap;
@@ -87,13 +94,29 @@ impl Cap {
     pub const NONE: u16 = 0;
     pub const WINDOW_CAPTURE: u16 = 1;
     pub const SELECT_WORKSPACE: u16 = 2;
+    pub const CURSOR_MODES: u16 = 3;
+    pub const FIND_TARGETS: u16 = 4;
+    pub const TARGET_SCREENSHOTS: u16 = 5;
+    pub const INPUT_INJECTION: u16 = 6;
+    pub const GLOBAL_SHORTCUTS: u16 = 7;
+    pub const APPEARANCE: u16 = 8;
 }
 
 impl JayCompositor {
     fn send_capabilities(&self) {
         self.client.event(Capabilities {
             self_id: self.id,
-            cap: &[Cap::NONE, Cap::WINDOW_CAPTURE, Cap::SELECT_WORKSPACE],
+            cap: &[
+                Cap::NONE,
+                Cap::WINDOW_CAPTURE,
+                Cap::SELECT_WORKSPACE,
+                Cap::CURSOR_MODES,
+                Cap::FIND_TARGETS,
+                Cap::TARGET_SCREENSHOTS,
+                Cap::INPUT_INJECTION,
+                Cap::GLOBAL_SHORTCUTS,
+                Cap::APPEARANCE,
+            ],
         });
     }
 
@@ -101,6 +124,14 @@ impl JayCompositor {
         &self,
         id: JayScreenshotId,
         include_cursor: bool,
+    ) -> Result<(), JayCompositorError> {
+        self.send_screenshot(id, take_screenshot(&self.client.state, include_cursor))
+    }
+
+    fn send_screenshot(
+        &self,
+        id: JayScreenshotId,
+        res: Result<Screenshot, ScreenshooterError>,
     ) -> Result<(), JayCompositorError> {
         let ss = Rc::new(JayScreenshot {
             id,
@@ -109,7 +140,7 @@ impl JayCompositor {
         });
         track!(self.client, ss);
         self.client.add_client_obj(&ss)?;
-        match take_screenshot(&self.client.state, include_cursor) {
+        match res {
             Ok(s) => {
                 let dmabuf = s.bo.dmabuf();
                 let plane = &dmabuf.planes[0];
@@ -354,6 +385,7 @@ impl JayCompositorRequestHandler for JayCompositor {
             client: self.client.clone(),
             tracker: Default::default(),
             destroyed: Cell::new(false),
+            version: self.version,
         });
         track!(self.client, obj);
         self.client.add_client_obj(&obj)?;
@@ -382,11 +414,155 @@ impl JayCompositorRequestHandler for JayCompositor {
         seat.global.select_workspace(selector);
         Ok(())
     }
+
+    fn get_tree(&self, req: GetTree, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayTree::new(req.id, &self.client));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.send_tree();
+        Ok(())
+    }
+
+    fn find_toplevel(&self, req: FindToplevel<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JaySelectToplevel {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            destroyed: Cell::new(false),
+            version: self.version,
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        let tl = self
+            .client
+            .state
+            .mapped_toplevels
+            .lock()
+            .values()
+            .filter(|tl| *tl.tl_data().app_id.borrow() == req.app_id)
+            .min_by_key(|tl| tl.node_id().raw())
+            .cloned();
+        drop(JayToplevelSelector {
+            tl: CloneCell::new(tl),
+            jst: obj,
+        });
+        Ok(())
+    }
+
+    fn find_workspace(&self, req: FindWorkspace<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JaySelectWorkspace {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            destroyed: Cell::new(false),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        let ws = self
+            .client
+            .state
+            .workspaces
+            .get(req.name)
+            .filter(|ws| !ws.is_dummy && !ws.output.get().is_dummy);
+        drop(JayWorkspaceSelector {
+            ws: CloneCell::new(ws),
+            jsw: obj,
+        });
+        Ok(())
+    }
+
+    fn take_output_screenshot(
+        &self,
+        req: TakeOutputScreenshot,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let res = match output.output.node() {
+            Some(node) => take_node_screenshot(
+                &self.client.state,
+                &*node,
+                node.global.persistent.scale.get(),
+                req.include_cursor != 0,
+            ),
+            _ => Err(ScreenshooterError::EmptyTarget),
+        };
+        self.send_screenshot(req.id, res)
+    }
+
+    fn take_toplevel_screenshot(
+        &self,
+        req: TakeToplevelScreenshot,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let tl = self.client.lookup(req.toplevel)?;
+        let res = match tl.destroyed.get() {
+            true => Err(ScreenshooterError::EmptyTarget),
+            false => {
+                let tl = &tl.toplevel;
+                let scale = match tl.tl_data().workspace.get() {
+                    None => Scale::default(),
+                    Some(w) => w.output.get().global.persistent.scale.get(),
+                };
+                take_node_screenshot(
+                    &self.client.state,
+                    tl.tl_as_node(),
+                    scale,
+                    req.include_cursor != 0,
+                )
+            }
+        };
+        self.send_screenshot(req.id, res)
+    }
+
+    fn create_input_injector(
+        &self,
+        req: CreateInputInjector<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayInputInjector::new(
+            req.id,
+            &self.client,
+            req.name,
+            req.capabilities,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn create_global_shortcuts(
+        &self,
+        req: CreateGlobalShortcuts,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        let obj = Rc::new(JayGlobalShortcuts::new(req.id, &self.client, &seat.global));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        seat.global.add_global_shortcuts(&obj);
+        Ok(())
+    }
+
+    fn watch_appearance(&self, req: WatchAppearance, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let watcher = Rc::new(JayAppearance {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, watcher);
+        self.client.add_client_obj(&watcher)?;
+        self.client
+            .state
+            .appearance_watchers
+            .set((self.client.id, req.id), watcher.clone());
+        watcher.send_appearance(&self.client.state.theme);
+        Ok(())
+    }
 }
 
 object_base! {
     self = JayCompositor;
-    version = Version(1);
+    version = self.version;
 }
 
 impl Object for JayCompositor {}
@@ -0,0 +1,141 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    ahash::AHashMap,
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+/// Shortcuts that a privileged client has registered on a seat.
+///
+/// Unlike the shortcuts of the config, these shortcuts report both the press and the
+/// release of the key.
+pub struct JayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub seat: Rc<WlSeatGlobal>,
+    shortcuts: RefCell<AHashMap<u32, (u32, u32)>>,
+    active: RefCell<AHashMap<u32, Vec<u32>>>,
+}
+
+impl JayGlobalShortcuts {
+    pub fn new(id: JayGlobalShortcutsId, client: &Rc<Client>, seat: &Rc<WlSeatGlobal>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            seat: seat.clone(),
+            shortcuts: Default::default(),
+            active: Default::default(),
+        }
+    }
+
+    /// Activates the shortcuts triggered by the key press.
+    ///
+    /// Returns whether any shortcut was activated.
+    pub fn key_pressed(&self, time_usec: u64, key: u32, mods: u32, syms: &[u32]) -> bool {
+        let mut activated = vec![];
+        for (&shortcut, &(sc_mods, sc_sym)) in &*self.shortcuts.borrow() {
+            if sc_mods == mods && syms.contains(&sc_sym) {
+                activated.push(shortcut);
+            }
+        }
+        if activated.is_empty() {
+            return false;
+        }
+        for &shortcut in &activated {
+            self.send_activated(shortcut, time_usec);
+        }
+        self.active.borrow_mut().insert(key, activated);
+        true
+    }
+
+    /// Deactivates the shortcuts that were activated by pressing the key.
+    pub fn key_released(&self, time_usec: u64, key: u32) {
+        let active = self.active.borrow_mut().remove(&key);
+        for shortcut in active.into_iter().flatten() {
+            self.send_deactivated(shortcut, time_usec);
+        }
+    }
+
+    fn send_activated(&self, shortcut: u32, time_usec: u64) {
+        self.client.event(Activated {
+            self_id: self.id,
+            shortcut,
+            time_usec,
+        });
+    }
+
+    fn send_deactivated(&self, shortcut: u32, time_usec: u64) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            shortcut,
+            time_usec,
+        });
+    }
+
+    pub fn send_recorded(&self, mods: u32, keysym: u32) {
+        self.client.event(Recorded {
+            self_id: self.id,
+            mods,
+            keysym,
+        });
+    }
+
+    fn detach(&self) {
+        self.seat.remove_global_shortcuts(self);
+    }
+}
+
+impl JayGlobalShortcutsRequestHandler for JayGlobalShortcuts {
+    type Error = JayGlobalShortcutsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn bind(&self, req: Bind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.shortcuts
+            .borrow_mut()
+            .insert(req.shortcut, (req.mods, req.keysym));
+        Ok(())
+    }
+
+    fn unbind(&self, req: Unbind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.shortcuts.borrow_mut().remove(&req.shortcut);
+        Ok(())
+    }
+
+    fn record(&self, _req: Record, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.seat.record_shortcut(slf);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayGlobalShortcuts;
+    version = Version(1);
+}
+
+impl Object for JayGlobalShortcuts {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(JayGlobalShortcuts);
+
+#[derive(Debug, Error)]
+pub enum JayGlobalShortcutsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayGlobalShortcutsError, ClientError);
@@ -0,0 +1,506 @@
+use {
+    crate::{
+        backend::{
+            AxisSource, BackendEvent, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
+            InputDeviceId, InputEvent, KeyState, ScrollAxis, TransformMatrix, AXIS_120,
+        },
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{
+            jay_output::JayOutput,
+            wl_output::WlOutputGlobal,
+            wl_seat::{
+                wl_pointer::{HORIZONTAL_SCROLL, VERTICAL_SCROLL},
+                KEYBOARD, POINTER, TOUCH,
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        state::DeviceHandlerData,
+        time::now_usec,
+        utils::{bitflags::BitflagsExt, clonecell::CloneCell, vecset::VecSet},
+        wire::{jay_input_injector::*, JayInputInjectorId},
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        mem,
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::c,
+};
+
+const KEY_LEFTSHIFT: u32 = 42;
+
+pub struct JayInputInjector {
+    pub id: JayInputInjectorId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    device: Rc<InjectedInputDevice>,
+    pressed_keys: RefCell<VecSet<u32>>,
+    pressed_buttons: RefCell<VecSet<u32>>,
+    pressed_keysyms: RefCell<AHashMap<u32, (u32, bool)>>,
+    touch_points: RefCell<VecSet<i32>>,
+}
+
+/// The input device through which injected events reach the seat.
+///
+/// The device is handled like any other input device. In particular, the config can
+/// assign it to a seat.
+struct InjectedInputDevice {
+    id: InputDeviceId,
+    name: Rc<String>,
+    capabilities: u32,
+    removed: Cell<bool>,
+    events: RefCell<VecDeque<InputEvent>>,
+    on_change: CloneCell<Option<Rc<dyn Fn()>>>,
+}
+
+impl JayInputInjector {
+    pub fn new(id: JayInputInjectorId, client: &Rc<Client>, name: &str, capabilities: u32) -> Self {
+        let device = Rc::new(InjectedInputDevice {
+            id: client.state.input_device_ids.next(),
+            name: Rc::new(name.to_string()),
+            capabilities,
+            removed: Cell::new(false),
+            events: Default::default(),
+            on_change: Default::default(),
+        });
+        client
+            .state
+            .backend_events
+            .push(BackendEvent::NewInputDevice(device.clone()));
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            device,
+            pressed_keys: Default::default(),
+            pressed_buttons: Default::default(),
+            pressed_keysyms: Default::default(),
+            touch_points: Default::default(),
+        }
+    }
+
+    fn device_data(&self) -> Option<Rc<DeviceHandlerData>> {
+        self.client
+            .state
+            .input_device_handlers
+            .borrow()
+            .get(&self.device.id)
+            .map(|d| d.data.clone())
+    }
+
+    fn require(&self, capability: u32) -> Result<(), JayInputInjectorError> {
+        if !self.device.capabilities.contains(capability) {
+            return Err(JayInputInjectorError::MissingCapability);
+        }
+        Ok(())
+    }
+
+    fn key_event(&self, key: u32, state: KeyState) {
+        let mut pressed_keys = self.pressed_keys.borrow_mut();
+        let valid = match state {
+            KeyState::Released => pressed_keys.remove(&key),
+            KeyState::Pressed => pressed_keys.insert(key),
+        };
+        if valid {
+            self.device.push(InputEvent::Key {
+                time_usec: now_usec(),
+                key,
+                state,
+            });
+        }
+    }
+
+    /// Converts coordinates in the pixel space of an output into logical coordinates
+    /// relative to the top-left corner of the output.
+    fn output_position(
+        &self,
+        output: &JayOutput,
+        x: Fixed,
+        y: Fixed,
+    ) -> Option<(Rc<WlOutputGlobal>, f64, f64)> {
+        let global = output.output.get()?;
+        let pos = global.pos.get();
+        let (width, height) = global.pixel_size();
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        let x = x.to_f64() * pos.width() as f64 / width as f64;
+        let y = y.to_f64() * pos.height() as f64 / height as f64;
+        Some((global, x, y))
+    }
+
+    fn touch_event(
+        &self,
+        output: &JayOutput,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+        down: bool,
+    ) -> Result<(), JayInputInjectorError> {
+        let Some((global, x, y)) = self.output_position(output, x, y) else {
+            return Ok(());
+        };
+        let Some(data) = self.device_data() else {
+            return Ok(());
+        };
+        let pos = global.pos.get();
+        let rect = data.get_rect(&self.client.state);
+        if rect.width() <= 0 || rect.height() <= 0 {
+            return Ok(());
+        }
+        let x_normed = (pos.x1() as f64 + x - rect.x1() as f64) / rect.width() as f64;
+        let y_normed = (pos.y1() as f64 + y - rect.y1() as f64) / rect.height() as f64;
+        let time_usec = now_usec();
+        let touch_points = &mut *self.touch_points.borrow_mut();
+        if down {
+            if !touch_points.insert(id) {
+                return Ok(());
+            }
+            self.device.push(InputEvent::TouchDown {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            });
+        } else {
+            if !touch_points.contains(&id) {
+                return Ok(());
+            }
+            self.device.push(InputEvent::TouchMotion {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            });
+        }
+        self.device.push(InputEvent::TouchFrame { time_usec });
+        Ok(())
+    }
+
+    /// Releases all keys, buttons, and touch points and removes the device.
+    fn remove_device(&self) {
+        let time_usec = now_usec();
+        self.pressed_keysyms.borrow_mut().clear();
+        for &key in mem::take(&mut *self.pressed_keys.borrow_mut()).iter() {
+            self.device.push(InputEvent::Key {
+                time_usec,
+                key,
+                state: KeyState::Released,
+            });
+        }
+        for &button in mem::take(&mut *self.pressed_buttons.borrow_mut()).iter() {
+            self.device.push(InputEvent::Button {
+                time_usec,
+                button,
+                state: KeyState::Released,
+            });
+        }
+        let touch_points = mem::take(&mut *self.touch_points.borrow_mut());
+        if touch_points.len() > 0 {
+            for &id in touch_points.iter() {
+                self.device.push(InputEvent::TouchCancel { time_usec, id });
+            }
+            self.device.push(InputEvent::TouchFrame { time_usec });
+        }
+        self.device.removed.set(true);
+        self.device.changed();
+    }
+}
+
+impl InjectedInputDevice {
+    fn push(&self, event: InputEvent) {
+        self.events.borrow_mut().push_back(event);
+        self.changed();
+    }
+
+    fn changed(&self) {
+        if let Some(cb) = self.on_change.get() {
+            cb();
+        }
+    }
+}
+
+impl JayInputInjectorRequestHandler for JayInputInjector {
+    type Error = JayInputInjectorError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_device();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn motion(&self, req: Motion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(POINTER)?;
+        self.device.push(InputEvent::Motion {
+            time_usec: now_usec(),
+            dx: req.dx,
+            dy: req.dy,
+            dx_unaccelerated: req.dx,
+            dy_unaccelerated: req.dy,
+        });
+        Ok(())
+    }
+
+    fn motion_absolute(&self, req: MotionAbsolute, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(POINTER)?;
+        let output = self.client.lookup(req.output)?;
+        let Some((global, x, y)) = self.output_position(&output, req.x, req.y) else {
+            return Ok(());
+        };
+        self.device.push(InputEvent::ConnectorPosition {
+            time_usec: now_usec(),
+            connector: global.connector.connector.id(),
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+        Ok(())
+    }
+
+    fn button(&self, req: Button, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(POINTER)?;
+        let mut pressed_buttons = self.pressed_buttons.borrow_mut();
+        let (valid, state) = match req.state {
+            0 => (pressed_buttons.remove(&req.button), KeyState::Released),
+            1 => (pressed_buttons.insert(req.button), KeyState::Pressed),
+            _ => return Err(JayInputInjectorError::UnknownState(req.state)),
+        };
+        if valid {
+            self.device.push(InputEvent::Button {
+                time_usec: now_usec(),
+                button: req.button,
+                state,
+            });
+        }
+        Ok(())
+    }
+
+    fn scroll(&self, req: Scroll, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(POINTER)?;
+        self.device.push(InputEvent::AxisSource {
+            source: AxisSource::Finger,
+        });
+        for (axis, dist) in [
+            (ScrollAxis::Horizontal, req.dx),
+            (ScrollAxis::Vertical, req.dy),
+        ] {
+            if dist != Fixed(0) {
+                self.device.push(InputEvent::AxisPx {
+                    dist,
+                    axis,
+                    inverted: false,
+                });
+            }
+            if req.stop != 0 {
+                self.device.push(InputEvent::AxisStop { axis });
+            }
+        }
+        self.device.push(InputEvent::AxisFrame {
+            time_usec: now_usec(),
+        });
+        Ok(())
+    }
+
+    fn scroll_discrete(&self, req: ScrollDiscrete, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(POINTER)?;
+        let axis = match req.axis {
+            VERTICAL_SCROLL => ScrollAxis::Vertical,
+            HORIZONTAL_SCROLL => ScrollAxis::Horizontal,
+            _ => return Err(JayInputInjectorError::UnknownAxis(req.axis)),
+        };
+        self.device.push(InputEvent::AxisSource {
+            source: AxisSource::Wheel,
+        });
+        self.device.push(InputEvent::Axis120 {
+            dist: req.steps.saturating_mul(AXIS_120),
+            axis,
+            inverted: false,
+        });
+        self.device.push(InputEvent::AxisFrame {
+            time_usec: now_usec(),
+        });
+        Ok(())
+    }
+
+    fn key(&self, req: Key, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(KEYBOARD)?;
+        let state = match req.state {
+            0 => KeyState::Released,
+            1 => KeyState::Pressed,
+            _ => return Err(JayInputInjectorError::UnknownState(req.state)),
+        };
+        self.key_event(req.key, state);
+        Ok(())
+    }
+
+    fn keysym(&self, req: Keysym, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(KEYBOARD)?;
+        match req.state {
+            0 => {
+                let key = self.pressed_keysyms.borrow_mut().remove(&req.keysym);
+                if let Some((key, shift)) = key {
+                    self.key_event(key, KeyState::Released);
+                    if shift {
+                        self.key_event(KEY_LEFTSHIFT, KeyState::Released);
+                    }
+                }
+            }
+            1 => {
+                if self.pressed_keysyms.borrow().contains_key(&req.keysym) {
+                    return Ok(());
+                }
+                let Some(data) = self.device_data() else {
+                    return Ok(());
+                };
+                let Some(seat) = data.seat.get() else {
+                    return Ok(());
+                };
+                let xkb_state = data.get_effective_xkb_state(&seat);
+                let Some((key, shift)) = xkb_state.borrow().find_keysym(req.keysym) else {
+                    log::warn!("No key produces keysym {:#x}", req.keysym);
+                    return Ok(());
+                };
+                if shift {
+                    self.key_event(KEY_LEFTSHIFT, KeyState::Pressed);
+                }
+                self.key_event(key, KeyState::Pressed);
+                self.pressed_keysyms
+                    .borrow_mut()
+                    .insert(req.keysym, (key, shift));
+            }
+            _ => return Err(JayInputInjectorError::UnknownState(req.state)),
+        }
+        Ok(())
+    }
+
+    fn touch_down(&self, req: TouchDown, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(TOUCH)?;
+        let output = self.client.lookup(req.output)?;
+        self.touch_event(&output, req.id, req.x, req.y, true)
+    }
+
+    fn touch_motion(&self, req: TouchMotion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(TOUCH)?;
+        let output = self.client.lookup(req.output)?;
+        self.touch_event(&output, req.id, req.x, req.y, false)
+    }
+
+    fn touch_up(&self, req: TouchUp, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.require(TOUCH)?;
+        if self.touch_points.borrow_mut().remove(&req.id) {
+            let time_usec = now_usec();
+            self.device.push(InputEvent::TouchUp {
+                time_usec,
+                id: req.id,
+            });
+            self.device.push(InputEvent::TouchFrame { time_usec });
+        }
+        Ok(())
+    }
+}
+
+impl InputDevice for InjectedInputDevice {
+    fn id(&self) -> InputDeviceId {
+        self.id
+    }
+
+    fn removed(&self) -> bool {
+        self.removed.get() && self.events.borrow().is_empty()
+    }
+
+    fn event(&self) -> Option<InputEvent> {
+        let event = self.events.borrow_mut().pop_front();
+        if event.is_none() && self.removed.get() {
+            // Wake up the handler once more so that it notices that the device is gone.
+            self.changed();
+        }
+        event
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.on_change.set(Some(cb));
+    }
+
+    fn grab(&self, _grab: bool) {
+        // nothing
+    }
+
+    fn has_capability(&self, cap: InputDeviceCapability) -> bool {
+        match cap {
+            InputDeviceCapability::Keyboard => self.capabilities.contains(KEYBOARD),
+            InputDeviceCapability::Pointer => self.capabilities.contains(POINTER),
+            InputDeviceCapability::Touch => self.capabilities.contains(TOUCH),
+            _ => false,
+        }
+    }
+
+    fn set_left_handed(&self, left_handed: bool) {
+        let _ = left_handed;
+    }
+
+    fn set_accel_profile(&self, profile: InputDeviceAccelProfile) {
+        let _ = profile;
+    }
+
+    fn set_accel_speed(&self, speed: f64) {
+        let _ = speed;
+    }
+
+    fn set_transform_matrix(&self, matrix: TransformMatrix) {
+        let _ = matrix;
+    }
+
+    fn name(&self) -> Rc<String> {
+        self.name.clone()
+    }
+
+    fn dev_t(&self) -> Option<c::dev_t> {
+        None
+    }
+
+    fn set_tap_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_drag_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_drag_lock_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_natural_scrolling_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+}
+
+object_base! {
+    self = JayInputInjector;
+    version = Version(1);
+}
+
+impl Object for JayInputInjector {
+    fn break_loops(&self) {
+        self.remove_device();
+    }
+}
+
+simple_add_obj!(JayInputInjector);
+
+#[derive(Debug, Error)]
+pub enum JayInputInjectorError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The injector does not have the capability for this request")]
+    MissingCapability,
+    #[error("Unknown key or button state {0}")]
+    UnknownState(u32),
+    #[error("Unknown axis {0}")]
+    UnknownAxis(u32),
+}
+efrom!(JayInputInjectorError, ClientError);
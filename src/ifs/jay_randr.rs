@@ -8,11 +8,17 @@ use {
         scale::Scale,
         state::{ConnectorData, DrmDevData, OutputData},
         tree::OutputNode,
-        utils::{gfx_api_ext::GfxApiExt, transform_ext::TransformExt},
+        utils::{
+            color_temperature::{MAX_COLOR_TEMPERATURE, MIN_COLOR_TEMPERATURE},
+            errorfmt::ErrorFmt,
+            gfx_api_ext::GfxApiExt,
+            transform_ext::TransformExt,
+            vrr_mode_ext::VrrModeExt,
+        },
         wire::{jay_randr::*, JayRandrId},
     },
-    jay_config::video::{GfxApi, Transform},
-    std::rc::Rc,
+    jay_config::video::{GfxApi, Transform, VrrMode},
+    std::{ops::Deref, rc::Rc},
     thiserror::Error,
 };
 
@@ -65,6 +71,10 @@ impl JayRandr {
             enabled: data.connector.enabled() as _,
             name: &data.name,
         });
+        self.client.event(ColorTemperature {
+            self_id: self.id,
+            kelvin: data.color_temperature.get(),
+        });
         let Some(output) = self.client.state.outputs.get(&data.connector.id()) else {
             return;
         };
@@ -97,6 +107,11 @@ impl JayRandr {
             width_mm: global.width_mm,
             height_mm: global.height_mm,
         });
+        self.client.event(VrrState {
+            self_id: self.id,
+            capable: output.monitor_info.vrr_capable as _,
+            mode: global.persistent.vrr_mode.get().to_raw(),
+        });
         let current_mode = global.mode.get();
         for mode in &global.modes {
             self.client.event(Mode {
@@ -253,11 +268,17 @@ impl JayRandrRequestHandler for JayRandr {
         let Some(c) = self.get_output(req.output) else {
             return Ok(());
         };
-        c.connector.connector.set_mode(backend::Mode {
+        let res = c.connector.connector.set_mode(backend::Mode {
             width: req.width,
             height: req.height,
             refresh_rate_millihz: req.refresh_rate_millihz,
         });
+        if let Err(e) = res {
+            self.send_error(&format!(
+                "Could not change the mode: {}",
+                ErrorFmt(e.deref())
+            ));
+        }
         Ok(())
     }
 
@@ -281,7 +302,12 @@ impl JayRandrRequestHandler for JayRandr {
         let Some(c) = self.get_connector(req.output) else {
             return Ok(());
         };
-        c.connector.set_enabled(req.enabled != 0);
+        if let Err(e) = c.connector.set_enabled(req.enabled != 0) {
+            self.send_error(&format!(
+                "Could not change the enabled state: {}",
+                ErrorFmt(e.deref())
+            ));
+        }
         Ok(())
     }
 
@@ -297,6 +323,36 @@ impl JayRandrRequestHandler for JayRandr {
         c.connector.set_non_desktop_override(non_desktop);
         Ok(())
     }
+
+    fn set_vrr_mode(&self, req: SetVrrMode<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        let Some(mode) = VrrMode::from_raw(req.mode) else {
+            self.send_error(&format!("Unknown VRR mode {}", req.mode));
+            return Ok(());
+        };
+        c.set_vrr_mode(mode);
+        Ok(())
+    }
+
+    fn set_color_temperature(
+        &self,
+        req: SetColorTemperature<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        if req.kelvin < MIN_COLOR_TEMPERATURE || req.kelvin > MAX_COLOR_TEMPERATURE {
+            self.send_error(&format!(
+                "The color temperature must be between {MIN_COLOR_TEMPERATURE} and {MAX_COLOR_TEMPERATURE}"
+            ));
+            return Ok(());
+        }
+        c.set_color_temperature(req.kelvin);
+        Ok(())
+    }
 }
 
 object_base! {
@@ -1,11 +1,14 @@
 use {
     crate::{
         client::{Client, ClientError},
-        format::XRGB8888,
+        cursor::Cursor,
+        fixed::Fixed,
+        format::{ARGB8888, XRGB8888},
         gfx_api::{GfxContext, GfxError, GfxFramebuffer, GfxTexture},
         ifs::{jay_output::JayOutput, jay_toplevel::JayToplevel},
         leaks::Tracker,
         object::{Object, Version},
+        rect::Rect,
         scale::Scale,
         state::State,
         tree::{OutputNode, ToplevelNode, WorkspaceNode, WorkspaceNodeId},
@@ -14,6 +17,8 @@ use {
             errorfmt::ErrorFmt,
             numcell::NumCell,
             option_ext::OptionExt,
+            oserror::OsError,
+            rc_eq::rc_eq,
         },
         video::{
             dmabuf::DmaBuf,
@@ -28,12 +33,27 @@ use {
     once_cell::sync::Lazy,
     std::{
         cell::{Cell, RefCell},
+        io::{self, Write},
         ops::DerefMut,
         rc::Rc,
     },
     thiserror::Error,
+    uapi::c,
 };
 
+const MAX_CURSOR_SIZE: i32 = 256;
+
+pub const CURSOR_MODE_EMBEDDED: u32 = 0;
+pub const CURSOR_MODE_HIDDEN: u32 = 1;
+pub const CURSOR_MODE_METADATA: u32 = 2;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum CursorMode {
+    Embedded,
+    Hidden,
+    Metadata,
+}
+
 pub async fn perform_toplevel_screencasts(state: Rc<State>) {
     loop {
         let screencast = state.pending_toplevel_screencasts.pop().await;
@@ -75,6 +95,8 @@ pub struct JayScreencast {
     pending: Pending,
     need_realloc: Cell<bool>,
     realloc_scheduled: Cell<bool>,
+    cursor_mode: Cell<CursorMode>,
+    cursor_image: RefCell<Option<(Rc<dyn Cursor>, Scale, Rect)>>,
 }
 
 #[derive(Clone)]
@@ -97,6 +119,7 @@ struct Pending {
     target: Cell<Option<Option<PendingTarget>>>,
     show_all: Cell<Option<bool>>,
     show_workspaces: RefCell<Option<AHashSet<WorkspaceNodeId>>>,
+    cursor_mode: Cell<Option<CursorMode>>,
 }
 
 struct ScreencastBuffer {
@@ -139,6 +162,8 @@ impl JayScreencast {
             pending: Default::default(),
             need_realloc: Cell::new(false),
             realloc_scheduled: Cell::new(false),
+            cursor_mode: Cell::new(CursorMode::Embedded),
+            cursor_image: Default::default(),
         }
     }
 
@@ -167,6 +192,7 @@ impl JayScreencast {
             None => Scale::default(),
             Some(w) => w.output.get().global.persistent.scale.get(),
         };
+        let embed_cursor = self.cursor_mode.get() == CursorMode::Embedded;
         let mut buffer = self.buffers.borrow_mut();
         for (idx, buffer) in buffer.deref_mut().iter_mut().enumerate() {
             if buffer.free {
@@ -176,13 +202,14 @@ impl JayScreencast {
                     Some(tl.node_absolute_position()),
                     None,
                     scale,
-                    true,
-                    true,
+                    embed_cursor,
+                    embed_cursor,
                     false,
                     Transform::None,
                 );
                 match res {
                     Ok(_) => {
+                        self.send_cursor(tl.node_absolute_position(), scale);
                         self.client.event(Ready {
                             self_id: self.id,
                             idx: idx as _,
@@ -288,18 +315,34 @@ impl JayScreencast {
         let mut buffer = self.buffers.borrow_mut();
         for (idx, buffer) in buffer.deref_mut().iter_mut().enumerate() {
             if buffer.free {
-                let res = self.client.state.perform_screencopy(
-                    texture,
-                    &buffer.fb,
-                    on.global.pos.get(),
-                    render_hardware_cursors,
-                    x_off,
-                    y_off,
-                    size,
-                    on.global.persistent.transform.get(),
-                );
+                let res = match self.cursor_mode.get() {
+                    CursorMode::Embedded => self.client.state.perform_screencopy(
+                        texture,
+                        &buffer.fb,
+                        on.global.pos.get(),
+                        render_hardware_cursors,
+                        x_off,
+                        y_off,
+                        size,
+                        on.global.persistent.transform.get(),
+                    ),
+                    // The texture might already contain the cursor. Render the output
+                    // again without it.
+                    CursorMode::Hidden | CursorMode::Metadata => buffer.fb.render_node(
+                        on,
+                        &self.client.state,
+                        Some(on.global.pos.get()),
+                        None,
+                        on.global.persistent.scale.get(),
+                        false,
+                        false,
+                        on.has_fullscreen(),
+                        on.global.persistent.transform.get(),
+                    ),
+                };
                 match res {
                     Ok(_) => {
+                        self.send_cursor(on.global.pos.get(), on.global.persistent.scale.get());
                         self.client.event(Ready {
                             self_id: self.id,
                             idx: idx as _,
@@ -318,6 +361,115 @@ impl JayScreencast {
         self.client.event(MissedFrame { self_id: self.id })
     }
 
+    /// Sends the position of the cursor inside the rectangle if the cursor is sent as
+    /// metadata.
+    ///
+    /// The cursor image is only sent if it has changed since it was last sent.
+    fn send_cursor(&self, rect: Rect, scale: Scale) {
+        if self.cursor_mode.get() != CursorMode::Metadata {
+            return;
+        }
+        let mut found = None;
+        for group in self.client.state.cursor_user_groups.lock().values() {
+            let Some(user) = group.active() else {
+                continue;
+            };
+            let Some(cursor) = user.get() else {
+                continue;
+            };
+            let (x, y) = user.position();
+            if rect.contains(x.round_down(), y.round_down()) {
+                found = Some((cursor, x, y));
+                break;
+            }
+        }
+        let Some((cursor, x, y)) = found else {
+            self.client.event(CursorPosition {
+                self_id: self.id,
+                visible: 0,
+                x: 0,
+                y: 0,
+            });
+            return;
+        };
+        let scalef = scale.to_f64();
+        let x = ((x - Fixed::from_int(rect.x1())).to_f64() * scalef).round() as i32;
+        let y = ((y - Fixed::from_int(rect.y1())).to_f64() * scalef).round() as i32;
+        let extents = cursor.extents_at_scale(scale);
+        let changed = match &*self.cursor_image.borrow() {
+            Some((c, s, e)) => !rc_eq(c, &cursor) || *s != scale || *e != extents,
+            None => true,
+        };
+        if changed {
+            if let Err(e) = self.send_cursor_image(&*cursor, scale, extents) {
+                log::error!("Could not send the cursor image: {}", ErrorFmt(e));
+            }
+            *self.cursor_image.borrow_mut() = Some((cursor, scale, extents));
+        }
+        self.client.event(CursorPosition {
+            self_id: self.id,
+            visible: 1,
+            x,
+            y,
+        });
+    }
+
+    fn send_cursor_image(
+        &self,
+        cursor: &dyn Cursor,
+        scale: Scale,
+        extents: Rect,
+    ) -> Result<(), JayScreencastError> {
+        let (width, height) = (extents.width(), extents.height());
+        if width <= 0 || height <= 0 || width > MAX_CURSOR_SIZE || height > MAX_CURSOR_SIZE {
+            return Ok(());
+        }
+        let state = &self.client.state;
+        let Some(ctx) = state.render_ctx.get() else {
+            return Ok(());
+        };
+        let stride = width * 4;
+        let fb = ctx.create_fb(width, height, stride, ARGB8888)?;
+        fb.render_hardware_cursor(cursor, state, scale, Transform::None)?;
+        let mem = vec![Cell::new(0u8); (stride * height) as usize];
+        fb.copy_to_shm(0, 0, width, height, stride, ARGB8888, &mem)?;
+        let bytes: Vec<_> = mem.iter().map(|c| c.get()).collect();
+        let mut memfd = uapi::memfd_create("cursor", c::MFD_CLOEXEC | c::MFD_ALLOW_SEALING)
+            .map_err(|e| JayScreencastError::CreateMemfd(e.into()))?;
+        memfd
+            .write_all(&bytes)
+            .map_err(JayScreencastError::WriteCursorImage)?;
+        uapi::fcntl_add_seals(
+            memfd.raw(),
+            c::F_SEAL_SEAL | c::F_SEAL_GROW | c::F_SEAL_SHRINK | c::F_SEAL_WRITE,
+        )
+        .map_err(|e| JayScreencastError::CreateMemfd(e.into()))?;
+        self.client.event(CursorImage {
+            self_id: self.id,
+            fd: Rc::new(memfd),
+            width,
+            height,
+            hotspot_x: -extents.x1(),
+            hotspot_y: -extents.y1(),
+        });
+        Ok(())
+    }
+
+    fn set_cursor_mode_(&self, mode: CursorMode) {
+        let old = self.cursor_mode.replace(mode);
+        if old == mode {
+            return;
+        }
+        let metadata_screencasts = &self.client.state.metadata_cursor_screencasts;
+        if old == CursorMode::Metadata {
+            metadata_screencasts.fetch_sub(1);
+        }
+        if mode == CursorMode::Metadata {
+            metadata_screencasts.fetch_add(1);
+        }
+        self.cursor_image.take();
+    }
+
     fn detach(&self) {
         if let Some(target) = self.target.take() {
             match target {
@@ -337,6 +489,7 @@ impl JayScreencast {
 
     pub fn do_destroy(&self) {
         self.detach();
+        self.set_cursor_mode_(CursorMode::Embedded);
         self.destroyed.set(true);
         self.client.event(Destroyed { self_id: self.id });
     }
@@ -431,6 +584,7 @@ impl JayScreencastRequestHandler for JayScreencast {
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.detach();
+        self.set_cursor_mode_(CursorMode::Embedded);
         self.client.remove_obj(self)?;
         Ok(())
     }
@@ -566,6 +720,12 @@ impl JayScreencastRequestHandler for JayScreencast {
         if let Some(running) = self.pending.running.take() {
             self.running.set(running);
         }
+        if let Some(cursor_mode) = self.pending.cursor_mode.take() {
+            if self.cursor_mode.get() != cursor_mode {
+                self.set_cursor_mode_(cursor_mode);
+                self.damage();
+            }
+        }
 
         if need_realloc {
             slf.schedule_realloc();
@@ -630,6 +790,20 @@ impl JayScreencastRequestHandler for JayScreencast {
         self.pending.target.set(Some(toplevel));
         Ok(())
     }
+
+    fn set_cursor_mode(&self, req: SetCursorMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mode = match req.mode {
+            CURSOR_MODE_EMBEDDED => CursorMode::Embedded,
+            CURSOR_MODE_HIDDEN => CursorMode::Hidden,
+            CURSOR_MODE_METADATA => CursorMode::Metadata,
+            _ => return Err(JayScreencastError::UnknownCursorMode(req.mode)),
+        };
+        if self.destroyed.get() || !self.config_acked.get() {
+            return Ok(());
+        }
+        self.pending.cursor_mode.set(Some(mode));
+        Ok(())
+    }
 }
 
 object_base! {
@@ -640,6 +814,7 @@ object_base! {
 impl Object for JayScreencast {
     fn break_loops(&self) {
         self.detach();
+        self.set_cursor_mode_(CursorMode::Embedded);
     }
 }
 
@@ -661,6 +836,12 @@ pub enum JayScreencastError {
     XRGB8888Writing,
     #[error("Render context supports neither linear or invalid modifier")]
     Modifier,
+    #[error("Unknown cursor mode {0}")]
+    UnknownCursorMode(u32),
+    #[error("Could not create a memfd")]
+    CreateMemfd(#[source] OsError),
+    #[error("Could not write the cursor image")]
+    WriteCursorImage(#[source] io::Error),
 }
 efrom!(JayScreencastError, ClientError);
 
@@ -1,7 +1,10 @@
 use {
     crate::{
         client::{Client, ClientError},
-        ifs::{jay_toplevel::JayToplevel, wl_seat::ToplevelSelector},
+        ifs::{
+            jay_toplevel::{JayToplevel, APP_ID_SINCE, MINIMIZED_SINCE},
+            wl_seat::ToplevelSelector,
+        },
         leaks::Tracker,
         object::{Object, Version},
         tree::ToplevelNode,
@@ -17,6 +20,7 @@ pub struct JaySelectToplevel {
     pub client: Rc<Client>,
     pub tracker: Tracker<Self>,
     pub destroyed: Cell<bool>,
+    pub version: Version,
 }
 
 pub struct JayToplevelSelector {
@@ -35,6 +39,7 @@ impl Drop for JayToplevelSelector {
         if self.jst.destroyed.get() {
             return;
         }
+        let mut jtl = None;
         let id = match self.tl.take() {
             None => JayToplevelId::NONE,
             Some(toplevel) => {
@@ -45,23 +50,34 @@ impl Drop for JayToplevelSelector {
                         return;
                     }
                 };
-                let jtl = Rc::new(JayToplevel {
+                let obj = Rc::new(JayToplevel {
                     id,
                     client: self.jst.client.clone(),
                     tracker: Default::default(),
                     toplevel,
                     destroyed: Cell::new(false),
+                    version: self.jst.version,
                 });
-                track!(self.jst.client, jtl);
-                self.jst.client.add_server_obj(&jtl);
-                jtl.toplevel
+                track!(self.jst.client, obj);
+                self.jst.client.add_server_obj(&obj);
+                obj.toplevel
                     .tl_data()
                     .jay_toplevels
-                    .set((jtl.client.id, jtl.id), jtl.clone());
-                jtl.id
+                    .set((obj.client.id, obj.id), obj.clone());
+                jtl = Some(obj);
+                id
             }
         };
         self.jst.send_done(id);
+        if let Some(jtl) = jtl {
+            let data = jtl.toplevel.tl_data();
+            if jtl.version >= APP_ID_SINCE {
+                jtl.send_app_id(&data.app_id.borrow());
+            }
+            if jtl.version >= MINIMIZED_SINCE && data.is_minimized() {
+                jtl.send_minimized(true);
+            }
+        }
         let _ = self.jst.client.remove_obj(&*self.jst);
     }
 }
@@ -81,7 +97,7 @@ impl JaySelectToplevelRequestHandler for JaySelectToplevel {
 
 object_base! {
     self = JaySelectToplevel;
-    version = Version(1);
+    version = self.version;
 }
 
 impl Object for JaySelectToplevel {
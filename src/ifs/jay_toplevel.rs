@@ -16,8 +16,12 @@ pub struct JayToplevel {
     pub tracker: Tracker<Self>,
     pub toplevel: Rc<dyn ToplevelNode>,
     pub destroyed: Cell<bool>,
+    pub version: Version,
 }
 
+pub const MINIMIZED_SINCE: Version = Version(2);
+pub const APP_ID_SINCE: Version = Version(2);
+
 impl JayToplevel {
     fn detach(&self) {
         self.destroyed.set(true);
@@ -35,6 +39,20 @@ impl JayToplevel {
     fn send_destroyed(&self) {
         self.client.event(Destroyed { self_id: self.id });
     }
+
+    pub fn send_minimized(&self, minimized: bool) {
+        self.client.event(Minimized {
+            self_id: self.id,
+            minimized: minimized as _,
+        });
+    }
+
+    pub fn send_app_id(&self, app_id: &str) {
+        self.client.event(AppId {
+            self_id: self.id,
+            app_id,
+        });
+    }
 }
 
 impl JayToplevelRequestHandler for JayToplevel {
@@ -49,7 +67,7 @@ impl JayToplevelRequestHandler for JayToplevel {
 
 object_base! {
     self = JayToplevel;
-    version = Version(1);
+    version = self.version;
 }
 
 impl Object for JayToplevel {
@@ -0,0 +1,135 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        json_ipc::tree::{self, NodeData, Split, TreeNode, WindowData},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{jay_tree::*, JayTreeId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const KIND_DISPLAY: u32 = 0;
+pub const KIND_OUTPUT: u32 = 1;
+pub const KIND_WORKSPACE: u32 = 2;
+pub const KIND_CONTAINER: u32 = 3;
+pub const KIND_FLOAT: u32 = 4;
+pub const KIND_WINDOW: u32 = 5;
+pub const KIND_PLACEHOLDER: u32 = 6;
+
+pub const SPLIT_HORIZONTAL: u32 = 0;
+pub const SPLIT_VERTICAL: u32 = 1;
+
+pub struct JayTree {
+    pub id: JayTreeId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl JayTree {
+    pub fn new(id: JayTreeId, client: &Rc<Client>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        }
+    }
+
+    pub fn send_tree(&self) {
+        let root = tree::tree(&self.client.state.root);
+        self.send_node(&root, 0);
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn send_node(&self, node: &TreeNode, parent: u32) {
+        let kind = match &node.data {
+            NodeData::Display => KIND_DISPLAY,
+            NodeData::Output { .. } => KIND_OUTPUT,
+            NodeData::Workspace { .. } => KIND_WORKSPACE,
+            NodeData::Container { .. } => KIND_CONTAINER,
+            NodeData::Float => KIND_FLOAT,
+            NodeData::Window(_) => KIND_WINDOW,
+            NodeData::Placeholder(_) => KIND_PLACEHOLDER,
+        };
+        self.client.event(Node {
+            self_id: self.id,
+            id: node.id,
+            parent,
+            kind,
+            x: node.rect.x,
+            y: node.rect.y,
+            width: node.rect.width,
+            height: node.rect.height,
+            visible: node.visible as _,
+        });
+        match &node.data {
+            NodeData::Display | NodeData::Float => {}
+            NodeData::Output { name } | NodeData::Workspace { name } => {
+                self.client.event(Name {
+                    self_id: self.id,
+                    name,
+                });
+            }
+            NodeData::Container { split, mono } => {
+                let split = match split {
+                    Split::Horizontal => SPLIT_HORIZONTAL,
+                    Split::Vertical => SPLIT_VERTICAL,
+                };
+                self.client.event(Container {
+                    self_id: self.id,
+                    split,
+                    mono: *mono as _,
+                });
+            }
+            NodeData::Window(window) | NodeData::Placeholder(window) => {
+                self.send_window(window);
+            }
+        }
+        for child in &node.nodes {
+            self.send_node(child, node.id);
+        }
+    }
+
+    fn send_window(&self, window: &WindowData) {
+        self.client.event(Window {
+            self_id: self.id,
+            title: &window.title,
+            app_id: &window.app_id,
+            floating: window.floating as _,
+            fullscreen: window.fullscreen as _,
+        });
+        if let (Some(client_id), Some(pid)) = (window.client_id, window.pid) {
+            self.client.event(ClientInfo {
+                self_id: self.id,
+                client_id,
+                pid,
+            });
+        }
+    }
+}
+
+impl JayTreeRequestHandler for JayTree {
+    type Error = JayTreeError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayTree;
+    version = Version(1);
+}
+
+impl Object for JayTree {}
+
+simple_add_obj!(JayTree);
+
+#[derive(Debug, Error)]
+pub enum JayTreeError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayTreeError, ClientError);
@@ -15,7 +15,7 @@ use {
         wire::{wl_output::*, WlOutputId, ZxdgOutputV1Id},
     },
     ahash::AHashMap,
-    jay_config::video::Transform,
+    jay_config::video::{Transform, VrrMode},
     std::{
         cell::{Cell, RefCell},
         collections::hash_map::Entry,
@@ -91,6 +91,7 @@ pub struct PersistentOutputState {
     pub transform: Cell<Transform>,
     pub scale: Cell<crate::scale::Scale>,
     pub pos: Cell<(i32, i32)>,
+    pub vrr_mode: Cell<VrrMode>,
 }
 
 #[derive(Eq, PartialEq, Hash)]
@@ -172,14 +173,7 @@ impl WlOutputGlobal {
         let bindings = self.bindings.borrow_mut();
         for binding in bindings.values() {
             for binding in binding.values() {
-                binding.send_geometry();
-                binding.send_mode();
-                binding.send_scale();
-                binding.send_done();
-                let xdg = binding.xdg_outputs.lock();
-                for xdg in xdg.values() {
-                    xdg.send_updates();
-                }
+                binding.send_updates();
                 // binding.client.flush();
             }
         }
@@ -259,15 +253,28 @@ pub const SEND_SCALE_SINCE: Version = Version(2);
 pub const SEND_NAME_SINCE: Version = Version(4);
 
 impl WlOutput {
+    pub fn send_updates(self: &Rc<Self>) {
+        self.send_geometry();
+        self.send_mode();
+        self.send_scale();
+        self.send_done();
+        let xdg = self.xdg_outputs.lock();
+        for xdg in xdg.values() {
+            xdg.send_updates();
+        }
+    }
+
     fn send_geometry(&self) {
         let Some(global) = self.global.get() else {
             return;
         };
         let pos = global.pos.get();
+        let (mut x, mut y) = pos.position();
+        logical_to_client_wire_scale!(self.client, x, y);
         let event = Geometry {
             self_id: self.id,
-            x: pos.x1(),
-            y: pos.y1(),
+            x,
+            y,
             physical_width: global.width_mm,
             physical_height: global.height_mm,
             subpixel: SP_UNKNOWN,
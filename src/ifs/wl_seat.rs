@@ -6,6 +6,7 @@ mod kb_owner;
 mod pointer_owner;
 pub mod tablet;
 pub mod text_input;
+mod touch_owner;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
@@ -22,9 +23,11 @@ pub mod zwp_virtual_keyboard_v1;
 use {
     crate::{
         async_engine::SpawnedFuture,
+        backend::InputDeviceCapability,
         client::{Client, ClientError, ClientId},
         cursor_user::{CursorUser, CursorUserGroup, CursorUserOwner},
         fixed::Fixed,
+        focus_history::{self, FocusHistory},
         globals::{Global, GlobalName},
         ifs::{
             ext_idle_notification_v1::ExtIdleNotificationV1,
@@ -42,6 +45,7 @@ use {
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
                 DynDataSource, IpcError,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
             wl_output::WlOutputGlobal,
             wl_seat::{
                 gesture_owner::GestureOwnerHolder,
@@ -52,6 +56,7 @@ use {
                     zwp_input_method_keyboard_grab_v2::ZwpInputMethodKeyboardGrabV2,
                     zwp_input_method_v2::ZwpInputMethodV2, zwp_text_input_v3::ZwpTextInputV3,
                 },
+                touch_owner::TouchOwnerHolder,
                 wl_keyboard::{WlKeyboard, WlKeyboardError, REPEAT_INFO_SINCE},
                 wl_pointer::WlPointer,
                 wl_touch::WlTouch,
@@ -64,14 +69,17 @@ use {
             wl_surface::WlSurface,
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
         },
+        json_ipc,
         leaks::Tracker,
+        minimize,
         object::{Object, Version},
         rect::Rect,
+        scratchpad,
         state::{DeviceHandlerData, State},
         time::now_usec,
         tree::{
-            generic_node_visitor, ContainerNode, ContainerSplit, Direction, FoundNode, Node,
-            OutputNode, ToplevelNode, WorkspaceNode,
+            generic_node_visitor, ContainerNode, ContainerSplit, Direction, FindTreeUsecase,
+            FoundNode, Node, OutputNode, ToplevelNode, WorkspaceNode,
         },
         utils::{
             asyncevent::AsyncEvent, bindings::PerClientBindings, clonecell::CloneCell,
@@ -79,13 +87,16 @@ use {
             rc_eq::rc_eq, smallmap::SmallMap,
         },
         wire::{
-            wl_seat::*, ExtIdleNotificationV1Id, WlDataDeviceId, WlKeyboardId, WlPointerId,
-            WlSeatId, ZwlrDataControlDeviceV1Id, ZwpPrimarySelectionDeviceV1Id,
-            ZwpRelativePointerV1Id, ZwpTextInputV3Id,
+            wl_seat::*, ExtIdleNotificationV1Id, JayGlobalShortcutsId, WlDataDeviceId,
+            WlKeyboardId, WlPointerId, WlSeatId, WlTouchId, ZwlrDataControlDeviceV1Id,
+            ZwpPrimarySelectionDeviceV1Id, ZwpRelativePointerV1Id, ZwpTextInputV3Id,
         },
+        workspaces,
         xkbcommon::{DynKeyboardState, KeyboardState, KeymapId, XkbKeymap, XkbState},
+        xwayland::XWaylandEvent,
     },
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
+    jay_config::{input::pointer::PointerTrigger, keyboard::mods::Modifiers, ResizeAmount},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -103,9 +114,8 @@ pub use {
 };
 
 pub const POINTER: u32 = 1;
-const KEYBOARD: u32 = 2;
-#[allow(dead_code)]
-const TOUCH: u32 = 4;
+pub const KEYBOARD: u32 = 2;
+pub const TOUCH: u32 = 4;
 
 #[allow(dead_code)]
 const MISSING_CAPABILITY: u32 = 0;
@@ -174,13 +184,22 @@ pub struct WlSeatGlobal {
     pointer_owner: PointerOwnerHolder,
     kb_owner: KbOwnerHolder,
     gesture_owner: GestureOwnerHolder,
+    touch_owner: TouchOwnerHolder,
+    num_touch_devices: NumCell<u32>,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
+    x_dnd_active: Cell<bool>,
+    x_dnd_target: CloneCell<Option<Rc<WlSurface>>>,
     shortcuts: RefCell<AHashMap<u32, SmallMap<u32, u32, 2>>>,
+    pointer_shortcuts: RefCell<AHashSet<(Modifiers, PointerTrigger)>>,
+    consumed_buttons: RefCell<AHashSet<u32>>,
+    scroll_shortcut_v120: [Cell<f64>; 2],
     queue_link: RefCell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     changes: NumCell<u32>,
     constraint: CloneCell<Option<Rc<SeatConstraint>>>,
     idle_notifications: CopyHashMap<(ClientId, ExtIdleNotificationV1Id), Rc<ExtIdleNotificationV1>>,
+    global_shortcuts: CopyHashMap<(ClientId, JayGlobalShortcutsId), Rc<JayGlobalShortcuts>>,
+    shortcut_recorder: CloneCell<Option<Rc<JayGlobalShortcuts>>>,
     last_input_usec: Cell<u64>,
     text_inputs: RefCell<AHashMap<ClientId, CopyHashMap<ZwpTextInputV3Id, Rc<ZwpTextInputV3>>>>,
     text_input: CloneCell<Option<Rc<ZwpTextInputV3>>>,
@@ -188,6 +207,10 @@ pub struct WlSeatGlobal {
     input_method_grab: CloneCell<Option<Rc<ZwpInputMethodKeyboardGrabV2>>>,
     forward: Cell<bool>,
     focus_follows_mouse: Cell<bool>,
+    binding_mode: CloneCell<Option<Rc<String>>>,
+    binding_mode_once: Cell<bool>,
+    focus_history: FocusHistory,
+    previous_workspace: CloneCell<Option<Rc<String>>>,
     swipe_bindings: PerClientBindings<ZwpPointerGestureSwipeV1>,
     pinch_bindings: PerClientBindings<ZwpPointerGesturePinchV1>,
     hold_bindings: PerClientBindings<ZwpPointerGestureHoldV1>,
@@ -238,13 +261,22 @@ impl WlSeatGlobal {
             pointer_owner: Default::default(),
             kb_owner: Default::default(),
             gesture_owner: Default::default(),
+            touch_owner: Default::default(),
+            num_touch_devices: NumCell::new(0),
             dropped_dnd: RefCell::new(None),
+            x_dnd_active: Cell::new(false),
+            x_dnd_target: Default::default(),
             shortcuts: Default::default(),
+            pointer_shortcuts: Default::default(),
+            consumed_buttons: Default::default(),
+            scroll_shortcut_v120: Default::default(),
             queue_link: Default::default(),
             tree_changed_handler: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
             constraint: Default::default(),
             idle_notifications: Default::default(),
+            global_shortcuts: Default::default(),
+            shortcut_recorder: Default::default(),
             last_input_usec: Cell::new(now_usec()),
             wlr_data_devices: Default::default(),
             text_inputs: Default::default(),
@@ -253,6 +285,10 @@ impl WlSeatGlobal {
             input_method_grab: Default::default(),
             forward: Cell::new(false),
             focus_follows_mouse: Cell::new(true),
+            binding_mode: Default::default(),
+            binding_mode_once: Cell::new(false),
+            focus_history: Default::default(),
+            previous_workspace: Default::default(),
             swipe_bindings: Default::default(),
             pinch_bindings: Default::default(),
             hold_bindings: Default::default(),
@@ -351,40 +387,33 @@ impl WlSeatGlobal {
             Some(tl) => tl,
             _ => return,
         };
-        if tl.tl_data().is_fullscreen.get() {
-            return;
-        }
-        let old_ws = match tl.tl_data().workspace.get() {
-            Some(ws) => ws,
-            _ => return,
-        };
-        if old_ws.id == ws.id {
-            return;
-        }
-        let cn = match tl.tl_data().parent.get() {
-            Some(cn) => cn,
-            _ => return,
-        };
-        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
-        cn.cnode_remove_child2(tl.tl_as_node(), true);
-        if !ws.visible.get() {
-            for focus in kb_foci {
-                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
-            }
+        self.state.set_tl_workspace(tl, ws);
+    }
+
+    pub fn focused_toplevel(&self) -> Option<Rc<dyn ToplevelNode>> {
+        self.keyboard_node.get().node_toplevel()
+    }
+
+    pub fn move_to_scratchpad(&self) {
+        if let Some(tl) = self.focused_toplevel() {
+            scratchpad::move_to_scratchpad(&self.state, tl);
         }
-        if tl.tl_data().is_floating.get() {
-            self.state.map_floating(
-                tl.clone(),
-                tl.tl_data().float_width.get(),
-                tl.tl_data().float_height.get(),
-                ws,
-                None,
-            );
-        } else {
-            self.state.map_tiled_on(tl, ws);
+    }
+
+    pub fn toggle_scratchpad(self: &Rc<Self>, app_id: Option<&str>) {
+        scratchpad::toggle_scratchpad(&self.state, self, app_id);
+    }
+
+    pub fn minimize(&self) {
+        if let Some(tl) = self.focused_toplevel() {
+            minimize::minimize(&self.state, tl);
         }
     }
 
+    pub fn restore_minimized(self: &Rc<Self>) {
+        minimize::restore_last_minimized(&self.state, self);
+    }
+
     pub fn mark_last_active(self: &Rc<Self>) {
         let link = &mut *self.queue_link.borrow_mut();
         if let Some(link) = link {
@@ -580,29 +609,7 @@ impl WlSeatGlobal {
             Some(tl) => tl,
             _ => return,
         };
-        self.set_tl_floating(tl, floating);
-    }
-
-    pub fn set_tl_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
-        let data = tl.tl_data();
-        if data.is_fullscreen.get() {
-            return;
-        }
-        if data.is_floating.get() == floating {
-            return;
-        }
-        let parent = match data.parent.get() {
-            Some(p) => p,
-            _ => return,
-        };
-        if !floating {
-            parent.cnode_remove_child2(tl.tl_as_node(), true);
-            self.state.map_tiled(tl);
-        } else if let Some(ws) = data.workspace.get() {
-            parent.cnode_remove_child2(tl.tl_as_node(), true);
-            let (width, height) = data.float_size(&ws);
-            self.state.map_floating(tl, width, height, &ws, None);
-        }
+        self.state.set_tl_floating(tl, floating);
     }
 
     pub fn get_rate(&self) -> (i32, i32) {
@@ -659,6 +666,78 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn resize_focused(&self, direction: Direction, amount: ResizeAmount) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        let (Some(parent), Some(ws)) = (data.parent.get(), data.workspace.get()) else {
+            return;
+        };
+        let output = ws.output.get().global.pos.get();
+        let size = match direction {
+            Direction::Up | Direction::Down => output.height(),
+            _ => output.width(),
+        };
+        let delta = match amount {
+            ResizeAmount::Px(px) => px,
+            ResizeAmount::Percent(p) => (size as f64 * p / 100.0).round() as i32,
+        };
+        let pos = tl.node_absolute_position();
+        let (mut x1, mut y1, mut x2, mut y2) = (None, None, None, None);
+        match direction {
+            Direction::Unspecified => return,
+            Direction::Left => x1 = Some(pos.x1() - delta),
+            Direction::Down => y2 = Some(pos.y2() + delta),
+            Direction::Up => y1 = Some(pos.y1() - delta),
+            Direction::Right => x2 = Some(pos.x2() + delta),
+        }
+        parent.cnode_resize_child(tl.tl_as_node(), x1, y1, x2, y2);
+    }
+
+    pub fn move_focused_floating(&self, dx: i32, dy: i32) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let data = tl.tl_data();
+        if !data.is_floating.get() || data.is_fullscreen.get() {
+            return;
+        }
+        let Some(parent) = data.parent.get() else {
+            return;
+        };
+        let pos = tl.node_absolute_position();
+        parent.cnode_set_child_position(tl.tl_as_node(), pos.x1() + dx, pos.y1() + dy);
+    }
+
+    pub fn set_focused_floating_geometry(&self, x: i32, y: i32, width: i32, height: i32) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let data = tl.tl_data();
+        if !data.is_floating.get() || data.is_fullscreen.get() {
+            return;
+        }
+        let (Some(parent), Some(ws)) = (data.parent.get(), data.workspace.get()) else {
+            return;
+        };
+        let output = ws.output.get().global.pos.get();
+        let (x1, y1) = (output.x1() + x, output.y1() + y);
+        parent
+            .clone()
+            .cnode_set_child_position(tl.tl_as_node(), x1, y1);
+        parent.cnode_resize_child(
+            tl.tl_as_node(),
+            None,
+            None,
+            Some(x1 + width),
+            Some(y1 + height),
+        );
+    }
+
     fn set_selection_<T, X, W, S>(
         self: &Rc<Self>,
         field: &CloneCell<Option<Rc<dyn DynDataSource>>>,
@@ -737,6 +816,61 @@ impl WlSeatGlobal {
         self.pointer_owner.cancel_dnd(self);
     }
 
+    /// Starts reporting the wayland surface under the pointer to the X window manager.
+    ///
+    /// This is used while an X client performs a drag-and-drop operation so that the X
+    /// window manager can place its proxy window over wayland surfaces.
+    pub fn start_x_dnd(&self) {
+        self.x_dnd_active.set(true);
+    }
+
+    pub fn end_x_dnd(&self) {
+        self.x_dnd_active.set(false);
+        if self.x_dnd_target.take().is_some() {
+            self.state
+                .xwayland
+                .queue
+                .push(XWaylandEvent::DndProxyTarget {
+                    seat: self.id,
+                    surface: None,
+                });
+        }
+    }
+
+    fn update_x_dnd_target(&self) {
+        let (x, y) = self.pointer_cursor.position();
+        let node = {
+            let mut found_tree = self.found_tree.borrow_mut();
+            found_tree.push(FoundNode {
+                node: self.state.root.clone(),
+                x: x.round_down(),
+                y: y.round_down(),
+            });
+            self.state.root.node_find_tree_at(
+                x.round_down(),
+                y.round_down(),
+                &mut found_tree,
+                FindTreeUsecase::None,
+            );
+            let node = found_tree.pop().unwrap().node;
+            found_tree.clear();
+            node
+        };
+        let surface = node.node_into_surface().filter(|s| !s.client.is_xwayland);
+        let old = self.x_dnd_target.get();
+        if old.as_ref().map(|s| s.node_id()) == surface.as_ref().map(|s| s.node_id()) {
+            return;
+        }
+        self.x_dnd_target.set(surface.clone());
+        self.state
+            .xwayland
+            .queue
+            .push(XWaylandEvent::DndProxyTarget {
+                seat: self.id,
+                surface,
+            });
+    }
+
     pub fn unset_selection(self: &Rc<Self>) {
         let _ = self.set_wl_data_source_selection(None, None);
     }
@@ -854,17 +988,22 @@ impl WlSeatGlobal {
         self.primary_selection.set(None);
         self.pointer_owner.clear();
         self.kb_owner.clear();
+        self.touch_owner.clear();
         *self.dropped_dnd.borrow_mut() = None;
+        self.x_dnd_target.take();
         self.queue_link.take();
         self.tree_changed_handler.set(None);
         self.constraint.take();
         self.text_inputs.borrow_mut().clear();
+        self.global_shortcuts.clear();
+        self.shortcut_recorder.take();
         self.text_input.take();
         self.input_method.take();
         self.input_method_grab.take();
         self.swipe_bindings.clear();
         self.pinch_bindings.clear();
         self.hold_bindings.clear();
+        self.focus_history.clear();
         self.cursor_user_group.detach();
         self.tablet_clear();
     }
@@ -890,6 +1029,7 @@ impl WlSeatGlobal {
             pointers: Default::default(),
             relative_pointers: Default::default(),
             keyboards: Default::default(),
+            touches: Default::default(),
             version,
             tracker: Default::default(),
         });
@@ -919,6 +1059,28 @@ impl WlSeatGlobal {
             .remove(&(notification.client.id, notification.id));
     }
 
+    pub fn add_global_shortcuts(&self, shortcuts: &Rc<JayGlobalShortcuts>) {
+        self.global_shortcuts
+            .set((shortcuts.client.id, shortcuts.id), shortcuts.clone());
+    }
+
+    pub fn remove_global_shortcuts(&self, shortcuts: &JayGlobalShortcuts) {
+        self.global_shortcuts
+            .remove(&(shortcuts.client.id, shortcuts.id));
+        if let Some(recorder) = self.shortcut_recorder.get() {
+            if recorder.id == shortcuts.id && recorder.client.id == shortcuts.client.id {
+                self.shortcut_recorder.take();
+            }
+        }
+    }
+
+    /// Reports the next key combination pressed on this seat to the object.
+    ///
+    /// The key press is not forwarded to clients or matched against shortcuts.
+    pub fn record_shortcut(&self, shortcuts: &Rc<JayGlobalShortcuts>) {
+        self.shortcut_recorder.set(Some(shortcuts.clone()));
+    }
+
     pub fn last_input(&self) -> u64 {
         self.last_input_usec.get()
     }
@@ -956,10 +1118,96 @@ impl WlSeatGlobal {
         self.focus_follows_mouse.set(focus_follows_mouse);
     }
 
+    pub fn binding_mode(&self) -> Option<Rc<String>> {
+        self.binding_mode.get()
+    }
+
+    /// Sets the binding mode shown in the bar.
+    ///
+    /// If `once` is set, the binding mode is cancelled by the next non-modifier key press
+    /// that does not invoke a shortcut.
+    pub fn set_binding_mode(&self, name: Option<Rc<String>>, once: bool) {
+        self.binding_mode_once.set(once && name.is_some());
+        if self.binding_mode.get() == name {
+            return;
+        }
+        self.binding_mode.set(name.clone());
+        for output in self.state.root.outputs.lock().values() {
+            output.schedule_update_render_data();
+        }
+        json_ipc::mode_event(
+            &self.state,
+            &self.seat_name,
+            name.as_deref().map(|n| n.as_str()),
+        );
+    }
+
+    fn cancel_binding_mode_once(&self) {
+        if !self.binding_mode_once.replace(false) {
+            return;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.binding_mode_cancelled(self.id);
+        }
+    }
+
+    pub fn focus_history(&self) -> &FocusHistory {
+        &self.focus_history
+    }
+
+    pub fn focus_last(self: &Rc<Self>) {
+        focus_history::focus_last(&self.state, self);
+    }
+
+    pub fn focus_next_mru(self: &Rc<Self>) {
+        focus_history::cycle_focus_history(&self.state, self, true);
+    }
+
+    pub fn focus_prev_mru(self: &Rc<Self>) {
+        focus_history::cycle_focus_history(&self.state, self, false);
+    }
+
+    pub fn previous_workspace(&self) -> Option<Rc<String>> {
+        self.previous_workspace.get()
+    }
+
+    pub fn set_previous_workspace(&self, name: Option<Rc<String>>) {
+        self.previous_workspace.set(name);
+    }
+
+    pub fn show_adjacent_workspace(self: &Rc<Self>, next: bool, global: bool) {
+        workspaces::show_adjacent_workspace(&self.state, self, next, global);
+    }
+
+    pub fn workspace_back_and_forth(self: &Rc<Self>) {
+        workspaces::show_previous_workspace(&self.state, self);
+    }
+
     pub fn set_window_management_enabled(self: &Rc<Self>, enabled: bool) {
         self.pointer_owner
             .set_window_management_enabled(self, enabled);
     }
+
+    fn add_touch_device(&self) {
+        if self.num_touch_devices.fetch_add(1) == 0 {
+            self.send_capabilities();
+        }
+    }
+
+    fn remove_touch_device(self: &Rc<Self>) {
+        if self.num_touch_devices.fetch_sub(1) == 1 {
+            self.touch_owner.cancel(self);
+            self.send_capabilities();
+        }
+    }
+
+    fn send_capabilities(&self) {
+        for bindings in self.bindings.borrow().values() {
+            for seat in bindings.values() {
+                seat.send_capabilities();
+            }
+        }
+    }
 }
 
 impl CursorUserOwner for WlSeatGlobal {
@@ -996,6 +1244,7 @@ pub struct WlSeat {
     pointers: CopyHashMap<WlPointerId, Rc<WlPointer>>,
     relative_pointers: CopyHashMap<ZwpRelativePointerV1Id, Rc<ZwpRelativePointerV1>>,
     keyboards: CopyHashMap<WlKeyboardId, Rc<WlKeyboard>>,
+    touches: CopyHashMap<WlTouchId, Rc<WlTouch>>,
     version: Version,
     tracker: Tracker<Self>,
 }
@@ -1004,9 +1253,13 @@ const READ_ONLY_KEYMAP_SINCE: Version = Version(7);
 
 impl WlSeat {
     fn send_capabilities(self: &Rc<Self>) {
+        let mut capabilities = POINTER | KEYBOARD;
+        if self.global.num_touch_devices.get() > 0 {
+            capabilities |= TOUCH;
+        }
         self.client.event(Capabilities {
             self_id: self.id,
-            capabilities: POINTER | KEYBOARD,
+            capabilities,
         })
     }
 
@@ -1061,6 +1314,7 @@ impl WlSeatRequestHandler for WlSeat {
         let p = Rc::new(WlTouch::new(req.id, slf));
         track!(self.client, p);
         self.client.add_client_obj(&p)?;
+        self.touches.set(req.id, p);
         Ok(())
     }
 
@@ -1098,6 +1352,7 @@ impl Object for WlSeat {
         self.pointers.clear();
         self.relative_pointers.clear();
         self.keyboards.clear();
+        self.touches.clear();
     }
 }
 
@@ -1148,6 +1403,9 @@ impl DeviceHandlerData {
             if let Some(info) = &self.tablet_pad_init {
                 old.tablet_remove_tablet_pad(info.id);
             }
+            if self.device.has_capability(InputDeviceCapability::Touch) {
+                old.remove_touch_device();
+            }
         }
         self.update_xkb_state();
         if let Some(seat) = &seat {
@@ -1157,6 +1415,9 @@ impl DeviceHandlerData {
             if let Some(info) = &self.tablet_pad_init {
                 seat.tablet_add_tablet_pad(self.device.id(), info);
             }
+            if self.device.has_capability(InputDeviceCapability::Touch) {
+                seat.add_touch_device();
+            }
         }
     }
 
@@ -1165,7 +1426,7 @@ impl DeviceHandlerData {
         self.update_xkb_state();
     }
 
-    fn get_effective_xkb_state(&self, seat: &WlSeatGlobal) -> Rc<RefCell<XkbState>> {
+    pub fn get_effective_xkb_state(&self, seat: &WlSeatGlobal) -> Rc<RefCell<XkbState>> {
         match self.xkb_state.get() {
             Some(s) => s,
             _ => seat.seat_xkb_state.get(),
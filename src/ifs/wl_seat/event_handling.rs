@@ -1,9 +1,10 @@
 use {
     crate::{
-        backend::{ConnectorId, InputDeviceId, InputEvent, KeyState, AXIS_120},
+        backend::{ConnectorId, InputDeviceId, InputEvent, KeyState, ScrollAxis, AXIS_120},
         client::ClientId,
         config::InvokedShortcut,
         fixed::Fixed,
+        focus_history,
         ifs::{
             ipc::{
                 wl_data_device::{ClipboardIpc, WlDataDevice},
@@ -24,6 +25,7 @@ use {
                     AXIS_STOP_SINCE_VERSION, AXIS_VALUE120_SINCE_VERSION, IDENTICAL, INVERTED,
                     POINTER_FRAME_SINCE_VERSION, WHEEL_TILT, WHEEL_TILT_SINCE_VERSION,
                 },
+                wl_touch::WlTouch,
                 zwp_pointer_constraints_v1::{ConstraintType, SeatConstraintStatus},
                 zwp_relative_pointer_v1::ZwpRelativePointerV1,
                 Dnd, SeatId, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED, CHANGE_TREE,
@@ -31,6 +33,7 @@ use {
             wl_surface::{xdg_surface::xdg_popup::XdgPopup, WlSurface},
         },
         object::Version,
+        rect::Rect,
         state::DeviceHandlerData,
         tree::{Direction, Node, ToplevelNode},
         utils::{bitflags::BitflagsExt, hash_map_ext::HashMapExt, smallmap::SmallMap},
@@ -39,7 +42,10 @@ use {
     },
     isnt::std_1::primitive::{IsntSlice2Ext, IsntSliceExt},
     jay_config::{
-        input::SwitchEvent,
+        input::{
+            pointer::{Button, PointerTrigger},
+            SwitchEvent,
+        },
         keyboard::{
             mods::{Modifiers, CAPS, NUM, RELEASE},
             syms::{KeySym, SYM_Escape},
@@ -49,12 +55,15 @@ use {
     std::{cell::RefCell, collections::hash_map::Entry, rc::Rc},
 };
 
+type JayDirection = jay_config::Direction;
+
 #[derive(Default)]
 pub struct NodeSeatState {
     pointer_foci: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     kb_foci: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     gesture_foci: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     pointer_grabs: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
+    touch_grabs: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     dnd_targets: SmallMap<SeatId, Rc<WlSeatGlobal>, 1>,
     tablet_pad_foci: SmallMap<TabletPadId, Rc<TabletPad>, 1>,
     tablet_tool_foci: SmallMap<TabletToolId, Rc<TabletTool>, 1>,
@@ -95,6 +104,14 @@ impl NodeSeatState {
         self.pointer_grabs.remove(&seat.id);
     }
 
+    pub(super) fn add_touch_grab(&self, seat: &Rc<WlSeatGlobal>) {
+        self.touch_grabs.insert(seat.id, seat.clone());
+    }
+
+    pub(super) fn remove_touch_grab(&self, seat: &WlSeatGlobal) {
+        self.touch_grabs.remove(&seat.id);
+    }
+
     pub(super) fn add_tablet_pad_focus(&self, pad: &Rc<TabletPad>) {
         self.tablet_pad_foci.insert(pad.id, pad.clone());
     }
@@ -167,6 +184,9 @@ impl NodeSeatState {
             seat.pointer_owner.grab_node_removed(&seat);
         }
         let node_id = node.node_id();
+        while let Some((_, seat)) = self.touch_grabs.pop() {
+            seat.touch_owner.grab_node_removed(&seat, node_id);
+        }
         while let Some((_, seat)) = self.dnd_targets.pop() {
             seat.pointer_owner.dnd_target_removed(&seat);
         }
@@ -202,6 +222,7 @@ impl NodeSeatState {
         self.pointer_foci.remove(&seat.id);
         self.dnd_targets.remove(&seat.id);
         self.pointer_grabs.remove(&seat.id);
+        self.touch_grabs.remove(&seat.id);
     }
 
     pub fn clear(&self) {
@@ -209,6 +230,7 @@ impl NodeSeatState {
         self.pointer_foci.clear();
         self.dnd_targets.clear();
         self.pointer_grabs.clear();
+        self.touch_grabs.clear();
     }
 }
 
@@ -234,7 +256,12 @@ impl WlSeatGlobal {
             | InputEvent::TabletPadButton { time_usec, .. }
             | InputEvent::TabletPadModeSwitch { time_usec, .. }
             | InputEvent::TabletPadRing { time_usec, .. }
-            | InputEvent::TabletPadStrip { time_usec, .. } => {
+            | InputEvent::TabletPadStrip { time_usec, .. }
+            | InputEvent::TouchDown { time_usec, .. }
+            | InputEvent::TouchUp { time_usec, .. }
+            | InputEvent::TouchMotion { time_usec, .. }
+            | InputEvent::TouchCancel { time_usec, .. }
+            | InputEvent::TouchFrame { time_usec, .. } => {
                 self.last_input_usec.set(time_usec);
                 if self.idle_notifications.is_not_empty() {
                     for notification in self.idle_notifications.lock().drain_values() {
@@ -278,6 +305,11 @@ impl WlSeatGlobal {
             InputEvent::TabletPadModeSwitch { .. } => {}
             InputEvent::TabletPadRing { .. } => {}
             InputEvent::TabletPadStrip { .. } => {}
+            InputEvent::TouchDown { .. } => {}
+            InputEvent::TouchUp { .. } => {}
+            InputEvent::TouchMotion { .. } => {}
+            InputEvent::TouchCancel { .. } => {}
+            InputEvent::TouchFrame { .. } => {}
         }
         match event {
             InputEvent::Key {
@@ -411,6 +443,21 @@ impl WlSeatGlobal {
                 source,
                 position,
             } => self.tablet_event_pad_strip(pad, strip, source, position, time_usec),
+            InputEvent::TouchDown {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            } => self.touch_down(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed),
+            InputEvent::TouchUp { time_usec, id } => self.touch_up(time_usec, id),
+            InputEvent::TouchMotion {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            } => self.touch_motion(time_usec, id, dev.get_rect(&self.state), x_normed, y_normed),
+            InputEvent::TouchCancel { time_usec, id } => self.touch_cancel(time_usec, id),
+            InputEvent::TouchFrame { time_usec } => self.touch_frame(time_usec),
         }
     }
 
@@ -496,6 +543,21 @@ impl WlSeatGlobal {
         self.state.for_each_seat_tester(|t| {
             t.send_button(self.id, time_usec, button, state);
         });
+        match state {
+            KeyState::Pressed => {
+                let mods = self.shortcut_mods();
+                let trigger = PointerTrigger::Button(Button(button));
+                if self.invoke_pointer_shortcut(mods, trigger) {
+                    self.consumed_buttons.borrow_mut().insert(button);
+                    return;
+                }
+            }
+            KeyState::Released => {
+                if self.consumed_buttons.borrow_mut().remove(&button) {
+                    return;
+                }
+            }
+        }
         self.pointer_owner.button(self, time_usec, button, state);
     }
 
@@ -590,6 +652,59 @@ impl WlSeatGlobal {
         self.gesture_owner.hold_end(self, time_usec, cancelled)
     }
 
+    fn touch_down(
+        self: &Rc<Self>,
+        time_usec: u64,
+        id: i32,
+        rect: Rect,
+        x_normed: f64,
+        y_normed: f64,
+    ) {
+        let x = Fixed::from_f64(rect.x1() as f64 + rect.width() as f64 * x_normed);
+        let y = Fixed::from_f64(rect.y1() as f64 + rect.height() as f64 * y_normed);
+        self.state.for_each_seat_tester(|t| {
+            t.send_touch_down(self.id, time_usec, id, x, y);
+        });
+        self.touch_owner.down(self, time_usec, id, x, y)
+    }
+
+    fn touch_up(self: &Rc<Self>, time_usec: u64, id: i32) {
+        self.state.for_each_seat_tester(|t| {
+            t.send_touch_up(self.id, time_usec, id);
+        });
+        self.touch_owner.up(self, time_usec, id)
+    }
+
+    fn touch_motion(
+        self: &Rc<Self>,
+        time_usec: u64,
+        id: i32,
+        rect: Rect,
+        x_normed: f64,
+        y_normed: f64,
+    ) {
+        let x = Fixed::from_f64(rect.x1() as f64 + rect.width() as f64 * x_normed);
+        let y = Fixed::from_f64(rect.y1() as f64 + rect.height() as f64 * y_normed);
+        self.state.for_each_seat_tester(|t| {
+            t.send_touch_motion(self.id, time_usec, id, x, y);
+        });
+        self.touch_owner.motion(self, time_usec, id, x, y)
+    }
+
+    fn touch_cancel(self: &Rc<Self>, time_usec: u64, id: i32) {
+        self.state.for_each_seat_tester(|t| {
+            t.send_touch_cancel(self.id, time_usec, id);
+        });
+        self.touch_owner.cancel_point(self, id)
+    }
+
+    fn touch_frame(self: &Rc<Self>, time_usec: u64) {
+        self.state.for_each_seat_tester(|t| {
+            t.send_touch_frame(self.id, time_usec);
+        });
+        self.touch_owner.frame(self)
+    }
+
     fn switch_event(self: &Rc<Self>, dev: InputDeviceId, time_usec: u64, event: SwitchEvent) {
         self.state.for_each_seat_tester(|t| {
             t.send_switch_event(self.id, dev, time_usec, event);
@@ -628,6 +743,7 @@ impl WlSeatGlobal {
         };
         let mut shortcuts = SmallVec::<[_; 1]>::new();
         let new_mods;
+        let mut global_shortcut = false;
         {
             let mut mods = xkb_state.mods().mods_effective & !(CAPS.0 | NUM.0);
             if state == wl_keyboard::RELEASED {
@@ -654,6 +770,30 @@ impl WlSeatGlobal {
                 }
             }
             new_mods = xkb_state.update(key, xkb_dir);
+            if !self.state.lock.locked.get() {
+                global_shortcut = self.handle_global_shortcuts(
+                    time_usec,
+                    key,
+                    state,
+                    mods & !RELEASE.0,
+                    xkb_state.unmodified_keysyms(key),
+                    new_mods,
+                );
+            }
+        }
+        if state == wl_keyboard::PRESSED
+            && shortcuts.is_empty()
+            && !new_mods
+            && self.binding_mode_once.get()
+        {
+            let id = xkb_state.kb_state.id;
+            drop(xkb_state);
+            self.cancel_binding_mode_once();
+            xkb_state_rc = get_state();
+            xkb_state = xkb_state_rc.borrow_mut();
+            if id != xkb_state.kb_state.id {
+                return;
+            }
         }
         self.state.for_each_seat_tester(|t| {
             t.send_key(self.id, time_usec, key, key_state);
@@ -677,6 +817,9 @@ impl WlSeatGlobal {
             }
             forward = self.forward.get();
         }
+        if global_shortcut {
+            forward = false;
+        }
         if forward {
             match &input_method_grab {
                 Some(g) => g.on_key(time_usec, key, state, &xkb_state.kb_state),
@@ -702,6 +845,9 @@ impl WlSeatGlobal {
         }
         drop(xkb_state);
         self.latest_kb_state.set(xkb_state_rc);
+        if new_mods {
+            focus_history::on_mods_changed(&self.state, self);
+        }
     }
 }
 
@@ -780,6 +926,18 @@ impl WlSeatGlobal {
         })
     }
 
+    fn for_each_touch<C>(&self, ver: Version, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<WlTouch>),
+    {
+        self.for_each_seat(ver, client, |seat| {
+            let touches = seat.touches.lock();
+            for touch in touches.values() {
+                f(touch);
+            }
+        })
+    }
+
     pub fn for_each_data_device<C>(&self, ver: Version, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<WlDataDevice>),
@@ -861,8 +1019,149 @@ impl WlSeatGlobal {
         self.apply_changes();
     }
 
+    /// Dispatches the key to the global shortcuts registered by privileged clients.
+    ///
+    /// Returns whether the key press has been consumed.
+    fn handle_global_shortcuts(
+        &self,
+        time_usec: u64,
+        key: u32,
+        state: u32,
+        mods: u32,
+        syms: &[u32],
+        is_modifier: bool,
+    ) -> bool {
+        if state == wl_keyboard::RELEASED {
+            for shortcuts in self.global_shortcuts.lock().values() {
+                shortcuts.key_released(time_usec, key);
+            }
+            return false;
+        }
+        if !is_modifier {
+            if let Some(&sym) = syms.first() {
+                if let Some(recorder) = self.shortcut_recorder.take() {
+                    recorder.send_recorded(mods, sym);
+                    return true;
+                }
+            }
+        }
+        let mut consumed = false;
+        for shortcuts in self.global_shortcuts.lock().values() {
+            consumed |= shortcuts.key_pressed(time_usec, key, mods, syms);
+        }
+        consumed
+    }
+
     pub fn clear_shortcuts(&self) {
         self.shortcuts.borrow_mut().clear();
+        self.pointer_shortcuts.borrow_mut().clear();
+    }
+
+    pub fn add_pointer_shortcut(&self, mods: Modifiers, trigger: PointerTrigger) {
+        self.pointer_shortcuts.borrow_mut().insert((mods, trigger));
+    }
+
+    pub fn remove_pointer_shortcut(&self, mods: Modifiers, trigger: PointerTrigger) {
+        self.pointer_shortcuts.borrow_mut().remove(&(mods, trigger));
+    }
+
+    pub fn shortcut_mods(&self) -> Modifiers {
+        let mods = self.latest_kb_state.get().borrow().mods.mods_effective;
+        Modifiers(mods & !(CAPS.0 | NUM.0))
+    }
+
+    fn has_pointer_shortcut<F>(&self, mods: Modifiers, mut f: F) -> bool
+    where
+        F: FnMut(&PointerTrigger) -> bool,
+    {
+        if self.state.lock.locked.get() {
+            return false;
+        }
+        self.pointer_shortcuts
+            .borrow()
+            .iter()
+            .any(|(m, t)| *m == mods && f(t))
+    }
+
+    /// Invokes the pointer shortcut, if it exists.
+    ///
+    /// Returns whether the shortcut exists.
+    pub(super) fn invoke_pointer_shortcut(&self, mods: Modifiers, trigger: PointerTrigger) -> bool {
+        if !self.has_pointer_shortcut(mods, |t| *t == trigger) {
+            return false;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.invoke_pointer_shortcut(self.id, mods, trigger);
+        }
+        true
+    }
+
+    /// Returns the active modifiers if there is a gesture shortcut matching the predicate.
+    pub(super) fn gesture_shortcut_mods<F>(&self, f: F) -> Option<Modifiers>
+    where
+        F: FnMut(&PointerTrigger) -> bool,
+    {
+        let mods = self.shortcut_mods();
+        self.has_pointer_shortcut(mods, f).then_some(mods)
+    }
+
+    /// Invokes the scroll shortcuts for the scroll distance in the frame.
+    ///
+    /// Pixel distances are accumulated into steps of `px_per_step` pixels. Axes that are
+    /// bound to a shortcut are removed from the frame.
+    ///
+    /// Returns whether the frame was consumed entirely.
+    pub(super) fn invoke_scroll_shortcuts(&self, scroll: &PendingScroll, px_per_step: f64) -> bool {
+        let mods = self.shortcut_mods();
+        let mut consumed = false;
+        let mut remaining = false;
+        let axes = [
+            (ScrollAxis::Vertical, JayDirection::Up, JayDirection::Down),
+            (
+                ScrollAxis::Horizontal,
+                JayDirection::Left,
+                JayDirection::Right,
+            ),
+        ];
+        for (axis, neg, pos) in axes {
+            let idx = axis as usize;
+            let acc = &self.scroll_shortcut_v120[idx];
+            let v120 = scroll.v120[idx].get();
+            let px = scroll.px[idx].get();
+            let dist = match (v120, px) {
+                (Some(v120), _) => v120 as f64,
+                (_, Some(px)) if px_per_step > 0.0 => px.to_f64() / px_per_step * AXIS_120 as f64,
+                _ => 0.0,
+            };
+            if dist == 0.0 {
+                if scroll.stop[idx].get() {
+                    acc.set(0.0);
+                }
+                remaining |= v120.is_some() || px.is_some() || scroll.stop[idx].get();
+                continue;
+            }
+            let trigger = PointerTrigger::Scroll(if dist < 0.0 { neg } else { pos });
+            if !self.has_pointer_shortcut(mods, |t| *t == trigger) {
+                acc.set(0.0);
+                remaining = true;
+                continue;
+            }
+            consumed = true;
+            scroll.v120[idx].take();
+            scroll.px[idx].take();
+            scroll.stop[idx].take();
+            let mut acc_v120 = acc.get();
+            if (acc_v120 < 0.0) != (dist < 0.0) {
+                acc_v120 = 0.0;
+            }
+            acc_v120 += dist;
+            while acc_v120.abs() >= AXIS_120 as f64 {
+                acc_v120 -= AXIS_120 as f64 * acc_v120.signum();
+                self.invoke_pointer_shortcut(mods, trigger);
+            }
+            acc.set(acc_v120);
+        }
+        consumed && !remaining
     }
 
     pub fn add_shortcut(&self, mod_mask: Modifiers, mods: Modifiers, keysym: KeySym) {
@@ -1123,6 +1422,9 @@ impl WlSeatGlobal {
 // Dnd callbacks
 impl WlSeatGlobal {
     pub fn dnd_surface_leave(&self, surface: &WlSurface, dnd: &Dnd) {
+        if surface.client.is_xwayland && dnd.src.is_some() {
+            self.for_each_x_data_device(|dd| dd.send_dnd_leave());
+        }
         if dnd.src.is_some() || surface.client.id == dnd.client.id {
             self.for_each_data_device(Version::ALL, surface.client.id, |dd| {
                 dd.send_leave();
@@ -1135,6 +1437,9 @@ impl WlSeatGlobal {
     }
 
     pub fn dnd_surface_drop(&self, surface: &WlSurface, dnd: &Dnd) {
+        if surface.client.is_xwayland && dnd.src.is_some() {
+            self.for_each_x_data_device(|dd| dd.send_dnd_drop());
+        }
         if dnd.src.is_some() || surface.client.id == dnd.client.id {
             self.for_each_data_device(Version::ALL, surface.client.id, |dd| {
                 dd.send_drop();
@@ -1152,7 +1457,9 @@ impl WlSeatGlobal {
         serial: u32,
     ) {
         if let Some(src) = &dnd.src {
-            if !surface.client.is_xwayland {
+            if surface.client.is_xwayland {
+                self.for_each_x_data_device(|dd| src.clone().offer_to_x(dd));
+            } else {
                 src.clone().offer_to_regular_client(&surface.client);
             }
             src.for_each_data_offer(|offer| {
@@ -1175,6 +1482,9 @@ impl WlSeatGlobal {
         x: Fixed,
         y: Fixed,
     ) {
+        if surface.client.is_xwayland && dnd.src.is_some() {
+            self.for_each_x_data_device(|dd| dd.send_dnd_motion(x, y));
+        }
         if dnd.src.is_some() || surface.client.id == dnd.client.id {
             self.for_each_data_device(Version::ALL, surface.client.id, |dd| {
                 dd.send_motion(time_usec, x, y);
@@ -1256,3 +1566,54 @@ impl WlSeatGlobal {
             })
     }
 }
+
+// Touch callbacks
+impl WlSeatGlobal {
+    pub fn touch_down_surface(
+        self: &Rc<Self>,
+        surface: &Rc<WlSurface>,
+        time_usec: u64,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(Version::ALL, surface.client.id, |t| {
+            t.send_down(serial, time, surface.id, id, x, y)
+        });
+        if let Some(node) = surface.get_focus_node(self.id) {
+            self.focus_node(node);
+        }
+    }
+
+    pub fn touch_up_surface(&self, surface: &WlSurface, time_usec: u64, id: i32) {
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(Version::ALL, surface.client.id, |t| {
+            t.send_up(serial, time, id)
+        });
+    }
+
+    pub fn touch_motion_surface(
+        &self,
+        surface: &WlSurface,
+        time_usec: u64,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(Version::ALL, surface.client.id, |t| {
+            t.send_motion(time, id, x, y)
+        });
+    }
+
+    pub fn touch_frame_surface(&self, surface: &WlSurface) {
+        self.for_each_touch(Version::ALL, surface.client.id, |t| t.send_frame());
+    }
+
+    pub fn touch_cancel_surface(&self, surface: &WlSurface) {
+        self.for_each_touch(Version::ALL, surface.client.id, |t| t.send_cancel());
+    }
+}
@@ -3,7 +3,12 @@ use {
         fixed::Fixed, ifs::wl_seat::WlSeatGlobal, time::now_usec, tree::Node,
         utils::clonecell::CloneCell,
     },
-    std::rc::Rc,
+    jay_config::{
+        input::pointer::{PinchDirection, PointerTrigger},
+        keyboard::mods::Modifiers,
+        Direction,
+    },
+    std::{cell::Cell, rc::Rc},
 };
 
 pub struct GestureOwnerHolder {
@@ -146,6 +151,18 @@ impl GestureOwner for NoGesture {
     }
 
     fn swipe_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        let mods = seat.gesture_shortcut_mods(
+            |t| matches!(t, PointerTrigger::Swipe { fingers, .. } if *fingers == finger_count),
+        );
+        if let Some(mods) = mods {
+            seat.gesture_owner.owner.set(Rc::new(BoundSwipeGesture {
+                mods,
+                fingers: finger_count,
+                dx: Cell::new(0.0),
+                dy: Cell::new(0.0),
+            }));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -155,6 +172,17 @@ impl GestureOwner for NoGesture {
     }
 
     fn pinch_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        let mods = seat.gesture_shortcut_mods(
+            |t| matches!(t, PointerTrigger::Pinch { fingers, .. } if *fingers == finger_count),
+        );
+        if let Some(mods) = mods {
+            seat.gesture_owner.owner.set(Rc::new(BoundPinchGesture {
+                mods,
+                fingers: finger_count,
+                scale: Cell::new(1.0),
+            }));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -164,6 +192,16 @@ impl GestureOwner for NoGesture {
     }
 
     fn hold_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        let mods = seat.gesture_shortcut_mods(
+            |t| matches!(t, PointerTrigger::Hold { fingers } if *fingers == finger_count),
+        );
+        if let Some(mods) = mods {
+            seat.gesture_owner.owner.set(Rc::new(BoundHoldGesture {
+                mods,
+                fingers: finger_count,
+            }));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -237,3 +275,110 @@ impl GestureOwner for HoldGesture {
         seat.gesture_owner.set_default_owner();
     }
 }
+
+struct BoundSwipeGesture {
+    mods: Modifiers,
+    fingers: u32,
+    dx: Cell<f64>,
+    dy: Cell<f64>,
+}
+
+impl GestureOwner for BoundSwipeGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.gesture_owner.set_default_owner();
+    }
+
+    fn swipe_update(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, dx: Fixed, dy: Fixed) {
+        self.dx.set(self.dx.get() + dx.to_f64());
+        self.dy.set(self.dy.get() + dy.to_f64());
+    }
+
+    fn swipe_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        if cancelled {
+            return;
+        }
+        let (dx, dy) = (self.dx.get(), self.dy.get());
+        let direction = if dx.abs() > dy.abs() {
+            match dx > 0.0 {
+                true => Direction::Right,
+                false => Direction::Left,
+            }
+        } else if dy != 0.0 {
+            match dy > 0.0 {
+                true => Direction::Down,
+                false => Direction::Up,
+            }
+        } else {
+            return;
+        };
+        let trigger = PointerTrigger::Swipe {
+            fingers: self.fingers,
+            direction,
+        };
+        seat.invoke_pointer_shortcut(self.mods, trigger);
+    }
+}
+
+struct BoundPinchGesture {
+    mods: Modifiers,
+    fingers: u32,
+    scale: Cell<f64>,
+}
+
+impl GestureOwner for BoundPinchGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.gesture_owner.set_default_owner();
+    }
+
+    fn pinch_update(
+        &self,
+        _seat: &Rc<WlSeatGlobal>,
+        _time_usec: u64,
+        _dx: Fixed,
+        _dy: Fixed,
+        scale: Fixed,
+        _rotation: Fixed,
+    ) {
+        self.scale.set(scale.to_f64());
+    }
+
+    fn pinch_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        let scale = self.scale.get();
+        if cancelled || scale == 1.0 {
+            return;
+        }
+        let direction = match scale < 1.0 {
+            true => PinchDirection::In,
+            false => PinchDirection::Out,
+        };
+        let trigger = PointerTrigger::Pinch {
+            fingers: self.fingers,
+            direction,
+        };
+        seat.invoke_pointer_shortcut(self.mods, trigger);
+    }
+}
+
+struct BoundHoldGesture {
+    mods: Modifiers,
+    fingers: u32,
+}
+
+impl GestureOwner for BoundHoldGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.gesture_owner.set_default_owner();
+    }
+
+    fn hold_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        if cancelled {
+            return;
+        }
+        let trigger = PointerTrigger::Hold {
+            fingers: self.fingers,
+        };
+        seat.invoke_pointer_shortcut(self.mods, trigger);
+    }
+}
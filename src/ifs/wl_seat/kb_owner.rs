@@ -1,6 +1,10 @@
 use {
     crate::{
-        ifs::wl_seat::WlSeatGlobal, tree::Node, utils::clonecell::CloneCell,
+        focus_history::add_to_focus_history,
+        ifs::wl_seat::WlSeatGlobal,
+        json_ipc::{self, messages::WindowChange},
+        tree::Node,
+        utils::clonecell::CloneCell,
         xwayland::XWaylandEvent,
     },
     std::rc::Rc,
@@ -79,6 +83,17 @@ impl KbOwner for DefaultKbOwner {
         // log::info!("focus {}", node.node_id());
         node.clone().node_on_focus(seat);
         seat.keyboard_node.set(node.clone());
+        if let Some(tl) = node.clone().node_toplevel() {
+            if old.node_toplevel().map(|o| o.node_id()) != Some(tl.node_id()) {
+                json_ipc::window_event(
+                    &seat.state,
+                    WindowChange::Focus,
+                    tl.tl_as_node(),
+                    tl.tl_data(),
+                );
+            }
+        }
+        add_to_focus_history(seat, node);
         seat.tablet_on_keyboard_node_change();
     }
 }
@@ -85,6 +85,9 @@ impl PointerOwnerHolder {
         seat.state.for_each_seat_tester(|t| {
             t.send_axis(seat.id, time_usec, &pending);
         });
+        if seat.invoke_scroll_shortcuts(&pending, dev.px_per_scroll_wheel.get()) {
+            return;
+        }
         if let Some(node) = self.owner.get().axis_node(seat) {
             node.node_on_axis_event(seat, &pending);
         }
@@ -413,6 +416,7 @@ impl<T: SimplePointerOwnerUsecase> PointerOwner for SimpleGrabPointerOwner<T> {
                 if self.buttons.is_empty() {
                     self.node.node_seat_state().remove_pointer_grab(seat);
                     // log::info!("button");
+                    seat.end_x_dnd();
                     self.usecase.release_grab(seat);
                     seat.tree_changed.trigger();
                 }
@@ -439,6 +443,9 @@ impl<T: SimplePointerOwnerUsecase> PointerOwner for SimpleGrabPointerOwner<T> {
         self.node
             .clone()
             .node_on_pointer_motion(seat, x.apply_fract(x_int), y.apply_fract(y_int));
+        if seat.x_dnd_active.get() {
+            seat.update_x_dnd_target();
+        }
     }
 
     fn start_drag(
@@ -455,6 +462,7 @@ impl<T: SimplePointerOwnerUsecase> PointerOwner for SimpleGrabPointerOwner<T> {
 
     fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
         self.node.node_seat_state().remove_pointer_grab(seat);
+        seat.end_x_dnd();
         seat.pointer_owner.set_default_pointer_owner(seat);
     }
 }
@@ -0,0 +1,228 @@
+use {
+    crate::{
+        fixed::Fixed,
+        ifs::wl_seat::WlSeatGlobal,
+        tree::{FindTreeUsecase, FoundNode, Node, NodeId},
+        utils::{clonecell::CloneCell, smallmap::SmallMap},
+    },
+    std::rc::Rc,
+};
+
+pub struct TouchOwnerHolder {
+    default: Rc<DefaultTouchOwner>,
+    owner: CloneCell<Rc<dyn TouchOwner>>,
+}
+
+struct DefaultTouchOwner;
+
+struct GrabTouchOwner {
+    nodes: SmallMap<i32, Rc<dyn Node>, 10>,
+    pending_frame: SmallMap<NodeId, Rc<dyn Node>, 10>,
+}
+
+impl Default for TouchOwnerHolder {
+    fn default() -> Self {
+        let default = Rc::new(DefaultTouchOwner);
+        Self {
+            owner: CloneCell::new(default.clone()),
+            default,
+        }
+    }
+}
+
+impl TouchOwnerHolder {
+    pub fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        self.owner.get().down(seat, time_usec, id, x, y)
+    }
+
+    pub fn up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
+        self.owner.get().up(seat, time_usec, id)
+    }
+
+    pub fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        self.owner.get().motion(seat, time_usec, id, x, y)
+    }
+
+    pub fn frame(&self, seat: &Rc<WlSeatGlobal>) {
+        self.owner.get().frame(seat)
+    }
+
+    pub fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        self.owner.get().cancel(seat)
+    }
+
+    pub fn cancel_point(&self, seat: &Rc<WlSeatGlobal>, id: i32) {
+        self.owner.get().cancel_point(seat, id)
+    }
+
+    pub fn grab_node_removed(&self, seat: &Rc<WlSeatGlobal>, node: NodeId) {
+        self.owner.get().grab_node_removed(seat, node)
+    }
+
+    pub fn clear(&self) {
+        self.set_default_owner();
+    }
+
+    fn set_default_owner(&self) {
+        self.owner.set(self.default.clone());
+    }
+}
+
+trait TouchOwner {
+    fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed);
+
+    fn up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
+        let _ = seat;
+        let _ = time_usec;
+        let _ = id;
+    }
+
+    fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        let _ = seat;
+        let _ = time_usec;
+        let _ = id;
+        let _ = x;
+        let _ = y;
+    }
+
+    fn frame(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
+
+    fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
+
+    fn cancel_point(&self, seat: &Rc<WlSeatGlobal>, id: i32) {
+        let _ = seat;
+        let _ = id;
+    }
+
+    fn grab_node_removed(&self, seat: &Rc<WlSeatGlobal>, node: NodeId) {
+        let _ = seat;
+        let _ = node;
+    }
+}
+
+fn find_node_at(seat: &Rc<WlSeatGlobal>, x: Fixed, y: Fixed) -> Option<Rc<dyn Node>> {
+    let x_int = x.round_down();
+    let y_int = y.round_down();
+    let tree = &mut *seat.found_tree.borrow_mut();
+    tree.push(FoundNode {
+        node: seat.state.root.clone(),
+        x: x_int,
+        y: y_int,
+    });
+    seat.state
+        .root
+        .node_find_tree_at(x_int, y_int, tree, FindTreeUsecase::None);
+    let node = tree.last().map(|n| n.node.clone());
+    tree.clear();
+    node
+}
+
+impl TouchOwner for DefaultTouchOwner {
+    fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        let owner = Rc::new(GrabTouchOwner {
+            nodes: Default::default(),
+            pending_frame: Default::default(),
+        });
+        seat.touch_owner.owner.set(owner.clone());
+        owner.down(seat, time_usec, id, x, y);
+    }
+}
+
+impl GrabTouchOwner {
+    fn has_points_on(&self, node: NodeId) -> bool {
+        self.nodes.iter().any(|(_, n)| n.node_id() == node)
+    }
+
+    fn remove_points_on(&self, node: NodeId) {
+        for (id, n) in self.nodes.take() {
+            if n.node_id() != node {
+                self.nodes.insert(id, n);
+            }
+        }
+        self.pending_frame.remove(&node);
+    }
+
+    fn maybe_revert(&self, seat: &Rc<WlSeatGlobal>) {
+        if self.nodes.is_empty() && self.pending_frame.is_empty() {
+            seat.touch_owner.set_default_owner();
+        }
+    }
+}
+
+impl TouchOwner for GrabTouchOwner {
+    fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        if self.nodes.contains(&id) {
+            return;
+        }
+        let Some(node) = find_node_at(seat, x, y) else {
+            self.maybe_revert(seat);
+            return;
+        };
+        node.node_seat_state().add_touch_grab(seat);
+        self.nodes.insert(id, node.clone());
+        self.pending_frame.insert(node.node_id(), node.clone());
+        let pos = node.node_absolute_position();
+        node.node_on_touch_down(seat, time_usec, id, x - pos.x1(), y - pos.y1());
+    }
+
+    fn up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
+        let Some(node) = self.nodes.remove(&id) else {
+            return;
+        };
+        self.pending_frame.insert(node.node_id(), node.clone());
+        node.node_on_touch_up(seat, time_usec, id);
+        if !self.has_points_on(node.node_id()) {
+            node.node_seat_state().remove_touch_grab(seat);
+        }
+    }
+
+    fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
+        self.pending_frame.insert(node.node_id(), node.clone());
+        let pos = node.node_absolute_position();
+        node.node_on_touch_motion(seat, time_usec, id, x - pos.x1(), y - pos.y1());
+    }
+
+    fn frame(&self, seat: &Rc<WlSeatGlobal>) {
+        for (_, node) in self.pending_frame.take() {
+            node.node_on_touch_frame(seat);
+        }
+        self.maybe_revert(seat);
+    }
+
+    fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        self.pending_frame.clear();
+        while let Some((_, node)) = self.nodes.pop() {
+            self.remove_points_on(node.node_id());
+            node.node_on_touch_cancel(seat);
+            node.node_seat_state().remove_touch_grab(seat);
+        }
+        seat.touch_owner.set_default_owner();
+    }
+
+    fn cancel_point(&self, seat: &Rc<WlSeatGlobal>, id: i32) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
+        // wl_touch.cancel cancels all touch points of the client's surface
+        self.remove_points_on(node.node_id());
+        node.node_on_touch_cancel(seat);
+        node.node_seat_state().remove_touch_grab(seat);
+        self.maybe_revert(seat);
+    }
+
+    fn grab_node_removed(&self, seat: &Rc<WlSeatGlobal>, node: NodeId) {
+        let Some(n) = self.nodes.iter().find(|(_, n)| n.node_id() == node) else {
+            return;
+        };
+        self.remove_points_on(node);
+        n.1.node_on_touch_cancel(seat);
+        self.maybe_revert(seat);
+    }
+}
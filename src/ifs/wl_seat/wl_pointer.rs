@@ -87,8 +87,9 @@ impl WlPointer {
         }
     }
 
-    pub fn send_enter(&self, serial: u32, surface: WlSurfaceId, x: Fixed, y: Fixed) {
+    pub fn send_enter(&self, serial: u32, surface: WlSurfaceId, mut x: Fixed, mut y: Fixed) {
         self.last_motion.set((x, y));
+        logical_to_client_wire_scale!(self.seat.client, x, y);
         self.seat.client.event(Enter {
             self_id: self.id,
             serial,
@@ -106,10 +107,11 @@ impl WlPointer {
         })
     }
 
-    pub fn send_motion(&self, time: u32, x: Fixed, y: Fixed) {
+    pub fn send_motion(&self, time: u32, mut x: Fixed, mut y: Fixed) {
         if self.last_motion.replace((x, y)) == (x, y) {
             return;
         }
+        logical_to_client_wire_scale!(self.seat.client, x, y);
         self.seat.client.event(Motion {
             self_id: self.id,
             time,
@@ -136,7 +138,8 @@ impl WlPointer {
         })
     }
 
-    pub fn send_axis(&self, time: u32, axis: u32, value: Fixed) {
+    pub fn send_axis(&self, time: u32, axis: u32, mut value: Fixed) {
+        logical_to_client_wire_scale!(self.seat.client, value);
         self.seat.client.event(Axis {
             self_id: self.id,
             time,
@@ -193,7 +196,9 @@ impl WlPointerRequestHandler for WlPointer {
         if req.surface.is_some() {
             let surface = self.seat.client.lookup(req.surface)?;
             let cursor = surface.get_cursor(&self.seat.global.pointer_cursor)?;
-            cursor.set_hotspot(req.hotspot_x, req.hotspot_y);
+            let (mut x, mut y) = (req.hotspot_x, req.hotspot_y);
+            client_wire_scale_to_logical!(self.seat.client, x, y);
+            cursor.set_hotspot(x, y);
             cursor_opt = Some(cursor as Rc<dyn Cursor>);
         }
         let pointer_node = match self.seat.global.pointer_node() {
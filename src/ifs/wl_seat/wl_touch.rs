@@ -1,25 +1,16 @@
 use {
     crate::{
         client::ClientError,
+        fixed::Fixed,
         ifs::wl_seat::WlSeat,
         leaks::Tracker,
         object::Object,
-        wire::{wl_touch::*, WlTouchId},
+        wire::{wl_touch::*, WlSurfaceId, WlTouchId},
     },
     std::rc::Rc,
     thiserror::Error,
 };
 
-#[allow(dead_code)]
-const DOWN: u32 = 0;
-#[allow(dead_code)]
-const UP: u32 = 1;
-#[allow(dead_code)]
-const MOTION: u32 = 2;
-#[allow(dead_code)]
-const FRAME: u32 = 3;
-#[allow(dead_code)]
-const CANCEL: u32 = 4;
 #[allow(dead_code)]
 const SHAPE: u32 = 5;
 #[allow(dead_code)]
@@ -27,7 +18,7 @@ const ORIENTATION: u32 = 6;
 
 pub struct WlTouch {
     id: WlTouchId,
-    seat: Rc<WlSeat>,
+    pub seat: Rc<WlSeat>,
     pub tracker: Tracker<Self>,
 }
 
@@ -39,12 +30,62 @@ impl WlTouch {
             tracker: Default::default(),
         }
     }
+
+    pub fn send_down(
+        &self,
+        serial: u32,
+        time: u32,
+        surface: WlSurfaceId,
+        id: i32,
+        mut x: Fixed,
+        mut y: Fixed,
+    ) {
+        logical_to_client_wire_scale!(self.seat.client, x, y);
+        self.seat.client.event(Down {
+            self_id: self.id,
+            serial,
+            time,
+            surface,
+            id,
+            x,
+            y,
+        })
+    }
+
+    pub fn send_up(&self, serial: u32, time: u32, id: i32) {
+        self.seat.client.event(Up {
+            self_id: self.id,
+            serial,
+            time,
+            id,
+        })
+    }
+
+    pub fn send_motion(&self, time: u32, id: i32, mut x: Fixed, mut y: Fixed) {
+        logical_to_client_wire_scale!(self.seat.client, x, y);
+        self.seat.client.event(Motion {
+            self_id: self.id,
+            time,
+            id,
+            x,
+            y,
+        })
+    }
+
+    pub fn send_frame(&self) {
+        self.seat.client.event(Frame { self_id: self.id })
+    }
+
+    pub fn send_cancel(&self) {
+        self.seat.client.event(Cancel { self_id: self.id })
+    }
 }
 
 impl WlTouchRequestHandler for WlTouch {
     type Error = WlTouchError;
 
     fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.seat.touches.remove(&self.id);
         self.seat.client.remove_obj(self)?;
         Ok(())
     }
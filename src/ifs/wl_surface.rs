@@ -282,7 +282,7 @@ pub struct WlSurface {
     pub constraints: SmallMap<SeatId, Rc<SeatConstraint>, 1>,
     xwayland_serial: Cell<Option<u64>>,
     tearing_control: CloneCell<Option<Rc<WpTearingControlV1>>>,
-    tearing: Cell<bool>,
+    pub tearing: Cell<bool>,
     version: Version,
     pub has_content_type_manager: Cell<bool>,
     pub content_type: Cell<Option<ContentType>>,
@@ -398,6 +398,8 @@ struct PendingState {
     frame_request: Vec<Rc<WlCallback>>,
     damage_full: bool,
     damage: Vec<Rect>,
+    surface_damage_full: bool,
+    surface_damage: Vec<Rect>,
     presentation_feedback: Vec<Rc<WpPresentationFeedback>>,
     src_rect: Option<Option<[Fixed; 4]>>,
     dst_size: Option<Option<(i32, i32)>>,
@@ -421,6 +423,18 @@ struct AttachedSubsurfaceState {
 }
 
 impl PendingState {
+    fn add_surface_damage(&mut self, rect: Rect) {
+        if self.surface_damage_full {
+            return;
+        }
+        if self.surface_damage.len() >= MAX_DAMAGE {
+            self.surface_damage_full = true;
+            self.surface_damage.clear();
+        } else {
+            self.surface_damage.push(rect);
+        }
+    }
+
     fn merge(&mut self, next: &mut Self, client: &Rc<Client>) {
         // discard state
 
@@ -477,6 +491,16 @@ impl PendingState {
                 self.damage.append(&mut next.damage);
             }
         }
+        if !self.surface_damage_full {
+            if mem::take(&mut next.surface_damage_full)
+                || self.surface_damage.len() + next.surface_damage.len() > MAX_DAMAGE
+            {
+                self.surface_damage_full = true;
+                self.surface_damage.clear();
+            } else {
+                self.surface_damage.append(&mut next.surface_damage);
+            }
+        }
         mem::swap(
             &mut self.presentation_feedback,
             &mut next.presentation_feedback,
@@ -866,10 +890,26 @@ impl WlSurfaceRequestHandler for WlSurface {
         Ok(())
     }
 
-    fn damage(&self, _req: Damage, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn damage(&self, req: Damage, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         let pending = &mut *self.pending.borrow_mut();
         pending.damage.clear();
         pending.damage_full = true;
+        let Some(mut rect) = Rect::new_sized(req.x, req.y, req.width, req.height) else {
+            pending.surface_damage_full = true;
+            return Ok(());
+        };
+        if self.client.is_xwayland {
+            if let Some(scale) = self.client.wire_scale.get() {
+                // Xwayland sends surface damage in wire pixels. Round outwards
+                // so that partially damaged logical pixels are included.
+                let x1 = rect.x1().div_euclid(scale);
+                let y1 = rect.y1().div_euclid(scale);
+                let x2 = rect.x2().saturating_add(scale - 1).div_euclid(scale);
+                let y2 = rect.y2().saturating_add(scale - 1).div_euclid(scale);
+                rect = Rect::new(x1, y1, x2, y2).unwrap();
+            }
+        }
+        pending.add_surface_damage(rect);
         Ok(())
     }
 
@@ -937,17 +977,37 @@ impl WlSurfaceRequestHandler for WlSurface {
 
     fn damage_buffer(&self, req: DamageBuffer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         let pending = &mut *self.pending.borrow_mut();
+        let Some(rect) = Rect::new_sized(req.x, req.y, req.width, req.height) else {
+            pending.damage.clear();
+            pending.damage_full = true;
+            pending.surface_damage_full = true;
+            return Ok(());
+        };
         if !pending.damage_full {
             if pending.damage.len() >= MAX_DAMAGE || self.shm_texture.is_none() {
                 pending.damage.clear();
                 pending.damage_full = true;
             } else {
-                let Some(rect) = Rect::new_sized(req.x, req.y, req.width, req.height) else {
-                    return Err(WlSurfaceError::InvalidRect);
-                };
                 pending.damage.push(rect);
             }
         }
+        let scale = pending.scale.unwrap_or(self.buffer_scale.get());
+        let transform = pending.transform.unwrap_or(self.buffer_transform.get());
+        let has_viewport = self.src_rect.is_some() || self.dst_size.is_some();
+        if transform != Transform::None || has_viewport {
+            pending.surface_damage_full = true;
+        } else {
+            let rect = Rect::new(
+                rect.x1() / scale,
+                rect.y1() / scale,
+                rect.x2().saturating_add(scale - 1) / scale,
+                rect.y2().saturating_add(scale - 1) / scale,
+            );
+            match rect {
+                Some(rect) => pending.add_surface_damage(rect),
+                None => pending.surface_damage_full = true,
+            }
+        }
         Ok(())
     }
 
@@ -959,13 +1019,19 @@ impl WlSurfaceRequestHandler for WlSurface {
 
 impl WlSurface {
     fn apply_state(self: &Rc<Self>, pending: &mut PendingState) -> Result<(), WlSurfaceError> {
+        let restacked = pending.subsurfaces.values().any(|ss| ss.pending.restacks());
         for (_, pending) in &mut pending.subsurfaces {
             pending.subsurface.apply_state(&mut pending.pending)?;
         }
         if self.destroyed.get() {
             return Ok(());
         }
+        let old_buffer_pos = self.buffer_abs_pos.get();
         self.ext.get().before_apply_commit(pending)?;
+        if self.client.is_xwayland {
+            let scale = self.client.wire_scale.get().unwrap_or(1);
+            pending.scale = Some(scale).filter(|&s| s != self.buffer_scale.get());
+        }
         let mut scale_changed = false;
         if let Some(scale) = pending.scale.take() {
             scale_changed = true;
@@ -1164,7 +1230,8 @@ impl WlSurface {
                 .queue
                 .push(XWaylandEvent::SurfaceSerialAssigned(self.id));
         }
-        if self.need_extents_update.get() {
+        let extents_changed = self.need_extents_update.get();
+        if extents_changed {
             self.calculate_extents();
         }
         if buffer_changed || transform_changed || alpha_changed {
@@ -1174,10 +1241,41 @@ impl WlSurface {
             }
         }
         self.ext.get().after_apply_commit();
-        self.client.state.damage();
+        let full_damage = restacked || extents_changed || transform_changed || alpha_changed;
+        self.damage_commit(pending, old_buffer_pos, full_damage);
         Ok(())
     }
 
+    fn damage_commit(&self, pending: &mut PendingState, old_buffer_pos: Rect, full: bool) {
+        let surface_damage_full = mem::take(&mut pending.surface_damage_full);
+        let state = &self.client.state;
+        if !self.visible.get() || self.cursors.is_not_empty() {
+            pending.surface_damage.clear();
+            state.damage();
+            return;
+        }
+        let pos = self.buffer_abs_pos.get();
+        if full || surface_damage_full || old_buffer_pos != pos {
+            pending.surface_damage.clear();
+            state.damage_rect(old_buffer_pos);
+            state.damage_rect(pos);
+        } else {
+            let bounds = pos.at_point(0, 0);
+            for rect in pending.surface_damage.drain(..) {
+                let rect = rect.intersect(bounds).move_(pos.x1(), pos.y1());
+                state.damage_rect(rect);
+            }
+        }
+        // Frame callbacks are dispatched during the next present even if nothing was
+        // damaged.
+        self.output
+            .get()
+            .global
+            .connector
+            .connector
+            .damage_rect(Rect::default());
+    }
+
     fn verify_explicit_sync(&self, pending: &mut PendingState) -> Result<(), WlSurfaceError> {
         pending.explicit_sync = self.sync_obj_surface.is_some();
         if !pending.explicit_sync {
@@ -1578,6 +1676,40 @@ impl Node for WlSurface {
         seat.hold_end_surface(self, time_usec, cancelled)
     }
 
+    fn node_on_touch_down(
+        self: Rc<Self>,
+        seat: &Rc<WlSeatGlobal>,
+        time_usec: u64,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        seat.touch_down_surface(&self, time_usec, id, x, y)
+    }
+
+    fn node_on_touch_up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
+        seat.touch_up_surface(self, time_usec, id)
+    }
+
+    fn node_on_touch_motion(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        time_usec: u64,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        seat.touch_motion_surface(self, time_usec, id, x, y)
+    }
+
+    fn node_on_touch_frame(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.touch_frame_surface(self)
+    }
+
+    fn node_on_touch_cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.touch_cancel_surface(self)
+    }
+
     fn node_on_tablet_pad_enter(&self, pad: &Rc<TabletPad>) {
         pad.surface_enter(self);
     }
@@ -1704,8 +1836,6 @@ pub enum WlSurfaceError {
     MissingSyncPoints,
     #[error("No buffer is attached but acquire or release point is set")]
     UnexpectedSyncPoints,
-    #[error("The supplied region is invalid")]
-    InvalidRect,
 }
 efrom!(WlSurfaceError, ClientError);
 efrom!(WlSurfaceError, XdgSurfaceError);
@@ -55,6 +55,10 @@ pub struct PendingSubsurfaceData {
 }
 
 impl PendingSubsurfaceData {
+    pub fn restacks(&self) -> bool {
+        self.node.is_some()
+    }
+
     pub fn merge(&mut self, next: &mut Self, client: &Rc<Client>) {
         if let Some(mut new) = next.state.take() {
             match &mut self.state {
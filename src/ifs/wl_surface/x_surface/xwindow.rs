@@ -15,11 +15,13 @@ use {
             StackedNode, ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
         utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, linkedlist::LinkedNode},
+        window_rules::{self, WindowProperties},
         wire::WlSurfaceId,
         wire_xcon::CreateNotify,
         xwayland::XWaylandEvent,
     },
     bstr::BString,
+    jay_config::window::WindowType,
     std::{
         cell::{Cell, RefCell},
         ops::{Deref, Not},
@@ -139,13 +141,10 @@ pub struct Xwindow {
 
 impl XwindowData {
     pub fn new(state: &Rc<State>, event: &CreateNotify, client: &Rc<Client>) -> Self {
-        let extents = Rect::new_sized(
-            event.x as _,
-            event.y as _,
-            event.width as _,
-            event.height as _,
-        )
-        .unwrap();
+        let (mut x, mut y) = (event.x as i32, event.y as i32);
+        let (mut width, mut height) = (event.width as i32, event.height as i32);
+        client_wire_scale_to_logical!(client, x, y, width, height);
+        let extents = Rect::new_sized(x, y, width, height).unwrap();
         // log::info!("xwin {} new {:?} or {}", event.window, extents, event.override_redirect);
         Self {
             state: state.clone(),
@@ -268,16 +267,22 @@ impl Xwindow {
                     Some(self.data.state.root.stacked.add_last(self.clone()));
                 self.data.state.tree_changed();
             }
-            Change::Map if self.data.info.wants_floating.get() => {
-                let ws = self.data.state.float_map_ws();
-                let ext = self.data.info.pending_extents.get();
-                self.data
-                    .state
-                    .map_floating(self.clone(), ext.width(), ext.height(), &ws, None);
-                self.data.title_changed();
-            }
             Change::Map => {
-                self.data.state.map_tiled(self.clone());
+                let state = &self.data.state;
+                let wants_floating = self.data.info.wants_floating.get();
+                let ext = self.data.info.pending_extents.get();
+                if wants_floating {
+                    self.toplevel_data.float_width.set(ext.width());
+                    self.toplevel_data.float_height.set(ext.height());
+                }
+                window_rules::map_toplevel(state, self.clone(), wants_floating, || {
+                    if wants_floating {
+                        let ws = state.float_map_ws();
+                        state.map_floating(self.clone(), ext.width(), ext.height(), &ws, None);
+                    } else {
+                        state.map_tiled(self.clone());
+                    }
+                });
                 self.data.title_changed();
             }
         }
@@ -402,6 +407,14 @@ impl ToplevelNodeBase for Xwindow {
             .push(XWaylandEvent::Activate(self.data.clone()));
     }
 
+    fn tl_set_minimized(&self, minimized: bool) {
+        self.data
+            .state
+            .xwayland
+            .queue
+            .push(XWaylandEvent::SetMinimized(self.data.clone(), minimized));
+    }
+
     fn tl_focus_child(&self, _seat: SeatId) -> Option<Rc<dyn Node>> {
         Some(self.x.surface.clone())
     }
@@ -455,6 +468,21 @@ impl ToplevelNodeBase for Xwindow {
     fn tl_admits_children(&self) -> bool {
         false
     }
+
+    fn tl_window_properties(&self) -> Option<WindowProperties> {
+        let info = &self.data.info;
+        let string = |s: &RefCell<Option<BString>>| s.borrow().as_ref().map(|s| s.to_string());
+        Some(WindowProperties {
+            ty: WindowType::XWindow,
+            app_id: self.toplevel_data.app_id.borrow().clone(),
+            title: info.title.borrow().clone().unwrap_or_default(),
+            class: string(&info.class),
+            instance: string(&info.instance),
+            role: string(&info.role),
+            pid: info.pid.get().map(|pid| pid as i32),
+            sandbox_engine: None,
+        })
+    }
 }
 
 impl StackedNode for Xwindow {
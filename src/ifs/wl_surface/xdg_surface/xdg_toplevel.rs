@@ -20,6 +20,7 @@ use {
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
         },
         leaks::Tracker,
+        minimize,
         object::{Object, Version},
         rect::Rect,
         renderer::Renderer,
@@ -30,9 +31,11 @@ use {
             WorkspaceNode,
         },
         utils::{clonecell::CloneCell, hash_map_ext::HashMapExt},
+        window_rules::{self, WindowProperties},
         wire::{xdg_toplevel::*, XdgToplevelId},
     },
     ahash::{AHashMap, AHashSet},
+    jay_config::window::WindowType,
     num_derive::FromPrimitive,
     std::{
         cell::{Cell, RefCell},
@@ -73,8 +76,7 @@ const CAP_WINDOW_MENU: u32 = 1;
 #[allow(dead_code)]
 const CAP_MAXIMIZE: u32 = 2;
 const CAP_FULLSCREEN: u32 = 3;
-#[allow(dead_code)]
-const CAP_MINIMIZE: u32 = 4;
+pub const CAP_MINIMIZE: u32 = 4;
 
 pub const WM_CAPABILITIES_SINCE: Version = Version(5);
 pub const SUSPENDED_SINCE: Version = Version(6);
@@ -200,7 +202,7 @@ impl XdgToplevel {
     pub fn send_wm_capabilities(&self) {
         self.xdg.surface.client.event(WmCapabilities {
             self_id: self.id,
-            capabilities: &[CAP_FULLSCREEN],
+            capabilities: &[CAP_FULLSCREEN, CAP_MINIMIZE],
         })
     }
 }
@@ -244,15 +246,17 @@ impl XdgToplevelRequestHandler for XdgToplevel {
         Ok(())
     }
 
-    fn set_title(&self, req: SetTitle, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_title(&self, req: SetTitle, slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.toplevel_data.set_title(req.title);
         self.tl_title_changed();
+        window_rules::reevaluate(&self.state, slf.clone());
         Ok(())
     }
 
-    fn set_app_id(&self, req: SetAppId, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_app_id(&self, req: SetAppId, slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.toplevel_data.set_app_id(req.app_id);
         self.bugs.set(bugs::get(req.app_id));
+        window_rules::reevaluate(&self.state, slf.clone());
         Ok(())
     }
 
@@ -342,7 +346,8 @@ impl XdgToplevelRequestHandler for XdgToplevel {
         Ok(())
     }
 
-    fn set_minimized(&self, _req: SetMinimized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_minimized(&self, _req: SetMinimized, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        minimize::minimize(&self.state, slf.clone());
         Ok(())
     }
 }
@@ -424,10 +429,15 @@ impl XdgToplevel {
             }
             self.state.tree_changed();
         } else {
-            if let Some(parent) = self.parent.get() {
-                self.map_child(&parent, pos);
+            let parent = self.parent.get();
+            let map = || match &parent {
+                Some(parent) => self.map_child(parent, pos),
+                None => self.map_tiled(),
+            };
+            if pos.is_some() {
+                map();
             } else {
-                self.map_tiled();
+                window_rules::map_toplevel(&self.state, self.clone(), parent.is_some(), map);
             }
             self.extents_changed();
             if let Some(workspace) = self.xdg.workspace.get() {
@@ -649,6 +659,20 @@ impl ToplevelNodeBase for XdgToplevel {
     fn tl_admits_children(&self) -> bool {
         false
     }
+
+    fn tl_window_properties(&self) -> Option<WindowProperties> {
+        let client = &self.xdg.surface.client;
+        Some(WindowProperties {
+            ty: WindowType::XdgToplevel,
+            app_id: self.toplevel_data.app_id.borrow().clone(),
+            title: self.toplevel_data.title.borrow().clone(),
+            class: None,
+            instance: None,
+            role: None,
+            pid: Some(client.pid_info.pid),
+            sandbox_engine: client.sandbox_engine.clone(),
+        })
+    }
 }
 
 impl XdgSurfaceExt for XdgToplevel {
@@ -0,0 +1,123 @@
+use {
+    crate::{
+        client::{Client, ClientCaps, ClientError, CAP_GAMMA_CONTROL_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::zwlr_gamma_control_v1::ZwlrGammaControlV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_gamma_control_manager_v1::*, ZwlrGammaControlManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwlrGammaControlManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrGammaControlManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrGammaControlManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwlrGammaControlManagerV1Error> {
+        let mgr = Rc::new(ZwlrGammaControlManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, mgr);
+        client.add_client_obj(&mgr)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrGammaControlManagerV1Global,
+    ZwlrGammaControlManagerV1,
+    ZwlrGammaControlManagerV1Error
+);
+
+simple_add_global!(ZwlrGammaControlManagerV1Global);
+
+impl Global for ZwlrGammaControlManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_GAMMA_CONTROL_MANAGER
+    }
+}
+
+pub struct ZwlrGammaControlManagerV1 {
+    pub id: ZwlrGammaControlManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwlrGammaControlManagerV1RequestHandler for ZwlrGammaControlManagerV1 {
+    type Error = ZwlrGammaControlManagerV1Error;
+
+    fn get_gamma_control(&self, req: GetGammaControl, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let control = Rc::new(ZwlrGammaControlV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            connector: Default::default(),
+            size: Default::default(),
+            reader: Default::default(),
+        });
+        track!(self.client, control);
+        self.client.add_client_obj(&control)?;
+        let Some(global) = output.global.get() else {
+            control.send_failed();
+            return Ok(());
+        };
+        let connector = &global.connector;
+        let size = connector.connector.gamma_lut_size();
+        if size == 0 || connector.gamma_control.is_some() {
+            control.send_failed();
+            return Ok(());
+        }
+        control.size.set(size);
+        control.connector.set(Some(connector.clone()));
+        connector.gamma_control.set(Some(control.clone()));
+        control.send_gamma_size(size);
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrGammaControlManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrGammaControlManagerV1 {}
+
+simple_add_obj!(ZwlrGammaControlManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrGammaControlManagerV1Error, ClientError);
@@ -0,0 +1,140 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        backend::GammaLut,
+        client::{Client, ClientError},
+        io_uring::IoUringError,
+        leaks::Tracker,
+        object::{Object, Version},
+        state::ConnectorData,
+        utils::{buf::Buf, clonecell::CloneCell, errorfmt::ErrorFmt},
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+const INVALID_GAMMA: u32 = 1;
+
+pub struct ZwlrGammaControlV1 {
+    pub id: ZwlrGammaControlV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub connector: CloneCell<Option<Rc<ConnectorData>>>,
+    pub size: Cell<u32>,
+    pub reader: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl ZwlrGammaControlV1 {
+    pub fn send_gamma_size(&self, size: u32) {
+        self.client.event(GammaSize {
+            self_id: self.id,
+            size,
+        });
+    }
+
+    pub fn send_failed(&self) {
+        self.detach();
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        if let Some(connector) = self.connector.take() {
+            connector.gamma_control.take();
+            connector.connector.set_gamma_lut(None);
+        }
+    }
+
+    async fn apply_lut(self: Rc<Self>, fd: Rc<OwnedFd>) {
+        let lut = match self.read_lut(&fd).await {
+            Ok(lut) => lut,
+            Err(e @ ZwlrGammaControlV1Error::InvalidGamma) => {
+                self.client
+                    .protocol_error(&*self, INVALID_GAMMA, &e.to_string());
+                return;
+            }
+            Err(e) => {
+                log::warn!("Could not read gamma table: {}", ErrorFmt(e));
+                self.send_failed();
+                return;
+            }
+        };
+        if let Some(connector) = self.connector.get() {
+            connector.connector.set_gamma_lut(Some(Rc::new(lut)));
+        }
+    }
+
+    async fn read_lut(&self, fd: &Rc<OwnedFd>) -> Result<GammaLut, ZwlrGammaControlV1Error> {
+        let size = self.size.get() as usize;
+        let mut bytes = Buf::new(3 * size * size_of::<u16>());
+        let mut pos = 0;
+        while pos < bytes.len() {
+            let n = self
+                .client
+                .state
+                .ring
+                .read(fd, bytes.slice(pos..))
+                .await
+                .map_err(ZwlrGammaControlV1Error::Read)?;
+            if n == 0 {
+                return Err(ZwlrGammaControlV1Error::InvalidGamma);
+            }
+            pos += n;
+        }
+        let mut values = bytes[..]
+            .chunks_exact(2)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]));
+        Ok(GammaLut {
+            red: values.by_ref().take(size).collect(),
+            green: values.by_ref().take(size).collect(),
+            blue: values.collect(),
+        })
+    }
+}
+
+impl ZwlrGammaControlV1RequestHandler for ZwlrGammaControlV1 {
+    type Error = ZwlrGammaControlV1Error;
+
+    fn set_gamma(&self, req: SetGamma, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.connector.is_none() {
+            return Ok(());
+        }
+        let future = self.client.state.eng.spawn(slf.clone().apply_lut(req.fd));
+        self.reader.set(Some(future));
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.reader.take();
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrGammaControlV1;
+    version = self.version;
+}
+
+impl Object for ZwlrGammaControlV1 {
+    fn break_loops(&self) {
+        self.reader.take();
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrGammaControlV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The gamma table has an invalid size")]
+    InvalidGamma,
+    #[error("Could not read the gamma table")]
+    Read(#[source] IoUringError),
+}
+efrom!(ZwlrGammaControlV1Error, ClientError);
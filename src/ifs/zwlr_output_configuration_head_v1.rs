@@ -0,0 +1,121 @@
+use {
+    crate::{
+        backend::{self, ConnectorId},
+        client::{Client, ClientError},
+        ifs::zwlr_output_head_v1::{ADAPTIVE_SYNC_DISABLED, ADAPTIVE_SYNC_ENABLED},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::transform_ext::TransformExt,
+        wire::{zwlr_output_configuration_head_v1::*, ZwlrOutputConfigurationHeadV1Id},
+    },
+    jay_config::video::Transform,
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputConfigurationHeadV1 {
+    pub id: ZwlrOutputConfigurationHeadV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub connector: ConnectorId,
+    pub mode: Cell<Option<backend::Mode>>,
+    pub position: Cell<Option<(i32, i32)>>,
+    pub transform: Cell<Option<Transform>>,
+    pub scale: Cell<Option<f64>>,
+    pub adaptive_sync: Cell<Option<bool>>,
+}
+
+fn set_once<T: Copy>(
+    cell: &Cell<Option<T>>,
+    value: T,
+) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+    if cell.replace(Some(value)).is_some() {
+        return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet);
+    }
+    Ok(())
+}
+
+impl ZwlrOutputConfigurationHeadV1RequestHandler for ZwlrOutputConfigurationHeadV1 {
+    type Error = ZwlrOutputConfigurationHeadV1Error;
+
+    fn set_mode(&self, req: SetMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mode = self.client.lookup(req.mode)?;
+        if mode.head.connector != self.connector {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidMode);
+        }
+        set_once(&self.mode, mode.mode)
+    }
+
+    fn set_custom_mode(&self, req: SetCustomMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.width <= 0 || req.height <= 0 || req.refresh < 0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidCustomMode);
+        }
+        let mode = backend::Mode {
+            width: req.width,
+            height: req.height,
+            refresh_rate_millihz: req.refresh as u32,
+        };
+        set_once(&self.mode, mode)
+    }
+
+    fn set_position(&self, req: SetPosition, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        set_once(&self.position, (req.x, req.y))
+    }
+
+    fn set_transform(&self, req: SetTransform, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(transform) = Transform::from_wl(req.transform) else {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidTransform(
+                req.transform,
+            ));
+        };
+        set_once(&self.transform, transform)
+    }
+
+    fn set_scale(&self, req: SetScale, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let scale = req.scale.to_f64();
+        if scale <= 0.0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidScale(scale));
+        }
+        set_once(&self.scale, scale)
+    }
+
+    fn set_adaptive_sync(&self, req: SetAdaptiveSync, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let enabled = match req.state {
+            ADAPTIVE_SYNC_DISABLED => false,
+            ADAPTIVE_SYNC_ENABLED => true,
+            _ => {
+                return Err(ZwlrOutputConfigurationHeadV1Error::InvalidAdaptiveSyncState(req.state))
+            }
+        };
+        set_once(&self.adaptive_sync, enabled)
+    }
+}
+
+object_base! {
+    self = ZwlrOutputConfigurationHeadV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputConfigurationHeadV1 {}
+
+simple_add_obj!(ZwlrOutputConfigurationHeadV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationHeadV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The property has already been set")]
+    AlreadySet,
+    #[error("The mode does not belong to the head")]
+    InvalidMode,
+    #[error("The custom mode is invalid")]
+    InvalidCustomMode,
+    #[error("Unknown transform {0}")]
+    InvalidTransform(i32),
+    #[error("Invalid scale {0}")]
+    InvalidScale(f64),
+    #[error("Unknown adaptive sync state {0}")]
+    InvalidAdaptiveSyncState(u32),
+}
+efrom!(ZwlrOutputConfigurationHeadV1Error, ClientError);
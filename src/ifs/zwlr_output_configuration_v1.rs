@@ -0,0 +1,337 @@
+use {
+    crate::{
+        backend::{self, ConnectorConfig, ConnectorId, ConnectorState},
+        client::{Client, ClientError},
+        compositor::MAX_EXTENTS,
+        ifs::{
+            wl_output::PersistentOutputState,
+            zwlr_output_configuration_head_v1::ZwlrOutputConfigurationHeadV1,
+            zwlr_output_manager_v1::OutputHeadState,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        scale::Scale,
+        state::{ConnectorData, State},
+        utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt},
+        wire::{zwlr_output_configuration_v1::*, ZwlrOutputConfigurationV1Id},
+    },
+    jay_config::video::{Transform, VrrMode},
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const ALREADY_CONFIGURED_HEAD: u32 = 1;
+const UNCONFIGURED_HEAD: u32 = 2;
+const ALREADY_USED: u32 = 3;
+
+pub struct ZwlrOutputConfigurationV1 {
+    pub id: ZwlrOutputConfigurationV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub serial: u32,
+    pub heads: CopyHashMap<ConnectorId, Option<Rc<ZwlrOutputConfigurationHeadV1>>>,
+    pub used: Cell<bool>,
+}
+
+struct HeadChange {
+    connector: Rc<ConnectorData>,
+    head: Rc<OutputHeadState>,
+    enabled: bool,
+    mode: backend::Mode,
+    position: (i32, i32),
+    transform: Transform,
+    scale: Scale,
+    vrr_mode: VrrMode,
+}
+
+impl ZwlrOutputConfigurationV1 {
+    fn send_succeeded(&self) {
+        self.client.event(Succeeded { self_id: self.id });
+    }
+
+    fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn send_cancelled(&self) {
+        self.client.event(Cancelled { self_id: self.id });
+    }
+
+    fn configure_head(&self, head: ConnectorId) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.get() {
+            return Err(self.already_used());
+        }
+        if self.heads.contains(&head) {
+            self.client.protocol_error(
+                self,
+                ALREADY_CONFIGURED_HEAD,
+                "The head has already been configured",
+            );
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyConfiguredHead);
+        }
+        Ok(())
+    }
+
+    fn already_used(&self) -> ZwlrOutputConfigurationV1Error {
+        self.client.protocol_error(
+            self,
+            ALREADY_USED,
+            "The configuration has already been applied or tested",
+        );
+        ZwlrOutputConfigurationV1Error::AlreadyUsed
+    }
+
+    fn apply_or_test(&self, apply: bool) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.replace(true) {
+            return Err(self.already_used());
+        }
+        let state = &self.client.state;
+        if self.serial != state.output_heads_serial.get() {
+            self.send_cancelled();
+            return Ok(());
+        }
+        for (id, head) in state.output_heads.lock().iter() {
+            if !self.heads.contains(id) {
+                self.client.protocol_error(
+                    self,
+                    UNCONFIGURED_HEAD,
+                    &format!("Head {} has not been configured", head.name),
+                );
+                return Err(ZwlrOutputConfigurationV1Error::UnconfiguredHead);
+            }
+        }
+        let mut changes = vec![];
+        for (id, config) in self.heads.lock().iter() {
+            match validate(state, *id, config.as_deref()) {
+                Some(change) => changes.push(change),
+                None => {
+                    self.send_failed();
+                    return Ok(());
+                }
+            }
+        }
+        let res = match apply {
+            true => apply_changes(state, &changes),
+            false => test_changes(state, &changes),
+        };
+        if let Err(e) = res {
+            log::warn!("Could not apply output configuration: {}", ErrorFmt(e));
+            self.send_failed();
+            return Ok(());
+        }
+        self.send_succeeded();
+        Ok(())
+    }
+}
+
+fn validate(
+    state: &State,
+    id: ConnectorId,
+    config: Option<&ZwlrOutputConfigurationHeadV1>,
+) -> Option<HeadChange> {
+    let head = state.output_heads.get(&id)?;
+    let connector = state.connectors.get(&id)?;
+    let mut change = HeadChange {
+        connector,
+        enabled: config.is_some(),
+        mode: head.mode,
+        position: head.position,
+        transform: head.transform,
+        scale: head.scale,
+        vrr_mode: head.vrr_mode,
+        head,
+    };
+    let Some(config) = config else {
+        return Some(change);
+    };
+    if let Some(mode) = config.mode.get() {
+        change.mode = *change
+            .head
+            .modes
+            .iter()
+            .filter(|m| (m.width, m.height) == (mode.width, mode.height))
+            .filter(|m| {
+                mode.refresh_rate_millihz == 0
+                    || m.refresh_rate_millihz == mode.refresh_rate_millihz
+            })
+            .max_by_key(|m| m.refresh_rate_millihz)?;
+    }
+    if let Some((x, y)) = config.position.get() {
+        if x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS {
+            return None;
+        }
+        change.position = (x, y);
+    }
+    if let Some(transform) = config.transform.get() {
+        change.transform = transform;
+    }
+    if let Some(scale) = config.scale.get() {
+        if !(0.1..=1000.0).contains(&scale) {
+            return None;
+        }
+        change.scale = Scale::from_f64(scale);
+    }
+    if let Some(enabled) = config.adaptive_sync.get() {
+        if enabled != (change.head.vrr_mode != VrrMode::Never) {
+            if enabled && !change.head.vrr_capable {
+                return None;
+            }
+            change.vrr_mode = match enabled {
+                true => VrrMode::Always,
+                false => VrrMode::Never,
+            };
+        }
+    }
+    Some(change)
+}
+
+fn connector_configs(changes: &[HeadChange]) -> Vec<ConnectorConfig> {
+    changes
+        .iter()
+        .map(|change| ConnectorConfig {
+            connector: change.connector.connector.clone(),
+            old: ConnectorState {
+                enabled: change.head.enabled,
+                mode: change.head.mode,
+            },
+            new: ConnectorState {
+                enabled: change.enabled,
+                mode: change.mode,
+            },
+        })
+        .collect()
+}
+
+fn test_changes(state: &State, changes: &[HeadChange]) -> Result<(), ApplyError> {
+    let configs = connector_configs(changes);
+    state
+        .backend
+        .get()
+        .test_connectors(&configs)
+        .map_err(ApplyError::Test)
+}
+
+fn apply_changes(state: &State, changes: &[HeadChange]) -> Result<(), ApplyError> {
+    let configs = connector_configs(changes);
+    let backend = state.backend.get();
+    backend
+        .test_connectors(&configs)
+        .map_err(ApplyError::Test)?;
+    backend
+        .apply_connectors(&configs)
+        .map_err(ApplyError::Apply)?;
+    for change in changes {
+        if !change.enabled {
+            continue;
+        }
+        match state.root.outputs.get(&change.connector.connector.id()) {
+            Some(node) => {
+                node.set_position(change.position.0, change.position.1);
+                node.update_transform(change.transform);
+                node.set_preferred_scale(change.scale);
+                if change.vrr_mode != change.head.vrr_mode {
+                    node.set_vrr_mode(change.vrr_mode);
+                }
+            }
+            None => {
+                let persistent = match state.persistent_output_states.get(&change.head.output_id) {
+                    Some(ps) => ps,
+                    None => {
+                        let ps = Rc::new(PersistentOutputState {
+                            transform: Default::default(),
+                            scale: Default::default(),
+                            pos: Default::default(),
+                            vrr_mode: Default::default(),
+                        });
+                        state
+                            .persistent_output_states
+                            .set(change.head.output_id.clone(), ps.clone());
+                        ps
+                    }
+                };
+                persistent.pos.set(change.position);
+                persistent.transform.set(change.transform);
+                persistent.scale.set(change.scale);
+                persistent.vrr_mode.set(change.vrr_mode);
+            }
+        }
+    }
+    Ok(())
+}
+
+impl ZwlrOutputConfigurationV1RequestHandler for ZwlrOutputConfigurationV1 {
+    type Error = ZwlrOutputConfigurationV1Error;
+
+    fn enable_head(&self, req: EnableHead, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let head = self.client.lookup(req.head)?;
+        self.configure_head(head.connector)?;
+        let obj = Rc::new(ZwlrOutputConfigurationHeadV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            connector: head.connector,
+            mode: Default::default(),
+            position: Default::default(),
+            transform: Default::default(),
+            scale: Default::default(),
+            adaptive_sync: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.heads.set(head.connector, Some(obj));
+        Ok(())
+    }
+
+    fn disable_head(&self, req: DisableHead, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let head = self.client.lookup(req.head)?;
+        self.configure_head(head.connector)?;
+        self.heads.set(head.connector, None);
+        Ok(())
+    }
+
+    fn apply(&self, _req: Apply, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.apply_or_test(true)
+    }
+
+    fn test(&self, _req: Test, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.apply_or_test(false)
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.heads.clear();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputConfigurationV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputConfigurationV1 {}
+
+simple_add_obj!(ZwlrOutputConfigurationV1);
+
+#[derive(Debug, Error)]
+enum ApplyError {
+    #[error("The backend rejected the configuration")]
+    Test(#[source] Box<dyn std::error::Error>),
+    #[error("Could not apply the configuration")]
+    Apply(#[source] Box<dyn std::error::Error>),
+}
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The head has already been configured")]
+    AlreadyConfiguredHead,
+    #[error("The configuration has already been applied or tested")]
+    AlreadyUsed,
+    #[error("Not all heads have been configured")]
+    UnconfiguredHead,
+}
+efrom!(ZwlrOutputConfigurationV1Error, ClientError);
@@ -0,0 +1,272 @@
+use {
+    crate::{
+        backend::ConnectorId,
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{zwlr_output_manager_v1::OutputHeadState, zwlr_output_mode_v1::ZwlrOutputModeV1},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::{clonecell::CloneCell, transform_ext::TransformExt},
+        wire::{zwlr_output_head_v1::*, ZwlrOutputHeadV1Id},
+    },
+    jay_config::video::VrrMode,
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+pub const ADAPTIVE_SYNC_DISABLED: u32 = 0;
+pub const ADAPTIVE_SYNC_ENABLED: u32 = 1;
+
+pub struct ZwlrOutputHeadV1 {
+    pub id: ZwlrOutputHeadV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub connector: ConnectorId,
+    pub state: CloneCell<Option<Rc<OutputHeadState>>>,
+    pub modes: RefCell<Vec<Rc<ZwlrOutputModeV1>>>,
+}
+
+impl ZwlrOutputHeadV1 {
+    pub fn detach(&self) {
+        self.modes.borrow_mut().clear();
+    }
+
+    pub fn send_state(self: &Rc<Self>, state: &Rc<OutputHeadState>) {
+        self.state.set(Some(state.clone()));
+        self.send_name(&state.name);
+        self.send_description(&format!(
+            "{} {} {}",
+            state.output_id.manufacturer, state.output_id.model, state.output_id.serial_number,
+        ));
+        if state.width_mm > 0 && state.height_mm > 0 {
+            self.send_physical_size(state.width_mm, state.height_mm);
+        }
+        self.send_modes(state);
+        self.send_enabled(state.enabled);
+        if state.enabled {
+            self.send_current_state(state);
+        }
+        if self.version >= 2 {
+            self.send_make(&state.output_id.manufacturer);
+            self.send_model(&state.output_id.model);
+            self.send_serial_number(&state.output_id.serial_number);
+        }
+        if self.version >= 4 {
+            self.send_adaptive_sync(state.vrr_mode);
+        }
+    }
+
+    pub fn update(self: &Rc<Self>, state: &Rc<OutputHeadState>) {
+        let Some(old) = self.state.get() else {
+            return;
+        };
+        self.state.set(Some(state.clone()));
+        let modes_changed = old.modes != state.modes;
+        if modes_changed {
+            for mode in self.modes.borrow_mut().drain(..) {
+                mode.send_finished();
+            }
+            self.send_modes(state);
+        }
+        if old.enabled != state.enabled {
+            self.send_enabled(state.enabled);
+        }
+        if state.enabled {
+            if !old.enabled || modes_changed {
+                self.send_current_state(state);
+            } else {
+                if old.mode != state.mode {
+                    self.send_current_mode(state);
+                }
+                if old.position != state.position {
+                    self.send_position(state);
+                }
+                if old.transform != state.transform {
+                    self.send_transform(state);
+                }
+                if old.scale != state.scale {
+                    self.send_scale(state);
+                }
+            }
+        }
+        if self.version >= 4 && old.vrr_mode != state.vrr_mode {
+            self.send_adaptive_sync(state.vrr_mode);
+        }
+    }
+
+    fn send_modes(self: &Rc<Self>, state: &OutputHeadState) {
+        let mut modes = self.modes.borrow_mut();
+        for mode in &state.modes {
+            let id = match self.client.new_id() {
+                Ok(id) => id,
+                Err(e) => {
+                    self.client.error(e);
+                    return;
+                }
+            };
+            let obj = Rc::new(ZwlrOutputModeV1 {
+                id,
+                client: self.client.clone(),
+                tracker: Default::default(),
+                version: self.version,
+                head: self.clone(),
+                mode: *mode,
+            });
+            track!(self.client, obj);
+            self.client.add_server_obj(&obj);
+            self.send_mode(&obj);
+            obj.send_size(mode.width, mode.height);
+            obj.send_refresh(mode.refresh_rate_millihz as i32);
+            modes.push(obj);
+        }
+    }
+
+    fn send_current_state(&self, state: &OutputHeadState) {
+        self.send_current_mode(state);
+        self.send_position(state);
+        self.send_transform(state);
+        self.send_scale(state);
+    }
+
+    pub fn send_finished(&self) {
+        if self.state.take().is_none() {
+            return;
+        }
+        for mode in self.modes.borrow_mut().drain(..) {
+            mode.send_finished();
+        }
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn send_name(&self, name: &str) {
+        self.client.event(Name {
+            self_id: self.id,
+            name,
+        });
+    }
+
+    fn send_description(&self, description: &str) {
+        self.client.event(Description {
+            self_id: self.id,
+            description,
+        });
+    }
+
+    fn send_physical_size(&self, width: i32, height: i32) {
+        self.client.event(PhysicalSize {
+            self_id: self.id,
+            width,
+            height,
+        });
+    }
+
+    fn send_mode(&self, mode: &ZwlrOutputModeV1) {
+        self.client.event(Mode {
+            self_id: self.id,
+            mode: mode.id,
+        });
+    }
+
+    fn send_enabled(&self, enabled: bool) {
+        self.client.event(Enabled {
+            self_id: self.id,
+            enabled: enabled as i32,
+        });
+    }
+
+    fn send_current_mode(&self, state: &OutputHeadState) {
+        let modes = self.modes.borrow();
+        if let Some(mode) = modes.iter().find(|m| m.mode == state.mode) {
+            self.client.event(CurrentMode {
+                self_id: self.id,
+                mode: mode.id,
+            });
+        }
+    }
+
+    fn send_position(&self, state: &OutputHeadState) {
+        self.client.event(Position {
+            self_id: self.id,
+            x: state.position.0,
+            y: state.position.1,
+        });
+    }
+
+    fn send_transform(&self, state: &OutputHeadState) {
+        self.client.event(Transform {
+            self_id: self.id,
+            transform: state.transform.to_wl(),
+        });
+    }
+
+    fn send_scale(&self, state: &OutputHeadState) {
+        self.client.event(Scale {
+            self_id: self.id,
+            scale: Fixed::from_f64(state.scale.to_f64()),
+        });
+    }
+
+    fn send_make(&self, make: &str) {
+        self.client.event(Make {
+            self_id: self.id,
+            make,
+        });
+    }
+
+    fn send_model(&self, model: &str) {
+        self.client.event(Model {
+            self_id: self.id,
+            model,
+        });
+    }
+
+    fn send_serial_number(&self, serial_number: &str) {
+        self.client.event(SerialNumber {
+            self_id: self.id,
+            serial_number,
+        });
+    }
+
+    fn send_adaptive_sync(&self, mode: VrrMode) {
+        let state = match mode {
+            VrrMode::Never => ADAPTIVE_SYNC_DISABLED,
+            _ => ADAPTIVE_SYNC_ENABLED,
+        };
+        self.client.event(AdaptiveSync {
+            self_id: self.id,
+            state,
+        });
+    }
+}
+
+impl ZwlrOutputHeadV1RequestHandler for ZwlrOutputHeadV1 {
+    type Error = ZwlrOutputHeadV1Error;
+
+    fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.state.take();
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputHeadV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputHeadV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+dedicated_add_obj!(ZwlrOutputHeadV1, ZwlrOutputHeadV1Id, output_heads);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputHeadV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputHeadV1Error, ClientError);
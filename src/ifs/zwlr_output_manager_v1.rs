@@ -0,0 +1,305 @@
+use {
+    crate::{
+        backend::{ConnectorId, Mode},
+        client::{Client, ClientCaps, ClientError, CAP_OUTPUT_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::{
+            wl_output::OutputId, zwlr_output_configuration_v1::ZwlrOutputConfigurationV1,
+            zwlr_output_head_v1::ZwlrOutputHeadV1,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        scale::Scale,
+        state::State,
+        tree::OutputNode,
+        utils::copyhashmap::CopyHashMap,
+        wire::{zwlr_output_manager_v1::*, ZwlrOutputManagerV1Id},
+    },
+    ahash::AHashMap,
+    jay_config::video::{Transform, VrrMode},
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrOutputManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrOutputManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwlrOutputManagerV1Error> {
+        let obj = Rc::new(ZwlrOutputManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            heads: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        let heads: Vec<_> = client
+            .state
+            .output_heads
+            .lock()
+            .iter()
+            .map(|(id, head)| (*id, head.clone()))
+            .collect();
+        for (connector, head) in heads {
+            obj.create_head(connector, &head);
+        }
+        obj.send_done(client.state.output_heads_serial.get());
+        client.state.output_managers.set((client.id, id), obj);
+        Ok(())
+    }
+}
+
+/// The state of a connector as advertised via `zwlr_output_head_v1`.
+///
+/// Connectors that have been disabled keep their last state with `enabled` set to
+/// `false` so that they can be re-enabled by clients.
+#[derive(Clone, PartialEq)]
+pub struct OutputHeadState {
+    pub name: String,
+    pub output_id: Rc<OutputId>,
+    pub width_mm: i32,
+    pub height_mm: i32,
+    pub modes: Vec<Mode>,
+    pub enabled: bool,
+    pub mode: Mode,
+    pub position: (i32, i32),
+    pub transform: Transform,
+    pub scale: Scale,
+    pub vrr_capable: bool,
+    pub vrr_mode: VrrMode,
+}
+
+impl OutputHeadState {
+    fn new(output: &OutputNode, vrr_capable: bool) -> Self {
+        let global = &output.global;
+        let pos = global.pos.get();
+        Self {
+            name: global.connector.name.clone(),
+            output_id: global.output_id.clone(),
+            width_mm: global.width_mm,
+            height_mm: global.height_mm,
+            modes: global.modes.clone(),
+            enabled: true,
+            mode: global.mode.get(),
+            position: (pos.x1(), pos.y1()),
+            transform: global.persistent.transform.get(),
+            scale: global.persistent.scale.get(),
+            vrr_capable,
+            vrr_mode: global.persistent.vrr_mode.get(),
+        }
+    }
+}
+
+pub async fn handle_output_head_changes(state: Rc<State>) {
+    loop {
+        state.output_heads_changed.triggered().await;
+        update_output_heads(&state);
+    }
+}
+
+fn update_output_heads(state: &Rc<State>) {
+    let mut heads = AHashMap::new();
+    for connector in state.connectors.lock().values() {
+        let id = connector.connector.id();
+        let head = match state.outputs.get(&id) {
+            Some(od) => match &od.node {
+                Some(node) => Rc::new(OutputHeadState::new(node, od.monitor_info.vrr_capable)),
+                None => continue,
+            },
+            None => match state.output_heads.get(&id) {
+                Some(old) if !connector.connector.enabled() => match old.enabled {
+                    true => Rc::new(OutputHeadState {
+                        enabled: false,
+                        ..(*old).clone()
+                    }),
+                    false => old,
+                },
+                _ => continue,
+            },
+        };
+        heads.insert(id, head);
+    }
+    {
+        let old = state.output_heads.lock();
+        if old.len() == heads.len() && heads.iter().all(|(id, head)| old.get(id) == Some(head)) {
+            return;
+        }
+    }
+    state.output_heads.clear();
+    for (id, head) in &heads {
+        state.output_heads.set(*id, head.clone());
+    }
+    let serial = state.output_heads_serial.fetch_add(1) + 1;
+    let managers: Vec<_> = state.output_managers.lock().values().cloned().collect();
+    for manager in managers {
+        manager.update_heads(&heads);
+        manager.send_done(serial);
+    }
+}
+
+pub struct ZwlrOutputManagerV1 {
+    pub id: ZwlrOutputManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub heads: CopyHashMap<ConnectorId, Rc<ZwlrOutputHeadV1>>,
+}
+
+impl ZwlrOutputManagerV1 {
+    fn detach(&self) {
+        self.client
+            .state
+            .output_managers
+            .remove(&(self.client.id, self.id));
+        for head in self.heads.clear().into_values() {
+            head.detach();
+        }
+    }
+
+    fn create_head(self: &Rc<Self>, connector: ConnectorId, state: &Rc<OutputHeadState>) {
+        let id = match self.client.new_id() {
+            Ok(id) => id,
+            Err(e) => {
+                self.client.error(e);
+                return;
+            }
+        };
+        let head = Rc::new(ZwlrOutputHeadV1 {
+            id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            connector,
+            state: Default::default(),
+            modes: Default::default(),
+        });
+        track!(self.client, head);
+        self.client.add_server_obj(&head);
+        self.send_head(&head);
+        head.send_state(state);
+        self.heads.set(connector, head);
+    }
+
+    fn update_heads(self: &Rc<Self>, heads: &AHashMap<ConnectorId, Rc<OutputHeadState>>) {
+        let removed: Vec<_> = self
+            .heads
+            .lock()
+            .keys()
+            .filter(|id| !heads.contains_key(id))
+            .copied()
+            .collect();
+        for id in removed {
+            if let Some(head) = self.heads.remove(&id) {
+                head.send_finished();
+            }
+        }
+        for (id, state) in heads {
+            match self.heads.get(id) {
+                Some(head) => head.update(state),
+                None => self.create_head(*id, state),
+            }
+        }
+    }
+
+    fn send_head(&self, head: &ZwlrOutputHeadV1) {
+        self.client.event(Head {
+            self_id: self.id,
+            head: head.id,
+        });
+    }
+
+    fn send_done(&self, serial: u32) {
+        self.client.event(Done {
+            self_id: self.id,
+            serial,
+        });
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+}
+
+impl ZwlrOutputManagerV1RequestHandler for ZwlrOutputManagerV1 {
+    type Error = ZwlrOutputManagerV1Error;
+
+    fn create_configuration(
+        &self,
+        req: CreateConfiguration,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(ZwlrOutputConfigurationV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            serial: req.serial,
+            heads: Default::default(),
+            used: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn stop(&self, _req: Stop, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.send_finished();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrOutputManagerV1Global,
+    ZwlrOutputManagerV1,
+    ZwlrOutputManagerV1Error
+);
+
+impl Global for ZwlrOutputManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        4
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_OUTPUT_MANAGER
+    }
+}
+
+simple_add_global!(ZwlrOutputManagerV1Global);
+
+object_base! {
+    self = ZwlrOutputManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputManagerV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrOutputManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputManagerV1Error, ClientError);
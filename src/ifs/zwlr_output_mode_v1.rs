@@ -0,0 +1,68 @@
+use {
+    crate::{
+        backend,
+        client::{Client, ClientError},
+        ifs::zwlr_output_head_v1::ZwlrOutputHeadV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_output_mode_v1::*, ZwlrOutputModeV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputModeV1 {
+    pub id: ZwlrOutputModeV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub head: Rc<ZwlrOutputHeadV1>,
+    pub mode: backend::Mode,
+}
+
+impl ZwlrOutputModeV1 {
+    pub fn send_size(&self, width: i32, height: i32) {
+        self.client.event(Size {
+            self_id: self.id,
+            width,
+            height,
+        });
+    }
+
+    pub fn send_refresh(&self, refresh: i32) {
+        self.client.event(Refresh {
+            self_id: self.id,
+            refresh,
+        });
+    }
+
+    pub fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+}
+
+impl ZwlrOutputModeV1RequestHandler for ZwlrOutputModeV1 {
+    type Error = ZwlrOutputModeV1Error;
+
+    fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.head.modes.borrow_mut().retain(|m| m.id != self.id);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputModeV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputModeV1 {}
+
+dedicated_add_obj!(ZwlrOutputModeV1, ZwlrOutputModeV1Id, output_modes);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputModeV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputModeV1Error, ClientError);
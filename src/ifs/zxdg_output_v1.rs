@@ -64,8 +64,11 @@ impl ZxdgOutputV1 {
             return;
         };
         let pos = global.position();
-        self.send_logical_position(pos.x1(), pos.y1());
-        self.send_logical_size(pos.width(), pos.height());
+        let (mut x, mut y) = pos.position();
+        let (mut width, mut height) = pos.size();
+        logical_to_client_wire_scale!(self.client, x, y, width, height);
+        self.send_logical_position(x, y);
+        self.send_logical_size(width, height);
         if self.version >= NAME_SINCE {
             self.send_name(&global.connector.name);
         }
@@ -10,12 +10,17 @@ use {
         compositor::TestFuture,
         drm_feedback::DrmFeedback,
         fixed::Fixed,
-        gfx_api::GfxError,
+        format::XRGB8888,
+        gfx_api::{GfxError, GfxFramebuffer},
         it::{
-            test_error::TestResult, test_gfx_api::TestGfxCtx, test_utils::test_expected_event::TEEH,
+            test_error::TestResult,
+            test_gfx_api::{TestGfxCtx, TestGfxFb},
+            test_utils::test_expected_event::TEEH,
         },
+        rect::Rect,
         state::State,
         time::now_usec,
+        tree::OutputNode,
         utils::{
             clonecell::CloneCell, copyhashmap::CopyHashMap, on_change::OnChange, oserror::OsError,
             syncqueue::SyncQueue,
@@ -23,7 +28,14 @@ use {
         video::drm::{ConnectorType, Drm},
     },
     bstr::ByteSlice,
-    std::{any::Any, cell::Cell, io, os::unix::ffi::OsStrExt, pin::Pin, rc::Rc},
+    std::{
+        any::Any,
+        cell::{Cell, RefCell},
+        io, mem,
+        os::unix::ffi::OsStrExt,
+        pin::Pin,
+        rc::Rc,
+    },
     thiserror::Error,
     uapi::c,
 };
@@ -47,6 +59,7 @@ pub struct TestBackend {
     pub default_connector: Rc<TestConnector>,
     pub default_mouse: Rc<TestBackendMouse>,
     pub default_kb: Rc<TestBackendKb>,
+    pub default_touch: Rc<TestBackendTouch>,
     pub render_context_installed: Cell<bool>,
     pub idle: TEEH<bool>,
 }
@@ -62,6 +75,10 @@ impl TestBackend {
             },
             events: Default::default(),
             feedback: Default::default(),
+            reject_modes: Cell::new(false),
+            damage: Default::default(),
+            damage_full: Cell::new(true),
+            fb: Default::default(),
         });
         let default_mouse = Rc::new(TestBackendMouse {
             common: TestInputDeviceCommon {
@@ -95,6 +112,20 @@ impl TestBackend {
                 name: Rc::new("default-keyboard".to_string()),
             },
         });
+        let default_touch = Rc::new(TestBackendTouch {
+            common: TestInputDeviceCommon {
+                id: state.input_device_ids.next(),
+                removed: Cell::new(false),
+                events: Default::default(),
+                on_change: Default::default(),
+                capabilities: {
+                    let chm = CopyHashMap::new();
+                    chm.set(InputDeviceCapability::Touch, ());
+                    chm
+                },
+                name: Rc::new("default-touch".to_string()),
+            },
+        });
         let mode = Mode {
             width: 800,
             height: 600,
@@ -109,6 +140,7 @@ impl TestBackend {
             width_mm: 80,
             height_mm: 60,
             non_desktop: false,
+            vrr_capable: false,
         };
         Self {
             state: state.clone(),
@@ -117,6 +149,7 @@ impl TestBackend {
             default_connector,
             default_mouse,
             default_kb,
+            default_touch,
             render_context_installed: Cell::new(false),
             idle: Rc::new(Default::default()),
         }
@@ -145,6 +178,9 @@ impl TestBackend {
         self.state
             .backend_events
             .push(BackendEvent::NewInputDevice(self.default_mouse.clone()));
+        self.state
+            .backend_events
+            .push(BackendEvent::NewInputDevice(self.default_touch.clone()));
         Ok(())
     }
 
@@ -229,6 +265,49 @@ pub struct TestConnector {
     pub kernel_id: ConnectorKernelId,
     pub events: OnChange<ConnectorEvent>,
     pub feedback: CloneCell<Option<Rc<DrmFeedback>>>,
+    pub reject_modes: Cell<bool>,
+    pub damage: RefCell<Vec<Rect>>,
+    pub damage_full: Cell<bool>,
+    pub fb: CloneCell<Option<Rc<TestGfxFb>>>,
+}
+
+impl TestConnector {
+    /// Renders the output into a framebuffer owned by the connector.
+    ///
+    /// Like the metal backend, only the region damaged since the last call is redrawn.
+    pub fn render(&self, state: &State, output: &OutputNode) -> Result<Rc<TestGfxFb>, GfxError> {
+        let (width, height) = output.global.pixel_size();
+        let fb = match self.fb.get() {
+            Some(fb) if fb.physical_size() == (width, height) => fb,
+            _ => {
+                let fb = TestGfxFb::new(width, height, width * 4, XRGB8888);
+                self.fb.set(Some(fb.clone()));
+                self.damage_full.set(true);
+                fb
+            }
+        };
+        let scale = output.global.persistent.scale.get();
+        let transform = output.global.persistent.transform.get();
+        let dyn_fb: Rc<dyn GfxFramebuffer> = fb.clone();
+        let damage = mem::take(&mut *self.damage.borrow_mut());
+        let region = match self.damage_full.replace(false) {
+            true => dyn_fb.full_region(),
+            false => dyn_fb.damage_region(&damage, scale, transform),
+        };
+        let pass = dyn_fb.create_render_pass(
+            output,
+            state,
+            Some(output.global.pos.get()),
+            None,
+            scale,
+            false,
+            false,
+            output.has_fullscreen(),
+            transform,
+        );
+        dyn_fb.perform_render_pass(pass, &region)?;
+        Ok(fb)
+    }
 }
 
 impl Connector for TestConnector {
@@ -249,15 +328,29 @@ impl Connector for TestConnector {
     }
 
     fn damage(&self) {
-        // nothing
+        self.damage_full.set(true);
+    }
+
+    fn damage_rect(&self, rect: Rect) {
+        if !rect.is_empty() {
+            self.damage.borrow_mut().push(rect);
+        }
     }
 
     fn drm_dev(&self) -> Option<DrmDeviceId> {
         None
     }
 
-    fn set_mode(&self, _mode: Mode) {
+    fn set_mode(&self, _mode: Mode) -> Result<(), Box<dyn std::error::Error>> {
         // todo
+        Ok(())
+    }
+
+    fn test_mode(&self, _mode: Mode) -> Result<(), Box<dyn std::error::Error>> {
+        if self.reject_modes.get() {
+            return Err("The mode has been rejected".into());
+        }
+        Ok(())
     }
 
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
@@ -386,12 +479,62 @@ impl TestBackendKb {
     }
 }
 
+pub struct TestBackendTouch {
+    pub common: TestInputDeviceCommon,
+}
+
+impl TestBackendTouch {
+    pub fn down(&self, id: i32, x_normed: f64, y_normed: f64) {
+        self.common.event(InputEvent::TouchDown {
+            time_usec: now_usec(),
+            id,
+            x_normed,
+            y_normed,
+        });
+    }
+
+    pub fn motion(&self, id: i32, x_normed: f64, y_normed: f64) {
+        self.common.event(InputEvent::TouchMotion {
+            time_usec: now_usec(),
+            id,
+            x_normed,
+            y_normed,
+        });
+    }
+
+    pub fn up(&self, id: i32) {
+        self.common.event(InputEvent::TouchUp {
+            time_usec: now_usec(),
+            id,
+        });
+    }
+
+    pub fn cancel(&self, id: i32) {
+        self.common.event(InputEvent::TouchCancel {
+            time_usec: now_usec(),
+            id,
+        });
+    }
+
+    pub fn frame(&self) {
+        self.common.event(InputEvent::TouchFrame {
+            time_usec: now_usec(),
+        });
+    }
+}
+
 impl TestInputDevice for TestBackendKb {
     fn common(&self) -> &TestInputDeviceCommon {
         &self.common
     }
 }
 
+impl TestInputDevice for TestBackendTouch {
+    fn common(&self) -> &TestInputDeviceCommon {
+        &self.common
+    }
+}
+
 impl TestInputDevice for TestBackendMouse {
     fn common(&self) -> &TestInputDeviceCommon {
         &self.common
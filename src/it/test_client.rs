@@ -3,6 +3,7 @@ use {
         cli::{screenshot::buf_to_bytes, ScreenshotFormat},
         client::Client,
         globals::GlobalBase,
+        ifs::wl_output::WlOutputGlobal,
         it::{
             test_error::{TestError, TestResult},
             test_ifs::{
@@ -12,7 +13,8 @@ use {
                 test_pointer::TestPointer, test_registry::TestRegistry, test_seat::TestSeat,
                 test_shm::TestShm, test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
                 test_subcompositor::TestSubcompositor, test_viewporter::TestViewporter,
-                test_xdg_activation::TestXdgActivation, test_xdg_base::TestXdgWmBase,
+                test_wl_output::TestWlOutput, test_xdg_activation::TestXdgActivation,
+                test_xdg_base::TestXdgWmBase,
             },
             test_transport::TestTransport,
             test_utils::{test_surface_ext::TestSurfaceExt, test_window::TestWindow},
@@ -91,6 +93,18 @@ impl TestClient {
         self.run.state.eng.yield_now().await;
     }
 
+    pub async fn get_output(&self, global: &WlOutputGlobal) -> TestResult<Rc<TestWlOutput>> {
+        let output = Rc::new(TestWlOutput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Default::default(),
+            name: Default::default(),
+        });
+        self.registry.bind(&output, global.name.raw(), 4)?;
+        self.tran.sync().await;
+        Ok(output)
+    }
+
     pub async fn take_screenshot(&self, include_cursor: bool) -> Result<Vec<u8>, TestError> {
         let dmabuf = self.jc.take_screenshot(include_cursor).await?;
         let qoi = buf_to_bytes(
@@ -11,15 +11,24 @@ use {
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ipc::{ClientMessage, Response, ServerMessage, WindowRuleData},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat},
-        keyboard::{Keymap, ModifiedKeySym},
+        input::{pointer::PointerTrigger, InputDevice, Seat},
+        keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
+        theme::{colors::Colorable, sized::Resizable, Color, ColorScheme},
         video::{Connector, Transform},
-        Axis, Direction,
+        window::Window,
+        xwayland::XScalingMode,
+        Axis, Direction, ResizeAmount, Workspace,
+    },
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        ptr,
+        rc::Rc,
+        time::Duration,
     },
-    std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
 
 pub static TEST_CONFIG_ENTRY: ConfigEntry = ConfigEntry {
@@ -41,7 +50,13 @@ where
         srv: Cell::new(None),
         responses: Default::default(),
         invoked_shortcuts: Default::default(),
+        invoked_pointer_shortcuts: Default::default(),
         graphics_initialized: Cell::new(false),
+        new_windows: Default::default(),
+        changed_window_titles: Default::default(),
+        closed_windows: Default::default(),
+        ipc_commands: Default::default(),
+        cancelled_binding_modes: Default::default(),
     });
     let old = CONFIG.get();
     CONFIG.set(tc.deref());
@@ -121,6 +136,40 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::InterestReady { .. } => {}
         ServerMessage::Features { .. } => {}
         ServerMessage::SwitchEvent { .. } => {}
+        ServerMessage::NewWindow { window } => {
+            tc.new_windows.set(window, ());
+        }
+        ServerMessage::WindowTitleChanged { window } => {
+            tc.changed_window_titles.set(window, ());
+        }
+        ServerMessage::WindowClosed { window } => {
+            tc.closed_windows.set(window, ());
+        }
+        ServerMessage::InvokePointerShortcut {
+            seat,
+            mods,
+            trigger,
+        } => {
+            tc.invoked_pointer_shortcuts
+                .set((SeatId::from_raw(seat.0 as _), mods, trigger), ());
+        }
+        ServerMessage::IpcCommand { command } => {
+            let error = match command.as_str() {
+                "fail" => Some("failed"),
+                _ => None,
+            };
+            tc.ipc_commands.borrow_mut().push(command);
+            let _ = tc.send(ClientMessage::IpcCommandResult { error });
+        }
+        ServerMessage::BindingModeCancelled { seat } => {
+            tc.cancelled_binding_modes
+                .set(SeatId::from_raw(seat.0 as _), ());
+            let _ = tc.send(ClientMessage::SetBindingMode {
+                seat,
+                name: None,
+                once: false,
+            });
+        }
     }
 }
 
@@ -135,7 +184,13 @@ pub struct TestConfig {
     srv: Cell<Option<ServerData>>,
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
+    pub invoked_pointer_shortcuts: CopyHashMap<(SeatId, Modifiers, PointerTrigger), ()>,
     pub graphics_initialized: Cell<bool>,
+    pub new_windows: CopyHashMap<Window, ()>,
+    pub changed_window_titles: CopyHashMap<Window, ()>,
+    pub closed_windows: CopyHashMap<Window, ()>,
+    pub ipc_commands: RefCell<Vec<String>>,
+    pub cancelled_binding_modes: CopyHashMap<SeatId, ()>,
 }
 
 macro_rules! get_response {
@@ -185,9 +240,46 @@ impl TestConfig {
         Ok(SeatId::from_raw(seat.0 as _))
     }
 
-    pub fn show_workspace(&self, seat: SeatId, name: &str) -> Result<(), TestError> {
+    pub fn get_workspace(&self, name: &str) -> Result<Workspace, TestError> {
         let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
         get_response!(reply, GetWorkspace { workspace });
+        Ok(workspace)
+    }
+
+    pub fn windows(&self) -> Result<Vec<Window>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindows)?;
+        get_response!(reply, GetWindows { windows });
+        Ok(windows)
+    }
+
+    pub fn focused_window(&self, seat: SeatId) -> Result<Window, TestError> {
+        let reply = self.send_with_reply(ClientMessage::SeatFocusedWindow {
+            seat: Seat(seat.raw() as _),
+        })?;
+        get_response!(reply, GetWindow { window });
+        Ok(window)
+    }
+
+    pub fn window_title(&self, window: Window) -> Result<String, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowTitle { window })?;
+        get_response!(reply, GetWindowTitle { title });
+        Ok(title)
+    }
+
+    pub fn set_window_floating(&self, window: Window, floating: bool) -> TestResult {
+        self.send(ClientMessage::SetWindowFloating { window, floating })
+    }
+
+    pub fn move_window_to_workspace(&self, window: Window, workspace: Workspace) -> TestResult {
+        self.send(ClientMessage::MoveWindowToWorkspace { window, workspace })
+    }
+
+    pub fn close_window(&self, window: Window) -> TestResult {
+        self.send(ClientMessage::CloseWindow { window })
+    }
+
+    pub fn show_workspace(&self, seat: SeatId, name: &str) -> Result<(), TestError> {
+        let workspace = self.get_workspace(name)?;
         self.send(ClientMessage::ShowWorkspace {
             seat: Seat(seat.raw() as _),
             workspace,
@@ -290,6 +382,144 @@ impl TestConfig {
             transform,
         })
     }
+
+    pub fn add_window_rule(&self, rule: WindowRuleData) -> TestResult {
+        self.send(ClientMessage::AddWindowRule { rule })
+    }
+
+    pub fn set_size(&self, sized: Resizable, size: i32) -> TestResult {
+        self.send(ClientMessage::SetSize { sized, size })
+    }
+
+    pub fn set_smart_gaps(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetSmartGaps { enabled })
+    }
+
+    pub fn set_color(&self, colorable: Colorable, color: Color) -> TestResult {
+        self.send(ClientMessage::SetColor { colorable, color })
+    }
+
+    pub fn set_color_scheme(&self, scheme: ColorScheme) -> TestResult {
+        self.send(ClientMessage::SetColorScheme { scheme })
+    }
+
+    pub fn set_workspace_outer_gap(&self, workspace: Workspace, gap: Option<i32>) -> TestResult {
+        self.send(ClientMessage::SetWorkspaceOuterGap { workspace, gap })
+    }
+
+    pub fn move_to_scratchpad(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatMoveToScratchpad {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn toggle_scratchpad(&self, seat: SeatId, app_id: Option<&str>) -> TestResult {
+        self.send(ClientMessage::SeatToggleScratchpad {
+            seat: Seat(seat.raw() as _),
+            app_id,
+        })
+    }
+
+    pub fn set_x_scaling_mode(&self, mode: XScalingMode) -> TestResult {
+        self.send(ClientMessage::SetXScalingMode { mode })
+    }
+
+    pub fn set_window_minimized(&self, window: Window, minimized: bool) -> TestResult {
+        self.send(ClientMessage::SetWindowMinimized { window, minimized })
+    }
+
+    pub fn workspace_minimized_windows(
+        &self,
+        workspace: Workspace,
+    ) -> Result<Vec<Window>, TestError> {
+        let reply =
+            self.send_with_reply(ClientMessage::GetWorkspaceMinimizedWindows { workspace })?;
+        get_response!(reply, GetWindows { windows });
+        Ok(windows)
+    }
+
+    pub fn restore_minimized(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatRestoreMinimized {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn resize(&self, seat: SeatId, direction: Direction, amount: ResizeAmount) -> TestResult {
+        self.send(ClientMessage::SeatResize {
+            seat: Seat(seat.raw() as _),
+            direction,
+            amount,
+        })
+    }
+
+    pub fn move_floating(&self, seat: SeatId, dx: i32, dy: i32) -> TestResult {
+        self.send(ClientMessage::SeatMoveFloating {
+            seat: Seat(seat.raw() as _),
+            dx,
+            dy,
+        })
+    }
+
+    pub fn set_floating_geometry(
+        &self,
+        seat: SeatId,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> TestResult {
+        self.send(ClientMessage::SeatSetFloatingGeometry {
+            seat: Seat(seat.raw() as _),
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn set_binding_mode(&self, seat: SeatId, name: Option<&str>) -> TestResult {
+        self.send(ClientMessage::SetBindingMode {
+            seat: Seat(seat.raw() as _),
+            name,
+            once: false,
+        })
+    }
+
+    pub fn set_binding_mode_once(&self, seat: SeatId, name: &str) -> TestResult {
+        self.send(ClientMessage::SetBindingMode {
+            seat: Seat(seat.raw() as _),
+            name: Some(name),
+            once: true,
+        })
+    }
+
+    pub fn add_pointer_shortcut(
+        &self,
+        seat: SeatId,
+        mods: Modifiers,
+        trigger: PointerTrigger,
+    ) -> TestResult {
+        self.send(ClientMessage::AddPointerShortcut {
+            seat: Seat(seat.raw() as _),
+            mods,
+            trigger,
+        })
+    }
+
+    pub fn rename_workspace(&self, workspace: Workspace, name: &str) -> TestResult {
+        self.send(ClientMessage::RenameWorkspace { workspace, name })
+    }
+
+    pub fn set_workspace_connector(
+        &self,
+        workspace: Workspace,
+        output: Option<&OutputNode>,
+    ) -> TestResult {
+        self.send(ClientMessage::SetWorkspaceConnector {
+            workspace,
+            connector: output.map(|o| Connector(o.global.connector.connector.id().raw() as _)),
+        })
+    }
 }
 
 impl Drop for TestConfig {
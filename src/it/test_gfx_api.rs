@@ -5,7 +5,7 @@ use {
             CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxContext, GfxError, GfxFormat,
             GfxFramebuffer, GfxImage, GfxTexture, ResetStatus, SyncFile,
         },
-        rect::Rect,
+        rect::{Rect, Region},
         theme::Color,
         video::{
             dmabuf::DmaBuf,
@@ -157,17 +157,7 @@ impl GfxContext for TestGfxCtx {
         stride: i32,
         format: &'static Format,
     ) -> Result<Rc<dyn GfxFramebuffer>, GfxError> {
-        assert!(stride >= width * 4);
-        Ok(Rc::new(TestGfxFb {
-            img: Rc::new(TestGfxImage::Shm(TestShmGfxImage {
-                data: RefCell::new(vec![0; (stride * height) as usize]),
-                width,
-                height,
-                stride,
-                format,
-            })),
-            staging: RefCell::new(vec![Color::TRANSPARENT; (width * height) as usize]),
-        }))
+        Ok(TestGfxFb::new(width, height, stride, format))
     }
 
     fn sync_obj_ctx(&self) -> &Rc<SyncObjCtx> {
@@ -180,9 +170,27 @@ enum TestGfxImage {
     DmaBuf(TestDmaBufGfxImage),
 }
 
-struct TestGfxFb {
+pub struct TestGfxFb {
     img: Rc<TestGfxImage>,
     staging: RefCell<Vec<Color>>,
+    pub render_regions: RefCell<Vec<Vec<Rect>>>,
+}
+
+impl TestGfxFb {
+    pub fn new(width: i32, height: i32, stride: i32, format: &'static Format) -> Rc<Self> {
+        assert!(stride >= width * 4);
+        Rc::new(Self {
+            img: Rc::new(TestGfxImage::Shm(TestShmGfxImage {
+                data: RefCell::new(vec![0; (stride * height) as usize]),
+                width,
+                height,
+                stride,
+                format,
+            })),
+            staging: RefCell::new(vec![Color::TRANSPARENT; (width * height) as usize]),
+            render_regions: Default::default(),
+        })
+    }
 }
 
 struct TestShmGfxImage {
@@ -319,6 +327,7 @@ impl GfxImage for TestGfxImage {
                 (self.width() * self.height()) as usize
             ]),
             img: self,
+            render_regions: Default::default(),
         }))
     }
 
@@ -357,7 +366,9 @@ impl GfxFramebuffer for TestGfxFb {
         &self,
         ops: Vec<GfxApiOpt>,
         clear: Option<&Color>,
+        region: &Region,
     ) -> Result<Option<SyncFile>, GfxError> {
+        self.render_regions.borrow_mut().push(region.to_vec());
         let fb_points = |width: i32, height: i32, rect: &FramebufferRect| {
             let points = rect.to_points();
             let x1 = points[1][0];
@@ -407,6 +418,9 @@ impl GfxFramebuffer for TestGfxFb {
                 let mut data = data;
                 for y in 0..height {
                     for x in 0..width {
+                        if !region.contains(x, y) {
+                            continue;
+                        }
                         let [r, g, b, a] =
                             staging[(y * width + x) as usize].to_rgba_premultiplied();
                         *data.add((x * 4) as usize).cast::<[u8; 4]>() = [b, g, r, a];
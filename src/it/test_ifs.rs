@@ -24,8 +24,23 @@ pub mod test_input_method;
 pub mod test_input_method_keyboard_grab;
 pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
+pub mod test_jay_appearance;
 pub mod test_jay_compositor;
+pub mod test_jay_global_shortcuts;
+pub mod test_jay_input_injector;
+pub mod test_jay_output;
+pub mod test_jay_screencast;
+pub mod test_jay_select_toplevel;
+pub mod test_jay_select_workspace;
+pub mod test_jay_toplevel;
+pub mod test_jay_tree;
+pub mod test_jay_workspace;
 pub mod test_keyboard;
+pub mod test_output_configuration;
+pub mod test_output_configuration_head;
+pub mod test_output_head;
+pub mod test_output_manager;
+pub mod test_output_mode;
 pub mod test_pointer;
 pub mod test_region;
 pub mod test_registry;
@@ -45,10 +60,12 @@ pub mod test_text_input;
 pub mod test_text_input_manager;
 pub mod test_toplevel_drag;
 pub mod test_toplevel_drag_manager;
+pub mod test_touch;
 pub mod test_viewport;
 pub mod test_viewporter;
 pub mod test_virtual_keyboard;
 pub mod test_virtual_keyboard_manager;
+pub mod test_wl_output;
 pub mod test_xdg_activation;
 pub mod test_xdg_activation_token;
 pub mod test_xdg_base;
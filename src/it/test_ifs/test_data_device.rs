@@ -8,10 +8,11 @@ use {
             },
             test_object::TestObject,
             test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
             testrun::ParseFull,
         },
         utils::buffd::MsgParser,
-        wire::{wl_data_device::*, WlDataDeviceId, WlSurfaceId},
+        wire::{wl_data_device::*, WlDataDeviceId, WlDataOfferId, WlSurfaceId},
     },
     std::{cell::Cell, rc::Rc},
 };
@@ -20,6 +21,9 @@ pub struct TestDataDevice {
     pub id: WlDataDeviceId,
     pub tran: Rc<TestTransport>,
     pub destroyed: Cell<bool>,
+    pub data_offer: TEEH<Rc<TestDataOffer>>,
+    pub enter: TEEH<(WlSurfaceId, WlDataOfferId)>,
+    pub drop: TEEH<()>,
 }
 
 impl TestDataDevice {
@@ -63,14 +67,22 @@ impl TestDataDevice {
             id: ev.id,
             tran: self.tran.clone(),
             destroyed: Cell::new(false),
+            mime_types: Default::default(),
+            source_actions: Cell::new(0),
+            action: Cell::new(0),
         });
         self.tran.add_obj(offer.clone())?;
-        offer.destroy()?;
+        if self.data_offer.is_expected() {
+            self.data_offer.push(offer);
+        } else {
+            offer.destroy()?;
+        }
         Ok(())
     }
 
     fn handle_enter(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Enter::parse_full(parser)?;
+        let ev = Enter::parse_full(parser)?;
+        self.enter.push((ev.surface, ev.id));
         Ok(())
     }
 
@@ -86,6 +98,7 @@ impl TestDataDevice {
 
     fn handle_drop(&self, parser: MsgParser<'_, '_>) -> TestResult {
         let _ev = Drop::parse_full(parser)?;
+        self.drop.push(());
         Ok(())
     }
 
@@ -33,6 +33,8 @@ impl TestDataDeviceManager {
             tran: self.tran.clone(),
             destroyed: Cell::new(false),
             sends: Rc::new(Default::default()),
+            targets: Rc::new(Default::default()),
+            dnd_finished: Rc::new(Default::default()),
         });
         self.tran.add_obj(data_source.clone())?;
         self.tran.send(CreateDataSource {
@@ -47,6 +49,9 @@ impl TestDataDeviceManager {
             id: self.tran.id(),
             tran: self.tran.clone(),
             destroyed: Cell::new(false),
+            data_offer: Default::default(),
+            enter: Default::default(),
+            drop: Default::default(),
         });
         self.tran.add_obj(data_device.clone())?;
         self.tran.send(GetDataDevice {
@@ -7,13 +7,19 @@ use {
         utils::buffd::MsgParser,
         wire::{wl_data_offer::*, WlDataOfferId},
     },
-    std::{cell::Cell, rc::Rc},
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
 };
 
 pub struct TestDataOffer {
     pub id: WlDataOfferId,
     pub tran: Rc<TestTransport>,
     pub destroyed: Cell<bool>,
+    pub mime_types: RefCell<Vec<String>>,
+    pub source_actions: Cell<u32>,
+    pub action: Cell<u32>,
 }
 
 impl TestDataOffer {
@@ -24,18 +30,44 @@ impl TestDataOffer {
         Ok(())
     }
 
+    pub fn accept(&self, serial: u32, mime_type: Option<&str>) -> TestResult {
+        self.tran.send(Accept {
+            self_id: self.id,
+            serial,
+            mime_type,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_actions(&self, dnd_actions: u32, preferred_action: u32) -> TestResult {
+        self.tran.send(SetActions {
+            self_id: self.id,
+            dnd_actions,
+            preferred_action,
+        })?;
+        Ok(())
+    }
+
+    pub fn finish(&self) -> TestResult {
+        self.tran.send(Finish { self_id: self.id })?;
+        Ok(())
+    }
+
     fn handle_offer(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Offer::parse_full(parser)?;
+        let ev = Offer::parse_full(parser)?;
+        self.mime_types.borrow_mut().push(ev.mime_type.to_string());
         Ok(())
     }
 
     fn handle_source_actions(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = SourceActions::parse_full(parser)?;
+        let ev = SourceActions::parse_full(parser)?;
+        self.source_actions.set(ev.source_actions);
         Ok(())
     }
 
     fn handle_action(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Action::parse_full(parser)?;
+        let ev = Action::parse_full(parser)?;
+        self.action.set(ev.dnd_action);
         Ok(())
     }
 }
@@ -16,6 +16,8 @@ pub struct TestDataSource {
     pub tran: Rc<TestTransport>,
     pub destroyed: Cell<bool>,
     pub sends: TEEH<(String, Rc<OwnedFd>)>,
+    pub targets: TEEH<Option<String>>,
+    pub dnd_finished: TEEH<()>,
 }
 
 impl TestDataSource {
@@ -45,7 +47,8 @@ impl TestDataSource {
     }
 
     fn handle_target(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Target::parse_full(parser)?;
+        let ev = Target::parse_full(parser)?;
+        self.targets.push(ev.mime_type.map(|mt| mt.to_string()));
         Ok(())
     }
 
@@ -67,6 +70,7 @@ impl TestDataSource {
 
     fn handle_dnd_finished(&self, parser: MsgParser<'_, '_>) -> TestResult {
         let _ev = DndFinished::parse_full(parser)?;
+        self.dnd_finished.push(());
         Ok(())
     }
 
@@ -20,8 +20,10 @@ pub struct TestDisplay {
 impl TestDisplay {
     fn handle_error(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = Error::parse_full(parser)?;
-        let msg = format!("Compositor sent an error: {}", ev.message);
-        self.tran.error(&msg);
+        if !self.tran.expect_error.get() {
+            let msg = format!("Compositor sent an error: {}", ev.message);
+            self.tran.error(&msg);
+        }
         self.tran.kill();
         Ok(())
     }
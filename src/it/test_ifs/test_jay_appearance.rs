@@ -0,0 +1,49 @@
+use {
+    crate::{
+        it::{test_error::TestResult, test_object::TestObject, testrun::ParseFull},
+        theme::Color,
+        utils::buffd::MsgParser,
+        wire::{jay_appearance::*, JayAppearanceId},
+    },
+    std::cell::{Cell, RefCell},
+};
+
+pub struct TestJayAppearance {
+    pub id: JayAppearanceId,
+    pub color_scheme: Cell<u32>,
+    pub accent_color: Cell<Color>,
+    pub done: RefCell<Vec<(u32, Color)>>,
+}
+
+impl TestJayAppearance {
+    fn handle_color_scheme(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = ColorScheme::parse_full(parser)?;
+        self.color_scheme.set(ev.scheme);
+        Ok(())
+    }
+
+    fn handle_accent_color(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = AccentColor::parse_full(parser)?;
+        self.accent_color
+            .set(Color::from_u32_rgba_premultiplied(ev.r, ev.g, ev.b, ev.a));
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        self.done
+            .borrow_mut()
+            .push((self.color_scheme.get(), self.accent_color.get()));
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayAppearance, JayAppearance;
+
+    COLOR_SCHEME => handle_color_scheme,
+    ACCENT_COLOR => handle_accent_color,
+    DONE => handle_done,
+}
+
+impl TestObject for TestJayAppearance {}
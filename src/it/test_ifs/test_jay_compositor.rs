@@ -3,16 +3,31 @@ use {
         client::ClientId,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_screenshot::TestJayScreenshot,
+            test_ifs::{
+                test_jay_appearance::TestJayAppearance,
+                test_jay_global_shortcuts::TestJayGlobalShortcuts,
+                test_jay_input_injector::TestJayInputInjector,
+                test_jay_output::TestJayOutput,
+                test_jay_screencast::TestJayScreencast,
+                test_jay_select_toplevel::TestJaySelectToplevel,
+                test_jay_select_workspace::TestJaySelectWorkspace,
+                test_jay_toplevel::TestJayToplevel,
+                test_jay_tree::{TestJayTree, TestTreeNode},
+                test_jay_workspace::TestJayWorkspace,
+                test_screenshot::TestJayScreenshot,
+                test_seat::TestSeat,
+                test_wl_output::TestWlOutput,
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
         },
+        theme::Color,
         utils::{buffd::MsgParser, cell_ext::CellExt},
         wire::{
             jay_compositor::{self, *},
             jay_screenshot::Dmabuf,
-            JayCompositorId,
+            jay_tree, JayCompositorId,
         },
     },
     std::{cell::Cell, rc::Rc},
@@ -61,6 +76,30 @@ impl TestJayCompositor {
         })?;
         self.tran.add_obj(js.clone())?;
         self.tran.sync().await;
+        Self::screenshot_result(&js)
+    }
+
+    pub async fn take_toplevel_screenshot(
+        &self,
+        toplevel: &TestJayToplevel,
+        include_cursor: bool,
+    ) -> Result<Dmabuf, TestError> {
+        let js = Rc::new(TestJayScreenshot {
+            id: self.tran.id(),
+            result: Cell::new(None),
+        });
+        self.tran.send(TakeToplevelScreenshot {
+            self_id: self.id,
+            id: js.id,
+            toplevel: toplevel.id,
+            include_cursor: include_cursor as _,
+        })?;
+        self.tran.add_obj(js.clone())?;
+        self.tran.sync().await;
+        Self::screenshot_result(&js)
+    }
+
+    fn screenshot_result(js: &TestJayScreenshot) -> Result<Dmabuf, TestError> {
         match js.result.take() {
             Some(Ok(res)) => Ok(res),
             Some(Err(res)) => bail!("Compositor could not take a screenshot: {}", res),
@@ -68,6 +107,174 @@ impl TestJayCompositor {
         }
     }
 
+    pub async fn get_tree(&self) -> Result<Vec<TestTreeNode>, TestError> {
+        let jt = Rc::new(TestJayTree {
+            id: self.tran.id(),
+            nodes: Default::default(),
+            done: Cell::new(false),
+        });
+        self.tran.send(GetTree {
+            self_id: self.id,
+            id: jt.id,
+        })?;
+        self.tran.add_obj(jt.clone())?;
+        self.tran.sync().await;
+        self.tran.send(jay_tree::Destroy { self_id: jt.id })?;
+        if !jt.done.get() {
+            bail!("Compositor did not send the tree");
+        }
+        Ok(jt.nodes.take())
+    }
+
+    pub async fn find_toplevel(
+        &self,
+        app_id: &str,
+    ) -> Result<Option<Rc<TestJayToplevel>>, TestError> {
+        let obj = Rc::new(TestJaySelectToplevel {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            done: Cell::new(false),
+            toplevel: Default::default(),
+        });
+        self.tran.send(FindToplevel {
+            self_id: self.id,
+            id: obj.id,
+            app_id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        if !obj.done.get() {
+            bail!("Compositor did not send a toplevel");
+        }
+        Ok(obj.toplevel.take())
+    }
+
+    pub async fn find_workspace(
+        &self,
+        name: &str,
+    ) -> Result<Option<Rc<TestJayWorkspace>>, TestError> {
+        let obj = Rc::new(TestJaySelectWorkspace {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            done: Cell::new(false),
+            workspace: Default::default(),
+        });
+        self.tran.send(FindWorkspace {
+            self_id: self.id,
+            id: obj.id,
+            name,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        if !obj.done.get() {
+            bail!("Compositor did not send a workspace");
+        }
+        Ok(obj.workspace.take())
+    }
+
+    pub async fn create_global_shortcuts(
+        &self,
+        seat: &TestSeat,
+    ) -> Result<Rc<TestJayGlobalShortcuts>, TestError> {
+        let obj = Rc::new(TestJayGlobalShortcuts {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            activated: Default::default(),
+            deactivated: Default::default(),
+            recorded: Default::default(),
+        });
+        self.tran.send(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: obj.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        Ok(obj)
+    }
+
+    pub async fn create_screencast(&self) -> Result<Rc<TestJayScreencast>, TestError> {
+        let obj = Rc::new(TestJayScreencast {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Default::default(),
+            server_destroyed: Default::default(),
+            pending_planes: Default::default(),
+            pending_buffers: Default::default(),
+            buffers: Default::default(),
+            buffers_serial: Default::default(),
+            ready: Default::default(),
+            missed_frame: Default::default(),
+            cursor_image: Default::default(),
+            cursor_position: Default::default(),
+        });
+        self.tran.send(CreateScreencast {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        Ok(obj)
+    }
+
+    pub async fn create_input_injector(
+        &self,
+        name: &str,
+        capabilities: u32,
+    ) -> Result<Rc<TestJayInputInjector>, TestError> {
+        let obj = Rc::new(TestJayInputInjector {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Default::default(),
+        });
+        self.tran.send(CreateInputInjector {
+            self_id: self.id,
+            id: obj.id,
+            name,
+            capabilities,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        Ok(obj)
+    }
+
+    pub async fn get_output(&self, output: &TestWlOutput) -> Result<Rc<TestJayOutput>, TestError> {
+        let obj = Rc::new(TestJayOutput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Default::default(),
+            server_destroyed: Default::default(),
+            linear_id: Default::default(),
+        });
+        self.tran.send(GetOutput {
+            self_id: self.id,
+            id: obj.id,
+            output: output.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        if obj.linear_id.get().is_none() {
+            bail!("Compositor did not send the linear id of the output");
+        }
+        Ok(obj)
+    }
+
+    pub async fn watch_appearance(&self) -> Result<Rc<TestJayAppearance>, TestError> {
+        let obj = Rc::new(TestJayAppearance {
+            id: self.tran.id(),
+            color_scheme: Cell::new(0),
+            accent_color: Cell::new(Color::SOLID_BLACK),
+            done: Default::default(),
+        });
+        self.tran.send(WatchAppearance {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        self.tran.add_obj(obj.clone())?;
+        self.tran.sync().await;
+        Ok(obj)
+    }
+
     fn handle_client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = jay_compositor::ClientId::parse_full(parser)?;
         self.client_id.set(Some(ClientId::from_raw(ev.client_id)));
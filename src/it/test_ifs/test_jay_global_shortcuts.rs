@@ -0,0 +1,64 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    std::{cell::RefCell, rc::Rc},
+};
+
+pub struct TestJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub tran: Rc<TestTransport>,
+    pub activated: RefCell<Vec<u32>>,
+    pub deactivated: RefCell<Vec<u32>>,
+    pub recorded: RefCell<Vec<(u32, u32)>>,
+}
+
+impl TestJayGlobalShortcuts {
+    pub fn bind(&self, shortcut: u32, mods: u32, keysym: u32) -> TestResult {
+        self.tran.send(Bind {
+            self_id: self.id,
+            shortcut,
+            mods,
+            keysym,
+        })?;
+        Ok(())
+    }
+
+    pub fn record(&self) -> TestResult {
+        self.tran.send(Record { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_activated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Activated::parse_full(parser)?;
+        self.activated.borrow_mut().push(ev.shortcut);
+        Ok(())
+    }
+
+    fn handle_deactivated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Deactivated::parse_full(parser)?;
+        self.deactivated.borrow_mut().push(ev.shortcut);
+        Ok(())
+    }
+
+    fn handle_recorded(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Recorded::parse_full(parser)?;
+        self.recorded.borrow_mut().push((ev.mods, ev.keysym));
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayGlobalShortcuts, JayGlobalShortcuts;
+
+    ACTIVATED => handle_activated,
+    DEACTIVATED => handle_deactivated,
+    RECORDED => handle_recorded,
+}
+
+impl TestObject for TestJayGlobalShortcuts {}
@@ -0,0 +1,102 @@
+use {
+    crate::{
+        backend::KeyState,
+        fixed::Fixed,
+        it::{
+            test_error::TestResult, test_ifs::test_jay_output::TestJayOutput,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{jay_input_injector::*, JayInputInjectorId},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestJayInputInjector {
+    pub id: JayInputInjectorId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestJayInputInjector {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn motion_absolute(&self, output: &TestJayOutput, x: Fixed, y: Fixed) -> TestResult {
+        self.tran.send(MotionAbsolute {
+            self_id: self.id,
+            output: output.id,
+            x,
+            y,
+        })?;
+        Ok(())
+    }
+
+    pub fn button(&self, button: u32, state: KeyState) -> TestResult {
+        self.tran.send(Button {
+            self_id: self.id,
+            button,
+            state: key_state(state),
+        })?;
+        Ok(())
+    }
+
+    pub fn key(&self, key: u32, state: KeyState) -> TestResult {
+        self.tran.send(Key {
+            self_id: self.id,
+            key,
+            state: key_state(state),
+        })?;
+        Ok(())
+    }
+
+    pub fn keysym(&self, keysym: u32, state: KeyState) -> TestResult {
+        self.tran.send(Keysym {
+            self_id: self.id,
+            keysym,
+            state: key_state(state),
+        })?;
+        Ok(())
+    }
+
+    pub fn touch_down(&self, output: &TestJayOutput, id: i32, x: Fixed, y: Fixed) -> TestResult {
+        self.tran.send(TouchDown {
+            self_id: self.id,
+            output: output.id,
+            id,
+            x,
+            y,
+        })?;
+        Ok(())
+    }
+
+    pub fn touch_up(&self, id: i32) -> TestResult {
+        self.tran.send(TouchUp {
+            self_id: self.id,
+            id,
+        })?;
+        Ok(())
+    }
+}
+
+fn key_state(state: KeyState) -> u32 {
+    match state {
+        KeyState::Released => 0,
+        KeyState::Pressed => 1,
+    }
+}
+
+impl Drop for TestJayInputInjector {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestJayInputInjector, JayInputInjector;
+}
+
+impl TestObject for TestJayInputInjector {}
@@ -0,0 +1,183 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_ifs::test_jay_toplevel::TestJayToplevel,
+            test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_screencast::*, JayScreencastId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    uapi::OwnedFd,
+};
+
+pub struct TestJayScreencast {
+    pub id: JayScreencastId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub server_destroyed: Cell<bool>,
+    pub pending_planes: RefCell<Vec<(Rc<OwnedFd>, u32, u32)>>,
+    pub pending_buffers: RefCell<Vec<TestScreencastBuffer>>,
+    pub buffers: RefCell<Vec<TestScreencastBuffer>>,
+    pub buffers_serial: Cell<Option<u32>>,
+    pub ready: TEEH<u32>,
+    pub missed_frame: TEEH<()>,
+    pub cursor_image: TEEH<CursorImage>,
+    pub cursor_position: TEEH<CursorPosition>,
+}
+
+#[derive(Clone)]
+pub struct TestScreencastBuffer {
+    pub fd: Rc<OwnedFd>,
+    pub offset: u32,
+    pub stride: u32,
+    pub format: u32,
+    pub modifier: u64,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl TestJayScreencast {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_toplevel(&self, toplevel: &TestJayToplevel) -> TestResult {
+        self.tran.send(SetToplevel {
+            self_id: self.id,
+            id: toplevel.id,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_use_linear_buffers(&self, use_linear: bool) -> TestResult {
+        self.tran.send(SetUseLinearBuffers {
+            self_id: self.id,
+            use_linear: use_linear as _,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_running(&self, running: bool) -> TestResult {
+        self.tran.send(SetRunning {
+            self_id: self.id,
+            running: running as _,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_cursor_mode(&self, mode: u32) -> TestResult {
+        self.tran.send(SetCursorMode {
+            self_id: self.id,
+            mode,
+        })?;
+        Ok(())
+    }
+
+    pub fn configure(&self) -> TestResult {
+        self.tran.send(Configure { self_id: self.id })?;
+        Ok(())
+    }
+
+    pub fn ack_buffers(&self, serial: u32) -> TestResult {
+        self.tran.send(AckBuffers {
+            self_id: self.id,
+            serial,
+        })?;
+        Ok(())
+    }
+
+    fn handle_plane(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Plane::parse_full(parser)?;
+        self.pending_planes
+            .borrow_mut()
+            .push((ev.fd, ev.offset, ev.stride));
+        Ok(())
+    }
+
+    fn handle_buffer(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Buffer::parse_full(parser)?;
+        let mut planes = self.pending_planes.borrow_mut();
+        if planes.len() != 1 {
+            bail!("Buffer has {} planes", planes.len());
+        }
+        let (fd, offset, stride) = planes.pop().unwrap();
+        self.pending_buffers
+            .borrow_mut()
+            .push(TestScreencastBuffer {
+                fd,
+                offset,
+                stride,
+                format: ev.format,
+                modifier: ev.modifier,
+                width: ev.width,
+                height: ev.height,
+            });
+        Ok(())
+    }
+
+    fn handle_buffers_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = BuffersDone::parse_full(parser)?;
+        *self.buffers.borrow_mut() = self.pending_buffers.take();
+        self.buffers_serial.set(Some(ev.serial));
+        Ok(())
+    }
+
+    fn handle_ready(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Ready::parse_full(parser)?;
+        self.ready.push(ev.idx);
+        Ok(())
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Destroyed::parse_full(parser)?;
+        self.server_destroyed.set(true);
+        Ok(())
+    }
+
+    fn handle_missed_frame(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = MissedFrame::parse_full(parser)?;
+        self.missed_frame.push(());
+        Ok(())
+    }
+
+    fn handle_cursor_image(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = CursorImage::parse_full(parser)?;
+        self.cursor_image.push(ev);
+        Ok(())
+    }
+
+    fn handle_cursor_position(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = CursorPosition::parse_full(parser)?;
+        self.cursor_position.push(ev);
+        Ok(())
+    }
+}
+
+impl Drop for TestJayScreencast {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestJayScreencast, JayScreencast;
+
+    PLANE => handle_plane,
+    BUFFER => handle_buffer,
+    BUFFERS_DONE => handle_buffers_done,
+    READY => handle_ready,
+    DESTROYED => handle_destroyed,
+    MISSED_FRAME => handle_missed_frame,
+    CURSOR_IMAGE => handle_cursor_image,
+    CURSOR_POSITION => handle_cursor_position,
+}
+
+impl TestObject for TestJayScreencast {}
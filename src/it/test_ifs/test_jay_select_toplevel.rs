@@ -0,0 +1,47 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_ifs::test_jay_toplevel::TestJayToplevel,
+            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_select_toplevel::*, JaySelectToplevelId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestJaySelectToplevel {
+    pub id: JaySelectToplevelId,
+    pub tran: Rc<TestTransport>,
+    pub done: Cell<bool>,
+    pub toplevel: RefCell<Option<Rc<TestJayToplevel>>>,
+}
+
+impl TestJaySelectToplevel {
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Done::parse_full(parser)?;
+        self.done.set(true);
+        if ev.id.is_some() {
+            let tl = Rc::new(TestJayToplevel {
+                id: ev.id,
+                destroyed: Cell::new(false),
+                minimized: Cell::new(false),
+                app_id: Default::default(),
+            });
+            self.tran.add_obj(tl.clone())?;
+            *self.toplevel.borrow_mut() = Some(tl);
+        }
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJaySelectToplevel, JaySelectToplevel;
+
+    DONE => handle_done,
+}
+
+impl TestObject for TestJaySelectToplevel {}
@@ -0,0 +1,52 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_ifs::test_jay_workspace::TestJayWorkspace,
+            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_select_workspace::*, JaySelectWorkspaceId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestJaySelectWorkspace {
+    pub id: JaySelectWorkspaceId,
+    pub tran: Rc<TestTransport>,
+    pub done: Cell<bool>,
+    pub workspace: RefCell<Option<Rc<TestJayWorkspace>>>,
+}
+
+impl TestJaySelectWorkspace {
+    fn handle_cancelled(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Cancelled::parse_full(parser)?;
+        self.done.set(true);
+        Ok(())
+    }
+
+    fn handle_selected(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Selected::parse_full(parser)?;
+        self.done.set(true);
+        let ws = Rc::new(TestJayWorkspace {
+            id: ev.id,
+            destroyed: Cell::new(false),
+            name: Default::default(),
+            visible: Cell::new(false),
+        });
+        self.tran.add_obj(ws.clone())?;
+        *self.workspace.borrow_mut() = Some(ws);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJaySelectWorkspace, JaySelectWorkspace;
+
+    CANCELLED => handle_cancelled,
+    SELECTED => handle_selected,
+}
+
+impl TestObject for TestJaySelectWorkspace {}
@@ -0,0 +1,45 @@
+use {
+    crate::{
+        it::{test_error::TestResult, test_object::TestObject, testrun::ParseFull},
+        utils::buffd::MsgParser,
+        wire::{jay_toplevel::*, JayToplevelId},
+    },
+    std::cell::{Cell, RefCell},
+};
+
+pub struct TestJayToplevel {
+    pub id: JayToplevelId,
+    pub destroyed: Cell<bool>,
+    pub minimized: Cell<bool>,
+    pub app_id: RefCell<String>,
+}
+
+impl TestJayToplevel {
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Destroyed::parse_full(parser)?;
+        self.destroyed.set(true);
+        Ok(())
+    }
+
+    fn handle_minimized(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Minimized::parse_full(parser)?;
+        self.minimized.set(ev.minimized != 0);
+        Ok(())
+    }
+
+    fn handle_app_id(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = AppId::parse_full(parser)?;
+        *self.app_id.borrow_mut() = ev.app_id.to_string();
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayToplevel, JayToplevel;
+
+    DESTROYED => handle_destroyed,
+    MINIMIZED => handle_minimized,
+    APP_ID => handle_app_id,
+}
+
+impl TestObject for TestJayToplevel {}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, testrun::ParseFull},
+        utils::buffd::MsgParser,
+        wire::{jay_tree::*, JayTreeId},
+    },
+    std::cell::{Cell, RefCell},
+};
+
+pub struct TestTreeNode {
+    pub id: u32,
+    pub parent: u32,
+    pub kind: u32,
+    pub name: Option<String>,
+    pub title: Option<String>,
+}
+
+pub struct TestJayTree {
+    pub id: JayTreeId,
+    pub nodes: RefCell<Vec<TestTreeNode>>,
+    pub done: Cell<bool>,
+}
+
+impl TestJayTree {
+    fn handle_node(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Node::parse_full(parser)?;
+        self.nodes.borrow_mut().push(TestTreeNode {
+            id: ev.id,
+            parent: ev.parent,
+            kind: ev.kind,
+            name: None,
+            title: None,
+        });
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Name::parse_full(parser)?;
+        if let Some(node) = self.nodes.borrow_mut().last_mut() {
+            node.name = Some(ev.name.to_string());
+        }
+        Ok(())
+    }
+
+    fn handle_container(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Container::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_window(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Window::parse_full(parser)?;
+        if let Some(node) = self.nodes.borrow_mut().last_mut() {
+            node.title = Some(ev.title.to_string());
+        }
+        Ok(())
+    }
+
+    fn handle_client_info(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ClientInfo::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        self.done.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayTree, JayTree;
+
+    NODE => handle_node,
+    NAME => handle_name,
+    CONTAINER => handle_container,
+    WINDOW => handle_window,
+    CLIENT_INFO => handle_client_info,
+    DONE => handle_done,
+}
+
+impl TestObject for TestJayTree {}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        it::{test_error::TestResult, test_object::TestObject, testrun::ParseFull},
+        utils::buffd::MsgParser,
+        wire::{jay_workspace::*, JayWorkspaceId},
+    },
+    std::cell::{Cell, RefCell},
+};
+
+pub struct TestJayWorkspace {
+    pub id: JayWorkspaceId,
+    pub destroyed: Cell<bool>,
+    pub name: RefCell<String>,
+    pub visible: Cell<bool>,
+}
+
+impl TestJayWorkspace {
+    fn handle_linear_id(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = LinearId::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Name::parse_full(parser)?;
+        *self.name.borrow_mut() = ev.name.to_string();
+        Ok(())
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Destroyed::parse_full(parser)?;
+        self.destroyed.set(true);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_output(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Output::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_visible(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Visible::parse_full(parser)?;
+        self.visible.set(ev.visible != 0);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayWorkspace, JayWorkspace;
+
+    LINEAR_ID => handle_linear_id,
+    NAME => handle_name,
+    DESTROYED => handle_destroyed,
+    DONE => handle_done,
+    OUTPUT => handle_output,
+    VISIBLE => handle_visible,
+}
+
+impl TestObject for TestJayWorkspace {}
@@ -0,0 +1,40 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_ifs::test_output_mode::TestOutputMode,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{zwlr_output_configuration_head_v1::*, ZwlrOutputConfigurationHeadV1Id},
+    },
+    std::rc::Rc,
+};
+
+pub struct TestOutputConfigurationHead {
+    pub id: ZwlrOutputConfigurationHeadV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestOutputConfigurationHead {
+    pub fn set_mode(&self, mode: &TestOutputMode) -> TestResult {
+        self.tran.send(SetMode {
+            self_id: self.id,
+            mode: mode.id,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_position(&self, x: i32, y: i32) -> TestResult {
+        self.tran.send(SetPosition {
+            self_id: self.id,
+            x,
+            y,
+        })?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutputConfigurationHead, ZwlrOutputConfigurationHeadV1;
+}
+
+impl TestObject for TestOutputConfigurationHead {}
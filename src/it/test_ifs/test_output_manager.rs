@@ -0,0 +1,97 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::{
+                test_output_configuration::TestOutputConfiguration,
+                test_output_head::TestOutputHead,
+            },
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{zwlr_output_manager_v1::*, ZwlrOutputManagerV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestOutputManager {
+    pub id: ZwlrOutputManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub heads: RefCell<Vec<Rc<TestOutputHead>>>,
+    pub serial: Cell<Option<u32>>,
+}
+
+impl TestOutputManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+            heads: Default::default(),
+            serial: Default::default(),
+        }
+    }
+
+    pub fn create_configuration(&self) -> TestResult<Rc<TestOutputConfiguration>> {
+        let Some(serial) = self.serial.get() else {
+            bail!("Compositor has not yet sent a done event");
+        };
+        let obj = Rc::new(TestOutputConfiguration {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            succeeded: Cell::new(false),
+            failed: Cell::new(false),
+            cancelled: Cell::new(false),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(CreateConfiguration {
+            self_id: self.id,
+            id: obj.id,
+            serial,
+        })?;
+        Ok(obj)
+    }
+
+    fn handle_head(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Head::parse_full(parser)?;
+        let head = Rc::new(TestOutputHead {
+            id: ev.head,
+            tran: self.tran.clone(),
+            name: Default::default(),
+            modes: Default::default(),
+            enabled: Cell::new(false),
+            position: Cell::new((0, 0)),
+            finished: Cell::new(false),
+        });
+        self.tran.add_obj(head.clone())?;
+        self.heads.borrow_mut().push(head);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Done::parse_full(parser)?;
+        self.heads.borrow_mut().retain(|h| !h.finished.get());
+        self.serial.set(Some(ev.serial));
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Finished::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutputManager, ZwlrOutputManagerV1;
+
+    HEAD => handle_head,
+    DONE => handle_done,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestOutputManager {}
@@ -0,0 +1,49 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, testrun::ParseFull},
+        utils::buffd::MsgParser,
+        wire::{zwlr_output_mode_v1::*, ZwlrOutputModeV1Id},
+    },
+    std::cell::Cell,
+};
+
+pub struct TestOutputMode {
+    pub id: ZwlrOutputModeV1Id,
+    pub size: Cell<(i32, i32)>,
+    pub refresh: Cell<i32>,
+}
+
+impl TestOutputMode {
+    fn handle_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Size::parse_full(parser)?;
+        self.size.set((ev.width, ev.height));
+        Ok(())
+    }
+
+    fn handle_refresh(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Refresh::parse_full(parser)?;
+        self.refresh.set(ev.refresh);
+        Ok(())
+    }
+
+    fn handle_preferred(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Preferred::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Finished::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutputMode, ZwlrOutputModeV1;
+
+    SIZE => handle_size,
+    REFRESH => handle_refresh,
+    PREFERRED => handle_preferred,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestOutputMode {}
@@ -21,6 +21,7 @@ pub struct TestPointer {
     pub enter: TEEH<Enter>,
     pub motion: TEEH<Motion>,
     pub button: TEEH<Button>,
+    pub axis: TEEH<Axis>,
     pub axis_relative_direction: TEEH<AxisRelativeDirection>,
 }
 
@@ -74,7 +75,8 @@ impl TestPointer {
     }
 
     fn handle_axis(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Axis::parse_full(parser)?;
+        let ev = Axis::parse_full(parser)?;
+        self.axis.push(ev);
         Ok(())
     }
 
@@ -12,8 +12,8 @@ use {
                 test_data_device_manager::TestDataDeviceManager, test_dmabuf::TestDmabuf,
                 test_ext_foreign_toplevel_list::TestExtForeignToplevelList,
                 test_input_method_manager::TestInputMethodManager,
-                test_jay_compositor::TestJayCompositor, test_shm::TestShm,
-                test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
+                test_jay_compositor::TestJayCompositor, test_output_manager::TestOutputManager,
+                test_shm::TestShm, test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
                 test_subcompositor::TestSubcompositor, test_syncobj_manager::TestSyncobjManager,
                 test_text_input_manager::TestTextInputManager,
                 test_toplevel_drag_manager::TestToplevelDragManager,
@@ -58,6 +58,7 @@ pub struct TestRegistrySingletons {
     pub zwp_virtual_keyboard_manager_v1: u32,
     pub zwp_input_method_manager_v2: u32,
     pub zwp_text_input_manager_v3: u32,
+    pub zwlr_output_manager_v1: u32,
 }
 
 pub struct TestRegistry {
@@ -85,6 +86,7 @@ pub struct TestRegistry {
     pub virtual_keyboard_manager: CloneCell<Option<Rc<TestVirtualKeyboardManager>>>,
     pub input_method_manager: CloneCell<Option<Rc<TestInputMethodManager>>>,
     pub text_input_manager: CloneCell<Option<Rc<TestTextInputManager>>>,
+    pub output_manager: CloneCell<Option<Rc<TestOutputManager>>>,
     pub seats: CopyHashMap<GlobalName, Rc<WlSeatGlobal>>,
 }
 
@@ -156,6 +158,7 @@ impl TestRegistry {
             zwp_virtual_keyboard_manager_v1,
             zwp_input_method_manager_v2,
             zwp_text_input_manager_v3,
+            zwlr_output_manager_v1,
         };
         self.singletons.set(Some(singletons.clone()));
         Ok(singletons)
@@ -165,7 +168,7 @@ impl TestRegistry {
         get_jay_compositor,
         jay_compositor,
         jay_compositor,
-        1,
+        2,
         TestJayCompositor
     );
     create_singleton!(get_compositor, compositor, wl_compositor, 6, TestCompositor);
@@ -271,6 +274,13 @@ impl TestRegistry {
         1,
         TestTextInputManager
     );
+    create_singleton!(
+        get_output_manager,
+        output_manager,
+        zwlr_output_manager_v1,
+        4,
+        TestOutputManager
+    );
 
     pub fn bind<O: TestObject>(
         &self,
@@ -51,6 +51,36 @@ impl TestSurface {
         Ok(())
     }
 
+    pub fn set_buffer_scale(&self, scale: i32) -> TestResult {
+        self.tran.send(SetBufferScale {
+            self_id: self.id,
+            scale,
+        })?;
+        Ok(())
+    }
+
+    pub fn damage(&self, x: i32, y: i32, width: i32, height: i32) -> TestResult {
+        self.tran.send(Damage {
+            self_id: self.id,
+            x,
+            y,
+            width,
+            height,
+        })?;
+        Ok(())
+    }
+
+    pub fn damage_buffer(&self, x: i32, y: i32, width: i32, height: i32) -> TestResult {
+        self.tran.send(DamageBuffer {
+            self_id: self.id,
+            x,
+            y,
+            width,
+            height,
+        })?;
+        Ok(())
+    }
+
     pub fn set_input_region(&self, region: &TestRegion) -> TestResult {
         self.tran.send(SetInputRegion {
             self_id: self.id,
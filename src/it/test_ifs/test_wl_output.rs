@@ -0,0 +1,80 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wl_output::*, WlOutputId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestWlOutput {
+    pub id: WlOutputId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub name: RefCell<String>,
+}
+
+impl TestWlOutput {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Release { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_geometry(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Geometry::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_mode(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Mode::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_scale(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Scale::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Name::parse_full(parser)?;
+        *self.name.borrow_mut() = ev.name.to_string();
+        Ok(())
+    }
+
+    fn handle_description(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Description::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+impl Drop for TestWlOutput {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestWlOutput, WlOutput;
+
+    GEOMETRY => handle_geometry,
+    MODE => handle_mode,
+    DONE => handle_done,
+    SCALE => handle_scale,
+    NAME => handle_name,
+    DESCRIPTION => handle_description,
+}
+
+impl TestObject for TestWlOutput {}
@@ -44,6 +44,7 @@ impl TestXdgSurface {
             height: Cell::new(0),
             states: Default::default(),
             close_requested: Cell::new(false),
+            wm_capabilities: Default::default(),
         });
         self.tran.add_obj(core.clone())?;
         self.tran.sync().await;
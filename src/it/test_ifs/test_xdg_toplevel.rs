@@ -28,6 +28,7 @@ pub struct TestXdgToplevelCore {
     pub states: RefCell<AHashSet<u32>>,
 
     pub close_requested: Cell<bool>,
+    pub wm_capabilities: RefCell<Vec<u32>>,
 }
 
 pub struct TestXdgToplevel {
@@ -76,6 +77,19 @@ impl TestXdgToplevelCore {
         Ok(())
     }
 
+    pub fn set_app_id(&self, app_id: &str) -> Result<(), TestError> {
+        self.tran.send(SetAppId {
+            self_id: self.id,
+            app_id,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_minimized(&self) -> Result<(), TestError> {
+        self.tran.send(SetMinimized { self_id: self.id })?;
+        Ok(())
+    }
+
     fn handle_configure(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = Configure::parse_full(parser)?;
         self.width.set(ev.width);
@@ -96,7 +110,8 @@ impl TestXdgToplevelCore {
     }
 
     fn handle_wm_capabilities(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
-        let _ev = WmCapabilities::parse_full(parser)?;
+        let ev = WmCapabilities::parse_full(parser)?;
+        *self.wm_capabilities.borrow_mut() = ev.capabilities.to_vec();
         Ok(())
     }
 }
@@ -44,6 +44,7 @@ pub struct TestTransport {
     pub objects: CopyHashMap<ObjectId, Rc<dyn TestObject>>,
     pub obj_ids: RefCell<Bitfield>,
     pub killed: Cell<bool>,
+    pub expect_error: Cell<bool>,
 }
 
 impl TestTransport {
@@ -73,6 +74,7 @@ impl TestTransport {
             virtual_keyboard_manager: Default::default(),
             input_method_manager: Default::default(),
             text_input_manager: Default::default(),
+            output_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
@@ -104,6 +106,7 @@ impl TestTransport {
     }
 
     pub fn kill(&self) {
+        self.killed.set(true);
         self.outgoing.take();
         self.incoming.take();
         for object in self.objects.lock().drain_values() {
@@ -62,6 +62,10 @@ impl<T> TestExpectedEventHolder<T> {
         })
     }
 
+    pub fn is_expected(&self) -> bool {
+        self.data.is_some()
+    }
+
     pub fn push(&self, t: T) {
         if let Some(data) = self.data.get() {
             data.events.borrow_mut().push_back(t);
@@ -1,10 +1,12 @@
 use {
     crate::{
-        client::{ClientId, RequestParser},
+        client::{ClientCaps, ClientId, RequestParser},
         fixed::Fixed,
         ifs::wl_seat::WlSeatGlobal,
         it::{
-            test_backend::{TestBackend, TestBackendKb, TestBackendMouse, TestConnector},
+            test_backend::{
+                TestBackend, TestBackendKb, TestBackendMouse, TestBackendTouch, TestConnector,
+            },
             test_client::TestClient,
             test_config::TestConfig,
             test_error::{TestError, TestErrorExt},
@@ -20,7 +22,7 @@ use {
         cell::{Cell, RefCell},
         rc::Rc,
     },
-    uapi::c,
+    uapi::{c, OwnedFd},
 };
 
 pub struct TestRun {
@@ -51,6 +53,38 @@ impl TestRun {
             .connect(&socket, &self.server_addr)
             .await
             .with_context(|| "Could not connect to the compositor")?;
+        self.init_client(socket).await
+    }
+
+    /// Creates a client that the compositor treats like the Xwayland client.
+    pub async fn create_xwayland_client(self: &Rc<Self>) -> Result<Rc<TestClient>, TestError> {
+        self.create_xwayland_client2()
+            .await
+            .with_context(|| "Could not create an Xwayland client")
+    }
+
+    async fn create_xwayland_client2(self: &Rc<Self>) -> Result<Rc<TestClient>, TestError> {
+        let (server, client) = uapi::socketpair(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+            .to_os_error()
+            .with_context(|| "Could not create a socketpair")?;
+        self.state.clients.spawn2(
+            self.state.clients.id(),
+            &self.state,
+            Rc::new(server),
+            uapi::getuid(),
+            uapi::getpid(),
+            ClientCaps::all(),
+            ClientCaps::all(),
+            None,
+            true,
+        )?;
+        self.init_client(Rc::new(client)).await
+    }
+
+    async fn init_client(
+        self: &Rc<Self>,
+        socket: Rc<OwnedFd>,
+    ) -> Result<Rc<TestClient>, TestError> {
         let mut obj_ids = Bitfield::default();
         obj_ids.take(0);
         obj_ids.take(1);
@@ -66,6 +100,7 @@ impl TestRun {
             objects: Default::default(),
             obj_ids: RefCell::new(obj_ids),
             killed: Cell::new(false),
+            expect_error: Cell::new(false),
         });
         tran.add_obj(Rc::new(TestDisplay {
             tran: tran.clone(),
@@ -117,6 +152,8 @@ impl TestRun {
             .set_input_device_seat(self.backend.default_kb.common.id, seat.id())?;
         self.cfg
             .set_input_device_seat(self.backend.default_mouse.common.id, seat.id())?;
+        self.cfg
+            .set_input_device_seat(self.backend.default_touch.common.id, seat.id())?;
         self.backend.default_mouse.click(1);
         self.state.eng.yield_now().await;
         self.cfg.show_workspace(seat.id(), "")?;
@@ -125,6 +162,7 @@ impl TestRun {
             output,
             kb: self.backend.default_kb.clone(),
             mouse: self.backend.default_mouse.clone(),
+            touch: self.backend.default_touch.clone(),
             seat,
         })
     }
@@ -151,6 +189,7 @@ pub struct DefaultSetup {
     pub output: Rc<OutputNode>,
     pub kb: Rc<TestBackendKb>,
     pub mouse: Rc<TestBackendMouse>,
+    pub touch: Rc<TestBackendTouch>,
     pub seat: Rc<WlSeatGlobal>,
 }
 
@@ -74,6 +74,35 @@ mod t0039_alpha_modifier;
 mod t0040_virtual_keyboard;
 mod t0041_input_method;
 mod t0042_toplevel_select;
+mod t0043_touch;
+mod t0044_window_rules;
+mod t0045_window_api;
+mod t0046_gaps;
+mod t0047_scratchpad;
+mod t0048_xdnd;
+mod t0049_x_scaling_mode;
+mod t0050_minimize;
+mod t0051_keyboard_resize;
+mod t0052_binding_mode;
+mod t0053_pointer_shortcuts;
+mod t0054_focus_history;
+mod t0055_workspace_navigation;
+mod t0056_json_ipc;
+mod t0057_jay_tree;
+mod t0058_find_targets;
+mod t0059_toplevel_screenshot;
+mod t0060_global_shortcuts;
+mod t0061_appearance;
+mod t0062_touch_multi;
+mod t0063_damage_overflow;
+mod t0064_output_management;
+mod t0065_wl_to_x_dnd;
+mod t0066_x_wire_scale;
+mod t0067_binding_mode_once;
+mod t0068_screencast_cursor_metadata;
+mod t0069_input_injector;
+mod t0070_damage_region;
+mod t0071_output_configuration_atomic;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -135,5 +164,34 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0040_virtual_keyboard,
         t0041_input_method,
         t0042_toplevel_select,
+        t0043_touch,
+        t0044_window_rules,
+        t0045_window_api,
+        t0046_gaps,
+        t0047_scratchpad,
+        t0048_xdnd,
+        t0049_x_scaling_mode,
+        t0050_minimize,
+        t0051_keyboard_resize,
+        t0052_binding_mode,
+        t0053_pointer_shortcuts,
+        t0054_focus_history,
+        t0055_workspace_navigation,
+        t0056_json_ipc,
+        t0057_jay_tree,
+        t0058_find_targets,
+        t0059_toplevel_screenshot,
+        t0060_global_shortcuts,
+        t0061_appearance,
+        t0062_touch_multi,
+        t0063_damage_overflow,
+        t0064_output_management,
+        t0065_wl_to_x_dnd,
+        t0066_x_wire_scale,
+        t0067_binding_mode_once,
+        t0068_screencast_cursor_metadata,
+        t0069_input_injector,
+        t0070_damage_region,
+        t0071_output_configuration_atomic,
     }
 }
@@ -29,6 +29,10 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         },
         events: Default::default(),
         feedback: Default::default(),
+        reject_modes: Default::default(),
+        damage: Default::default(),
+        damage_full: Default::default(),
+        fb: Default::default(),
     });
     let new_monitor_info = MonitorInfo {
         modes: vec![],
@@ -43,6 +47,7 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         width_mm: 0,
         height_mm: 0,
         non_desktop: false,
+        vrr_capable: false,
     };
     run.backend
         .state
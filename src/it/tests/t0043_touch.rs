@@ -0,0 +1,78 @@
+use {
+    crate::{
+        ifs::wl_seat::TOUCH,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let seat = client.get_default_seat().await?;
+    tassert_eq!(seat.seat.caps.get() & TOUCH, TOUCH);
+    let touch = seat.seat.get_touch().await?;
+    let down = touch.down.expect()?;
+    let motion = touch.motion.expect()?;
+    let up = touch.up.expect()?;
+    let frame = touch.frame.expect()?;
+    let cancel = touch.cancel.expect()?;
+
+    let extents = run.state.root.extents.get();
+    let pos = win.surface.surface.server.node_absolute_position();
+    let normed = |x: i32, y: i32| {
+        (
+            (x - extents.x1()) as f64 / extents.width() as f64 + 0.5 / extents.width() as f64,
+            (y - extents.y1()) as f64 / extents.height() as f64 + 0.5 / extents.height() as f64,
+        )
+    };
+
+    let (x, y) = normed(pos.x1() + 10, pos.y1() + 20);
+    ds.touch.down(0, x, y);
+    ds.touch.frame();
+    client.sync().await;
+    let ev = down.next()?;
+    tassert_eq!(ev.surface, win.surface.id);
+    tassert_eq!(ev.id, 0);
+    tassert_eq!(ev.x.round_down(), 10);
+    tassert_eq!(ev.y.round_down(), 20);
+    frame.next()?;
+
+    let (x, y) = normed(pos.x1() + 30, pos.y1() + 40);
+    ds.touch.motion(0, x, y);
+    ds.touch.frame();
+    client.sync().await;
+    let ev = motion.next()?;
+    tassert_eq!(ev.id, 0);
+    tassert_eq!(ev.x.round_down(), 30);
+    tassert_eq!(ev.y.round_down(), 40);
+    frame.next()?;
+
+    ds.touch.up(0);
+    ds.touch.frame();
+    client.sync().await;
+    tassert_eq!(up.next()?.id, 0);
+    frame.next()?;
+
+    ds.touch.motion(0, x, y);
+    ds.touch.frame();
+    client.sync().await;
+    motion.none()?;
+
+    ds.touch.down(1, x, y);
+    ds.touch.frame();
+    ds.touch.cancel(1);
+    client.sync().await;
+    tassert_eq!(down.next()?.id, 1);
+    cancel.next()?;
+    up.none()?;
+
+    Ok(())
+}
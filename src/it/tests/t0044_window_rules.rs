@@ -0,0 +1,52 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    jay_config::{_private::ipc::WindowRuleData, window::WindowType},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    run.cfg.add_window_rule(WindowRuleData {
+        app_id: Some("floating".to_string()),
+        window_type: Some(WindowType::XdgToplevel),
+        floating_size: Some((300, 200)),
+        opacity: Some(0.5),
+        ..Default::default()
+    })?;
+    run.cfg.add_window_rule(WindowRuleData {
+        title: Some("^move me$".to_string()),
+        workspace: Some(run.cfg.get_workspace("2")?),
+        reevaluate: true,
+        ..Default::default()
+    })?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.tl.core.set_app_id("floating")?;
+    win1.map2().await?;
+    let data = win1.tl.server.tl_data();
+    tassert!(data.is_floating.get());
+    tassert_eq!(win1.tl.core.width.get(), 300);
+    tassert_eq!(win1.tl.core.height.get(), 200);
+    tassert_eq!(data.opacity.get(), 0.5);
+
+    let win2 = client.create_window().await?;
+    win2.tl.core.set_title("do not move me")?;
+    win2.map2().await?;
+    let data = win2.tl.server.tl_data();
+    tassert!(!data.is_floating.get());
+    tassert_eq!(data.workspace.get().unwrap().name.borrow().as_str(), "1");
+
+    win2.tl.core.set_title("move me")?;
+    client.sync().await;
+    tassert_eq!(data.workspace.get().unwrap().name.borrow().as_str(), "2");
+
+    Ok(())
+}
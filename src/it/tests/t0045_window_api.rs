@@ -0,0 +1,49 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.tl.core.set_title("first")?;
+    win.map2().await?;
+
+    let windows = run.cfg.windows()?;
+    tassert_eq!(windows.len(), 1);
+    let window = windows[0];
+    tassert!(run.cfg.new_windows.contains(&window));
+    tassert_eq!(run.cfg.focused_window(ds.seat.id())?, window);
+    tassert_eq!(run.cfg.window_title(window)?, "first");
+
+    win.tl.core.set_title("second")?;
+    client.sync().await;
+    tassert!(run.cfg.changed_window_titles.contains(&window));
+    tassert_eq!(run.cfg.window_title(window)?, "second");
+
+    run.cfg.set_window_floating(window, true)?;
+    win.tl.float_parent()?;
+
+    run.cfg
+        .move_window_to_workspace(window, run.cfg.get_workspace("2")?)?;
+    let data = win.tl.server.tl_data();
+    tassert_eq!(data.workspace.get().unwrap().name.borrow().as_str(), "2");
+
+    run.cfg.close_window(window)?;
+    client.sync().await;
+    tassert!(win.tl.core.close_requested.get());
+
+    win.tl.core.destroy()?;
+    client.sync().await;
+    tassert!(run.cfg.closed_windows.contains(&window));
+    tassert!(run.cfg.windows()?.is_empty());
+
+    Ok(())
+}
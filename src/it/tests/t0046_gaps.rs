@@ -0,0 +1,65 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        tree::Node,
+    },
+    jay_config::theme::sized::{INNER_GAP, OUTER_GAP},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.set_size(INNER_GAP, 10)?;
+    run.cfg.set_size(OUTER_GAP, 20)?;
+    run.cfg.set_smart_gaps(true)?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    let ws = ds.output.workspace.get().unwrap();
+    let wpos = ws.position.get();
+    let th = run.state.theme.sizes.title_height.get() + 1;
+
+    tassert_eq!(
+        win1.tl.server.node_absolute_position(),
+        Rect::new_sized(wpos.x1(), wpos.y1() + th, wpos.width(), wpos.height() - th).unwrap()
+    );
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let width = (wpos.width() - 40 - 10) / 2;
+    let height = wpos.height() - 40 - th;
+    tassert_eq!(
+        win1.tl.server.node_absolute_position(),
+        Rect::new_sized(wpos.x1() + 20, wpos.y1() + 20 + th, width, height).unwrap()
+    );
+    tassert_eq!(
+        win2.tl.server.node_absolute_position(),
+        Rect::new_sized(
+            wpos.x1() + 20 + width + 10,
+            wpos.y1() + 20 + th,
+            width,
+            height
+        )
+        .unwrap()
+    );
+
+    run.cfg
+        .set_workspace_outer_gap(run.cfg.get_workspace(&ws.name.borrow())?, Some(0))?;
+    client.sync().await;
+
+    let width = (wpos.width() - 10) / 2;
+    tassert_eq!(
+        win1.tl.server.node_absolute_position(),
+        Rect::new_sized(wpos.x1(), wpos.y1() + th, width, wpos.height() - th).unwrap()
+    );
+
+    Ok(())
+}
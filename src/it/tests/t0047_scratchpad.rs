@@ -0,0 +1,57 @@
+use {
+    crate::{
+        ifs::wl_surface::xdg_surface::xdg_toplevel::STATE_SUSPENDED,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    isnt::std_1::collections::IsntHashSet2Ext,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let data = win2.tl.server.tl_data();
+    tassert!(data.parent.is_some());
+
+    run.cfg.move_to_scratchpad(ds.seat.id())?;
+    client.sync().await;
+
+    tassert!(data.parent.is_none());
+    tassert!(!win2.tl.server.node_visible());
+    tassert!(win2.tl.core.states.borrow().contains(&STATE_SUSPENDED));
+
+    run.cfg.toggle_scratchpad(ds.seat.id(), Some("other"))?;
+    client.sync().await;
+    tassert!(data.parent.is_none());
+
+    run.cfg.toggle_scratchpad(ds.seat.id(), None)?;
+    client.sync().await;
+
+    tassert!(data.parent.is_some());
+    tassert!(data.is_floating.get());
+    tassert!(win2.tl.server.node_visible());
+    tassert!(win2.tl.core.states.borrow().not_contains(&STATE_SUSPENDED));
+
+    let wpos = ds.output.workspace.get().unwrap().position.get();
+    let pos = win2.tl.server.node_absolute_position();
+    tassert!((pos.x1() + pos.x2() - wpos.x1() - wpos.x2()).abs() <= 1);
+
+    run.cfg.toggle_scratchpad(ds.seat.id(), None)?;
+    client.sync().await;
+
+    tassert!(data.parent.is_none());
+    tassert!(win2.tl.core.states.borrow().contains(&STATE_SUSPENDED));
+
+    Ok(())
+}
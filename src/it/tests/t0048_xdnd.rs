@@ -0,0 +1,112 @@
+use {
+    crate::{
+        fixed::Fixed,
+        ifs::ipc::{
+            add_data_source_mime_type,
+            wl_data_device_manager::{DND_COPY, DND_MOVE},
+            x_data_device::{XClipboardIpc, XIpcDevice},
+            x_data_source::XDataSource,
+            IpcLocation, SourceData,
+        },
+        it::{test_error::TestResult, testrun::TestRun},
+        xwayland::XWaylandEvent,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests the wayland side of a drag-and-drop operation started by an X client. The test
+/// plays the part of the X window manager.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let seat = client.get_default_seat().await?;
+    let dev = client.data_device_manager.get_data_device(&seat.seat)?;
+    client.sync().await;
+
+    let xclient = run.create_client().await?;
+    let device = Rc::new(XIpcDevice {
+        id: run.state.xwayland.ipc_device_ids.next(),
+        clipboard: Default::default(),
+        primary_selection: Default::default(),
+        seat: ds.seat.clone(),
+        state: run.state.clone(),
+        client: xclient.server.clone(),
+    });
+    let create_source = || -> TestResult<Rc<XDataSource>> {
+        let source = Rc::new(XDataSource {
+            state: run.state.clone(),
+            device: device.clone(),
+            data: SourceData::new(&xclient.server),
+            location: IpcLocation::Clipboard,
+        });
+        add_data_source_mime_type::<XClipboardIpc>(&source, "text/plain");
+        source.start_dnd(DND_COPY | DND_MOVE)?;
+        Ok(source)
+    };
+    let xwm_events = || {
+        let mut events = vec![];
+        while let Some(event) = run.state.xwayland.queue.try_pop() {
+            events.push(event);
+        }
+        events
+    };
+
+    let data_offer = dev.data_offer.expect()?;
+    let enter = dev.enter.expect()?;
+    let drop = dev.drop.expect()?;
+
+    let source = create_source()?;
+    let id = source.data.id;
+    source.dnd_enter(&win.surface.server, Fixed::from_int(1), Fixed::from_int(2));
+    client.sync().await;
+
+    let offer = data_offer.next()?;
+    tassert_eq!(enter.next()?, (win.surface.id, offer.id));
+    tassert_eq!(offer.mime_types.borrow().clone(), ["text/plain"]);
+    tassert_eq!(offer.source_actions.get(), DND_COPY | DND_MOVE);
+    tassert!(!source.can_drop());
+
+    xwm_events();
+    offer.accept(0, Some("text/plain"))?;
+    offer.set_actions(DND_MOVE, DND_MOVE)?;
+    client.sync().await;
+    tassert!(source.can_drop());
+    tassert_eq!(source.selected_action(), DND_MOVE);
+    tassert_eq!(offer.action.get(), DND_MOVE);
+    let status_sent = xwm_events()
+        .iter()
+        .any(|e| matches!(e, XWaylandEvent::DndStatus { source, .. } if *source == id));
+    tassert!(status_sent);
+
+    tassert!(source.dnd_drop(&win.surface.server));
+    client.sync().await;
+    drop.next()?;
+
+    offer.finish()?;
+    client.sync().await;
+    let finished_sent = xwm_events()
+        .iter()
+        .any(|e| matches!(e, XWaylandEvent::DndFinished { source, .. } if *source == id));
+    tassert!(finished_sent);
+    source.end_dnd();
+
+    let source = create_source()?;
+    let id = source.data.id;
+    source.dnd_enter(&win.surface.server, Fixed::from_int(1), Fixed::from_int(2));
+    client.sync().await;
+    data_offer.next()?;
+
+    xwm_events();
+    tassert!(!source.dnd_drop(&win.surface.server));
+    let cancel_sent = xwm_events()
+        .iter()
+        .any(|e| matches!(e, XWaylandEvent::IpcCancelSource { source, .. } if *source == id));
+    tassert!(cancel_sent);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use {
+    crate::{
+        fixed::Fixed,
+        it::{test_error::TestResult, testrun::TestRun},
+        xwayland::XWaylandEvent,
+    },
+    jay_config::xwayland::XScalingMode,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that the Xwayland wire scale follows the outputs and that coordinates sent to
+/// clients with a wire scale are scaled.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let wire_scale_changed = || {
+        let mut changed = false;
+        while let Some(event) = run.state.xwayland.queue.try_pop() {
+            changed |= matches!(event, XWaylandEvent::WireScaleChanged);
+        }
+        changed
+    };
+
+    run.cfg.set_x_scaling_mode(XScalingMode::Downscaled)?;
+    tassert_eq!(run.state.xwayland.wire_scale.get(), None);
+    tassert!(!wire_scale_changed());
+
+    run.cfg.set_scale(&ds.output, 1.5)?;
+    tassert_eq!(run.state.xwayland.wire_scale.get(), Some(2));
+    tassert!(wire_scale_changed());
+
+    run.cfg.set_x_scaling_mode(XScalingMode::Default)?;
+    tassert_eq!(run.state.xwayland.wire_scale.get(), None);
+    tassert!(wire_scale_changed());
+
+    client.server.wire_scale.set(Some(2));
+    let enter = seat.pointer.enter.expect()?;
+    let motion = seat.pointer.motion.expect()?;
+    let pos = win.surface.server.buffer_abs_pos.get();
+    ds.move_to(pos.x1() + 10, pos.y1() + 20);
+    client.sync().await;
+    let enter = enter.next()?;
+    tassert_eq!(enter.surface_x, Fixed::from_int(20));
+    tassert_eq!(enter.surface_y, Fixed::from_int(40));
+
+    ds.move_to(pos.x1() + 15, pos.y1() + 20);
+    client.sync().await;
+    let motion = motion.next()?;
+    tassert_eq!(motion.surface_x, Fixed::from_int(30));
+    tassert_eq!(motion.surface_y, Fixed::from_int(40));
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use {
+    crate::{
+        ifs::wl_surface::xdg_surface::xdg_toplevel::{CAP_MINIMIZE, STATE_SUSPENDED},
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    isnt::std_1::collections::IsntHashSet2Ext,
+    jay_config::window::Window,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that minimized windows leave the layout of their workspace and can be restored.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let ws = ds.output.workspace.get().unwrap();
+    let workspace = run.cfg.get_workspace(&ws.name.borrow())?;
+    let window = Window(win2.tl.server.node_id().raw() as _);
+    let data = win2.tl.server.tl_data();
+    let width = win1.tl.server.node_absolute_position().width();
+    tassert!(win2
+        .tl
+        .core
+        .wm_capabilities
+        .borrow()
+        .contains(&CAP_MINIMIZE));
+
+    win2.tl.core.set_minimized()?;
+    client.sync().await;
+
+    tassert!(data.is_minimized());
+    tassert!(data.parent.is_none());
+    tassert!(!win2.tl.server.node_visible());
+    tassert!(win2.tl.core.states.borrow().contains(&STATE_SUSPENDED));
+    tassert!(win1.tl.server.node_absolute_position().width() > width);
+    tassert_eq!(run.cfg.workspace_minimized_windows(workspace)?, [window]);
+
+    run.cfg.restore_minimized(ds.seat.id())?;
+    client.sync().await;
+
+    tassert!(!data.is_minimized());
+    tassert!(data.parent.is_some());
+    tassert!(win2.tl.server.node_visible());
+    tassert!(win2.tl.core.states.borrow().not_contains(&STATE_SUSPENDED));
+    tassert_eq!(win1.tl.server.node_absolute_position().width(), width);
+    tassert_eq!(run.cfg.focused_window(ds.seat.id())?, window);
+    tassert!(run.cfg.workspace_minimized_windows(workspace)?.is_empty());
+
+    run.cfg.set_window_minimized(window, true)?;
+    client.sync().await;
+    tassert!(data.is_minimized());
+    tassert!(!ws.is_empty());
+
+    run.cfg.set_window_minimized(window, false)?;
+    client.sync().await;
+    tassert!(!data.is_minimized());
+    tassert!(win2.tl.server.node_visible());
+
+    Ok(())
+}
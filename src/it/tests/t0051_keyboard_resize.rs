@@ -0,0 +1,69 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::{window::Window, Direction, ResizeAmount},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests resizing and moving the focused window via the seat.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let pos1 = win1.tl.server.node_absolute_position();
+    let pos2 = win2.tl.server.node_absolute_position();
+
+    run.cfg
+        .resize(seat, Direction::Left, ResizeAmount::Px(20))?;
+    client.sync().await;
+    tassert_eq!(
+        win1.tl.server.node_absolute_position().width(),
+        pos1.width() - 20
+    );
+    tassert_eq!(win2.tl.server.node_absolute_position().x1(), pos2.x1() - 20);
+    tassert_eq!(win2.tl.server.node_absolute_position().x2(), pos2.x2());
+
+    run.cfg
+        .resize(seat, Direction::Left, ResizeAmount::Px(-20))?;
+    client.sync().await;
+    tassert_eq!(win2.tl.server.node_absolute_position(), pos2);
+
+    let window = Window(win2.tl.server.node_id().raw() as _);
+    run.cfg.set_window_floating(window, true)?;
+    client.sync().await;
+    win2.tl.float_parent()?;
+
+    let output = ds.output.global.pos.get();
+    run.cfg.set_floating_geometry(seat, 100, 50, 300, 200)?;
+    client.sync().await;
+    let pos = win2.tl.server.node_absolute_position();
+    tassert_eq!(pos.position(), (output.x1() + 100, output.y1() + 50));
+    tassert_eq!(pos.size(), (300, 200));
+
+    run.cfg.move_floating(seat, 10, -20)?;
+    client.sync().await;
+    let pos = win2.tl.server.node_absolute_position();
+    tassert_eq!(pos.position(), (output.x1() + 110, output.y1() + 30));
+    tassert_eq!(pos.size(), (300, 200));
+
+    run.cfg
+        .resize(seat, Direction::Down, ResizeAmount::Percent(10.0))?;
+    client.sync().await;
+    let pos = win2.tl.server.node_absolute_position();
+    tassert_eq!(pos.position(), (output.x1() + 110, output.y1() + 30));
+    tassert_eq!(pos.height(), 200 + output.height() / 10);
+
+    Ok(())
+}
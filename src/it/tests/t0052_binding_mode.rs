@@ -0,0 +1,37 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that the active binding mode is shown in the bar next to the status.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    run.state.set_status("status");
+    client.sync().await;
+    tassert!(ds.output.render_data.borrow().binding_mode.is_none());
+
+    run.cfg.set_binding_mode(ds.seat.id(), Some("resize"))?;
+    client.sync().await;
+    tassert_eq!(
+        ds.seat.binding_mode().as_deref().map(|m| m.as_str()),
+        Some("resize")
+    );
+    {
+        let rd = ds.output.render_data.borrow();
+        let status = rd.status.as_ref().unwrap();
+        let mode = rd.binding_mode.as_ref().unwrap();
+        let (width, _) = mode.tex.texture.size();
+        tassert!(mode.tex_x + width < status.tex_x);
+    }
+
+    run.cfg.set_binding_mode(ds.seat.id(), None)?;
+    client.sync().await;
+    tassert_eq!(ds.seat.binding_mode(), None);
+    tassert!(ds.output.render_data.borrow().binding_mode.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use {
+    crate::{
+        backend::{AxisSource, InputEvent, ScrollAxis},
+        fixed::Fixed,
+        ifs::wl_seat::{wl_pointer, BTN_LEFT, BTN_RIGHT},
+        it::{test_error::TestResult, test_utils::test_rect_ext::TestRectExt, testrun::TestRun},
+        time::now_usec,
+        tree::Node,
+    },
+    jay_config::{
+        input::pointer::{self, PointerTrigger},
+        keyboard::mods::Modifiers,
+        Direction,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that bound pointer buttons, scroll directions, and gestures invoke the shortcut
+/// and are not forwarded to clients.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let seat = client.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+
+    let seat_id = ds.seat.id();
+    let invoked = |trigger: PointerTrigger| {
+        run.cfg
+            .invoked_pointer_shortcuts
+            .remove(&(seat_id, Modifiers(0), trigger))
+            .is_some()
+    };
+
+    let right = PointerTrigger::Button(pointer::BTN_RIGHT);
+    run.cfg.add_pointer_shortcut(seat_id, Modifiers(0), right)?;
+    drop(ds.mouse.click(BTN_RIGHT));
+    client.sync().await;
+    tassert!(invoked(right));
+    tassert!(button.next().is_err());
+
+    drop(ds.mouse.click(BTN_LEFT));
+    client.sync().await;
+    tassert!(run.cfg.invoked_pointer_shortcuts.is_empty());
+    tassert_eq!(button.next()?.button, BTN_LEFT);
+
+    let axis = seat.pointer.axis.expect()?;
+    let scroll = PointerTrigger::Scroll(Direction::Down);
+    run.cfg
+        .add_pointer_shortcut(seat_id, Modifiers(0), scroll)?;
+    ds.mouse.scroll(1);
+    client.sync().await;
+    tassert!(invoked(scroll));
+    tassert!(axis.next().is_err());
+    ds.mouse.scroll(-1);
+    client.sync().await;
+    tassert!(run.cfg.invoked_pointer_shortcuts.is_empty());
+    tassert_eq!(axis.next()?.axis, wl_pointer::VERTICAL_SCROLL);
+
+    ds.mouse.scroll_px(10);
+    client.sync().await;
+    tassert!(run.cfg.invoked_pointer_shortcuts.is_empty());
+    ds.mouse.scroll_px(10);
+    client.sync().await;
+    tassert!(invoked(scroll));
+    tassert!(axis.next().is_err());
+
+    ds.mouse.common.event(InputEvent::AxisSource {
+        source: AxisSource::Wheel,
+    });
+    ds.mouse.common.event(InputEvent::Axis120 {
+        dist: 120,
+        axis: ScrollAxis::Vertical,
+        inverted: false,
+    });
+    ds.mouse.common.event(InputEvent::Axis120 {
+        dist: 120,
+        axis: ScrollAxis::Horizontal,
+        inverted: false,
+    });
+    ds.mouse.common.event(InputEvent::AxisFrame {
+        time_usec: now_usec(),
+    });
+    client.sync().await;
+    tassert!(invoked(scroll));
+    tassert_eq!(axis.next()?.axis, wl_pointer::HORIZONTAL_SCROLL);
+    tassert!(axis.next().is_err());
+
+    let swipe = PointerTrigger::Swipe {
+        fingers: 3,
+        direction: Direction::Left,
+    };
+    run.cfg.add_pointer_shortcut(seat_id, Modifiers(0), swipe)?;
+    ds.mouse.common.event(InputEvent::SwipeBegin {
+        time_usec: now_usec(),
+        finger_count: 3,
+    });
+    ds.mouse.common.event(InputEvent::SwipeUpdate {
+        time_usec: now_usec(),
+        dx: Fixed::from_int(-50),
+        dy: Fixed::from_int(10),
+        dx_unaccelerated: Fixed::from_int(-50),
+        dy_unaccelerated: Fixed::from_int(10),
+    });
+    ds.mouse.common.event(InputEvent::SwipeEnd {
+        time_usec: now_usec(),
+        cancelled: false,
+    });
+    client.sync().await;
+    tassert!(invoked(swipe));
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, NodeId},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_LEFTALT: u32 = 56;
+
+/// Tests the focus history of a seat and the window switcher.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+    let id1 = win1.tl.server.node_id();
+    let id2 = win2.tl.server.node_id();
+    let id3 = win3.tl.server.node_id();
+
+    for win in [&win1, &win2, &win3] {
+        ds.seat.focus_toplevel(win.tl.server.clone());
+    }
+    let focused = || -> Option<NodeId> { ds.seat.focused_toplevel().map(|tl| tl.node_id()) };
+    tassert_eq!(focused(), Some(id3));
+
+    ds.seat.focus_last();
+    tassert_eq!(focused(), Some(id2));
+    ds.seat.focus_last();
+    tassert_eq!(focused(), Some(id3));
+
+    let alt = ds.kb.press(KEY_LEFTALT);
+    client.sync().await;
+    ds.seat.focus_next_mru();
+    client.sync().await;
+    tassert_eq!(focused(), Some(id3));
+    tassert_eq!(
+        ds.output
+            .render_data
+            .borrow()
+            .window_switcher
+            .as_ref()
+            .map(|s| s.titles.len()),
+        Some(3)
+    );
+    ds.seat.focus_next_mru();
+    client.sync().await;
+    tassert_eq!(focused(), Some(id3));
+
+    drop(alt);
+    client.sync().await;
+    tassert_eq!(focused(), Some(id1));
+    tassert!(ds.output.render_data.borrow().window_switcher.is_none());
+
+    ds.seat.focus_prev_mru();
+    tassert_eq!(focused(), Some(id2));
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use {
+    crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId, Mode, MonitorInfo},
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests workspace navigation, renaming workspaces, and preferred connectors of
+/// workspaces.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let mut windows = vec![];
+    for name in ["1", "a", "10", "2"] {
+        run.cfg.show_workspace(ds.seat.id(), name)?;
+        let win = client.create_window().await?;
+        win.map2().await?;
+        windows.push(win);
+    }
+    let current = || ds.output.workspace.get().map(|ws| ws.name.borrow().clone());
+    tassert_eq!(current().as_deref(), Some("2"));
+
+    ds.seat.show_adjacent_workspace(true, false);
+    tassert_eq!(current().as_deref(), Some("10"));
+    ds.seat.show_adjacent_workspace(true, false);
+    tassert_eq!(current().as_deref(), Some("a"));
+    ds.seat.show_adjacent_workspace(true, false);
+    tassert_eq!(current().as_deref(), Some("1"));
+    ds.seat.show_adjacent_workspace(false, false);
+    tassert_eq!(current().as_deref(), Some("a"));
+
+    ds.seat.workspace_back_and_forth();
+    tassert_eq!(current().as_deref(), Some("1"));
+    ds.seat.workspace_back_and_forth();
+    tassert_eq!(current().as_deref(), Some("a"));
+
+    run.cfg.rename_workspace(run.cfg.get_workspace("a")?, "b")?;
+    tassert_eq!(current().as_deref(), Some("b"));
+    tassert!(run.state.workspaces.get("a").is_none());
+    tassert!(run.state.workspaces.get("b").is_some());
+    run.cfg.rename_workspace(run.cfg.get_workspace("b")?, "1")?;
+    tassert_eq!(current().as_deref(), Some("b"));
+
+    ds.seat.workspace_back_and_forth();
+    tassert_eq!(current().as_deref(), Some("1"));
+    ds.seat.workspace_back_and_forth();
+    tassert_eq!(current().as_deref(), Some("b"));
+
+    let new_connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        reject_modes: Default::default(),
+        damage: Default::default(),
+        damage_full: Default::default(),
+        fb: Default::default(),
+    });
+    let new_monitor_info = MonitorInfo {
+        modes: vec![],
+        manufacturer: "jay".to_string(),
+        product: "jay second connector".to_string(),
+        serial_number: "".to_string(),
+        initial_mode: Mode {
+            width: 400,
+            height: 400,
+            refresh_rate_millihz: 60000,
+        },
+        width_mm: 0,
+        height_mm: 0,
+        non_desktop: false,
+        vrr_capable: false,
+    };
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(new_connector.clone()));
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Connected(new_monitor_info.clone()));
+    run.state.eng.yield_now().await;
+    let Some(output2) = run.state.root.outputs.get(&new_connector.id) else {
+        bail!("second output does not exist");
+    };
+
+    let ws_output = |name: &str| run.state.workspaces.get(name).map(|ws| ws.output.get().id);
+
+    run.cfg
+        .set_workspace_connector(run.cfg.get_workspace("1")?, Some(&output2))?;
+    tassert_eq!(ws_output("1"), Some(output2.id));
+
+    run.cfg
+        .set_workspace_connector(run.cfg.get_workspace("5")?, Some(&output2))?;
+    run.cfg.show_workspace(ds.seat.id(), "5")?;
+    tassert_eq!(ws_output("5"), Some(output2.id));
+
+    ds.seat.show_adjacent_workspace(true, true);
+    tassert_eq!(current().as_deref(), Some("b"));
+    tassert_eq!(
+        output2
+            .workspace
+            .get()
+            .map(|ws| ws.name.borrow().clone())
+            .as_deref(),
+        Some("1")
+    );
+
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Disconnected);
+    run.state.eng.yield_now().await;
+    tassert_eq!(ws_output("1"), Some(ds.output.id));
+
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Connected(new_monitor_info.clone()));
+    run.state.eng.yield_now().await;
+    let Some(output2) = run.state.root.outputs.get(&new_connector.id) else {
+        bail!("second output does not exist");
+    };
+    tassert_eq!(ws_output("1"), Some(output2.id));
+
+    Ok(())
+}
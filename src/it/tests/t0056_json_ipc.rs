@@ -0,0 +1,184 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+        json_ipc::{
+            messages::{Event, EventType, Reply, Request, WindowChange},
+            tree::{NodeData, TreeNode},
+        },
+        utils::{buf::Buf, oserror::OsErrorExt},
+    },
+    std::{cell::RefCell, rc::Rc},
+    uapi::{c, OwnedFd},
+};
+
+testcase!();
+
+/// Tests requests, commands, and events of the JSON IPC socket.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let ipc = IpcConn::connect(&run).await?;
+
+    let Reply::Workspaces { workspaces } = ipc.request(&Request::GetWorkspaces).await? else {
+        bail!("Expected a workspaces reply");
+    };
+    tassert_eq!(workspaces.len(), 1);
+    tassert_eq!(&workspaces[0].output, &ds.output.global.connector.name);
+    tassert!(workspaces[0].visible);
+
+    let Reply::Tree { tree } = ipc.request(&Request::GetTree).await? else {
+        bail!("Expected a tree reply");
+    };
+    let Some(window) = find_window(&tree) else {
+        bail!("The tree contains no window");
+    };
+    tassert_eq!(window.id, win.tl.server.node_id.raw());
+
+    let reply = ipc
+        .request(&Request::Command {
+            command: "focus-left".to_string(),
+        })
+        .await?;
+    tassert!(matches!(reply, Reply::Success));
+    tassert_eq!(run.cfg.ipc_commands.borrow().last().unwrap(), "focus-left");
+
+    let reply = ipc
+        .request(&Request::Command {
+            command: "fail".to_string(),
+        })
+        .await?;
+    tassert!(matches!(reply, Reply::Error { .. }));
+
+    let reply = ipc
+        .request(&Request::Subscribe {
+            events: vec![EventType::Window],
+        })
+        .await?;
+    tassert!(matches!(reply, Reply::Subscribed));
+
+    win.tl.core.set_title("abc")?;
+    client.sync().await;
+    let Reply::Event(Event::Window { change, window }) = ipc.read().await? else {
+        bail!("Expected a window event");
+    };
+    tassert_eq!(change, WindowChange::Title);
+    let NodeData::Window(data) = &window.data else {
+        bail!("Expected a window node");
+    };
+    tassert_eq!(data.title.as_str(), "abc");
+
+    // A subscriber that does not read its events is disconnected.
+    let title = "a".repeat(3000);
+    for i in 0..3000 {
+        win.tl.core.set_title(&format!("{title}{i}"))?;
+        if i % 100 == 0 {
+            client.sync().await;
+        }
+    }
+    client.sync().await;
+    let mut disconnected = false;
+    for _ in 0..3000 {
+        if ipc.read().await.is_err() {
+            disconnected = true;
+            break;
+        }
+    }
+    tassert!(disconnected);
+
+    Ok(())
+}
+
+fn find_window(node: &TreeNode) -> Option<&TreeNode> {
+    if let NodeData::Window(_) = node.data {
+        return Some(node);
+    }
+    node.nodes.iter().find_map(find_window)
+}
+
+struct IpcConn {
+    run: Rc<TestRun>,
+    socket: Rc<OwnedFd>,
+    pending: RefCell<Vec<u8>>,
+}
+
+impl IpcConn {
+    async fn connect(run: &Rc<TestRun>) -> Result<Self, TestError> {
+        let Some(ipc) = run.state.json_ipc.get() else {
+            bail!("The IPC socket is not available");
+        };
+        let mut addr: c::sockaddr_un = uapi::pod_zeroed();
+        addr.sun_family = c::AF_UNIX as _;
+        let path = ipc.path();
+        let sun_path = uapi::as_bytes_mut(&mut addr.sun_path[..]);
+        sun_path[..path.len()].copy_from_slice(path.as_bytes());
+        sun_path[path.len()] = 0;
+        let socket = uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+            .to_os_error()
+            .with_context(|| "Could not create a unix socket")?;
+        let socket = Rc::new(socket);
+        run.state
+            .ring
+            .connect(&socket, &addr)
+            .await
+            .with_context(|| "Could not connect to the IPC socket")?;
+        Ok(Self {
+            run: run.clone(),
+            socket,
+            pending: Default::default(),
+        })
+    }
+
+    async fn request(&self, request: &Request) -> Result<Reply, TestError> {
+        let mut msg = serde_json::to_vec(request).unwrap();
+        msg.push(b'\n');
+        let mut buf = Buf::from_slice(&msg);
+        let mut pos = 0;
+        while pos < buf.len() {
+            pos += self
+                .run
+                .state
+                .ring
+                .write(&self.socket, buf.slice(pos..), None)
+                .await
+                .with_context(|| "Could not write the request")?;
+        }
+        self.read().await
+    }
+
+    async fn read(&self) -> Result<Reply, TestError> {
+        let mut buf = Buf::new(4096);
+        loop {
+            let line = {
+                let mut pending = self.pending.borrow_mut();
+                match pending.iter().position(|b| *b == b'\n') {
+                    Some(pos) => {
+                        let line: Vec<_> = pending.drain(..=pos).collect();
+                        Some(line)
+                    }
+                    None => None,
+                }
+            };
+            if let Some(line) = line {
+                return serde_json::from_slice(&line).with_context(|| "Could not parse the reply");
+            }
+            let n = self
+                .run
+                .state
+                .ring
+                .read(&self.socket, buf.clone())
+                .await
+                .with_context(|| "Could not read the reply")?;
+            if n == 0 {
+                bail!("The compositor closed the connection");
+            }
+            self.pending.borrow_mut().extend_from_slice(&buf[..n]);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{
+        ifs::jay_tree::{KIND_DISPLAY, KIND_OUTPUT, KIND_WINDOW, KIND_WORKSPACE},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that the jay_tree object describes the window tree.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.tl.core.set_title("abc")?;
+    win.map2().await?;
+
+    let nodes = client.jc.get_tree().await?;
+    let find = |id: u32| nodes.iter().find(|n| n.id == id);
+
+    let Some(root) = nodes.first() else {
+        bail!("The tree is empty");
+    };
+    tassert_eq!(root.kind, KIND_DISPLAY);
+    tassert_eq!(root.parent, 0);
+
+    let Some(window) = find(win.tl.server.node_id.raw()) else {
+        bail!("The tree does not contain the window");
+    };
+    tassert_eq!(window.kind, KIND_WINDOW);
+    tassert_eq!(window.title.as_deref(), Some("abc"));
+
+    let mut ws = window;
+    while ws.kind != KIND_WORKSPACE {
+        let Some(parent) = find(ws.parent) else {
+            bail!("The tree does not contain the workspace of the window");
+        };
+        ws = parent;
+    }
+    let Some(output) = find(ws.parent) else {
+        bail!("The tree does not contain the output of the workspace");
+    };
+    tassert_eq!(output.kind, KIND_OUTPUT);
+    tassert_eq!(output.id, ds.output.id.raw());
+    tassert_eq!(
+        output.name.as_deref(),
+        Some(ds.output.global.connector.name.as_str())
+    );
+    tassert_eq!(output.parent, root.id);
+
+    Ok(())
+}
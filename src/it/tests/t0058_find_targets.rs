@@ -0,0 +1,37 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that toplevels and workspaces can be looked up by app-id and name.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.tl.core.set_app_id("abc")?;
+    win.map2().await?;
+
+    let Some(tl) = client.jc.find_toplevel("abc").await? else {
+        bail!("Compositor did not find the toplevel");
+    };
+    tassert_eq!(tl.app_id.borrow().as_str(), "abc");
+    tassert!(!tl.minimized.get());
+    win.tl.core.set_minimized()?;
+    client.sync().await;
+    tassert!(tl.minimized.get());
+    tassert!(client.jc.find_toplevel("def").await?.is_none());
+
+    let Some(name) = ds.output.workspace.get().map(|ws| ws.name.borrow().clone()) else {
+        bail!("Output has no workspace");
+    };
+    let Some(ws) = client.jc.find_workspace(&name).await? else {
+        bail!("Compositor did not find the workspace");
+    };
+    tassert_eq!(ws.name.borrow().as_str(), name.as_str());
+    tassert!(client.jc.find_workspace("does not exist").await?.is_none());
+
+    Ok(())
+}
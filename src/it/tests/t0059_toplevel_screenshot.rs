@@ -0,0 +1,29 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that a screenshot of a single window has the size of the window.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.tl.core.set_app_id("abc")?;
+    win.map2().await?;
+
+    let Some(tl) = client.jc.find_toplevel("abc").await? else {
+        bail!("Compositor did not find the toplevel");
+    };
+    let buf = client.jc.take_toplevel_screenshot(&tl, false).await?;
+    let pos = win.tl.server.node_absolute_position();
+    tassert_eq!(buf.width, pos.width() as u32);
+    tassert_eq!(buf.height, pos.height() as u32);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::{
+        mods::CTRL,
+        syms::{SYM_a, SYM_b},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_LEFTCTRL: u32 = 29;
+const KEY_A: u32 = 30;
+const KEY_B: u32 = 48;
+
+/// Tests that global shortcuts are activated, deactivated, and recorded.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let gs = client.jc.create_global_shortcuts(&seat.seat).await?;
+    gs.bind(1, CTRL.0, SYM_a.0)?;
+    client.sync().await;
+
+    {
+        let _a = ds.kb.press(KEY_A);
+        client.sync().await;
+        tassert!(gs.activated.borrow().is_empty());
+    }
+
+    {
+        let _ctrl = ds.kb.press(KEY_LEFTCTRL);
+        let a = ds.kb.press(KEY_A);
+        client.sync().await;
+        tassert_eq!(gs.activated.borrow().as_slice(), &[1]);
+        tassert!(gs.deactivated.borrow().is_empty());
+        drop(a);
+        client.sync().await;
+        tassert_eq!(gs.deactivated.borrow().as_slice(), &[1]);
+    }
+
+    gs.record()?;
+    client.sync().await;
+    {
+        let _ctrl = ds.kb.press(KEY_LEFTCTRL);
+        client.sync().await;
+        tassert!(gs.recorded.borrow().is_empty());
+        let _b = ds.kb.press(KEY_B);
+        client.sync().await;
+        tassert_eq!(gs.recorded.borrow().as_slice(), &[(CTRL.0, SYM_b.0)]);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use {
+    crate::{
+        ifs::jay_appearance::{COLOR_SCHEME_DARK, COLOR_SCHEME_NO_PREFERENCE},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    jay_config::theme::{colors::FOCUSED_TITLE_BACKGROUND_COLOR, Color, ColorScheme},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that appearance watchers are notified about theme changes.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let appearance = client.jc.watch_appearance().await?;
+    {
+        let done = appearance.done.borrow();
+        tassert_eq!(done.len(), 1);
+        tassert_eq!(done[0].0, COLOR_SCHEME_NO_PREFERENCE);
+    }
+
+    run.cfg.set_color_scheme(ColorScheme::Dark)?;
+    client.sync().await;
+    tassert_eq!(appearance.color_scheme.get(), COLOR_SCHEME_DARK);
+    tassert_eq!(appearance.done.borrow().len(), 2);
+
+    run.cfg
+        .set_color(FOCUSED_TITLE_BACKGROUND_COLOR, Color::new(255, 0, 0))?;
+    client.sync().await;
+    let accent = appearance.accent_color.get().to_rgba_premultiplied();
+    tassert_eq!(accent, [255, 0, 0, 255]);
+
+    Ok(())
+}
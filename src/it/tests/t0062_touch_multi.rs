@@ -0,0 +1,88 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that every touch point is delivered to the surface it started on.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client1 = run.create_client().await?;
+    let win1 = client1.create_window().await?;
+    win1.map2().await?;
+    let client2 = run.create_client().await?;
+    let win2 = client2.create_window().await?;
+    win2.map2().await?;
+
+    let touch1 = client1.get_default_seat().await?.seat.get_touch().await?;
+    let touch2 = client2.get_default_seat().await?.seat.get_touch().await?;
+    let down1 = touch1.down.expect()?;
+    let down2 = touch2.down.expect()?;
+    let motion1 = touch1.motion.expect()?;
+    let motion2 = touch2.motion.expect()?;
+    let up1 = touch1.up.expect()?;
+    let up2 = touch2.up.expect()?;
+    let cancel1 = touch1.cancel.expect()?;
+    let cancel2 = touch2.cancel.expect()?;
+
+    let extents = run.state.root.extents.get();
+    let normed = |x: i32, y: i32| {
+        (
+            (x - extents.x1()) as f64 / extents.width() as f64 + 0.5 / extents.width() as f64,
+            (y - extents.y1()) as f64 / extents.height() as f64 + 0.5 / extents.height() as f64,
+        )
+    };
+    let pos1 = win1.surface.surface.server.node_absolute_position();
+    let pos2 = win2.surface.surface.server.node_absolute_position();
+    tassert!(!pos1.intersects(&pos2));
+
+    let (x, y) = normed(pos1.x1() + 10, pos1.y1() + 10);
+    ds.touch.down(0, x, y);
+    ds.touch.frame();
+    let (x, y) = normed(pos2.x1() + 20, pos2.y1() + 20);
+    ds.touch.down(1, x, y);
+    ds.touch.frame();
+    client1.sync().await;
+    client2.sync().await;
+    let ev = down1.next()?;
+    tassert_eq!(ev.surface, win1.surface.id);
+    tassert_eq!(ev.id, 0);
+    down1.none()?;
+    let ev = down2.next()?;
+    tassert_eq!(ev.surface, win2.surface.id);
+    tassert_eq!(ev.id, 1);
+    tassert_eq!(ev.x.round_down(), 20);
+    tassert_eq!(ev.y.round_down(), 20);
+
+    let (x, y) = normed(pos2.x1() + 30, pos2.y1() + 40);
+    ds.touch.motion(1, x, y);
+    ds.touch.frame();
+    client1.sync().await;
+    client2.sync().await;
+    motion1.none()?;
+    let ev = motion2.next()?;
+    tassert_eq!(ev.id, 1);
+    tassert_eq!(ev.x.round_down(), 30);
+    tassert_eq!(ev.y.round_down(), 40);
+
+    ds.touch.cancel(0);
+    ds.touch.frame();
+    client1.sync().await;
+    client2.sync().await;
+    cancel1.next()?;
+    cancel2.none()?;
+
+    ds.touch.up(1);
+    ds.touch.frame();
+    client1.sync().await;
+    client2.sync().await;
+    up1.none()?;
+    tassert_eq!(up2.next()?.id, 1);
+
+    Ok(())
+}
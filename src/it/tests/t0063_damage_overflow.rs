@@ -0,0 +1,24 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that huge buffer damage with a buffer scale does not overflow.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let surface = &win.surface.surface;
+    surface.set_buffer_scale(2)?;
+    surface.damage_buffer(0, 0, i32::MAX, i32::MAX)?;
+    surface.damage_buffer(1, 1, i32::MAX, i32::MAX)?;
+    client.sync().await;
+    tassert!(!client.tran.killed.get());
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests applying and testing output configurations.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let om = client.registry.get_output_manager().await?;
+    client.sync().await;
+    let head = {
+        let heads = om.heads.borrow();
+        tassert_eq!(heads.len(), 1);
+        heads[0].clone()
+    };
+    tassert!(head.enabled.get());
+    tassert_eq!(head.position.get(), (0, 0));
+    let mode = {
+        let modes = head.modes.borrow();
+        tassert_eq!(modes.len(), 1);
+        modes[0].clone()
+    };
+    tassert_eq!(mode.size.get(), (800, 600));
+    tassert_eq!(mode.refresh.get(), 60_000);
+
+    let config = om.create_configuration()?;
+    let config_head = config.enable_head(&head)?;
+    config_head.set_mode(&mode)?;
+    config_head.set_position(100, 50)?;
+    config.apply()?;
+    client.sync().await;
+    tassert!(config.succeeded.get());
+    tassert_eq!(ds.output.global.pos.get().position(), (100, 50));
+    config.destroy()?;
+
+    let config = om.create_configuration()?;
+    let config_head = config.enable_head(&head)?;
+    config_head.set_position(200, 0)?;
+    config.test()?;
+    client.sync().await;
+    tassert!(config.succeeded.get());
+    tassert_eq!(ds.output.global.pos.get().position(), (100, 50));
+    config.destroy()?;
+
+    ds.connector.reject_modes.set(true);
+    let config = om.create_configuration()?;
+    config.enable_head(&head)?;
+    config.test()?;
+    client.sync().await;
+    tassert!(config.failed.get());
+    config.destroy()?;
+    ds.connector.reject_modes.set(false);
+
+    let config = om.create_configuration()?;
+    client.tran.expect_error.set(true);
+    config.apply()?;
+    client.sync().await;
+    tassert!(client.tran.killed.get());
+    tassert!(!config.succeeded.get());
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use {
+    crate::{
+        ifs::{
+            ipc::{wl_data_device_manager::DND_COPY, x_data_device::XIpcDevice},
+            wl_seat::BTN_LEFT,
+        },
+        it::{test_error::TestResult, test_utils::test_rect_ext::TestRectExt, testrun::TestRun},
+        tree::Node,
+        xwayland::XWaylandEvent,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests the wayland side of a drag-and-drop operation of a wayland client over an X
+/// window and the placement of the XDND proxy window. The test plays the part of the X
+/// window manager.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let seat = client.get_default_seat().await?;
+
+    let xclient = run.create_xwayland_client().await?;
+    let xwin = xclient.create_window().await?;
+    xwin.map2().await?;
+    let device = Rc::new(XIpcDevice {
+        id: run.state.xwayland.ipc_device_ids.next(),
+        clipboard: Default::default(),
+        primary_selection: Default::default(),
+        seat: ds.seat.clone(),
+        state: run.state.clone(),
+        client: xclient.server.clone(),
+    });
+    ds.seat.set_x_data_device(&device);
+
+    let xwm_events = || {
+        let mut events = vec![];
+        while let Some(event) = run.state.xwayland.queue.try_pop() {
+            events.push(event);
+        }
+        events
+    };
+
+    let button = seat.pointer.button.expect()?;
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    let dev = client.data_device_manager.get_data_device(&seat.seat)?;
+    let src = client.data_device_manager.create_data_source()?;
+    src.offer("text/plain")?;
+    src.offer("text/uri-list")?;
+    src.set_actions(DND_COPY)?;
+    dev.start_drag(&src, &win.surface, None, button.next()?.serial)?;
+    client.sync().await;
+    xwm_events();
+
+    let targets = src.targets.expect()?;
+    let dnd_finished = src.dnd_finished.expect()?;
+
+    let (x, y) = xwin.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    client.sync().await;
+    let mut offer = None;
+    let mut entered = false;
+    for event in xwm_events() {
+        match event {
+            XWaylandEvent::IpcSetOffer { offer: o, .. } => offer = Some(o),
+            XWaylandEvent::DndEnter {
+                surface, offer: id, ..
+            } => {
+                tassert_eq!(surface, xwin.surface.id);
+                tassert_eq!(Some(id), offer.as_ref().map(|o| o.offer_id));
+                entered = true;
+            }
+            _ => {}
+        }
+    }
+    tassert!(entered);
+    let Some(offer) = offer else {
+        bail!("No offer was sent to the X window manager");
+    };
+    tassert!(offer.is_dnd());
+    tassert_eq!(offer.source_actions(), DND_COPY);
+    tassert_eq!(
+        offer.mime_types.borrow().as_slice(),
+        &["text/plain", "text/uri-list"]
+    );
+
+    ds.move_to(x + 1, y);
+    client.sync().await;
+    let moved = xwm_events()
+        .iter()
+        .any(|e| matches!(e, XWaylandEvent::DndMotion { .. }));
+    tassert!(moved);
+
+    // XdndStatus: the X window accepts the drop.
+    offer.dnd_status(true, DND_COPY);
+    client.sync().await;
+    tassert_eq!(targets.next()?.as_deref(), Some("text/plain"));
+
+    // The X window converts the XdndSelection to the type it accepts.
+    offer.dnd_request_mime_type("text/uri-list");
+    client.sync().await;
+    tassert_eq!(targets.next()?.as_deref(), Some("text/uri-list"));
+    offer.dnd_request_mime_type("image/png");
+    client.sync().await;
+    targets.none()?;
+
+    // XdndDrop
+    drop(click);
+    client.sync().await;
+    let dropped = xwm_events()
+        .iter()
+        .any(|e| matches!(e, XWaylandEvent::DndDrop { .. }));
+    tassert!(dropped);
+
+    // XdndFinished
+    offer.dnd_finished();
+    client.sync().await;
+    dnd_finished.next()?;
+
+    // An X client drags over the wayland window.
+    let (x, y) = xwin.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    xwm_events();
+    ds.seat.start_x_dnd();
+    let (x, y) = win.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+    client.sync().await;
+    let target = xwm_events().into_iter().find_map(|e| match e {
+        XWaylandEvent::DndProxyTarget { surface, .. } => Some(surface),
+        _ => None,
+    });
+    let Some(Some(target)) = target else {
+        bail!("The XDND proxy window was not placed over the wayland window");
+    };
+    tassert_eq!(target.id, win.surface.id);
+
+    ds.seat.end_x_dnd();
+    let unmapped = xwm_events()
+        .iter()
+        .any(|e| matches!(e, XWaylandEvent::DndProxyTarget { surface: None, .. }));
+    tassert!(unmapped);
+    drop(click);
+
+    Ok(())
+}
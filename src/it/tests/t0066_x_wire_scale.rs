@@ -0,0 +1,41 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that X geometry and surface damage sent by Xwayland with a wire scale are
+/// rounded consistently when converted to logical coordinates.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_xwayland_client().await?;
+    client.server.wire_scale.set(Some(2));
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let (mut x, mut y, mut width, mut height) = (-3i32, 5i32, 801i32, 600i32);
+    client_wire_scale_to_logical!(client.server, x, y, width, height);
+    tassert_eq!((x, y, width, height), (-2, 2, 401, 300));
+    logical_to_client_wire_scale!(client.server, x, y, width, height);
+    tassert_eq!((x, y, width, height), (-4, 4, 802, 600));
+    client_wire_scale_to_logical!(client.server, x, y, width, height);
+    tassert_eq!((x, y, width, height), (-2, 2, 401, 300));
+
+    ds.connector.damage.borrow_mut().clear();
+    win.surface.damage(3, 5, 5, 4)?;
+    win.surface.commit()?;
+    client.sync().await;
+    let pos = win.surface.server.buffer_abs_pos.get();
+    let output = ds.output.global.pos.get();
+    let expected = Rect::new(1, 2, 4, 5)
+        .unwrap()
+        .move_(pos.x1() - output.x1(), pos.y1() - output.y1());
+    tassert_eq!(&*ds.connector.damage.borrow(), &[expected]);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::SYM_a,
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_A: u32 = 30;
+const KEY_B: u32 = 48;
+const KEY_LEFTSHIFT: u32 = 42;
+
+/// Tests switching between binding modes and that a binding mode activated once is
+/// cancelled by a key press that does not invoke a shortcut.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+    let mode = || ds.seat.binding_mode().map(|m| m.to_string());
+
+    run.cfg.set_binding_mode(seat, Some("resize"))?;
+    run.sync().await;
+    tassert_eq!(mode().as_deref(), Some("resize"));
+
+    run.cfg.set_binding_mode(seat, Some("move"))?;
+    run.sync().await;
+    tassert_eq!(mode().as_deref(), Some("move"));
+
+    ds.kb.press(KEY_B);
+    run.sync().await;
+    tassert_eq!(mode().as_deref(), Some("move"));
+    tassert!(run.cfg.cancelled_binding_modes.is_empty());
+
+    run.cfg.add_shortcut(seat, SYM_a)?;
+    run.cfg.set_binding_mode_once(seat, "chord")?;
+    run.sync().await;
+    tassert_eq!(mode().as_deref(), Some("chord"));
+
+    ds.kb.press(KEY_LEFTSHIFT);
+    run.sync().await;
+    tassert!(run.cfg.cancelled_binding_modes.is_empty());
+
+    ds.kb.press(KEY_A);
+    run.sync().await;
+    tassert!(run.cfg.invoked_shortcuts.contains(&(seat, SYM_a.into())));
+    tassert!(run.cfg.cancelled_binding_modes.is_empty());
+    tassert_eq!(mode().as_deref(), Some("chord"));
+
+    ds.kb.press(KEY_B);
+    run.sync().await;
+    tassert!(run.cfg.cancelled_binding_modes.contains(&seat));
+    tassert_eq!(mode(), None);
+    tassert!(ds.output.render_data.borrow().binding_mode.is_none());
+
+    run.cfg.cancelled_binding_modes.clear();
+    ds.kb.press(KEY_B);
+    run.sync().await;
+    tassert!(run.cfg.cancelled_binding_modes.is_empty());
+
+    Ok(())
+}
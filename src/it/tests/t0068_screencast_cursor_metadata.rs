@@ -0,0 +1,99 @@
+use {
+    crate::{
+        cli::screenshot::ScreenshotImage,
+        format::XRGB8888,
+        ifs::jay_screencast::{JayScreencast, CURSOR_MODE_METADATA},
+        it::{test_error::TestResult, test_utils::test_rect_ext::TestRectExt, testrun::TestRun},
+        tree::Node,
+        wire::{jay_screenshot::Dmabuf, JayScreenshotId},
+    },
+    std::rc::Rc,
+    uapi::c,
+};
+
+testcase!();
+
+/// Tests that a screencast with the metadata cursor mode sends the cursor image and
+/// position as events and does not embed the cursor in the frame.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.set_color(255, 0, 0, 255);
+    win.tl.core.set_app_id("abc")?;
+    win.map2().await?;
+
+    let seat = client.get_default_seat().await?;
+    let enter = seat.pointer.enter.expect()?;
+    let rect = win.tl.server.node_absolute_position();
+    let (x, y) = rect.center();
+    ds.move_to(x, y);
+    client.sync().await;
+    let enter = enter.next()?;
+
+    let cursor = client.create_surface_ext().await?;
+    cursor.set_color(0, 255, 0, 255);
+    cursor.map(4, 4).await?;
+    seat.pointer.set_cursor(enter.serial, Some(&cursor), 0, 0)?;
+    client.sync().await;
+
+    let Some(tl) = client.jc.find_toplevel("abc").await? else {
+        bail!("Compositor did not find the toplevel");
+    };
+    let sc = client.jc.create_screencast().await?;
+    sc.set_toplevel(&tl)?;
+    sc.set_use_linear_buffers(true)?;
+    sc.set_cursor_mode(CURSOR_MODE_METADATA)?;
+    sc.set_running(true)?;
+    sc.configure()?;
+    client.sync().await;
+    let Some(serial) = sc.buffers_serial.get() else {
+        bail!("Compositor did not send buffers");
+    };
+    sc.ack_buffers(serial)?;
+
+    let ready = sc.ready.expect()?;
+    let cursor_image = sc.cursor_image.expect()?;
+    let cursor_position = sc.cursor_position.expect()?;
+    let server_sc = client.tran.get_server_obj::<_, JayScreencast>(sc.id)?;
+    server_sc.schedule_toplevel_screencast();
+    client.sync().await;
+
+    let image = cursor_image.next()?;
+    tassert_eq!((image.width, image.height), (4, 4));
+    tassert_eq!((image.hotspot_x, image.hotspot_y), (0, 0));
+    let mut pixel = [0u8; 4];
+    uapi::pread(image.fd.raw(), &mut pixel[..], 0)?;
+    tassert_eq!(pixel, [0, 255, 0, 255]);
+
+    let position = cursor_position.next()?;
+    tassert_eq!(position.visible, 1);
+    tassert_eq!((position.x, position.y), (x - rect.x1(), y - rect.y1()));
+
+    let idx = ready.next()?;
+    let buffer = sc.buffers.borrow()[idx as usize].clone();
+    tassert_eq!(buffer.format, XRGB8888.drm);
+    let Some(ctx) = run.state.render_ctx.get() else {
+        bail!("There is no render context");
+    };
+    let drm_dev = uapi::open(&**ctx.render_node(), c::O_RDWR | c::O_CLOEXEC, 0)?;
+    let frame = ScreenshotImage::from_dmabuf(
+        &run.state.dma_buf_ids,
+        &Dmabuf {
+            self_id: JayScreenshotId::NONE,
+            drm_dev: Rc::new(drm_dev),
+            fd: buffer.fd.clone(),
+            width: buffer.width as _,
+            height: buffer.height as _,
+            offset: buffer.offset,
+            stride: buffer.stride,
+            modifier_lo: buffer.modifier as u32,
+            modifier_hi: (buffer.modifier >> 32) as u32,
+        },
+    )?;
+    let offset = (position.y as u32 * frame.stride + position.x as u32 * 4) as usize;
+    tassert_eq!(&frame.data[offset..offset + 3], &[0, 0, 255]);
+
+    Ok(())
+}
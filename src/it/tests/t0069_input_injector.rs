@@ -0,0 +1,145 @@
+use {
+    crate::{
+        backend::KeyState,
+        fixed::Fixed,
+        ifs::wl_seat::{BTN_LEFT, KEYBOARD, POINTER, TOUCH},
+        it::{test_error::TestResult, test_utils::test_rect_ext::TestRectExt, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_LEFTSHIFT: u32 = 42;
+const KEY_A: u32 = 30;
+const XK_A: u32 = 0x41;
+
+/// Tests that events sent through an input injector reach the focused client and that
+/// destroying the injector releases everything that is still held.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let seat = client.get_default_seat().await?;
+    let touch = seat.seat.get_touch().await?;
+    let wl_output = client.get_output(&ds.output.global).await?;
+    let output = client.jc.get_output(&wl_output).await?;
+
+    let injector = client
+        .jc
+        .create_input_injector("injector", KEYBOARD | POINTER | TOUCH)
+        .await?;
+    client.sync().await;
+    let device = run
+        .state
+        .input_device_handlers
+        .borrow()
+        .values()
+        .find(|d| *d.data.device.name() == "injector")
+        .map(|d| d.id);
+    let Some(device) = device else {
+        bail!("The injector did not create an input device");
+    };
+    run.cfg.set_input_device_seat(device, ds.seat.id())?;
+
+    let rect = win.tl.server.node_absolute_position();
+    let output_pos = ds.output.global.pos.get();
+    ds.move_to(rect.x1() + 1, rect.y1() + 1);
+    client.sync().await;
+
+    let motion = seat.pointer.motion.expect()?;
+    let button = seat.pointer.button.expect()?;
+    let key = seat.kb.key.expect()?;
+    let down = touch.down.expect()?;
+    let up = touch.up.expect()?;
+    let cancel = touch.cancel.expect()?;
+
+    let (x, y) = rect.center();
+    injector.motion_absolute(
+        &output,
+        Fixed::from_int(x - output_pos.x1()),
+        Fixed::from_int(y - output_pos.y1()),
+    )?;
+    client.sync().await;
+    let ev = motion.last()?;
+    tassert_eq!(
+        (ev.surface_x.round_down(), ev.surface_y.round_down()),
+        (x - rect.x1(), y - rect.y1())
+    );
+
+    injector.button(BTN_LEFT, KeyState::Pressed)?;
+    injector.button(BTN_LEFT, KeyState::Pressed)?;
+    injector.button(BTN_LEFT, KeyState::Released)?;
+    injector.button(BTN_LEFT, KeyState::Released)?;
+    client.sync().await;
+    let ev = button.next()?;
+    tassert_eq!((ev.button, ev.state), (BTN_LEFT, 1));
+    let ev = button.next()?;
+    tassert_eq!((ev.button, ev.state), (BTN_LEFT, 0));
+    button.none()?;
+
+    injector.key(KEY_A, KeyState::Pressed)?;
+    injector.key(KEY_A, KeyState::Pressed)?;
+    injector.key(KEY_A, KeyState::Released)?;
+    injector.key(KEY_A, KeyState::Released)?;
+    client.sync().await;
+    let (_, ev) = key.next()?;
+    tassert_eq!((ev.key, ev.state), (KEY_A, 1));
+    let (_, ev) = key.next()?;
+    tassert_eq!((ev.key, ev.state), (KEY_A, 0));
+    key.none()?;
+
+    injector.keysym(XK_A, KeyState::Pressed)?;
+    injector.keysym(XK_A, KeyState::Pressed)?;
+    injector.keysym(XK_A, KeyState::Released)?;
+    client.sync().await;
+    let mut keys = vec![];
+    while let Ok((_, ev)) = key.next() {
+        keys.push((ev.key, ev.state));
+    }
+    tassert_eq!(
+        keys,
+        [
+            (KEY_LEFTSHIFT, 1),
+            (KEY_A, 1),
+            (KEY_A, 0),
+            (KEY_LEFTSHIFT, 0),
+        ]
+    );
+
+    let touch_x = Fixed::from_f64((rect.x1() - output_pos.x1()) as f64 + 10.5);
+    let touch_y = Fixed::from_f64((rect.y1() - output_pos.y1()) as f64 + 20.5);
+    injector.touch_down(&output, 0, touch_x, touch_y)?;
+    injector.touch_down(&output, 0, touch_x, touch_y)?;
+    injector.touch_up(0)?;
+    injector.touch_up(0)?;
+    client.sync().await;
+    let ev = down.next()?;
+    tassert_eq!(ev.surface, win.surface.id);
+    tassert_eq!((ev.x.round_down(), ev.y.round_down()), (10, 20));
+    down.none()?;
+    tassert_eq!(up.next()?.id, 0);
+    up.none()?;
+
+    injector.key(KEY_A, KeyState::Pressed)?;
+    injector.button(BTN_LEFT, KeyState::Pressed)?;
+    injector.touch_down(&output, 1, touch_x, touch_y)?;
+    client.sync().await;
+    tassert_eq!(key.next()?.1.state, 1);
+    tassert_eq!(button.next()?.state, 1);
+    tassert_eq!(down.next()?.id, 1);
+
+    injector.destroy()?;
+    client.sync().await;
+    let (_, ev) = key.next()?;
+    tassert_eq!((ev.key, ev.state), (KEY_A, 0));
+    let ev = button.next()?;
+    tassert_eq!((ev.button, ev.state), (BTN_LEFT, 0));
+    cancel.next()?;
+    up.none()?;
+
+    Ok(())
+}
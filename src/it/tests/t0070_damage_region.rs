@@ -0,0 +1,72 @@
+use {
+    crate::{
+        format::XRGB8888,
+        gfx_api::GfxFramebuffer,
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        theme::Color,
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+testcase!();
+
+/// Tests that partial buffer damage limits the region of the framebuffer that is
+/// redrawn.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.set_color(255, 0, 0, 255);
+    win.map2().await?;
+
+    let buffer = client.shm.create_buffer(100, 100)?;
+    buffer.fill(Color::from_rgb(0, 255, 0));
+    let ss = client.comp.create_surface().await?;
+    ss.attach(buffer.buffer.id)?;
+    ss.commit()?;
+    let sub = client.sub.get_subsurface(ss.id, win.surface.id).await?;
+    sub.set_desync()?;
+    sub.set_position(10, 10)?;
+    win.surface.commit()?;
+    client.sync().await;
+
+    let fb = ds.connector.render(&run.state, &ds.output)?;
+    let (width, height) = fb.physical_size();
+    tassert_eq!(
+        fb.render_regions.borrow_mut().pop(),
+        Some(vec![Rect::new_sized(0, 0, width, height).unwrap()])
+    );
+
+    buffer.fill(Color::from_rgb(0, 0, 255));
+    ss.attach(buffer.buffer.id)?;
+    ss.damage_buffer(10, 20, 30, 40)?;
+    ss.commit()?;
+    client.sync().await;
+
+    let fb = ds.connector.render(&run.state, &ds.output)?;
+    let pos = ss.server.buffer_abs_pos.get();
+    let output = ds.output.global.pos.get();
+    let (x, y) = (pos.x1() - output.x1(), pos.y1() - output.y1());
+    // The damage is expanded by one pixel to account for texture filtering.
+    let expected = Rect::new(x + 9, y + 19, x + 41, y + 61).unwrap();
+    tassert_eq!(fb.render_regions.borrow_mut().pop(), Some(vec![expected]));
+
+    let stride = width * 4;
+    let pixels = vec![Cell::new(0u8); (stride * height) as usize];
+    let dyn_fb: Rc<dyn GfxFramebuffer> = fb;
+    dyn_fb.copy_to_shm(0, 0, width, height, stride, XRGB8888, &pixels)?;
+    let pixel = |px: i32, py: i32| {
+        let offset = (py * stride + px * 4) as usize;
+        [
+            pixels[offset + 2].get(),
+            pixels[offset + 1].get(),
+            pixels[offset].get(),
+        ]
+    };
+    tassert_eq!(pixel(x + 20, y + 30), [0, 0, 255]);
+    tassert_eq!(pixel(x + 80, y + 80), [0, 255, 0]);
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use {
+    crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId, Mode, MonitorInfo},
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Tests that an output configuration is rejected as a whole if one of its heads fails
+/// and that the other heads are left unchanged.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let connector2 = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        reject_modes: Default::default(),
+        damage: Default::default(),
+        damage_full: Default::default(),
+        fb: Default::default(),
+    });
+    let mode = Mode {
+        width: 400,
+        height: 400,
+        refresh_rate_millihz: 60000,
+    };
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(connector2.clone()));
+    connector2
+        .events
+        .send_event(ConnectorEvent::Connected(MonitorInfo {
+            modes: vec![mode],
+            manufacturer: "jay".to_string(),
+            product: "jay second connector".to_string(),
+            serial_number: "".to_string(),
+            initial_mode: mode,
+            width_mm: 0,
+            height_mm: 0,
+            non_desktop: false,
+            vrr_capable: false,
+        }));
+    run.state.eng.yield_now().await;
+    tassert!(run.state.root.outputs.contains(&connector2.id));
+
+    let client = run.create_client().await?;
+    let om = client.registry.get_output_manager().await?;
+    client.sync().await;
+    let (head1, head2) = {
+        let heads = om.heads.borrow();
+        tassert_eq!(heads.len(), 2);
+        let swapped = *heads[0].name.borrow() == "VGA-2";
+        match swapped {
+            true => (heads[1].clone(), heads[0].clone()),
+            false => (heads[0].clone(), heads[1].clone()),
+        }
+    };
+    let pos = ds.output.global.pos.get().position();
+    tassert_eq!(head1.position.get(), pos);
+
+    connector2.reject_modes.set(true);
+    for apply in [false, true] {
+        let config = om.create_configuration()?;
+        let config_head1 = config.enable_head(&head1)?;
+        config_head1.set_position(pos.0 + 1000, pos.1 + 500)?;
+        config.enable_head(&head2)?;
+        match apply {
+            true => config.apply()?,
+            false => config.test()?,
+        }
+        client.sync().await;
+        tassert!(config.failed.get());
+        tassert_eq!(ds.output.global.pos.get().position(), pos);
+        tassert_eq!(head1.position.get(), pos);
+        config.destroy()?;
+    }
+
+    let config = om.create_configuration()?;
+    config.disable_head(&head1)?;
+    config.enable_head(&head2)?;
+    config.apply()?;
+    client.sync().await;
+    tassert!(config.failed.get());
+    tassert!(head1.enabled.get());
+    tassert!(run.state.root.outputs.contains(&ds.connector.id));
+    config.destroy()?;
+
+    connector2.reject_modes.set(false);
+    let config = om.create_configuration()?;
+    let config_head1 = config.enable_head(&head1)?;
+    config_head1.set_position(pos.0 + 1000, pos.1 + 500)?;
+    config.enable_head(&head2)?;
+    config.apply()?;
+    client.sync().await;
+    tassert!(config.succeeded.get());
+    tassert_eq!(
+        ds.output.global.pos.get().position(),
+        (pos.0 + 1000, pos.1 + 500)
+    );
+
+    Ok(())
+}
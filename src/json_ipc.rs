@@ -0,0 +1,472 @@
+pub mod messages;
+pub mod tree;
+
+use {
+    crate::{
+        acceptor::Acceptor,
+        async_engine::SpawnedFuture,
+        backend::InputDeviceCapability,
+        io_uring::IoUringError,
+        json_ipc::messages::{
+            Event, EventType, InputInfo, ModeInfo, OutputChange, OutputInfo, Reply, Request,
+            WindowChange, WorkspaceChange, WorkspaceInfo,
+        },
+        state::{DeviceHandlerData, State},
+        tree::{Node, OutputNode, ToplevelData, WorkspaceNode},
+        utils::{
+            buf::Buf, copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell,
+            oserror::OsError, queue::AsyncQueue,
+        },
+        workspaces,
+    },
+    futures_util::{select, FutureExt},
+    jay_config::video::Transform,
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+    uapi::{c, format_ustr, Errno, OwnedFd, Ustr, Ustring},
+};
+
+const MAX_REQUEST_SIZE: usize = 1 << 16;
+const MAX_OUTGOING_SIZE: usize = 1 << 22;
+
+#[derive(Debug, Error)]
+pub enum JsonIpcError {
+    #[error("The socket path {0:?} is too long to form a unix socket address")]
+    PathTooLong(String),
+    #[error("Could not create the IPC socket")]
+    SocketFailed(#[source] OsError),
+    #[error("Could not stat the existing socket")]
+    SocketStat(#[source] OsError),
+    #[error("Could not bind the socket to an address")]
+    BindFailed(#[source] OsError),
+    #[error("Could not start listening for incoming connections")]
+    ListenFailed(#[source] OsError),
+    #[error("Could not read from the client")]
+    Read(#[source] IoUringError),
+    #[error("Could not write to the client")]
+    Write(#[source] IoUringError),
+    #[error("The request is larger than {} bytes", MAX_REQUEST_SIZE)]
+    RequestTooLarge,
+}
+
+bitflags! {
+    EventMask: u32;
+        EM_WINDOW    = 1 << 0,
+        EM_WORKSPACE = 1 << 1,
+        EM_OUTPUT    = 1 << 2,
+        EM_MODE      = 1 << 3,
+}
+
+impl From<EventType> for EventMask {
+    fn from(ty: EventType) -> Self {
+        match ty {
+            EventType::Window => EM_WINDOW,
+            EventType::Workspace => EM_WORKSPACE,
+            EventType::Output => EM_OUTPUT,
+            EventType::Mode => EM_MODE,
+        }
+    }
+}
+
+/// A unix socket that allows scripts to query and control the compositor.
+///
+/// Clients send one JSON request per line and receive one JSON reply per line. The
+/// socket is created next to the wayland socket, e.g. `/run/user/1000/wayland-1.ipc`.
+pub struct JsonIpc {
+    // /run/user/1000/wayland-x.ipc
+    path: Ustring,
+    next_id: NumCell<u64>,
+    clients: CopyHashMap<u64, Rc<IpcClient>>,
+}
+
+struct IpcClient {
+    id: u64,
+    fd: Rc<OwnedFd>,
+    events: Cell<EventMask>,
+    // `None` closes the connection once all previous messages have been sent.
+    outgoing: AsyncQueue<Option<Vec<u8>>>,
+    outgoing_size: NumCell<usize>,
+    overflowed: Cell<bool>,
+    handler: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl Drop for JsonIpc {
+    fn drop(&mut self) {
+        let _ = uapi::unlink(&self.path);
+    }
+}
+
+impl JsonIpc {
+    pub fn install(
+        state: &Rc<State>,
+        acceptor: &Acceptor,
+    ) -> Result<(Rc<JsonIpc>, SpawnedFuture<()>), JsonIpcError> {
+        let path = format_ustr!("{}.ipc", acceptor.path().display());
+        let mut addr: c::sockaddr_un = uapi::pod_zeroed();
+        addr.sun_family = c::AF_UNIX as _;
+        if path.len() + 1 > addr.sun_path.len() {
+            return Err(JsonIpcError::PathTooLong(path.display().to_string()));
+        }
+        let fd = match uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0) {
+            Ok(f) => Rc::new(f),
+            Err(e) => return Err(JsonIpcError::SocketFailed(e.into())),
+        };
+        match uapi::lstat(&path) {
+            Ok(_) => {
+                log::info!("Unlinking {}", path.display());
+                let _ = uapi::unlink(&path);
+            }
+            Err(Errno(c::ENOENT)) => {}
+            Err(e) => return Err(JsonIpcError::SocketStat(e.into())),
+        }
+        let sun_path = uapi::as_bytes_mut(&mut addr.sun_path[..]);
+        sun_path[..path.len()].copy_from_slice(path.as_bytes());
+        sun_path[path.len()] = 0;
+        if let Err(e) = uapi::bind(fd.raw(), &addr) {
+            return Err(JsonIpcError::BindFailed(e.into()));
+        }
+        let ipc = Rc::new(JsonIpc {
+            path,
+            next_id: NumCell::new(1),
+            clients: Default::default(),
+        });
+        if let Err(e) = uapi::listen(fd.raw(), 4096) {
+            return Err(JsonIpcError::ListenFailed(e.into()));
+        }
+        log::info!("bound to IPC socket {}", ipc.path.display());
+        let future = state.eng.spawn(accept(fd, state.clone()));
+        state.json_ipc.set(Some(ipc.clone()));
+        Ok((ipc, future))
+    }
+
+    pub fn path(&self) -> &Ustr {
+        self.path.as_ustr()
+    }
+
+    pub fn clear(&self) {
+        for client in self.clients.clear().into_values() {
+            client.handler.take();
+        }
+    }
+}
+
+async fn accept(fd: Rc<OwnedFd>, state: Rc<State>) {
+    loop {
+        let fd = match state.ring.accept(&fd, c::SOCK_CLOEXEC).await {
+            Ok(fd) => fd,
+            Err(e) => {
+                log::error!("Could not accept an IPC client: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let Some(ipc) = state.json_ipc.get() else {
+            return;
+        };
+        let client = Rc::new(IpcClient {
+            id: ipc.next_id.fetch_add(1),
+            fd,
+            events: Cell::new(EventMask::none()),
+            outgoing: Default::default(),
+            outgoing_size: NumCell::new(0),
+            overflowed: Cell::new(false),
+            handler: Default::default(),
+        });
+        let handler = state.eng.spawn(client.clone().run(state.clone()));
+        client.handler.set(Some(handler));
+        ipc.clients.set(client.id, client);
+    }
+}
+
+impl IpcClient {
+    async fn run(self: Rc<Self>, state: Rc<State>) {
+        let mut incoming = state.eng.spawn(self.clone().incoming(state.clone())).fuse();
+        let mut outgoing = state.eng.spawn(self.clone().outgoing(state.clone())).fuse();
+        let mut flush = true;
+        select! {
+            res = incoming => {
+                if let Err(e) = res {
+                    log::error!("Could not handle IPC client {}: {}", self.id, ErrorFmt(e));
+                }
+            },
+            res = outgoing => {
+                flush = false;
+                if let Err(e) = res {
+                    log::debug!("Could not handle IPC client {}: {}", self.id, ErrorFmt(e));
+                }
+            },
+        }
+        if flush {
+            // Clients that have subscribed to events keep receiving them after they have
+            // closed their write end.
+            if !self.events.get().is_some() {
+                self.outgoing.push(None);
+            }
+            if let Err(e) = outgoing.await {
+                log::debug!("Could not handle IPC client {}: {}", self.id, ErrorFmt(e));
+            }
+        }
+        if let Some(ipc) = state.json_ipc.get() {
+            ipc.clients.remove(&self.id);
+        }
+        self.handler.take();
+    }
+
+    async fn incoming(self: Rc<Self>, state: Rc<State>) -> Result<(), JsonIpcError> {
+        let mut buf = Buf::new(4096);
+        let mut line = vec![];
+        loop {
+            let n = match state.ring.read(&self.fd, buf.clone()).await {
+                Ok(n) => n,
+                Err(e) => return Err(JsonIpcError::Read(e)),
+            };
+            if n == 0 {
+                return Ok(());
+            }
+            let mut data = &buf[..n];
+            while let Some(pos) = data.iter().position(|b| *b == b'\n') {
+                line.extend_from_slice(&data[..pos]);
+                self.handle_request(&state, &line);
+                line.clear();
+                data = &data[pos + 1..];
+            }
+            line.extend_from_slice(data);
+            if line.len() > MAX_REQUEST_SIZE {
+                self.send(&Reply::Error {
+                    message: JsonIpcError::RequestTooLarge.to_string(),
+                });
+                return Err(JsonIpcError::RequestTooLarge);
+            }
+        }
+    }
+
+    async fn outgoing(self: Rc<Self>, state: Rc<State>) -> Result<(), JsonIpcError> {
+        while let Some(msg) = self.outgoing.pop().await {
+            let mut buf = Buf::from_slice(&msg);
+            let mut pos = 0;
+            while pos < buf.len() {
+                match state.ring.write(&self.fd, buf.slice(pos..), None).await {
+                    Ok(n) => pos += n,
+                    Err(e) => return Err(JsonIpcError::Write(e)),
+                }
+            }
+            self.outgoing_size.fetch_sub(msg.len());
+        }
+        Ok(())
+    }
+
+    fn push(&self, msg: Vec<u8>) {
+        if self.overflowed.get() {
+            return;
+        }
+        if self.outgoing_size.get() + msg.len() > MAX_OUTGOING_SIZE {
+            log::error!("IPC client {} is too slow at reading replies", self.id);
+            self.overflowed.set(true);
+            self.outgoing.clear();
+            self.outgoing.push(None);
+            // Wakes up pending reads and writes so that the client is disconnected.
+            let _ = uapi::shutdown(self.fd.raw(), c::SHUT_RDWR);
+            return;
+        }
+        self.outgoing_size.fetch_add(msg.len());
+        self.outgoing.push(Some(msg));
+    }
+
+    fn handle_request(&self, state: &Rc<State>, line: &[u8]) {
+        if line.iter().all(|b| b.is_ascii_whitespace()) {
+            return;
+        }
+        let reply = match serde_json::from_slice::<Request>(line) {
+            Ok(request) => self.reply(state, request),
+            Err(e) => Reply::Error {
+                message: format!("Could not parse the request: {}", e),
+            },
+        };
+        self.send(&reply);
+    }
+
+    fn reply(&self, state: &Rc<State>, request: Request) -> Reply {
+        match request {
+            Request::GetTree => Reply::Tree {
+                tree: tree::tree(&state.root),
+            },
+            Request::GetWorkspaces => {
+                let mut workspaces: Vec<_> = state.workspaces.lock().values().cloned().collect();
+                workspaces
+                    .sort_by(|a, b| workspaces::cmp_names(&a.name.borrow(), &b.name.borrow()));
+                Reply::Workspaces {
+                    workspaces: workspaces.iter().map(|ws| workspace_info(ws)).collect(),
+                }
+            }
+            Request::GetOutputs => {
+                let mut outputs: Vec<_> = state.root.outputs.lock().values().cloned().collect();
+                outputs.sort_by(|a, b| a.global.connector.name.cmp(&b.global.connector.name));
+                Reply::Outputs {
+                    outputs: outputs.iter().map(|o| output_info(o)).collect(),
+                }
+            }
+            Request::GetInputs => {
+                let mut inputs: Vec<_> = state
+                    .input_device_handlers
+                    .borrow()
+                    .values()
+                    .map(|dev| (dev.id.raw(), dev.data.clone()))
+                    .collect();
+                inputs.sort_by_key(|(id, _)| *id);
+                Reply::Inputs {
+                    inputs: inputs
+                        .iter()
+                        .map(|(id, data)| input_info(*id, data))
+                        .collect(),
+                }
+            }
+            Request::Command { command } => {
+                let res = match state.config.get() {
+                    Some(config) => config.run_ipc_command(&command),
+                    None => Err("No configuration is loaded".to_string()),
+                };
+                match res {
+                    Ok(()) => Reply::Success,
+                    Err(message) => Reply::Error { message },
+                }
+            }
+            Request::Subscribe { events } => {
+                let mut mask = self.events.get();
+                for event in events {
+                    mask |= event.into();
+                }
+                self.events.set(mask);
+                Reply::Subscribed
+            }
+        }
+    }
+
+    fn send(&self, reply: &Reply) {
+        self.push(serialize(reply));
+    }
+}
+
+fn serialize(reply: &Reply) -> Vec<u8> {
+    let mut buf = serde_json::to_vec(reply).unwrap();
+    buf.push(b'\n');
+    buf
+}
+
+fn workspace_info(ws: &WorkspaceNode) -> WorkspaceInfo {
+    WorkspaceInfo {
+        id: ws.id.raw(),
+        name: ws.name.borrow().clone(),
+        output: ws.output.get().global.connector.name.clone(),
+        visible: ws.visible.get(),
+        urgent: ws.attention_requests.active(),
+        rect: ws.position.get().into(),
+    }
+}
+
+fn output_info(output: &OutputNode) -> OutputInfo {
+    let global = &output.global;
+    let mode = global.mode.get();
+    let transform = match global.persistent.transform.get() {
+        Transform::None => "none",
+        Transform::Rotate90 => "rotate-90",
+        Transform::Rotate180 => "rotate-180",
+        Transform::Rotate270 => "rotate-270",
+        Transform::Flip => "flip",
+        Transform::FlipRotate90 => "flip-rotate-90",
+        Transform::FlipRotate180 => "flip-rotate-180",
+        Transform::FlipRotate270 => "flip-rotate-270",
+    };
+    OutputInfo {
+        id: output.id.raw(),
+        name: global.connector.name.clone(),
+        manufacturer: global.output_id.manufacturer.clone(),
+        model: global.output_id.model.clone(),
+        serial_number: global.output_id.serial_number.clone(),
+        rect: global.pos.get().into(),
+        scale: global.persistent.scale.get().to_f64(),
+        transform: transform.to_string(),
+        mode: ModeInfo {
+            width: mode.width,
+            height: mode.height,
+            refresh_rate_millihz: mode.refresh_rate_millihz,
+        },
+        workspace: output.workspace.get().map(|ws| ws.name.borrow().clone()),
+    }
+}
+
+fn input_info(id: u32, data: &DeviceHandlerData) -> InputInfo {
+    let caps = [
+        (InputDeviceCapability::Keyboard, "keyboard"),
+        (InputDeviceCapability::Pointer, "pointer"),
+        (InputDeviceCapability::Touch, "touch"),
+        (InputDeviceCapability::TabletTool, "tablet-tool"),
+        (InputDeviceCapability::TabletPad, "tablet-pad"),
+        (InputDeviceCapability::Gesture, "gesture"),
+        (InputDeviceCapability::Switch, "switch"),
+    ];
+    InputInfo {
+        id,
+        name: data.device.name().to_string(),
+        seat: data.seat.get().map(|s| s.seat_name().to_string()),
+        syspath: data.syspath.clone(),
+        devnode: data.devnode.clone(),
+        capabilities: caps
+            .into_iter()
+            .filter(|(cap, _)| data.device.has_capability(*cap))
+            .map(|(_, name)| name.to_string())
+            .collect(),
+    }
+}
+
+fn emit(state: &State, mask: EventMask, event: impl FnOnce() -> Event) {
+    let Some(ipc) = state.json_ipc.get() else {
+        return;
+    };
+    let clients: Vec<_> = ipc
+        .clients
+        .lock()
+        .values()
+        .filter(|c| c.events.get().contains(mask))
+        .cloned()
+        .collect();
+    if clients.is_empty() {
+        return;
+    }
+    let msg = serialize(&Reply::Event(event()));
+    for client in clients {
+        client.push(msg.clone());
+    }
+}
+
+pub fn window_event(state: &State, change: WindowChange, node: &dyn Node, data: &ToplevelData) {
+    emit(state, EM_WINDOW, || Event::Window {
+        change,
+        window: tree::window_node(node, data),
+    });
+}
+
+pub fn workspace_event(state: &State, change: WorkspaceChange, ws: &WorkspaceNode) {
+    if ws.is_dummy {
+        return;
+    }
+    emit(state, EM_WORKSPACE, || Event::Workspace {
+        change,
+        workspace: workspace_info(ws),
+    });
+}
+
+pub fn output_event(state: &State, change: OutputChange, output: &OutputNode) {
+    if output.is_dummy {
+        return;
+    }
+    emit(state, EM_OUTPUT, || Event::Output {
+        change,
+        output: output_info(output),
+    });
+}
+
+pub fn mode_event(state: &State, seat: &str, mode: Option<&str>) {
+    emit(state, EM_MODE, || Event::Mode {
+        seat: seat.to_string(),
+        mode: mode.map(|m| m.to_string()),
+    });
+}
@@ -0,0 +1,152 @@
+use {
+    crate::{json_ipc::tree::TreeNode, rect::Rect},
+    serde::{Deserialize, Serialize},
+};
+
+/// A request sent by a client.
+///
+/// Requests are sent as JSON objects, one per line.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    GetTree,
+    GetWorkspaces,
+    GetOutputs,
+    GetInputs,
+    Command { command: String },
+    Subscribe { events: Vec<EventType> },
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventType {
+    Window,
+    Workspace,
+    Output,
+    Mode,
+}
+
+/// A message sent to a client.
+///
+/// Every request is answered by exactly one reply. Events are interleaved with the
+/// replies once the client has subscribed to them.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Reply {
+    Tree { tree: TreeNode },
+    Workspaces { workspaces: Vec<WorkspaceInfo> },
+    Outputs { outputs: Vec<OutputInfo> },
+    Inputs { inputs: Vec<InputInfo> },
+    Success,
+    Subscribed,
+    Error { message: String },
+    Event(Event),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Window {
+        change: WindowChange,
+        window: TreeNode,
+    },
+    Workspace {
+        change: WorkspaceChange,
+        workspace: WorkspaceInfo,
+    },
+    Output {
+        change: OutputChange,
+        output: OutputInfo,
+    },
+    Mode {
+        seat: String,
+        mode: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowChange {
+    New,
+    Close,
+    Title,
+    Focus,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceChange {
+    Init,
+    Empty,
+    Focus,
+    Rename,
+    Move,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputChange {
+    Connected,
+    Disconnected,
+    Mode,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub struct JsonRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<Rect> for JsonRect {
+    fn from(rect: Rect) -> Self {
+        Self {
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorkspaceInfo {
+    pub id: u32,
+    pub name: String,
+    pub output: String,
+    pub visible: bool,
+    pub urgent: bool,
+    pub rect: JsonRect,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OutputInfo {
+    pub id: u32,
+    pub name: String,
+    pub manufacturer: String,
+    pub model: String,
+    pub serial_number: String,
+    pub rect: JsonRect,
+    pub scale: f64,
+    pub transform: String,
+    pub mode: ModeInfo,
+    pub workspace: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct ModeInfo {
+    pub width: i32,
+    pub height: i32,
+    pub refresh_rate_millihz: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InputInfo {
+    pub id: u32,
+    pub name: String,
+    pub seat: Option<String>,
+    pub syspath: Option<String>,
+    pub devnode: Option<String>,
+    pub capabilities: Vec<String>,
+}
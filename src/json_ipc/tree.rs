@@ -0,0 +1,200 @@
+use {
+    crate::{
+        ifs::wl_surface::{
+            ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
+            x_surface::xwindow::Xwindow,
+            xdg_surface::{xdg_popup::XdgPopup, xdg_toplevel::XdgToplevel},
+            zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+            WlSurface,
+        },
+        json_ipc::messages::JsonRect,
+        tree::{
+            ContainerNode, ContainerSplit, DisplayNode, FloatNode, Node, NodeVisitorBase,
+            OutputNode, PlaceholderNode, ToplevelData, ToplevelNode, WorkspaceNode,
+        },
+    },
+    serde::{Deserialize, Serialize},
+    std::{ops::Deref, rc::Rc},
+};
+
+/// A node of the window tree.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TreeNode {
+    pub id: u32,
+    pub rect: JsonRect,
+    pub visible: bool,
+    #[serde(flatten)]
+    pub data: NodeData,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<TreeNode>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NodeData {
+    Display,
+    Output { name: String },
+    Workspace { name: String },
+    Container { split: Split, mono: bool },
+    Float,
+    Window(WindowData),
+    Placeholder(WindowData),
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Split {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WindowData {
+    pub title: String,
+    pub app_id: String,
+    pub client_id: Option<u64>,
+    pub pid: Option<i32>,
+    pub floating: bool,
+    pub fullscreen: bool,
+}
+
+/// Returns the tree of outputs, workspaces, containers, floats, and windows.
+///
+/// Popups, layer surfaces, and lock surfaces are not part of the tree.
+pub fn tree(root: &Rc<DisplayNode>) -> TreeNode {
+    let mut builder = TreeBuilder::default();
+    builder.visit_display(root);
+    builder.root.unwrap()
+}
+
+/// Returns the node of a toplevel without its children.
+pub fn window_node(node: &dyn Node, data: &ToplevelData) -> TreeNode {
+    let window = WindowData {
+        title: data.title.borrow().clone(),
+        app_id: data.app_id.borrow().clone(),
+        client_id: data.client.as_ref().map(|c| c.id.raw()),
+        pid: data.client.as_ref().map(|c| c.pid_info.pid),
+        floating: data.is_floating.get(),
+        fullscreen: data.is_fullscreen.get(),
+    };
+    let data = match node.node_is_placeholder() {
+        true => NodeData::Placeholder(window),
+        false => NodeData::Window(window),
+    };
+    TreeNode {
+        id: node.node_id().raw(),
+        rect: node.node_absolute_position().into(),
+        visible: node.node_visible(),
+        data,
+        nodes: vec![],
+    }
+}
+
+#[derive(Default)]
+struct TreeBuilder {
+    stack: Vec<TreeNode>,
+    root: Option<TreeNode>,
+}
+
+impl TreeBuilder {
+    fn add(&mut self, node: &dyn Node, data: NodeData, children: impl FnOnce(&mut Self)) {
+        self.stack.push(TreeNode {
+            id: node.node_id().raw(),
+            rect: node.node_absolute_position().into(),
+            visible: node.node_visible(),
+            data,
+            nodes: vec![],
+        });
+        children(self);
+        let node = self.stack.pop().unwrap();
+        self.finish(node);
+    }
+
+    fn add_window(&mut self, tl: &dyn ToplevelNode) {
+        self.finish(window_node(tl.tl_as_node(), tl.tl_data()));
+    }
+
+    fn finish(&mut self, node: TreeNode) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.nodes.push(node),
+            None => self.root = Some(node),
+        }
+    }
+}
+
+impl NodeVisitorBase for TreeBuilder {
+    fn visit_surface(&mut self, _node: &Rc<WlSurface>) {
+        // nothing
+    }
+
+    fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+        let split = match node.split.get() {
+            ContainerSplit::Horizontal => Split::Horizontal,
+            ContainerSplit::Vertical => Split::Vertical,
+        };
+        let data = NodeData::Container {
+            split,
+            mono: node.mono_child.is_some(),
+        };
+        self.add(&**node, data, |s| node.node_visit_children(s));
+    }
+
+    fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
+        self.add_window(&**node);
+    }
+
+    fn visit_popup(&mut self, _node: &Rc<XdgPopup>) {
+        // nothing
+    }
+
+    fn visit_display(&mut self, node: &Rc<DisplayNode>) {
+        self.add(&**node, NodeData::Display, |s| {
+            for output in node.outputs.lock().values() {
+                s.visit_output(output);
+            }
+        });
+    }
+
+    fn visit_output(&mut self, node: &Rc<OutputNode>) {
+        let data = NodeData::Output {
+            name: node.global.connector.name.clone(),
+        };
+        self.add(&**node, data, |s| {
+            for ws in node.workspaces.iter() {
+                s.visit_workspace(ws.deref());
+            }
+        });
+    }
+
+    fn visit_float(&mut self, node: &Rc<FloatNode>) {
+        self.add(&**node, NodeData::Float, |s| node.node_visit_children(s));
+    }
+
+    fn visit_workspace(&mut self, node: &Rc<WorkspaceNode>) {
+        let data = NodeData::Workspace {
+            name: node.name.borrow().clone(),
+        };
+        self.add(&**node, data, |s| {
+            node.node_visit_children(s);
+            for stacked in node.stacked.iter() {
+                stacked.deref().clone().node_visit(s);
+            }
+        });
+    }
+
+    fn visit_layer_surface(&mut self, _node: &Rc<ZwlrLayerSurfaceV1>) {
+        // nothing
+    }
+
+    fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+        self.add_window(&**node);
+    }
+
+    fn visit_placeholder(&mut self, node: &Rc<PlaceholderNode>) {
+        self.add_window(&**node);
+    }
+
+    fn visit_lock_surface(&mut self, _node: &Rc<ExtSessionLockSurfaceV1>) {
+        // nothing
+    }
+}
@@ -19,6 +19,8 @@ pub struct libinput_event_pointer(u8);
 #[repr(transparent)]
 pub struct libinput_event_gesture(u8);
 #[repr(transparent)]
+pub struct libinput_event_touch(u8);
+#[repr(transparent)]
 pub struct libinput_event_switch(u8);
 #[repr(transparent)]
 pub struct libinput_event_tablet_tool(u8);
@@ -170,6 +172,18 @@ extern "C" {
     pub fn libinput_event_gesture_get_scale(event: *mut libinput_event_gesture) -> f64;
     pub fn libinput_event_gesture_get_angle_delta(event: *mut libinput_event_gesture) -> f64;
 
+    pub fn libinput_event_get_touch_event(event: *mut libinput_event) -> *mut libinput_event_touch;
+    pub fn libinput_event_touch_get_time_usec(event: *mut libinput_event_touch) -> u64;
+    pub fn libinput_event_touch_get_seat_slot(event: *mut libinput_event_touch) -> i32;
+    pub fn libinput_event_touch_get_x_transformed(
+        event: *mut libinput_event_touch,
+        width: u32,
+    ) -> f64;
+    pub fn libinput_event_touch_get_y_transformed(
+        event: *mut libinput_event_touch,
+        height: u32,
+    ) -> f64;
+
     pub fn libinput_event_get_switch_event(
         event: *mut libinput_event,
     ) -> *mut libinput_event_switch;
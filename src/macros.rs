@@ -465,6 +465,35 @@ macro_rules! fatal {
     }}
 }
 
+macro_rules! logical_to_client_wire_scale {
+    ($client:expr, $($field:expr),+ $(,)?) => {
+        if let Some(scale) = $client.wire_scale.get() {
+            $(
+                $field *= scale;
+            )+
+        }
+    };
+}
+
+macro_rules! client_wire_scale_to_logical {
+    ($client:expr, $x:expr, $y:expr $(,)?) => {
+        if let Some(scale) = $client.wire_scale.get() {
+            $x = $x.div_euclid(scale);
+            $y = $y.div_euclid(scale);
+        }
+    };
+    ($client:expr, $x:expr, $y:expr, $width:expr, $height:expr $(,)?) => {
+        if let Some(scale) = $client.wire_scale.get() {
+            // Positions are rounded down and sizes up so that converting the
+            // result back to the wire never shrinks the window.
+            $x = $x.div_euclid(scale);
+            $y = $y.div_euclid(scale);
+            $width = ($width + scale - 1).div_euclid(scale);
+            $height = ($height + scale - 1).div_euclid(scale);
+        }
+    };
+}
+
 macro_rules! stacked_node_impl {
     () => {
         fn stacked_into_node(self: Rc<Self>) -> Rc<dyn Node> {
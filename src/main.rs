@@ -58,6 +58,7 @@ mod dbus;
 mod drm_feedback;
 mod edid;
 mod fixed;
+mod focus_history;
 mod forker;
 mod format;
 mod gfx_api;
@@ -67,9 +68,11 @@ mod ifs;
 mod io_uring;
 #[cfg(feature = "it")]
 mod it;
+mod json_ipc;
 mod libinput;
 mod logger;
 mod logind;
+mod minimize;
 mod object;
 mod pango;
 mod pipewire;
@@ -77,6 +80,7 @@ mod portal;
 mod rect;
 mod renderer;
 mod scale;
+mod scratchpad;
 mod screenshoter;
 mod security_context_acceptor;
 mod sighand;
@@ -93,10 +97,12 @@ mod utils;
 mod version;
 mod video;
 mod wheel;
+mod window_rules;
 mod wire;
 mod wire_dbus;
 mod wire_xcon;
 mod wl_usr;
+mod workspaces;
 mod xcon;
 mod xkbcommon;
 mod xwayland;
@@ -0,0 +1,86 @@
+use {
+    crate::{
+        ifs::wl_seat::{collect_kb_foci, WlSeatGlobal},
+        state::State,
+        tree::{Direction, MinimizedData, Node, ToplevelNode, WorkspaceNode},
+    },
+    std::rc::Rc,
+};
+
+/// Minimizes the toplevel.
+///
+/// The toplevel is removed from the layout of its workspace and appended to the minimized
+/// windows of the workspace. It stays there until it is restored or destroyed.
+pub fn minimize(state: &Rc<State>, tl: Rc<dyn ToplevelNode>) {
+    let data = tl.tl_data();
+    if data.is_minimized() {
+        return;
+    }
+    if data.is_fullscreen.get() {
+        tl.clone().tl_set_fullscreen(false);
+    }
+    let (Some(ws), Some(parent)) = (data.workspace.get(), data.parent.get()) else {
+        return;
+    };
+    let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+    parent.cnode_remove_child2(tl.tl_as_node(), true);
+    data.parent.take();
+    data.workspace.take();
+    tl.tl_set_visible(false);
+    if ws.visible.get() {
+        for seat in kb_foci {
+            ws.clone().node_do_focus(&seat, Direction::Unspecified);
+        }
+    }
+    *data.minimized_data.borrow_mut() = Some(MinimizedData {
+        workspace: ws.clone(),
+        _link: ws.minimized.add_last(tl.clone()),
+    });
+    tl.tl_set_minimized(true);
+    data.send_minimized();
+    state.tree_changed();
+}
+
+/// Removes the toplevel from the minimized windows of its workspace without mapping it.
+///
+/// Returns the workspace the toplevel was minimized on.
+pub fn unminimize(tl: &dyn ToplevelNode) -> Option<Rc<WorkspaceNode>> {
+    let data = tl.tl_data();
+    let md = data.minimized_data.borrow_mut().take()?;
+    tl.tl_set_minimized(false);
+    data.send_minimized();
+    Some(md.workspace)
+}
+
+/// Restores a minimized toplevel on the workspace it was minimized on.
+///
+/// Tiled windows are added to the layout of the workspace. Floating windows are centered
+/// on the output of the workspace.
+pub fn restore(state: &Rc<State>, tl: Rc<dyn ToplevelNode>) {
+    let Some(ws) = unminimize(&*tl) else {
+        return;
+    };
+    let data = tl.tl_data();
+    if data.is_floating.get() {
+        let (width, height) = data.float_size(&ws);
+        state.map_floating(tl.clone(), width, height, &ws, None);
+    } else {
+        state.map_tiled_on(tl.clone(), &ws);
+    }
+    state.tree_changed();
+}
+
+/// Restores the most recently minimized window of the workspace shown on the output that
+/// contains the cursor and focuses it.
+pub fn restore_last_minimized(state: &Rc<State>, seat: &Rc<WlSeatGlobal>) {
+    let Some(ws) = seat.get_output().workspace.get() else {
+        return;
+    };
+    let Some(tl) = ws.minimized.last().map(|tl| (*tl).clone()) else {
+        return;
+    };
+    restore(state, tl.clone());
+    if tl.node_visible() {
+        tl.node_do_focus(seat, Direction::Unspecified);
+    }
+}
@@ -101,8 +101,17 @@ bitflags! {
     SUPPORTED_META_HEADER = 1 << 0,
     SUPPORTED_META_BUSY = 1 << 1,
     SUPPORTED_META_VIDEO_CROP = 1 << 2,
+    SUPPORTED_META_CURSOR = 1 << 3,
 }
 
+/// The largest cursor bitmap that fits into the cursor meta.
+pub const META_CURSOR_MAX_SIZE: usize = 256;
+
+/// The size of the cursor meta including a BGRA bitmap of the maximum size.
+pub const META_CURSOR_SIZE: usize = mem::size_of::<spa_meta_cursor>()
+    + mem::size_of::<spa_meta_bitmap>()
+    + META_CURSOR_MAX_SIZE * META_CURSOR_MAX_SIZE * 4;
+
 pub struct PwClientNodePort {
     pub node: Rc<PwClientNode>,
 
@@ -139,6 +148,7 @@ pub struct PwClientNodeBuffer {
     pub meta_header: Option<Rc<PwMemTyped<spa_meta_header>>>,
     pub meta_busy: Option<Rc<PwMemTyped<spa_meta_busy>>>,
     pub meta_video_crop: Option<Rc<PwMemTyped<spa_meta_region>>>,
+    pub meta_cursor: Option<Rc<PwMemSlice>>,
     pub chunks: Vec<Rc<PwMemTyped<spa_chunk>>>,
     pub slices: Vec<Rc<PwMemSlice>>,
 }
@@ -322,6 +332,9 @@ impl PwClientNode {
                 if sm.contains(SUPPORTED_META_VIDEO_CROP) {
                     metas.push((SPA_META_VideoCrop, mem::size_of::<spa_meta_region>()));
                 }
+                if sm.contains(SUPPORTED_META_CURSOR) {
+                    metas.push((SPA_META_Cursor, META_CURSOR_SIZE));
+                }
                 let sf = port.supported_formats.borrow_mut();
                 let bc = port.buffer_config.get();
                 let mut num_params = metas.len() as u32;
@@ -608,6 +621,7 @@ impl PwClientNode {
             let mut meta_header = Default::default();
             let mut meta_video_crop = Default::default();
             let mut meta_busy = Default::default();
+            let mut meta_cursor = Default::default();
             let mut chunks = vec![];
             let mut slices = vec![];
 
@@ -638,7 +652,9 @@ impl PwClientNode {
                         let _bitmap = mem.typed_at::<spa_meta_bitmap>(offset);
                     }
                     SPA_META_Cursor => {
-                        let _cursor = mem.typed_at::<spa_meta_cursor>(offset);
+                        if size >= mem::size_of::<spa_meta_cursor>() {
+                            meta_cursor = Some(mem.slice(offset..offset + size));
+                        }
                     }
                     SPA_META_Control => {}
                     SPA_META_Busy => {
@@ -679,6 +695,7 @@ impl PwClientNode {
                 meta_header,
                 meta_busy,
                 meta_video_crop,
+                meta_cursor,
                 chunks,
                 slices,
             }));
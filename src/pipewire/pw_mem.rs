@@ -41,7 +41,6 @@ pub struct PwMemTyped<T> {
     _phantom: PhantomData<T>,
 }
 
-#[allow(dead_code)]
 pub struct PwMemSlice {
     mem: Rc<PwMemMap>,
     range: Range<usize>,
@@ -145,6 +144,15 @@ impl<T: Pod> PwMemTyped<T> {
     }
 }
 
+impl PwMemSlice {
+    pub unsafe fn bytes_mut(&self) -> &mut [u8] {
+        std::slice::from_raw_parts_mut(
+            self.mem.map.ptr.cast::<u8>().add(self.range.start) as _,
+            self.range.len(),
+        )
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum PwMemError {
     #[error("mmap failed")]
@@ -9,7 +9,7 @@ use {
         fmt::{Debug, Formatter},
         sync::atomic::AtomicU32,
     },
-    uapi::{c, Pod},
+    uapi::{c, Packed, Pod},
 };
 
 macro_rules! ty {
@@ -786,6 +786,7 @@ pub struct spa_meta_bitmap {
 }
 
 unsafe impl Pod for spa_meta_bitmap {}
+unsafe impl Packed for spa_meta_bitmap {}
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -798,6 +799,7 @@ pub struct spa_meta_cursor {
 }
 
 unsafe impl Pod for spa_meta_cursor {}
+unsafe impl Packed for spa_meta_cursor {}
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -1,6 +1,10 @@
 mod ptl_display;
+mod ptl_global_shortcuts;
+mod ptl_remote_desktop;
 mod ptl_render_ctx;
 mod ptl_screencast;
+mod ptl_screenshot;
+mod ptl_settings;
 mod ptr_gui;
 
 use {
@@ -17,8 +21,12 @@ use {
         pipewire::pw_con::{PwCon, PwConHolder, PwConOwner},
         portal::{
             ptl_display::{watch_displays, PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::{add_global_shortcuts_dbus_members, GlobalShortcutsSession},
+            ptl_remote_desktop::add_remote_desktop_dbus_members,
             ptl_render_ctx::PortalRenderCtx,
             ptl_screencast::{add_screencast_dbus_members, ScreencastSession},
+            ptl_screenshot::add_screenshot_dbus_members,
+            ptl_settings::{add_settings_dbus_members, PortalSettings},
         },
         utils::{
             clone3::{fork_with_pidfd, Forked},
@@ -47,8 +55,9 @@ use {
     uapi::{c, getpid, OwnedFd},
 };
 
+const PORTAL_OBJECT_PATH: &str = "/org/freedesktop/portal/desktop";
+
 const PORTAL_SUCCESS: u32 = 0;
-#[allow(dead_code)]
 const PORTAL_CANCELLED: u32 = 1;
 #[allow(dead_code)]
 const PORTAL_ENDED: u32 = 2;
@@ -195,16 +204,19 @@ async fn run_async(
         displays: Default::default(),
         dbus,
         screencasts: Default::default(),
+        global_shortcuts: Default::default(),
+        settings: Default::default(),
         next_id: NumCell::new(1),
         render_ctxs: Default::default(),
         dma_buf_ids: Default::default(),
     });
     let _root = {
-        let obj = state
-            .dbus
-            .add_object("/org/freedesktop/portal/desktop")
-            .unwrap();
+        let obj = state.dbus.add_object(PORTAL_OBJECT_PATH).unwrap();
+        add_global_shortcuts_dbus_members(&state, &obj);
+        add_remote_desktop_dbus_members(&state, &obj);
         add_screencast_dbus_members(&state, &obj);
+        add_screenshot_dbus_members(&state, &obj);
+        add_settings_dbus_members(&state, &obj);
         obj
     };
     state.pw_con.owner.set(Some(state.clone()));
@@ -285,6 +297,8 @@ struct PortalState {
     displays: CopyHashMap<PortalDisplayId, Rc<PortalDisplay>>,
     dbus: Rc<DbusSocket>,
     screencasts: CopyHashMap<String, Rc<ScreencastSession>>,
+    global_shortcuts: CopyHashMap<String, Rc<GlobalShortcutsSession>>,
+    settings: PortalSettings,
     next_id: NumCell<u32>,
     render_ctxs: CopyHashMap<c::dev_t, Weak<PortalRenderCtx>>,
     dma_buf_ids: Rc<DmaBufIds>,
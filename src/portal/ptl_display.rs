@@ -6,17 +6,20 @@ use {
             ptl_render_ctx::PortalRenderCtx, ptl_screencast::ScreencastSession,
             ptr_gui::WindowData, PortalState,
         },
+        theme::Color,
         utils::{
             bitflags::BitflagsExt, clonecell::CloneCell, copyhashmap::CopyHashMap,
             errorfmt::ErrorFmt, hash_map_ext::HashMapExt, oserror::OsError,
         },
         video::drm::Drm,
         wire::{
-            wl_pointer, JayCompositor, WlCompositor, WlOutput, WlSeat, WlSurfaceId,
-            WpFractionalScaleManagerV1, WpViewporter, ZwlrLayerShellV1, ZwpLinuxDmabufV1,
+            wl_output::Name, wl_pointer, JayCompositor, WlCompositor, WlOutput, WlSeat,
+            WlSurfaceId, WpFractionalScaleManagerV1, WpViewporter, ZwlrLayerShellV1,
+            ZwpLinuxDmabufV1,
         },
         wl_usr::{
             usr_ifs::{
+                usr_jay_appearance::UsrJayAppearanceOwner,
                 usr_jay_compositor::UsrJayCompositor,
                 usr_jay_output::{UsrJayOutput, UsrJayOutputOwner},
                 usr_jay_pointer::UsrJayPointer,
@@ -80,6 +83,7 @@ pub struct PortalOutput {
     pub dpy: Rc<PortalDisplay>,
     pub wl: Rc<UsrWlOutput>,
     pub jay: Rc<UsrJayOutput>,
+    pub name: RefCell<String>,
 }
 
 pub struct PortalSeat {
@@ -188,12 +192,21 @@ impl UsrJayRenderCtxOwner for PortalDisplay {
     }
 }
 
+impl UsrJayAppearanceOwner for PortalDisplay {
+    fn done(&self, color_scheme: u32, accent_color: Color) {
+        self.state.update_appearance(color_scheme, accent_color);
+    }
+}
+
 impl UsrConOwner for PortalDisplay {
     fn killed(&self) {
         log::info!("Removing display {}", self.id);
         for sc in self.screencasts.lock().drain_values() {
             sc.kill();
         }
+        for gs in self.state.global_shortcuts.lock().values() {
+            gs.remove_display(self.id);
+        }
         self.windows.clear();
         self.state.displays.remove(&self.id);
     }
@@ -231,7 +244,11 @@ impl UsrJayOutputOwner for PortalOutput {
     }
 }
 
-impl UsrWlOutputOwner for PortalOutput {}
+impl UsrWlOutputOwner for PortalOutput {
+    fn name(&self, ev: &Name) {
+        *self.name.borrow_mut() = ev.name.to_string();
+    }
+}
 
 async fn maybe_add_display(state: &Rc<PortalState>, name: &str) {
     let tail = match name.strip_prefix("wayland-") {
@@ -393,6 +410,14 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
     let jrc = dpy.jc.get_render_context();
     jrc.owner.set(Some(dpy.clone()));
 
+    let dpy2 = dpy.clone();
+    dpy.con.sync(move || {
+        if dpy2.jc.caps.appearance.get() {
+            let ja = dpy2.jc.watch_appearance();
+            ja.owner.set(Some(dpy2.clone()));
+        }
+    });
+
     for (name, version) in outputs {
         add_output(&dpy, name, version);
     }
@@ -439,6 +464,7 @@ fn add_output(dpy: &Rc<PortalDisplay>, name: u32, version: u32) {
         dpy: dpy.clone(),
         wl: wl.clone(),
         jay: jo.clone(),
+        name: Default::default(),
     });
     po.wl.owner.set(Some(po.clone()));
     po.jay.owner.set(Some(po.clone()));
@@ -0,0 +1,480 @@
+mod shortcut_store;
+mod shortcuts_gui;
+mod trigger;
+
+use {
+    crate::{
+        dbus::{
+            prelude::{ObjectPath, Variant},
+            DbusObject, DictEntry, DynamicType, PendingReply,
+        },
+        portal::{
+            ptl_display::{PortalDisplayId, PortalSeat},
+            ptl_global_shortcuts::{shortcuts_gui::ShortcutsGui, trigger::Trigger},
+            PortalState, PORTAL_CANCELLED, PORTAL_OBJECT_PATH, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            hash_map_ext::HashMapExt,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                global_shortcuts::{
+                    Activated, BindShortcuts, BindShortcutsReply, CreateSession,
+                    CreateSessionReply, Deactivated, ListShortcuts, ListShortcutsReply,
+                },
+                session::{CloseReply as SessionCloseReply, Closed},
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_global_shortcuts::{
+            UsrJayGlobalShortcuts, UsrJayGlobalShortcutsOwner,
+        },
+    },
+    jay_config::keyboard::syms::{SYM_BackSpace, SYM_Escape},
+    std::{borrow::Cow, cell::RefCell, ops::Deref, rc::Rc},
+};
+
+pub struct GlobalShortcutsSession {
+    state: Rc<PortalState>,
+    pub app: String,
+    session_obj: DbusObject,
+    pub phase: CloneCell<GlobalShortcutsPhase>,
+    shortcuts: RefCell<Vec<Shortcut>>,
+    bindings: CopyHashMap<(PortalDisplayId, u32), Rc<SeatShortcuts>>,
+}
+
+#[derive(Clone)]
+pub struct Shortcut {
+    pub id: String,
+    pub description: String,
+    pub trigger: Option<Trigger>,
+}
+
+#[derive(Clone)]
+pub enum GlobalShortcutsPhase {
+    Idle,
+    Binding(Rc<BindingShortcuts>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for GlobalShortcutsPhase {}
+
+/// A `BindShortcuts` request that is waiting for the user to confirm the triggers.
+pub struct BindingShortcuts {
+    pub session: Rc<GlobalShortcutsSession>,
+    request_obj: DbusObject,
+    reply: PendingReply<BindShortcutsReply<'static>>,
+    pub shortcuts: RefCell<Vec<Shortcut>>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ShortcutsGui>>,
+    pub recording: CloneCell<Option<Rc<RecordingShortcut>>>,
+}
+
+/// Waits for the user to press the new trigger of a shortcut.
+pub struct RecordingShortcut {
+    binding: Rc<BindingShortcuts>,
+    pub idx: usize,
+    gs: Rc<UsrJayGlobalShortcuts>,
+}
+
+/// The shortcuts of a session registered on one seat.
+struct SeatShortcuts {
+    session: Rc<GlobalShortcutsSession>,
+    gs: Rc<UsrJayGlobalShortcuts>,
+}
+
+impl GlobalShortcutsSession {
+    pub(super) fn kill(&self) {
+        self.session_obj.emit_signal(&Closed);
+        self.state.global_shortcuts.remove(self.session_obj.path());
+        if let GlobalShortcutsPhase::Binding(b) = self.phase.set(GlobalShortcutsPhase::Terminated) {
+            b.reply.err("Session has been terminated");
+            b.stop_recording();
+            b.kill_guis();
+        }
+        self.unbind();
+    }
+
+    /// Forgets the registrations on a display that no longer exists.
+    pub(super) fn remove_display(&self, dpy: PortalDisplayId) {
+        self.bindings.lock().retain(|&(id, _), _| id != dpy);
+    }
+
+    fn unbind(&self) {
+        for binding in self.bindings.lock().drain_values() {
+            binding.gs.con.remove_obj(binding.gs.deref());
+        }
+    }
+
+    /// Registers the shortcuts on all seats of all displays.
+    fn apply(self: &Rc<Self>, shortcuts: Vec<Shortcut>) {
+        self.unbind();
+        for dpy in self.state.displays.lock().values() {
+            if !dpy.jc.caps.global_shortcuts.get() {
+                continue;
+            }
+            for seat in dpy.seats.lock().values() {
+                let gs = dpy.jc.create_global_shortcuts(&seat.wl);
+                for (idx, shortcut) in shortcuts.iter().enumerate() {
+                    if let Some(trigger) = shortcut.trigger {
+                        gs.bind(idx as u32, trigger.mods.0, trigger.sym);
+                    }
+                }
+                let binding = Rc::new(SeatShortcuts {
+                    session: self.clone(),
+                    gs,
+                });
+                binding.gs.owner.set(Some(binding.clone()));
+                self.bindings.set((dpy.id, seat.global_id), binding);
+            }
+        }
+        *self.shortcuts.borrow_mut() = shortcuts;
+    }
+
+    fn dbus_bind_shortcuts(
+        self: &Rc<Self>,
+        req: BindShortcuts,
+        reply: PendingReply<BindShortcutsReply<'static>>,
+    ) {
+        match self.phase.get() {
+            GlobalShortcutsPhase::Idle => {}
+            GlobalShortcutsPhase::Binding(_) => {
+                reply.err("Another BindShortcuts request is already pending");
+                return;
+            }
+            GlobalShortcutsPhase::Terminated => {
+                reply.err("Session has been terminated");
+                return;
+            }
+        }
+        let stored = shortcut_store::load(&self.app);
+        let mut confirmed = true;
+        let mut shortcuts = vec![];
+        for (id, options) in req.shortcuts.iter() {
+            let mut description = String::new();
+            let mut preferred_trigger = None;
+            for option in options.iter() {
+                match (&*option.key, &option.value) {
+                    ("description", Variant::String(s)) => description = s.to_string(),
+                    ("preferred_trigger", Variant::String(s)) => {
+                        preferred_trigger = Trigger::parse(s);
+                        if preferred_trigger.is_none() {
+                            log::warn!("Could not parse trigger `{}`", s);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let trigger = match stored.get(&**id) {
+                Some(t) => *t,
+                None => {
+                    confirmed = false;
+                    preferred_trigger
+                }
+            };
+            shortcuts.push(Shortcut {
+                id: id.to_string(),
+                description,
+                trigger,
+            });
+        }
+        if confirmed {
+            log::info!("Restoring the global shortcuts of `{}`", self.app);
+            let results = shortcuts_results(&shortcuts);
+            self.apply(shortcuts);
+            reply.ok(&BindShortcutsReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Owned(results),
+            });
+            return;
+        }
+        let request_obj = match self.state.dbus.add_object(req.handle.0.to_string()) {
+            Ok(r) => r,
+            Err(_) => {
+                reply.err("Request handle is not unique");
+                return;
+            }
+        };
+        let binding = Rc::new(BindingShortcuts {
+            session: self.clone(),
+            request_obj,
+            reply,
+            shortcuts: RefCell::new(shortcuts),
+            guis: Default::default(),
+            recording: Default::default(),
+        });
+        {
+            use org::freedesktop::impl_::portal::request::*;
+            let session = self.clone();
+            binding.request_obj.add_method::<Close, _>(move |_, pr| {
+                if let GlobalShortcutsPhase::Binding(b) = session.phase.get() {
+                    b.cancel();
+                }
+                pr.ok(&CloseReply);
+            });
+        }
+        binding.show_guis();
+        if binding.guis.is_empty() {
+            binding
+                .reply
+                .err("There are no running displays that support global shortcuts");
+            return;
+        }
+        self.phase.set(GlobalShortcutsPhase::Binding(binding));
+    }
+
+    fn dbus_list_shortcuts(
+        self: &Rc<Self>,
+        _req: ListShortcuts,
+        reply: PendingReply<ListShortcutsReply<'static>>,
+    ) {
+        let results = shortcuts_results(&self.shortcuts.borrow());
+        reply.ok(&ListShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(results),
+        });
+    }
+
+    fn shortcut_id(&self, shortcut: u32) -> Option<String> {
+        self.shortcuts
+            .borrow()
+            .get(shortcut as usize)
+            .map(|s| s.id.clone())
+    }
+}
+
+impl BindingShortcuts {
+    /// Shows the dialog on all displays, replacing the dialogs that are currently shown.
+    pub fn show_guis(self: &Rc<Self>) {
+        self.kill_guis();
+        for dpy in self.session.state.displays.lock().values() {
+            if dpy.outputs.len() > 0 && dpy.jc.caps.global_shortcuts.get() {
+                self.guis.set(dpy.id, ShortcutsGui::new(self, dpy));
+            }
+        }
+    }
+
+    fn kill_guis(&self) {
+        for gui in self.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+    }
+
+    fn stop_recording(&self) {
+        if let Some(recording) = self.recording.take() {
+            recording.gs.con.remove_obj(recording.gs.deref());
+        }
+    }
+
+    fn finish(&self) {
+        self.stop_recording();
+        self.kill_guis();
+        if let GlobalShortcutsPhase::Binding(b) = self.session.phase.get() {
+            if std::ptr::eq(&*b, self) {
+                self.session.phase.set(GlobalShortcutsPhase::Idle);
+            }
+        }
+    }
+
+    /// Lets the user press the new trigger of the shortcut at `idx`.
+    pub fn record(self: &Rc<Self>, seat: &PortalSeat, idx: usize) {
+        self.stop_recording();
+        let gs = seat.dpy.jc.create_global_shortcuts(&seat.wl);
+        let recording = Rc::new(RecordingShortcut {
+            binding: self.clone(),
+            idx,
+            gs,
+        });
+        recording.gs.owner.set(Some(recording.clone()));
+        recording.gs.record();
+        self.recording.set(Some(recording));
+        self.show_guis();
+    }
+
+    pub fn accept(&self) {
+        log::info!("User has accepted the global shortcuts");
+        self.finish();
+        let shortcuts = self.shortcuts.borrow().clone();
+        shortcut_store::store(&self.session.app, &shortcuts);
+        let results = shortcuts_results(&shortcuts);
+        self.session.apply(shortcuts);
+        self.reply.ok(&BindShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(results),
+        });
+    }
+
+    pub fn cancel(&self) {
+        self.finish();
+        self.reply.ok(&BindShortcutsReply {
+            response: PORTAL_CANCELLED,
+            results: Default::default(),
+        });
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for RecordingShortcut {
+    fn recorded(&self, mods: u32, keysym: u32) {
+        let binding = &self.binding;
+        binding.stop_recording();
+        let trigger = Trigger::new(mods, keysym);
+        if trigger.mods.0 == 0 && keysym == SYM_Escape.0 {
+            // keep the previous trigger
+        } else if let Some(shortcut) = binding.shortcuts.borrow_mut().get_mut(self.idx) {
+            match trigger.mods.0 == 0 && keysym == SYM_BackSpace.0 {
+                true => shortcut.trigger = None,
+                false => shortcut.trigger = Some(trigger),
+            }
+        }
+        if binding.guis.is_not_empty() {
+            binding.show_guis();
+        }
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for SeatShortcuts {
+    fn activated(&self, shortcut: u32, time_usec: u64) {
+        if let Some(id) = self.session.shortcut_id(shortcut) {
+            self.session.state.dbus.emit_signal(
+                PORTAL_OBJECT_PATH,
+                &Activated {
+                    session_handle: ObjectPath(self.session.session_obj.path().into()),
+                    shortcut_id: id.into(),
+                    timestamp: time_usec / 1000,
+                    options: Default::default(),
+                },
+            );
+        }
+    }
+
+    fn deactivated(&self, shortcut: u32, time_usec: u64) {
+        if let Some(id) = self.session.shortcut_id(shortcut) {
+            self.session.state.dbus.emit_signal(
+                PORTAL_OBJECT_PATH,
+                &Deactivated {
+                    session_handle: ObjectPath(self.session.session_obj.path().into()),
+                    shortcut_id: id.into(),
+                    timestamp: time_usec / 1000,
+                    options: Default::default(),
+                },
+            );
+        }
+    }
+}
+
+/// Formats the shortcuts as the `shortcuts` result of `BindShortcuts` and `ListShortcuts`.
+fn shortcuts_results(
+    shortcuts: &[Shortcut],
+) -> Vec<DictEntry<Cow<'static, str>, Variant<'static>>> {
+    let dict_type = DynamicType::DictEntry(
+        Box::new(DynamicType::String),
+        Box::new(DynamicType::Variant),
+    );
+    let struct_type = DynamicType::Struct(vec![
+        DynamicType::String,
+        DynamicType::Array(Box::new(dict_type.clone())),
+    ]);
+    let entry = |key: &'static str, value: String| {
+        Variant::DictEntry(
+            Box::new(Variant::String(key.into())),
+            Box::new(Variant::Variant(Box::new(Variant::String(value.into())))),
+        )
+    };
+    let mut elements = vec![];
+    for shortcut in shortcuts {
+        let trigger = match shortcut.trigger {
+            Some(t) => t.to_string(),
+            None => String::new(),
+        };
+        let props = vec![
+            entry("description", shortcut.description.clone()),
+            entry("trigger_description", trigger),
+        ];
+        elements.push(Variant::Struct(vec![
+            Variant::String(shortcut.id.clone().into()),
+            Variant::Array(dict_type.clone(), props),
+        ]));
+    }
+    vec![DictEntry {
+        key: "shortcuts".into(),
+        value: Variant::Array(struct_type, elements),
+    }]
+}
+
+pub(super) fn add_global_shortcuts_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::global_shortcuts::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<BindShortcuts, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_bind_shortcuts(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<ListShortcuts, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_list_shortcuts(req, pr);
+        }
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create Global Shortcuts Session {:#?}", req);
+    if state.global_shortcuts.contains(&*req.session_handle.0) {
+        reply.err("Session already exists");
+        return;
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(GlobalShortcutsSession {
+        state: state.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        phase: CloneCell::new(GlobalShortcutsPhase::Idle),
+        shortcuts: Default::default(),
+        bindings: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    state
+        .global_shortcuts
+        .set(req.session_handle.0.to_string(), session);
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<GlobalShortcutsSession>> {
+    let res = state.global_shortcuts.get(handle);
+    if res.is_none() {
+        let msg = format!("Global shortcuts session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
@@ -0,0 +1,74 @@
+use {
+    crate::{
+        portal::ptl_global_shortcuts::{trigger::Trigger, Shortcut},
+        utils::errorfmt::ErrorFmt,
+    },
+    std::{collections::BTreeMap, fs, path::PathBuf},
+};
+
+/// The triggers that the user has confirmed, per application and shortcut id.
+///
+/// An empty string means that the user has removed the trigger of the shortcut.
+type Assignments = BTreeMap<String, BTreeMap<String, String>>;
+
+fn path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("jay");
+    path.push("global-shortcuts.json");
+    Some(path)
+}
+
+fn load_all() -> Assignments {
+    let Some(path) = path() else {
+        return Default::default();
+    };
+    let contents = match fs::read(&path) {
+        Ok(c) => c,
+        Err(_) => return Default::default(),
+    };
+    match serde_json::from_slice(&contents) {
+        Ok(a) => a,
+        Err(e) => {
+            log::error!("Could not parse {}: {}", path.display(), ErrorFmt(e));
+            Default::default()
+        }
+    }
+}
+
+/// Returns the confirmed triggers of the application.
+pub fn load(app: &str) -> BTreeMap<String, Option<Trigger>> {
+    let mut res = BTreeMap::new();
+    if let Some(shortcuts) = load_all().remove(app) {
+        for (id, trigger) in shortcuts {
+            res.insert(id, Trigger::parse(&trigger));
+        }
+    }
+    res
+}
+
+/// Stores the triggers of the shortcuts that the user has confirmed.
+pub fn store(app: &str, shortcuts: &[Shortcut]) {
+    let Some(path) = path() else {
+        log::warn!("Cannot store global shortcuts because $HOME is not set");
+        return;
+    };
+    let mut all = load_all();
+    let assignments = all.entry(app.to_string()).or_default();
+    for shortcut in shortcuts {
+        let trigger = match shortcut.trigger {
+            Some(t) => t.to_string(),
+            None => String::new(),
+        };
+        assignments.insert(shortcut.id.clone(), trigger);
+    }
+    if let Some(dir) = path.parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            log::error!("Could not create {}: {}", dir.display(), ErrorFmt(e));
+            return;
+        }
+    }
+    let contents = serde_json::to_vec_pretty(&all).unwrap();
+    if let Err(e) = fs::write(&path, contents) {
+        log::error!("Could not write {}: {}", path.display(), ErrorFmt(e));
+    }
+}
@@ -0,0 +1,174 @@
+use {
+    crate::{
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_global_shortcuts::{BindingShortcuts, GlobalShortcutsPhase},
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner,
+            },
+        },
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+    },
+    std::rc::Rc,
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct ShortcutsGui {
+    binding: Rc<BindingShortcuts>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<ShortcutsGuiSurface>>,
+}
+
+pub struct ShortcutsGuiSurface {
+    gui: Rc<ShortcutsGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    binding: Rc<BindingShortcuts>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Accept,
+    Change(usize),
+    Reject,
+}
+
+impl ShortcutsGui {
+    pub fn new(binding: &Rc<BindingShortcuts>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(ShortcutsGui {
+            binding: binding.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let sgs = Rc::new(ShortcutsGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+            });
+            let element = create_dialog(binding);
+            sgs.overlay.data.content.set(Some(element));
+            sgs.overlay.owner.set(Some(sgs.clone()));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let GlobalShortcutsPhase::Binding(b) = self.binding.session.phase.get() {
+            b.guis.remove(&self.dpy.id);
+            if upwards && b.guis.is_empty() {
+                b.cancel();
+            }
+        }
+    }
+}
+
+fn create_dialog(binding: &Rc<BindingShortcuts>) -> Rc<dyn GuiElement> {
+    let app = &binding.session.app;
+    let text = if app.is_empty() {
+        format!("An application wants to register global shortcuts")
+    } else {
+        format!("`{}` wants to register global shortcuts", app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label];
+    let recording = binding.recording.get().map(|r| r.idx);
+    for (idx, shortcut) in binding.shortcuts.borrow().iter().enumerate() {
+        let description = match shortcut.description.is_empty() {
+            true => &shortcut.id,
+            false => &shortcut.description,
+        };
+        let trigger = match (recording == Some(idx), shortcut.trigger) {
+            (true, _) => "Press the new shortcut (Escape to keep, Backspace to remove)".to_string(),
+            (false, Some(t)) => t.to_string(),
+            (false, None) => "Not assigned".to_string(),
+        };
+        let label = Rc::new(Label::default());
+        *label.text.borrow_mut() = format!("{}: {}", description, trigger);
+        let button = static_button(binding, ButtonRole::Change(idx), "Change");
+        button.bg_color.set(Color::from_rgb(170, 170, 200));
+        button.bg_hover_color.set(Color::from_rgb(170, 170, 255));
+        let row = Rc::new(Flow::default());
+        row.orientation.set(Orientation::Horizontal);
+        row.cross_align.set(Align::Center);
+        row.in_margin.set(H_MARGIN / 2.0);
+        *row.elements.borrow_mut() = vec![label, button];
+        elements.push(row);
+    }
+    let accept_button = static_button(binding, ButtonRole::Accept, "Allow");
+    accept_button.bg_color.set(Color::from_rgb(170, 200, 170));
+    accept_button
+        .bg_hover_color
+        .set(Color::from_rgb(170, 255, 170));
+    let reject_button = static_button(binding, ButtonRole::Reject, "Reject");
+    reject_button.bg_color.set(Color::from_rgb(200, 170, 170));
+    reject_button
+        .bg_hover_color
+        .set(Color::from_rgb(255, 170, 170));
+    elements.push(accept_button);
+    elements.push(reject_button);
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    *flow.elements.borrow_mut() = elements;
+    flow
+}
+
+impl OverlayWindowOwner for ShortcutsGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        match self.role {
+            ButtonRole::Accept => self.binding.accept(),
+            ButtonRole::Change(idx) => self.binding.record(seat, idx),
+            ButtonRole::Reject => {
+                log::info!("User has rejected the global shortcuts");
+                self.binding.cancel();
+            }
+        }
+    }
+}
+
+fn static_button(binding: &Rc<BindingShortcuts>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        binding: binding.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button.border_color.set(Color::from_gray(100));
+    button.border.set(2.0);
+    button.padding.set(5.0);
+    button
+}
@@ -0,0 +1,63 @@
+use {
+    crate::xkbcommon::{keysym_from_name, keysym_name},
+    jay_config::keyboard::mods::{Modifiers, ALT, CTRL, LOGO, SHIFT},
+    std::fmt::{Display, Formatter},
+};
+
+const MODS: [(&str, Modifiers); 4] = [
+    ("CTRL", CTRL),
+    ("ALT", ALT),
+    ("SHIFT", SHIFT),
+    ("LOGO", LOGO),
+];
+
+/// A key combination that activates a global shortcut.
+///
+/// Triggers are written as in the XDG shortcuts specification, e.g. `CTRL+SHIFT+a`. The
+/// keysym is the one produced by the key without any modifiers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Trigger {
+    pub mods: Modifiers,
+    pub sym: u32,
+}
+
+impl Trigger {
+    pub fn new(mods: u32, sym: u32) -> Self {
+        let mask = MODS.iter().fold(0, |m, (_, mods)| m | mods.0);
+        Self {
+            mods: Modifiers(mods & mask),
+            sym,
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut mods = Modifiers::NONE;
+        let mut parts = s.split('+').map(|p| p.trim()).peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                let name = match part.chars().count() {
+                    1 => part.to_lowercase(),
+                    _ => part.to_string(),
+                };
+                let sym = keysym_from_name(&name)?;
+                return Some(Self { mods, sym });
+            }
+            let (_, m) = MODS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(part))?;
+            mods |= *m;
+        }
+        None
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, m) in MODS {
+            if self.mods.0 & m.0 != 0 {
+                write!(f, "{}+", name)?;
+            }
+        }
+        f.write_str(&keysym_name(self.sym))
+    }
+}
@@ -0,0 +1,473 @@
+mod remote_desktop_gui;
+
+use {
+    crate::{
+        dbus::{prelude::Variant, DbusObject, DictEntry, PendingReply, FALSE},
+        ifs::wl_seat,
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
+            ptl_remote_desktop::remote_desktop_gui::{ConsentGui, RemoteDesktopIndicator},
+            ptl_screencast::{
+                create_session, PendingStartReply, ScreencastPhase, ScreencastSession,
+            },
+            PortalState, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            hash_map_ext::HashMapExt,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::remote_desktop::{
+                CreateSession, CreateSessionReply, SelectDevices, SelectDevicesReply, Start,
+                StartReply,
+            },
+        },
+        wl_usr::usr_ifs::{
+            usr_jay_input_injector::UsrJayInputInjector, usr_jay_output::UsrJayOutput,
+        },
+    },
+    std::{borrow::Cow, cell::Cell, ops::Deref, rc::Rc},
+};
+
+pub struct RemoteDesktopSession {
+    state: Rc<PortalState>,
+    /// The session object is shared with the screencast portal.
+    pub screencast: Rc<ScreencastSession>,
+    pub phase: CloneCell<RemoteDesktopPhase>,
+    device_types: Cell<DeviceTypes>,
+}
+
+#[derive(Clone)]
+pub enum RemoteDesktopPhase {
+    Init,
+    Confirming(Rc<ConfirmingRemoteDesktop>),
+    Started(Rc<StartedRemoteDesktop>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for RemoteDesktopPhase {}
+
+pub struct ConfirmingRemoteDesktop {
+    _request_obj: DbusObject,
+    reply: PendingReply<StartReply<'static>>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ConsentGui>>,
+}
+
+pub struct StartedRemoteDesktop {
+    dpy: Rc<PortalDisplay>,
+    injector: Option<Rc<UsrJayInputInjector>>,
+    devices: DeviceTypes,
+    stream: Option<u32>,
+    output: Option<Rc<PortalOutput>>,
+    pub indicators: CopyHashMap<u32, Rc<RemoteDesktopIndicator>>,
+}
+
+bitflags! {
+    DeviceTypes: u32;
+
+    KEYBOARD = 1,
+    POINTER = 2,
+    TOUCHSCREEN = 4,
+}
+
+const AVAILABLE_DEVICE_TYPES: DeviceTypes = DeviceTypes(KEYBOARD.0 | POINTER.0 | TOUCHSCREEN.0);
+
+impl RemoteDesktopSession {
+    pub fn app(&self) -> &str {
+        &self.screencast.app
+    }
+
+    /// Tears down the remote desktop part of the session.
+    ///
+    /// This is called when the screencast session is killed.
+    pub(super) fn kill(&self) {
+        match self.phase.set(RemoteDesktopPhase::Terminated) {
+            RemoteDesktopPhase::Init => {}
+            RemoteDesktopPhase::Terminated => {}
+            RemoteDesktopPhase::Confirming(c) => {
+                c.reply.err("Session has been terminated");
+                for gui in c.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            RemoteDesktopPhase::Started(s) => {
+                if let Some(injector) = &s.injector {
+                    s.dpy.con.remove_obj(injector.deref());
+                }
+                for indicator in s.indicators.lock().drain_values() {
+                    indicator.kill();
+                }
+                s.dpy.screencasts.remove(self.screencast.session_path());
+            }
+        }
+    }
+
+    fn dbus_select_devices(
+        self: &Rc<Self>,
+        req: SelectDevices,
+        reply: PendingReply<SelectDevicesReply<'static>>,
+    ) {
+        match self.phase.get() {
+            RemoteDesktopPhase::Init => {}
+            _ => {
+                self.screencast.kill();
+                reply.err("Session has already been started");
+                return;
+            }
+        }
+        for option in req.options.iter() {
+            if let ("types", &Variant::U32(types)) = (&*option.key, &option.value) {
+                self.device_types
+                    .set(DeviceTypes(types) & AVAILABLE_DEVICE_TYPES);
+            }
+        }
+        reply.ok(&SelectDevicesReply {
+            response: PORTAL_SUCCESS,
+            results: Default::default(),
+        });
+    }
+
+    fn dbus_start(self: &Rc<Self>, req: Start<'_>, reply: PendingReply<StartReply<'static>>) {
+        match self.phase.get() {
+            RemoteDesktopPhase::Init => {}
+            _ => {
+                self.screencast.kill();
+                reply.err("Session has already been started");
+                return;
+            }
+        }
+        match self.screencast.phase.get() {
+            ScreencastPhase::SourcesSelected => {
+                // The user grants access to the input devices when selecting the source.
+                self.screencast
+                    .start(&req.handle.0, PendingStartReply::RemoteDesktop(reply));
+            }
+            ScreencastPhase::Init => self.confirm(&req.handle.0, reply),
+            _ => {
+                self.screencast.kill();
+                reply.err("Session is not in the correct phase for starting");
+            }
+        }
+    }
+
+    /// Asks the user to allow the remote control without a screencast.
+    fn confirm(self: &Rc<Self>, handle: &str, reply: PendingReply<StartReply<'static>>) {
+        let request_obj = match self.state.dbus.add_object(handle.to_string()) {
+            Ok(r) => r,
+            Err(_) => {
+                self.screencast.kill();
+                reply.err("Request handle is not unique");
+                return;
+            }
+        };
+        {
+            use org::freedesktop::impl_::portal::request::*;
+            request_obj.add_method::<Close, _>({
+                let screencast = self.screencast.clone();
+                move |_, pr| {
+                    screencast.kill();
+                    pr.ok(&CloseReply);
+                }
+            });
+        }
+        let guis = CopyHashMap::new();
+        for dpy in self.state.displays.lock().values() {
+            if dpy.outputs.len() > 0 && dpy.jc.caps.input_injection.get() {
+                guis.set(dpy.id, ConsentGui::new(self, dpy));
+            }
+        }
+        if guis.is_empty() {
+            self.screencast.kill();
+            reply.err("There are no running displays that support remote control");
+            return;
+        }
+        self.phase.set(RemoteDesktopPhase::Confirming(Rc::new(
+            ConfirmingRemoteDesktop {
+                _request_obj: request_obj,
+                reply,
+                guis,
+            },
+        )));
+    }
+
+    /// Starts the session after the user has allowed it on the display.
+    pub(super) fn accept(self: &Rc<Self>, dpy: &Rc<PortalDisplay>) {
+        let RemoteDesktopPhase::Confirming(c) = self.phase.get() else {
+            return;
+        };
+        for gui in c.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        let devices = self.start(dpy, None, None);
+        c.reply.ok(&StartReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(vec![DictEntry {
+                key: "devices".into(),
+                value: Variant::U32(devices.0),
+            }]),
+        });
+    }
+
+    /// Creates the input injector and shows the indicator on all outputs.
+    ///
+    /// `stream` is the node id of the screencast of this session and `output` the output
+    /// shown in it. Returns the device types that can be used.
+    pub(super) fn start(
+        self: &Rc<Self>,
+        dpy: &Rc<PortalDisplay>,
+        stream: Option<u32>,
+        output: Option<Rc<PortalOutput>>,
+    ) -> DeviceTypes {
+        let mut devices = self.device_types.get();
+        let injector = if dpy.jc.caps.input_injection.get() {
+            let name = match self.app() {
+                "" => "remote desktop".to_string(),
+                app => format!("remote desktop ({})", app),
+            };
+            let mut capabilities = 0;
+            if devices.contains(KEYBOARD) {
+                capabilities |= wl_seat::KEYBOARD;
+            }
+            if devices.contains(POINTER) {
+                capabilities |= wl_seat::POINTER;
+            }
+            if devices.contains(TOUCHSCREEN) {
+                capabilities |= wl_seat::TOUCH;
+            }
+            Some(dpy.jc.create_input_injector(&name, capabilities))
+        } else {
+            log::warn!("The compositor does not support input injection");
+            devices = DeviceTypes::none();
+            None
+        };
+        let started = Rc::new(StartedRemoteDesktop {
+            dpy: dpy.clone(),
+            injector,
+            devices,
+            stream,
+            output,
+            indicators: Default::default(),
+        });
+        self.phase.set(RemoteDesktopPhase::Started(started.clone()));
+        for output in dpy.outputs.lock().values() {
+            started
+                .indicators
+                .set(output.global_id, RemoteDesktopIndicator::new(self, output));
+        }
+        dpy.screencasts.set(
+            self.screencast.session_path().to_owned(),
+            self.screencast.clone(),
+        );
+        devices
+    }
+}
+
+impl StartedRemoteDesktop {
+    fn stream_output(&self, stream: u32) -> Result<&Rc<UsrJayOutput>, &'static str> {
+        if self.stream != Some(stream) {
+            return Err("Unknown stream");
+        }
+        match &self.output {
+            Some(o) => Ok(&o.jay),
+            None => Err("Absolute positions are only supported for output streams"),
+        }
+    }
+}
+
+pub(super) fn add_remote_desktop_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::remote_desktop::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SelectDevices, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_select_devices(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Start, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_start(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerMotion, _>(move |req, pr| {
+        if let Some((_, injector)) = get_injector(&state, &pr, &req.session_handle.0, POINTER) {
+            injector.motion(req.dx, req.dy);
+            pr.ok(&NotifyPointerMotionReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerMotionAbsolute, _>(move |req, pr| {
+        if let Some((s, injector)) = get_injector(&state, &pr, &req.session_handle.0, POINTER) {
+            match s.stream_output(req.stream) {
+                Ok(output) => {
+                    injector.motion_absolute(output, req.x, req.y);
+                    pr.ok(&NotifyPointerMotionAbsoluteReply);
+                }
+                Err(e) => pr.err(e),
+            }
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerButton, _>(move |req, pr| {
+        if let Some((_, injector)) = get_injector(&state, &pr, &req.session_handle.0, POINTER) {
+            if req.button < 0 || req.state > 1 {
+                pr.err("Invalid button event");
+                return;
+            }
+            injector.button(req.button as u32, req.state);
+            pr.ok(&NotifyPointerButtonReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerAxis, _>(move |req, pr| {
+        if let Some((_, injector)) = get_injector(&state, &pr, &req.session_handle.0, POINTER) {
+            let mut finish = false;
+            for option in req.options.iter() {
+                if let ("finish", &Variant::Bool(f)) = (&*option.key, &option.value) {
+                    finish = f != FALSE;
+                }
+            }
+            injector.scroll(req.dx, req.dy, finish);
+            pr.ok(&NotifyPointerAxisReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerAxisDiscrete, _>(move |req, pr| {
+        if let Some((_, injector)) = get_injector(&state, &pr, &req.session_handle.0, POINTER) {
+            if req.axis > 1 {
+                pr.err("Invalid axis");
+                return;
+            }
+            injector.scroll_discrete(req.axis, req.steps);
+            pr.ok(&NotifyPointerAxisDiscreteReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyKeyboardKeycode, _>(move |req, pr| {
+        if let Some((_, injector)) = get_injector(&state, &pr, &req.session_handle.0, KEYBOARD) {
+            if req.keycode < 0 || req.state > 1 {
+                pr.err("Invalid key event");
+                return;
+            }
+            injector.key(req.keycode as u32, req.state);
+            pr.ok(&NotifyKeyboardKeycodeReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyKeyboardKeysym, _>(move |req, pr| {
+        if let Some((_, injector)) = get_injector(&state, &pr, &req.session_handle.0, KEYBOARD) {
+            if req.state > 1 {
+                pr.err("Invalid key event");
+                return;
+            }
+            injector.keysym(req.keysym as u32, req.state);
+            pr.ok(&NotifyKeyboardKeysymReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyTouchDown, _>(move |req, pr| {
+        let session = &req.session_handle.0;
+        if let Some((s, injector)) = get_injector(&state, &pr, session, TOUCHSCREEN) {
+            match s.stream_output(req.stream) {
+                Ok(output) => {
+                    injector.touch_down(output, req.slot as i32, req.x, req.y);
+                    pr.ok(&NotifyTouchDownReply);
+                }
+                Err(e) => pr.err(e),
+            }
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyTouchMotion, _>(move |req, pr| {
+        let session = &req.session_handle.0;
+        if let Some((s, injector)) = get_injector(&state, &pr, session, TOUCHSCREEN) {
+            match s.stream_output(req.stream) {
+                Ok(output) => {
+                    injector.touch_motion(output, req.slot as i32, req.x, req.y);
+                    pr.ok(&NotifyTouchMotionReply);
+                }
+                Err(e) => pr.err(e),
+            }
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyTouchUp, _>(move |req, pr| {
+        let session = &req.session_handle.0;
+        if let Some((_, injector)) = get_injector(&state, &pr, session, TOUCHSCREEN) {
+            injector.touch_up(req.slot as i32);
+            pr.ok(&NotifyTouchUpReply);
+        }
+    });
+    object.set_property::<AvailableDeviceTypes>(Variant::U32(AVAILABLE_DEVICE_TYPES.0));
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create Remote Desktop Session {:#?}", req);
+    let screencast = match create_session(state, &req.session_handle.0, &req.app_id) {
+        Ok(s) => s,
+        Err(e) => {
+            reply.err(e);
+            return;
+        }
+    };
+    let session = Rc::new(RemoteDesktopSession {
+        state: state.clone(),
+        screencast: screencast.clone(),
+        phase: CloneCell::new(RemoteDesktopPhase::Init),
+        device_types: Cell::new(AVAILABLE_DEVICE_TYPES),
+    });
+    screencast.remote_desktop.set(Some(session));
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<RemoteDesktopSession>> {
+    let res = state
+        .screencasts
+        .get(handle)
+        .and_then(|s| s.remote_desktop.get());
+    if res.is_none() {
+        let msg = format!("Remote desktop session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
+
+/// Returns the injector of a started session if the device type has been granted.
+fn get_injector<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+    device: DeviceTypes,
+) -> Option<(Rc<StartedRemoteDesktop>, Rc<UsrJayInputInjector>)> {
+    let session = get_session(state, reply, handle)?;
+    let RemoteDesktopPhase::Started(started) = session.phase.get() else {
+        reply.err("Session has not been started");
+        return None;
+    };
+    let injector = match &started.injector {
+        Some(i) if started.devices.contains(device) => i.clone(),
+        _ => {
+            reply.err("The device type has not been granted");
+            return None;
+        }
+    };
+    Some((started, injector))
+}
@@ -0,0 +1,236 @@
+use {
+    crate::{
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_remote_desktop::{
+                RemoteDesktopPhase, RemoteDesktopSession, KEYBOARD, POINTER, TOUCHSCREEN,
+            },
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner,
+            },
+        },
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+    },
+    std::rc::Rc,
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct ConsentGui {
+    session: Rc<RemoteDesktopSession>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<ConsentGuiSurface>>,
+}
+
+pub struct ConsentGuiSurface {
+    gui: Rc<ConsentGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+/// A bar at the top of each output that is shown while the session is running.
+pub struct RemoteDesktopIndicator {
+    session: Rc<RemoteDesktopSession>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    session: Rc<RemoteDesktopSession>,
+    dpy: Rc<PortalDisplay>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Accept,
+    Reject,
+    Stop,
+}
+
+impl ConsentGui {
+    pub fn new(session: &Rc<RemoteDesktopSession>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(ConsentGui {
+            session: session.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let cgs = Rc::new(ConsentGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+            });
+            let element = create_consent_gui(&gui);
+            cgs.overlay.data.content.set(Some(element));
+            cgs.overlay.owner.set(Some(cgs.clone()));
+            gui.dpy
+                .windows
+                .set(cgs.overlay.data.surface.id, cgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, cgs);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let RemoteDesktopPhase::Confirming(c) = self.session.phase.get() {
+            c.guis.remove(&self.dpy.id);
+            if upwards && c.guis.is_empty() {
+                self.session.screencast.kill();
+            }
+        }
+    }
+}
+
+fn create_consent_gui(gui: &Rc<ConsentGui>) -> Rc<dyn GuiElement> {
+    let session = &gui.session;
+    let mut text = match session.app() {
+        "" => format!("An application wants to control the input devices"),
+        app => format!("`{}` wants to control the input devices", app),
+    };
+    let devices = session.device_types.get();
+    let mut names = vec![];
+    for (device, name) in [
+        (KEYBOARD, "keyboard"),
+        (POINTER, "pointer"),
+        (TOUCHSCREEN, "touchscreen"),
+    ] {
+        if devices.contains(device) {
+            names.push(name);
+        }
+    }
+    if names.len() > 0 {
+        text.push_str(&format!(" ({})", names.join(", ")));
+    }
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let accept_button = static_button(gui, ButtonRole::Accept, "Allow");
+    let reject_button = static_button(gui, ButtonRole::Reject, "Reject");
+    for button in [&accept_button, &reject_button] {
+        button.border_color.set(Color::from_gray(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+    }
+    accept_button.bg_color.set(Color::from_rgb(170, 200, 170));
+    accept_button
+        .bg_hover_color
+        .set(Color::from_rgb(170, 255, 170));
+    reject_button.bg_color.set(Color::from_rgb(200, 170, 170));
+    reject_button
+        .bg_hover_color
+        .set(Color::from_rgb(255, 170, 170));
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    *flow.elements.borrow_mut() = vec![label, accept_button, reject_button];
+    flow
+}
+
+impl OverlayWindowOwner for ConsentGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl RemoteDesktopIndicator {
+    pub fn new(session: &Rc<RemoteDesktopSession>, output: &Rc<PortalOutput>) -> Rc<Self> {
+        let indicator = Rc::new(RemoteDesktopIndicator {
+            session: session.clone(),
+            output: output.clone(),
+            overlay: OverlayWindow::new_top(output),
+        });
+        let text = match session.app() {
+            "" => format!("An application is controlling the input devices"),
+            app => format!("`{}` is controlling the input devices", app),
+        };
+        let label = Rc::new(Label::default());
+        *label.text.borrow_mut() = text;
+        let stop_button = Rc::new(Button::default());
+        stop_button.owner.set(Some(Rc::new(StaticButton {
+            session: session.clone(),
+            dpy: output.dpy.clone(),
+            role: ButtonRole::Stop,
+        })));
+        *stop_button.text.borrow_mut() = "Stop".to_string();
+        stop_button.border_color.set(Color::from_gray(100));
+        stop_button.border.set(2.0);
+        stop_button.padding.set(5.0);
+        stop_button.bg_color.set(Color::from_rgb(200, 170, 170));
+        stop_button
+            .bg_hover_color
+            .set(Color::from_rgb(255, 170, 170));
+        let flow = Rc::new(Flow::default());
+        flow.orientation.set(Orientation::Horizontal);
+        flow.cross_align.set(Align::Center);
+        flow.in_margin.set(H_MARGIN);
+        flow.cross_margin.set(V_MARGIN / 2.0);
+        *flow.elements.borrow_mut() = vec![label, stop_button];
+        let data = &indicator.overlay.data;
+        data.clear_color.set(Color::from_rgb(120, 30, 30));
+        data.content.set(Some(flow));
+        indicator.overlay.owner.set(Some(indicator.clone()));
+        output.dpy.windows.set(data.surface.id, data.clone());
+        indicator
+    }
+
+    pub fn kill(&self) {
+        self.overlay.data.kill(false);
+    }
+}
+
+impl OverlayWindowOwner for RemoteDesktopIndicator {
+    fn kill(&self, _upwards: bool) {
+        let dpy = &self.output.dpy;
+        dpy.windows.remove(&self.overlay.data.surface.id);
+        if let RemoteDesktopPhase::Started(s) = self.session.phase.get() {
+            s.indicators.remove(&self.output.global_id);
+        }
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        match self.role {
+            ButtonRole::Accept => {
+                log::info!("User has accepted the remote desktop request");
+                self.session.accept(&self.dpy);
+            }
+            ButtonRole::Reject => {
+                log::info!("User has rejected the remote desktop request");
+                self.session.screencast.kill();
+            }
+            ButtonRole::Stop => {
+                log::info!("User has stopped the remote desktop session");
+                self.session.screencast.kill();
+            }
+        }
+    }
+}
+
+fn static_button(gui: &Rc<ConsentGui>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        session: gui.session.clone(),
+        dpy: gui.dpy.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
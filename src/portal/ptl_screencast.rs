@@ -1,33 +1,42 @@
+mod restore_data;
 mod screencast_gui;
 
 use {
     crate::{
         dbus::{prelude::Variant, DbusObject, DictEntry, DynamicType, PendingReply},
+        ifs::jay_screencast::{CURSOR_MODE_EMBEDDED, CURSOR_MODE_HIDDEN, CURSOR_MODE_METADATA},
         pipewire::{
             pw_ifs::pw_client_node::{
                 PwClientNode, PwClientNodeBufferConfig, PwClientNodeOwner, PwClientNodePort,
-                PwClientNodePortSupportedFormats, SUPPORTED_META_VIDEO_CROP,
+                PwClientNodePortSupportedFormats, META_CURSOR_MAX_SIZE, SUPPORTED_META_CURSOR,
+                SUPPORTED_META_VIDEO_CROP,
             },
+            pw_mem::PwMemSlice,
             pw_pod::{
-                spa_point, spa_rectangle, spa_region, PwPodRectangle, SPA_DATA_DmaBuf,
-                SPA_MEDIA_SUBTYPE_raw, SPA_MEDIA_TYPE_video, SpaChunkFlags, SPA_STATUS_HAVE_DATA,
+                spa_meta_bitmap, spa_meta_cursor, spa_point, spa_rectangle, spa_region,
+                PwPodRectangle, SPA_DATA_DmaBuf, SPA_MEDIA_SUBTYPE_raw, SPA_MEDIA_TYPE_video,
+                SpaChunkFlags, SPA_STATUS_HAVE_DATA, SPA_VIDEO_FORMAT_BGRA,
             },
         },
         portal::{
             ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
-            ptl_screencast::screencast_gui::SelectionGui,
+            ptl_remote_desktop::RemoteDesktopSession,
+            ptl_screencast::{restore_data::RestoreData, screencast_gui::SelectionGui},
             PortalState, PORTAL_SUCCESS,
         },
         utils::{
             clonecell::{CloneCell, UnsafeCellCloneSafe},
             copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
             hash_map_ext::HashMapExt,
+            oserror::OsError,
         },
         video::dmabuf::{DmaBuf, PlaneVec},
-        wire::jay_screencast::Ready,
+        wire::jay_screencast::{CursorImage, CursorPosition, Ready},
         wire_dbus::{
             org,
             org::freedesktop::impl_::portal::{
+                remote_desktop::StartReply as RemoteDesktopStartReply,
                 screen_cast::{
                     CreateSession, CreateSessionReply, SelectSources, SelectSourcesReply, Start,
                     StartReply,
@@ -46,10 +55,12 @@ use {
     std::{
         borrow::Cow,
         cell::{Cell, RefCell},
+        mem,
         ops::Deref,
         rc::Rc,
         sync::atomic::Ordering::{Acquire, Relaxed, Release},
     },
+    uapi::c,
 };
 
 shared_ids!(ScreencastSessionId);
@@ -59,6 +70,11 @@ pub struct ScreencastSession {
     pub app: String,
     session_obj: DbusObject,
     pub phase: CloneCell<ScreencastPhase>,
+    cursor_mode: Cell<CursorModes>,
+    persist_mode: Cell<u32>,
+    restore_data: RefCell<Option<RestoreData>>,
+    /// Set if the session was created by the remote desktop portal.
+    pub remote_desktop: CloneCell<Option<Rc<RemoteDesktopSession>>>,
 }
 
 #[derive(Clone)]
@@ -75,11 +91,17 @@ pub enum ScreencastPhase {
 
 unsafe impl UnsafeCellCloneSafe for ScreencastPhase {}
 
+/// The reply to a `Start` call of either the screencast or the remote desktop portal.
+pub enum PendingStartReply {
+    ScreenCast(PendingReply<StartReply<'static>>),
+    RemoteDesktop(PendingReply<RemoteDesktopStartReply<'static>>),
+}
+
 #[derive(Clone)]
 pub struct SelectingScreencastCore {
     pub session: Rc<ScreencastSession>,
     pub request_obj: Rc<DbusObject>,
-    pub reply: Rc<PendingReply<StartReply<'static>>>,
+    pub reply: Rc<PendingStartReply>,
 }
 
 pub struct SelectingScreencast {
@@ -91,18 +113,20 @@ pub struct SelectingWindowScreencast {
     pub core: SelectingScreencastCore,
     pub dpy: Rc<PortalDisplay>,
     pub selector: Rc<UsrJaySelectToplevel>,
+    pub restoring: bool,
 }
 
 pub struct SelectingWorkspaceScreencast {
     pub core: SelectingScreencastCore,
     pub dpy: Rc<PortalDisplay>,
     pub selector: Rc<UsrJaySelectWorkspace>,
+    pub restoring: bool,
 }
 
 pub struct StartingScreencast {
     pub session: Rc<ScreencastSession>,
     pub request_obj: Rc<DbusObject>,
-    pub reply: Rc<PendingReply<StartReply<'static>>>,
+    pub reply: Rc<PendingStartReply>,
     pub node: Rc<PwClientNode>,
     pub dpy: Rc<PortalDisplay>,
     pub target: ScreencastTarget,
@@ -122,6 +146,21 @@ pub struct StartedScreencast {
     buffers_valid: Cell<bool>,
     dpy: Rc<PortalDisplay>,
     jay_screencast: Rc<UsrJayScreencast>,
+    cursor: RefCell<CursorState>,
+}
+
+#[derive(Default)]
+struct CursorState {
+    position: Option<(i32, i32)>,
+    image: Option<CursorBitmap>,
+}
+
+struct CursorBitmap {
+    width: i32,
+    height: i32,
+    hotspot_x: i32,
+    hotspot_y: i32,
+    data: Vec<u8>,
 }
 
 bitflags! {
@@ -139,6 +178,56 @@ bitflags! {
     WINDOW = 2,
 }
 
+const PERSIST_NONE: u32 = 0;
+const PERSIST_PERMANENT: u32 = 2;
+
+impl PendingStartReply {
+    pub fn ok(&self, results: Vec<DictEntry<Cow<'static, str>, Variant<'static>>>) {
+        match self {
+            PendingStartReply::ScreenCast(r) => r.ok(&StartReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Owned(results),
+            }),
+            PendingStartReply::RemoteDesktop(r) => r.ok(&RemoteDesktopStartReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Owned(results),
+            }),
+        }
+    }
+
+    pub fn err(&self, msg: &str) {
+        match self {
+            PendingStartReply::ScreenCast(r) => r.err(msg),
+            PendingStartReply::RemoteDesktop(r) => r.err(msg),
+        }
+    }
+}
+
+impl ScreencastTarget {
+    fn restore_data(&self) -> Option<RestoreData> {
+        let data = match self {
+            ScreencastTarget::Output(o) => RestoreData::Output {
+                connector: o.name.borrow().clone(),
+            },
+            ScreencastTarget::Workspace(_, ws) => RestoreData::Workspace {
+                workspace: ws.name.borrow().clone(),
+            },
+            ScreencastTarget::Toplevel(tl) => RestoreData::Toplevel {
+                app_id: tl.app_id.borrow().clone(),
+            },
+        };
+        let name = match &data {
+            RestoreData::Output { connector } => connector,
+            RestoreData::Workspace { workspace } => workspace,
+            RestoreData::Toplevel { app_id } => app_id,
+        };
+        if name.is_empty() {
+            return None;
+        }
+        Some(data)
+    }
+}
+
 impl PwClientNodeOwner for StartingScreencast {
     fn bound_id(&self, node_id: u32) {
         {
@@ -150,21 +239,52 @@ impl PwClientNodeOwner for StartingScreencast {
                 DynamicType::U32,
                 DynamicType::Array(Box::new(inner_type.clone())),
             ]);
-            let variants = &[DictEntry {
+            let mut variants = vec![DictEntry {
                 key: "streams".into(),
                 value: Variant::Array(
                     kt,
                     vec![Variant::U32(node_id), Variant::Array(inner_type, vec![])],
                 ),
             }];
-            self.reply.ok(&StartReply {
-                response: PORTAL_SUCCESS,
-                results: Cow::Borrowed(variants),
-            });
+            if let Some(rd) = self.session.remote_desktop.get() {
+                let output = match &self.target {
+                    ScreencastTarget::Output(o) | ScreencastTarget::Workspace(o, _) => {
+                        Some(o.clone())
+                    }
+                    ScreencastTarget::Toplevel(_) => None,
+                };
+                let devices = rd.start(&self.dpy, Some(node_id), output);
+                variants.push(DictEntry {
+                    key: "devices".into(),
+                    value: Variant::U32(devices.0),
+                });
+            }
+            let persist_mode = self.session.persist_mode.get();
+            if persist_mode != PERSIST_NONE {
+                if let Some(restore_data) = self.target.restore_data() {
+                    variants.push(DictEntry {
+                        key: "persist_mode".into(),
+                        value: Variant::U32(persist_mode),
+                    });
+                    variants.push(DictEntry {
+                        key: "restore_data".into(),
+                        value: restore_data.to_variant(),
+                    });
+                }
+            }
+            self.reply.ok(variants);
+        }
+        let mut cursor_mode = self.session.cursor_mode.get();
+        if !self.dpy.jc.caps.cursor_modes.get() {
+            cursor_mode = EMBEDDED;
         }
         let port = self.node.create_port(true);
         port.can_alloc_buffers.set(true);
-        port.supported_metas.set(SUPPORTED_META_VIDEO_CROP);
+        let mut metas = SUPPORTED_META_VIDEO_CROP;
+        if cursor_mode == METADATA {
+            metas |= SUPPORTED_META_CURSOR;
+        }
+        port.supported_metas.set(metas);
         let jsc = self.dpy.jc.create_screencast();
         match &self.target {
             ScreencastTarget::Output(o) => {
@@ -178,6 +298,14 @@ impl PwClientNodeOwner for StartingScreencast {
             ScreencastTarget::Toplevel(t) => jsc.set_toplevel(t),
         }
         jsc.set_use_linear_buffers(true);
+        if cursor_mode != EMBEDDED {
+            let mode = match cursor_mode {
+                HIDDEN => CURSOR_MODE_HIDDEN,
+                METADATA => CURSOR_MODE_METADATA,
+                _ => CURSOR_MODE_EMBEDDED,
+            };
+            jsc.set_cursor_mode(mode);
+        }
         jsc.configure();
         match &self.target {
             ScreencastTarget::Output(_) => {}
@@ -196,6 +324,7 @@ impl PwClientNodeOwner for StartingScreencast {
             buffers_valid: Cell::new(false),
             dpy: self.dpy.clone(),
             jay_screencast: jsc,
+            cursor: Default::default(),
         });
         self.session
             .phase
@@ -262,6 +391,9 @@ impl ScreencastSession {
     pub(super) fn kill(&self) {
         self.session_obj.emit_signal(&Closed);
         self.state.screencasts.remove(self.session_obj.path());
+        if let Some(rd) = self.remote_desktop.take() {
+            rd.kill();
+        }
         match self.phase.set(ScreencastPhase::Terminated) {
             ScreencastPhase::Init => {}
             ScreencastPhase::SourcesSelected => {}
@@ -304,7 +436,7 @@ impl ScreencastSession {
 
     fn dbus_select_sources(
         self: &Rc<Self>,
-        _req: SelectSources,
+        req: SelectSources,
         reply: PendingReply<SelectSourcesReply<'static>>,
     ) {
         match self.phase.get() {
@@ -315,6 +447,23 @@ impl ScreencastSession {
                 return;
             }
         }
+        for option in req.options.iter() {
+            match (&*option.key, &option.value) {
+                ("cursor_mode", &Variant::U32(mode)) => {
+                    let mode = CursorModes(mode);
+                    if [HIDDEN, EMBEDDED, METADATA].contains(&mode) {
+                        self.cursor_mode.set(mode);
+                    }
+                }
+                ("persist_mode", &Variant::U32(mode)) => {
+                    self.persist_mode.set(mode.min(PERSIST_PERMANENT));
+                }
+                ("restore_data", data) => {
+                    *self.restore_data.borrow_mut() = RestoreData::from_variant(data);
+                }
+                _ => {}
+            }
+        }
         self.phase.set(ScreencastPhase::SourcesSelected);
         reply.ok(&SelectSourcesReply {
             response: PORTAL_SUCCESS,
@@ -323,6 +472,15 @@ impl ScreencastSession {
     }
 
     fn dbus_start(self: &Rc<Self>, req: Start<'_>, reply: PendingReply<StartReply<'static>>) {
+        self.start(&req.handle.0, PendingStartReply::ScreenCast(reply));
+    }
+
+    pub(super) fn session_path(&self) -> &str {
+        self.session_obj.path()
+    }
+
+    /// Asks the user to select a source or restores the source of a previous session.
+    pub(super) fn start(self: &Rc<Self>, handle: &str, reply: PendingStartReply) {
         match self.phase.get() {
             ScreencastPhase::SourcesSelected => {}
             _ => {
@@ -331,7 +489,7 @@ impl ScreencastSession {
                 return;
             }
         }
-        let request_obj = match self.state.dbus.add_object(req.handle.to_string()) {
+        let request_obj = match self.state.dbus.add_object(handle.to_string()) {
             Ok(r) => r,
             Err(_) => {
                 self.kill();
@@ -349,6 +507,23 @@ impl ScreencastSession {
                 }
             });
         }
+        let core = SelectingScreencastCore {
+            session: self.clone(),
+            request_obj: Rc::new(request_obj),
+            reply: Rc::new(reply),
+        };
+        let restore_data = self.restore_data.take();
+        if let Some(restore_data) = restore_data {
+            if self.restore(&restore_data, &core) {
+                return;
+            }
+            log::info!("Could not restore the target of the previous session");
+        }
+        self.select(&core);
+    }
+
+    /// Shows the selection dialog on all displays.
+    pub(super) fn select(self: &Rc<Self>, core: &SelectingScreencastCore) {
         let guis = CopyHashMap::new();
         for dpy in self.state.displays.lock().values() {
             if dpy.outputs.len() > 0 {
@@ -357,19 +532,76 @@ impl ScreencastSession {
         }
         if guis.is_empty() {
             self.kill();
-            reply.err("There are no running displays");
+            core.reply.err("There are no running displays");
             return;
         }
         self.phase
             .set(ScreencastPhase::Selecting(Rc::new(SelectingScreencast {
-                core: SelectingScreencastCore {
-                    session: self.clone(),
-                    request_obj: Rc::new(request_obj),
-                    reply: Rc::new(reply),
-                },
+                core: core.clone(),
                 guis,
             })));
     }
+
+    /// Starts the screencast with the target of a previous session without asking the
+    /// user.
+    ///
+    /// Returns `false` if the target cannot be looked up.
+    fn restore(
+        self: &Rc<Self>,
+        restore_data: &RestoreData,
+        core: &SelectingScreencastCore,
+    ) -> bool {
+        let displays: Vec<_> = self.state.displays.lock().values().cloned().collect();
+        for dpy in displays {
+            match restore_data {
+                RestoreData::Output { connector } => {
+                    let output = dpy
+                        .outputs
+                        .lock()
+                        .values()
+                        .find(|o| *o.name.borrow() == *connector)
+                        .cloned();
+                    if let Some(output) = output {
+                        log::info!("Restoring a screencast of output {}", connector);
+                        core.starting(&dpy, ScreencastTarget::Output(output));
+                        return true;
+                    }
+                }
+                RestoreData::Workspace { workspace } => {
+                    if !dpy.jc.caps.find_targets.get() {
+                        continue;
+                    }
+                    let selector = dpy.jc.find_workspace(workspace);
+                    let selecting = Rc::new(SelectingWorkspaceScreencast {
+                        core: core.clone(),
+                        dpy: dpy.clone(),
+                        selector: selector.clone(),
+                        restoring: true,
+                    });
+                    selector.owner.set(Some(selecting.clone()));
+                    self.phase
+                        .set(ScreencastPhase::SelectingWorkspace(selecting));
+                    return true;
+                }
+                RestoreData::Toplevel { app_id } => {
+                    if !dpy.jc.caps.find_targets.get() {
+                        continue;
+                    }
+                    let selector = dpy.jc.find_toplevel(app_id);
+                    let selecting = Rc::new(SelectingWindowScreencast {
+                        core: core.clone(),
+                        dpy: dpy.clone(),
+                        selector: selector.clone(),
+                        restoring: true,
+                    });
+                    selector.owner.set(Some(selecting.clone()));
+                    self.phase.set(ScreencastPhase::SelectingWindow(selecting));
+                    return true;
+                }
+            }
+        }
+        false
+    }
 }
 
 impl UsrJayScreencastOwner for StartedScreencast {
@@ -444,6 +676,9 @@ impl UsrJayScreencastOwner for StartedScreencast {
                 },
             };
         }
+        if let Some(cursor) = &pbuffer.meta_cursor {
+            self.write_cursor_meta(cursor);
+        }
         let buffer_id = io.buffer_id.load(Relaxed) as usize;
         if buffer_id != idx {
             if buffer_id < buffers.len() {
@@ -460,6 +695,91 @@ impl UsrJayScreencastOwner for StartedScreencast {
     fn destroyed(&self) {
         self.session.kill();
     }
+
+    fn cursor_image(&self, ev: CursorImage) {
+        let bitmap = match read_cursor_image(&ev) {
+            Ok(b) => b,
+            Err(e) => {
+                log::error!("Could not read the cursor image: {}", ErrorFmt(e));
+                None
+            }
+        };
+        self.cursor.borrow_mut().image = bitmap;
+    }
+
+    fn cursor_position(&self, ev: &CursorPosition) {
+        self.cursor.borrow_mut().position = match ev.visible != 0 {
+            true => Some((ev.x, ev.y)),
+            false => None,
+        };
+    }
+}
+
+impl StartedScreencast {
+    fn write_cursor_meta(&self, meta: &PwMemSlice) {
+        let bytes = unsafe { meta.bytes_mut() };
+        let cursor = self.cursor.borrow();
+        let mut cursor_meta = spa_meta_cursor {
+            id: 0,
+            flags: 0,
+            position: spa_point { x: 0, y: 0 },
+            hotspot: spa_point { x: 0, y: 0 },
+            bitmap_offset: 0,
+        };
+        if let Some((x, y)) = cursor.position {
+            cursor_meta.id = 1;
+            cursor_meta.position = spa_point { x, y };
+            if let Some(image) = &cursor.image {
+                let bitmap_offset = mem::size_of::<spa_meta_cursor>();
+                let data_offset = bitmap_offset + mem::size_of::<spa_meta_bitmap>();
+                let data_end = data_offset + image.data.len();
+                if data_end <= bytes.len() {
+                    let bitmap = spa_meta_bitmap {
+                        format: SPA_VIDEO_FORMAT_BGRA,
+                        size: spa_rectangle {
+                            width: image.width as _,
+                            height: image.height as _,
+                        },
+                        stride: image.width * 4,
+                        offset: mem::size_of::<spa_meta_bitmap>() as _,
+                    };
+                    bytes[bitmap_offset..data_offset].copy_from_slice(uapi::as_bytes(&bitmap));
+                    bytes[data_offset..data_end].copy_from_slice(&image.data);
+                    cursor_meta.hotspot = spa_point {
+                        x: image.hotspot_x,
+                        y: image.hotspot_y,
+                    };
+                    cursor_meta.bitmap_offset = bitmap_offset as _;
+                }
+            }
+        }
+        let len = mem::size_of::<spa_meta_cursor>();
+        bytes[..len].copy_from_slice(uapi::as_bytes(&cursor_meta));
+    }
+}
+
+fn read_cursor_image(ev: &CursorImage) -> Result<Option<CursorBitmap>, OsError> {
+    let max = META_CURSOR_MAX_SIZE as i32;
+    if ev.width <= 0 || ev.height <= 0 || ev.width > max || ev.height > max {
+        return Ok(None);
+    }
+    let mut data = vec![0u8; (ev.width * ev.height * 4) as usize];
+    let mut pos = 0;
+    while pos < data.len() {
+        match uapi::pread(ev.fd.raw(), &mut data[pos..], pos as _) {
+            Ok([]) => return Ok(None),
+            Ok(b) => pos += b.len(),
+            Err(uapi::Errno(c::EINTR)) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(Some(CursorBitmap {
+        width: ev.width,
+        height: ev.height,
+        hotspot_x: ev.hotspot_x,
+        hotspot_y: ev.hotspot_y,
+        data,
+    }))
 }
 
 pub(super) fn add_screencast_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
@@ -477,7 +797,7 @@ pub(super) fn add_screencast_dbus_members(state_: &Rc<PortalState>, object: &Dbu
         dbus_start(&state, req, pr);
     });
     object.set_property::<AvailableSourceTypes>(Variant::U32(MONITOR.0));
-    object.set_property::<AvailableCursorModes>(Variant::U32(EMBEDDED.0));
+    object.set_property::<AvailableCursorModes>(Variant::U32((HIDDEN | EMBEDDED | METADATA).0));
     object.set_property::<version>(Variant::U32(4));
 }
 
@@ -487,23 +807,42 @@ fn dbus_create_session(
     reply: PendingReply<CreateSessionReply<'static>>,
 ) {
     log::info!("Create Session {:#?}", req);
-    if state.screencasts.contains(req.session_handle.0.deref()) {
-        reply.err("Session already exists");
+    if let Err(e) = create_session(state, &req.session_handle.0, &req.app_id) {
+        reply.err(e);
         return;
     }
-    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+/// Creates a session object and registers it with the screencast portal.
+///
+/// Remote desktop sessions also use this session so that the application can select
+/// sources via the screencast portal.
+pub(super) fn create_session(
+    state: &Rc<PortalState>,
+    session_handle: &str,
+    app_id: &str,
+) -> Result<Rc<ScreencastSession>, &'static str> {
+    if state.screencasts.contains(session_handle) {
+        return Err("Session already exists");
+    }
+    let obj = match state.dbus.add_object(session_handle.to_string()) {
         Ok(obj) => obj,
-        Err(_) => {
-            reply.err("Session path is not unique");
-            return;
-        }
+        Err(_) => return Err("Session path is not unique"),
     };
     let session = Rc::new(ScreencastSession {
         _id: state.id(),
         state: state.clone(),
-        app: req.app_id.to_string(),
+        app: app_id.to_string(),
         session_obj: obj,
         phase: CloneCell::new(ScreencastPhase::Init),
+        cursor_mode: Cell::new(EMBEDDED),
+        persist_mode: Cell::new(PERSIST_NONE),
+        restore_data: Default::default(),
+        remote_desktop: Default::default(),
     });
     {
         use org::freedesktop::impl_::portal::session::*;
@@ -516,11 +855,8 @@ fn dbus_create_session(
     }
     state
         .screencasts
-        .set(req.session_handle.0.to_string(), session);
-    reply.ok(&CreateSessionReply {
-        response: PORTAL_SUCCESS,
-        results: Default::default(),
-    });
+        .set(session_handle.to_string(), session.clone());
+    Ok(session)
 }
 
 fn dbus_select_sources(
@@ -0,0 +1,121 @@
+use {
+    crate::dbus::{prelude::Variant, DynamicType},
+    std::borrow::Cow,
+};
+
+const VENDOR: &str = "jay";
+const VERSION: u32 = 1;
+
+const TYPE: &str = "type";
+const CONNECTOR: &str = "connector";
+const WORKSPACE: &str = "workspace";
+const APP_ID: &str = "app-id";
+
+const TYPE_OUTPUT: &str = "output";
+const TYPE_WORKSPACE: &str = "workspace";
+const TYPE_TOPLEVEL: &str = "toplevel";
+
+/// The data that the portal frontend stores for persistent screencast sessions.
+///
+/// The data identifies the target by names that are stable across compositor and portal
+/// restarts.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RestoreData {
+    Output { connector: String },
+    Workspace { workspace: String },
+    Toplevel { app_id: String },
+}
+
+impl RestoreData {
+    /// Parses the `(suv)` restore data sent by the portal frontend.
+    ///
+    /// Returns `None` if the data was not created by this portal or has an unknown format.
+    pub fn from_variant(v: &Variant<'_>) -> Option<Self> {
+        let Variant::Struct(fields) = v else {
+            return None;
+        };
+        let [Variant::String(vendor), Variant::U32(version), Variant::Variant(data)] = &fields[..]
+        else {
+            return None;
+        };
+        if vendor != VENDOR || *version != VERSION {
+            return None;
+        }
+        let Variant::Array(_, entries) = &**data else {
+            return None;
+        };
+        let mut ty = None;
+        let mut connector = None;
+        let mut workspace = None;
+        let mut app_id = None;
+        for entry in entries {
+            let Variant::DictEntry(k, v) = entry else {
+                return None;
+            };
+            let (Variant::String(k), Variant::Variant(v)) = (&**k, &**v) else {
+                return None;
+            };
+            let Variant::String(v) = &**v else {
+                return None;
+            };
+            let field = match &**k {
+                TYPE => &mut ty,
+                CONNECTOR => &mut connector,
+                WORKSPACE => &mut workspace,
+                APP_ID => &mut app_id,
+                _ => continue,
+            };
+            *field = Some(v.to_string());
+        }
+        let data = match ty?.as_str() {
+            TYPE_OUTPUT => Self::Output {
+                connector: connector?,
+            },
+            TYPE_WORKSPACE => Self::Workspace {
+                workspace: workspace?,
+            },
+            TYPE_TOPLEVEL => Self::Toplevel { app_id: app_id? },
+            _ => return None,
+        };
+        Some(data)
+    }
+
+    /// Creates the `(suv)` restore data that is returned to the portal frontend.
+    pub fn to_variant(&self) -> Variant<'static> {
+        let mut fields = vec![];
+        match self {
+            Self::Output { connector } => {
+                fields.push((TYPE, TYPE_OUTPUT));
+                fields.push((CONNECTOR, connector));
+            }
+            Self::Workspace { workspace } => {
+                fields.push((TYPE, TYPE_WORKSPACE));
+                fields.push((WORKSPACE, workspace));
+            }
+            Self::Toplevel { app_id } => {
+                fields.push((TYPE, TYPE_TOPLEVEL));
+                fields.push((APP_ID, app_id));
+            }
+        }
+        let entries = fields
+            .into_iter()
+            .map(|(k, v)| {
+                Variant::DictEntry(
+                    Box::new(Variant::String(Cow::Owned(k.to_string()))),
+                    Box::new(Variant::Variant(Box::new(Variant::String(Cow::Owned(
+                        v.to_string(),
+                    ))))),
+                )
+            })
+            .collect();
+        let ty = DynamicType::DictEntry(
+            Box::new(DynamicType::String),
+            Box::new(DynamicType::Variant),
+        );
+        Variant::Struct(vec![
+            Variant::String(Cow::Borrowed(VENDOR)),
+            Variant::U32(VERSION),
+            Variant::Variant(Box::new(Variant::Array(ty, entries))),
+        ])
+    }
+}
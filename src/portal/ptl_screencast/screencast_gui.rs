@@ -66,12 +66,16 @@ impl SelectionGui {
 }
 
 fn create_accept_gui(surface: &Rc<SelectionGuiSurface>) -> Rc<dyn GuiElement> {
-    let app = &surface.gui.screencast_session.app;
-    let text = if app.is_empty() {
+    let session = &surface.gui.screencast_session;
+    let app = &session.app;
+    let mut text = if app.is_empty() {
         format!("An application wants to capture the screen")
     } else {
         format!("`{}` wants to capture the screen", app)
     };
+    if session.remote_desktop.is_some() {
+        text.push_str(" and to control the input devices");
+    }
     let label = Rc::new(Label::default());
     *label.text.borrow_mut() = text;
     let accept_button = static_button(surface, ButtonRole::Accept, "Share This Output");
@@ -173,6 +177,7 @@ impl ButtonOwner for StaticButton {
                         core: selecting.core.clone(),
                         dpy: dpy.clone(),
                         selector: selector.clone(),
+                        restoring: false,
                     });
                     selector.owner.set(Some(selecting.clone()));
                     self.surface
@@ -186,6 +191,7 @@ impl ButtonOwner for StaticButton {
                         core: selecting.core.clone(),
                         dpy: dpy.clone(),
                         selector: selector.clone(),
+                        restoring: false,
                     });
                     selector.owner.set(Some(selecting.clone()));
                     self.surface
@@ -206,6 +212,17 @@ impl ButtonOwner for StaticButton {
 impl UsrJaySelectToplevelOwner for SelectingWindowScreencast {
     fn done(&self, tl: Option<Rc<UsrJayToplevel>>) {
         let Some(tl) = tl else {
+            if self.restoring {
+                if let ScreencastPhase::SelectingWindow(_) = self.core.session.phase.get() {
+                    log::info!("The window of the previous session no longer exists");
+                    self.core
+                        .session
+                        .phase
+                        .set(ScreencastPhase::SourcesSelected);
+                    self.core.session.select(&self.core);
+                }
+                return;
+            }
             log::info!("User has aborted the selection");
             self.core.session.kill();
             return;
@@ -219,7 +236,10 @@ impl UsrJaySelectToplevelOwner for SelectingWindowScreencast {
                 return;
             }
         }
-        log::info!("User has selected a window");
+        match self.restoring {
+            true => log::info!("Restoring a screencast of a window"),
+            false => log::info!("User has selected a window"),
+        }
         self.core
             .starting(&self.dpy, ScreencastTarget::Toplevel(tl));
     }
@@ -228,6 +248,17 @@ impl UsrJaySelectToplevelOwner for SelectingWindowScreencast {
 impl UsrJaySelectWorkspaceOwner for SelectingWorkspaceScreencast {
     fn done(&self, output: u32, ws: Option<Rc<UsrJayWorkspace>>) {
         let Some(ws) = ws else {
+            if self.restoring {
+                if let ScreencastPhase::SelectingWorkspace(_) = self.core.session.phase.get() {
+                    log::info!("The workspace of the previous session no longer exists");
+                    self.core
+                        .session
+                        .phase
+                        .set(ScreencastPhase::SourcesSelected);
+                    self.core.session.select(&self.core);
+                }
+                return;
+            }
             log::info!("User has aborted the selection");
             self.core.session.kill();
             return;
@@ -241,7 +272,10 @@ impl UsrJaySelectWorkspaceOwner for SelectingWorkspaceScreencast {
                 return;
             }
         }
-        log::info!("User has selected a workspace");
+        match self.restoring {
+            true => log::info!("Restoring a screencast of a workspace"),
+            false => log::info!("User has selected a workspace"),
+        }
         let output = match self.dpy.outputs.get(&output) {
             Some(o) => o,
             _ => {
@@ -0,0 +1,430 @@
+mod screenshot_gui;
+
+use {
+    crate::{
+        cli::{
+            screenshot::{ScreenshotImage, ScreenshotImageError},
+            ScreenshotFormat,
+        },
+        dbus::{prelude::Variant, DbusObject, DictEntry, PendingReply, FALSE},
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_screenshot::screenshot_gui::{GuiMode, ScreenshotGui},
+            PortalState, PORTAL_CANCELLED, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        wire::jay_screenshot::{Dmabuf, Error},
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::screenshot::{
+                PickColor, PickColorReply, Screenshot, ScreenshotReply,
+            },
+        },
+        wl_usr::usr_ifs::{
+            usr_jay_screenshot::{UsrJayScreenshot, UsrJayScreenshotOwner},
+            usr_jay_select_toplevel::UsrJaySelectToplevel,
+            usr_jay_select_workspace::UsrJaySelectWorkspace,
+        },
+    },
+    chrono::Local,
+    std::{borrow::Cow, cell::RefCell, io, rc::Rc},
+    thiserror::Error,
+};
+
+shared_ids!(ScreenshotRequestId);
+pub struct ScreenshotRequest {
+    id: ScreenshotRequestId,
+    state: Rc<PortalState>,
+    pub app: String,
+    pub kind: ScreenshotKind,
+    request_obj: RefCell<Option<DbusObject>>,
+    reply: PendingScreenshotReply,
+    pub phase: CloneCell<ScreenshotPhase>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ScreenshotKind {
+    /// The user selects what to capture.
+    Interactive,
+    /// The user has to allow a screenshot of all outputs.
+    Confirm,
+    /// The user selects a pixel whose color is returned.
+    PickColor,
+}
+
+enum PendingScreenshotReply {
+    Screenshot(PendingReply<ScreenshotReply<'static>>),
+    PickColor(PendingReply<PickColorReply<'static>>),
+}
+
+#[derive(Clone)]
+pub enum ScreenshotPhase {
+    Init,
+    Selecting(Rc<SelectingScreenshot>),
+    SelectingWindow(Rc<SelectingWindowScreenshot>),
+    SelectingWorkspace(Rc<SelectingWorkspaceScreenshot>),
+    Capturing(Rc<CapturingScreenshot>),
+    Done,
+}
+
+unsafe impl UnsafeCellCloneSafe for ScreenshotPhase {}
+
+pub struct SelectingScreenshot {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ScreenshotGui>>,
+}
+
+pub struct SelectingWindowScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub selector: Rc<UsrJaySelectToplevel>,
+}
+
+pub struct SelectingWorkspaceScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub selector: Rc<UsrJaySelectWorkspace>,
+}
+
+pub struct CapturingScreenshot {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    screenshot: Rc<UsrJayScreenshot>,
+    area: CaptureArea,
+}
+
+/// The part of the captured image that is returned to the application.
+///
+/// Coordinates are in the logical coordinate space of an output with the given size. They
+/// are converted to pixels once the size of the image is known.
+#[derive(Copy, Clone, Debug)]
+pub enum CaptureArea {
+    Full,
+    Region {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        width: f32,
+        height: f32,
+    },
+    Pixel {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+}
+
+#[derive(Debug, Error)]
+enum ScreenshotPortalError {
+    #[error("The compositor could not take the screenshot: {0}")]
+    Compositor(String),
+    #[error(transparent)]
+    ReadImage(#[from] ScreenshotImageError),
+    #[error("Could not create directory {0}")]
+    CreateDir(String, #[source] io::Error),
+    #[error("Could not write {0}")]
+    WriteFile(String, #[source] io::Error),
+    #[error("The selected pixel is outside the screenshot")]
+    PixelOutOfBounds,
+}
+
+impl PendingScreenshotReply {
+    fn ok(&self, response: u32, results: Vec<DictEntry<Cow<'static, str>, Variant<'static>>>) {
+        match self {
+            PendingScreenshotReply::Screenshot(r) => r.ok(&ScreenshotReply {
+                response,
+                results: Cow::Owned(results),
+            }),
+            PendingScreenshotReply::PickColor(r) => r.ok(&PickColorReply {
+                response,
+                results: Cow::Owned(results),
+            }),
+        }
+    }
+
+    fn err(&self, msg: &str) {
+        match self {
+            PendingScreenshotReply::Screenshot(r) => r.err(msg),
+            PendingScreenshotReply::PickColor(r) => r.err(msg),
+        }
+    }
+}
+
+impl ScreenshotRequest {
+    /// Shows the selection GUI on all displays.
+    pub(super) fn select(self: &Rc<Self>, mode: GuiMode) {
+        let guis = CopyHashMap::new();
+        for dpy in self.state.displays.lock().values() {
+            if mode != GuiMode::Dialog && !dpy.jc.caps.target_screenshots.get() {
+                continue;
+            }
+            if dpy.outputs.len() > 0 {
+                guis.set(dpy.id, ScreenshotGui::new(self, dpy, mode));
+            }
+        }
+        if guis.is_empty() {
+            self.fail("There are no running displays");
+            return;
+        }
+        self.phase
+            .set(ScreenshotPhase::Selecting(Rc::new(SelectingScreenshot {
+                guis,
+            })));
+    }
+
+    /// Waits for the screenshot and replies with the selected area.
+    pub(super) fn capture(
+        self: &Rc<Self>,
+        dpy: &Rc<PortalDisplay>,
+        screenshot: Rc<UsrJayScreenshot>,
+        area: CaptureArea,
+    ) {
+        let capturing = Rc::new(CapturingScreenshot {
+            request: self.clone(),
+            dpy: dpy.clone(),
+            screenshot,
+            area,
+        });
+        capturing.screenshot.owner.set(Some(capturing.clone()));
+        self.phase.set(ScreenshotPhase::Capturing(capturing));
+    }
+
+    pub(super) fn cancel(&self) {
+        if self.is_done() {
+            return;
+        }
+        self.kill();
+        self.reply.ok(PORTAL_CANCELLED, vec![]);
+    }
+
+    fn fail(&self, msg: &str) {
+        if self.is_done() {
+            return;
+        }
+        self.kill();
+        self.reply.err(msg);
+    }
+
+    fn kill(&self) {
+        self.request_obj.take();
+        match self.phase.set(ScreenshotPhase::Done) {
+            ScreenshotPhase::Selecting(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::SelectingWindow(s) => {
+                s.dpy.con.remove_obj(&*s.selector);
+            }
+            ScreenshotPhase::SelectingWorkspace(s) => {
+                s.dpy.con.remove_obj(&*s.selector);
+            }
+            ScreenshotPhase::Capturing(s) => {
+                s.dpy.con.remove_obj(&*s.screenshot);
+            }
+            ScreenshotPhase::Init => {}
+            ScreenshotPhase::Done => {}
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(self.phase.get(), ScreenshotPhase::Done)
+    }
+
+    fn save_screenshot(&self, image: &ScreenshotImage) -> Result<String, ScreenshotPortalError> {
+        let dir = format!("{}/jay-screenshots", self.state.xrd);
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            return Err(ScreenshotPortalError::CreateDir(dir, e));
+        }
+        let file = Local::now().format("%Y-%m-%d-%H%M%S").to_string();
+        let path = format!("{}/{}-{}_jay.png", dir, file, self.id.raw());
+        if let Err(e) = std::fs::write(&path, image.encode(ScreenshotFormat::Png)) {
+            return Err(ScreenshotPortalError::WriteFile(path, e));
+        }
+        Ok(path)
+    }
+}
+
+impl CapturingScreenshot {
+    fn complete(&self, ev: &Dmabuf) -> Result<(), ScreenshotPortalError> {
+        let state = &self.request.state;
+        let image = ScreenshotImage::from_dmabuf(&state.dma_buf_ids, ev)?;
+        let scale_x = |x: f32, width: f32| (x * image.width as f32 / width).round() as u32;
+        let scale_y = |y: f32, height: f32| (y * image.height as f32 / height).round() as u32;
+        let results = match self.area {
+            CaptureArea::Pixel {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let x = scale_x(x, width);
+                let y = scale_y(y, height);
+                let Some(color) = image.pixel(x, y) else {
+                    return Err(ScreenshotPortalError::PixelOutOfBounds);
+                };
+                let [r, g, b] = color.map(|c| Variant::F64(c as f64 / 255.0));
+                vec![DictEntry {
+                    key: "color".into(),
+                    value: Variant::Struct(vec![r, g, b]),
+                }]
+            }
+            area => {
+                let image = match area {
+                    CaptureArea::Region {
+                        x1,
+                        y1,
+                        x2,
+                        y2,
+                        width,
+                        height,
+                    } => {
+                        let x = scale_x(x1, width);
+                        let y = scale_y(y1, height);
+                        let x2 = scale_x(x2, width);
+                        let y2 = scale_y(y2, height);
+                        image.crop(x, y, x2.saturating_sub(x), y2.saturating_sub(y))
+                    }
+                    _ => image,
+                };
+                let path = self.request.save_screenshot(&image)?;
+                log::info!("Saved screenshot to {}", path);
+                vec![DictEntry {
+                    key: "uri".into(),
+                    value: Variant::String(format!("file://{}", path).into()),
+                }]
+            }
+        };
+        self.request.kill();
+        self.request.reply.ok(PORTAL_SUCCESS, results);
+        Ok(())
+    }
+}
+
+impl UsrJayScreenshotOwner for CapturingScreenshot {
+    fn dmabuf(&self, ev: &Dmabuf) {
+        if self.request.is_done() {
+            return;
+        }
+        if let Err(e) = self.complete(ev) {
+            log::error!("Could not complete the screenshot: {}", ErrorFmt(&e));
+            self.request.fail(&ErrorFmt(e).to_string());
+        }
+    }
+
+    fn error(&self, ev: &Error) {
+        if self.request.is_done() {
+            return;
+        }
+        let e = ScreenshotPortalError::Compositor(ev.msg.to_string());
+        log::error!("{}", ErrorFmt(&e));
+        self.request.fail(&ErrorFmt(e).to_string());
+    }
+}
+
+pub(super) fn add_screenshot_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::screenshot::*;
+    let state = state_.clone();
+    object.add_method::<Screenshot, _>(move |req, pr| {
+        dbus_screenshot(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<PickColor, _>(move |req, pr| {
+        dbus_pick_color(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(2));
+}
+
+fn create_request(
+    state: &Rc<PortalState>,
+    handle: &str,
+    app: &str,
+    kind: ScreenshotKind,
+    reply: PendingScreenshotReply,
+) -> Option<Rc<ScreenshotRequest>> {
+    let request_obj = match state.dbus.add_object(handle.to_string()) {
+        Ok(r) => r,
+        Err(_) => {
+            reply.err("Request handle is not unique");
+            return None;
+        }
+    };
+    let request = Rc::new(ScreenshotRequest {
+        id: state.id(),
+        state: state.clone(),
+        app: app.to_string(),
+        kind,
+        request_obj: RefCell::new(None),
+        reply,
+        phase: CloneCell::new(ScreenshotPhase::Init),
+    });
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        request_obj.add_method::<Close, _>({
+            let request = request.clone();
+            move |_, pr| {
+                request.cancel();
+                pr.ok(&CloseReply);
+            }
+        });
+    }
+    *request.request_obj.borrow_mut() = Some(request_obj);
+    Some(request)
+}
+
+fn dbus_screenshot(
+    state: &Rc<PortalState>,
+    req: Screenshot,
+    reply: PendingReply<ScreenshotReply<'static>>,
+) {
+    log::info!("Screenshot {:#?}", req);
+    let mut interactive = false;
+    let mut permission_store_checked = false;
+    for option in req.options.iter() {
+        match (&*option.key, &option.value) {
+            ("interactive", &Variant::Bool(v)) => interactive = v != FALSE,
+            ("permission_store_checked", &Variant::Bool(v)) => {
+                permission_store_checked = v != FALSE
+            }
+            _ => {}
+        }
+    }
+    let kind = match interactive {
+        true => ScreenshotKind::Interactive,
+        false => ScreenshotKind::Confirm,
+    };
+    let reply = PendingScreenshotReply::Screenshot(reply);
+    let Some(request) = create_request(state, &req.handle.0, &req.app_id, kind, reply) else {
+        return;
+    };
+    if kind == ScreenshotKind::Confirm && permission_store_checked {
+        let Some(dpy) = state.displays.lock().values().next().cloned() else {
+            request.fail("There are no running displays");
+            return;
+        };
+        log::info!("Taking a screenshot without asking the user");
+        request.capture(&dpy, dpy.jc.take_screenshot(false), CaptureArea::Full);
+        return;
+    }
+    request.select(GuiMode::Dialog);
+}
+
+fn dbus_pick_color(
+    state: &Rc<PortalState>,
+    req: PickColor,
+    reply: PendingReply<PickColorReply<'static>>,
+) {
+    log::info!("Pick Color {:#?}", req);
+    let reply = PendingScreenshotReply::PickColor(reply);
+    let kind = ScreenshotKind::PickColor;
+    let Some(request) = create_request(state, &req.handle.0, &req.app_id, kind, reply) else {
+        return;
+    };
+    request.select(GuiMode::PickColor);
+}
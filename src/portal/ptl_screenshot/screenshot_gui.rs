@@ -0,0 +1,483 @@
+use {
+    crate::{
+        cursor::KnownCursor,
+        gfx_api::GfxContext,
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT, BTN_RIGHT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{
+                CaptureArea, ScreenshotKind, ScreenshotPhase, ScreenshotRequest,
+                SelectingWindowScreenshot, SelectingWorkspaceScreenshot,
+            },
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, GuiElementData, Label, Orientation,
+                OverlayWindow, OverlayWindowOwner,
+            },
+        },
+        renderer::renderer_base::RendererBase,
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+        wl_usr::usr_ifs::{
+            usr_jay_select_toplevel::UsrJaySelectToplevelOwner,
+            usr_jay_select_workspace::UsrJaySelectWorkspaceOwner, usr_jay_toplevel::UsrJayToplevel,
+            usr_jay_workspace::UsrJayWorkspace,
+        },
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+const SELECTION_BORDER: f32 = 1.0;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum GuiMode {
+    /// Asks the user what to capture.
+    Dialog,
+    /// Lets the user drag a rectangle on an output.
+    Region,
+    /// Lets the user click on a pixel of an output.
+    PickColor,
+}
+
+pub struct ScreenshotGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<ScreenshotGuiSurface>>,
+}
+
+pub struct ScreenshotGuiSurface {
+    gui: Rc<ScreenshotGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    surface: Rc<ScreenshotGuiSurface>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Everything,
+    Output,
+    Workspace,
+    Window,
+    Region,
+    Reject,
+}
+
+struct AreaSelector {
+    data: GuiElementData,
+    surface: Rc<ScreenshotGuiSurface>,
+    mode: GuiMode,
+    start: Cell<Option<(f32, f32)>>,
+    pos: Cell<(f32, f32)>,
+}
+
+impl ScreenshotGui {
+    pub fn new(
+        request: &Rc<ScreenshotRequest>,
+        dpy: &Rc<PortalDisplay>,
+        mode: GuiMode,
+    ) -> Rc<Self> {
+        let gui = Rc::new(ScreenshotGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let overlay = match mode {
+                GuiMode::Dialog => OverlayWindow::new(output),
+                GuiMode::Region | GuiMode::PickColor => OverlayWindow::new_fullscreen(output),
+            };
+            let sgs = Rc::new(ScreenshotGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay,
+            });
+            let element: Rc<dyn GuiElement> = match mode {
+                GuiMode::Dialog => create_dialog(&sgs),
+                GuiMode::Region | GuiMode::PickColor => Rc::new(AreaSelector {
+                    data: Default::default(),
+                    surface: sgs.clone(),
+                    mode,
+                    start: Cell::new(None),
+                    pos: Cell::new((0.0, 0.0)),
+                }),
+            };
+            sgs.overlay.data.content.set(Some(element));
+            sgs.overlay.owner.set(Some(sgs.clone()));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::Selecting(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.cancel();
+            }
+        }
+    }
+}
+
+fn create_dialog(surface: &Rc<ScreenshotGuiSurface>) -> Rc<dyn GuiElement> {
+    let request = &surface.gui.request;
+    let text = if request.app.is_empty() {
+        format!("An application wants to take a screenshot")
+    } else {
+        format!("`{}` wants to take a screenshot", request.app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let mut buttons = vec![];
+    if request.kind == ScreenshotKind::Confirm {
+        buttons.push(static_button(surface, ButtonRole::Everything, "Allow"));
+        buttons.push(static_button(surface, ButtonRole::Reject, "Reject"));
+    } else {
+        let caps = &surface.gui.dpy.jc.caps;
+        if caps.target_screenshots.get() {
+            buttons.push(static_button(
+                surface,
+                ButtonRole::Output,
+                "Capture This Output",
+            ));
+            if caps.select_workspace.get() {
+                buttons.push(static_button(
+                    surface,
+                    ButtonRole::Workspace,
+                    "Capture A Workspace",
+                ));
+            }
+            if caps.window_capture.get() {
+                buttons.push(static_button(
+                    surface,
+                    ButtonRole::Window,
+                    "Capture A Window",
+                ));
+            }
+            buttons.push(static_button(
+                surface,
+                ButtonRole::Region,
+                "Capture A Region",
+            ));
+        }
+        buttons.push(static_button(
+            surface,
+            ButtonRole::Everything,
+            "Capture All Outputs",
+        ));
+        buttons.push(static_button(surface, ButtonRole::Reject, "Cancel"));
+    }
+    for (button, _) in &buttons {
+        button.border_color.set(Color::from_gray(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+        button.bg_color.set(Color::from_rgb(170, 200, 170));
+        button.bg_hover_color.set(Color::from_rgb(170, 255, 170));
+    }
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label];
+    for (button, role) in buttons {
+        if role == ButtonRole::Reject {
+            button.bg_color.set(Color::from_rgb(200, 170, 170));
+            button.bg_hover_color.set(Color::from_rgb(255, 170, 170));
+        }
+        elements.push(button);
+    }
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    *flow.elements.borrow_mut() = elements;
+    flow
+}
+
+impl OverlayWindowOwner for ScreenshotGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        let request = &self.surface.gui.request;
+        if self.role == ButtonRole::Reject {
+            log::info!("User has rejected the screenshot request");
+            request.cancel();
+            return;
+        }
+        let selecting = match request.phase.get() {
+            ScreenshotPhase::Selecting(selecting) => selecting,
+            _ => return,
+        };
+        log::info!("User has accepted the request");
+        for gui in selecting.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        let output = &self.surface.output;
+        let dpy = &output.dpy;
+        match self.role {
+            ButtonRole::Everything => {
+                request.capture(dpy, dpy.jc.take_screenshot(false), CaptureArea::Full);
+            }
+            ButtonRole::Output => {
+                let screenshot = dpy.jc.take_output_screenshot(&output.jay, false);
+                request.capture(dpy, screenshot, CaptureArea::Full);
+            }
+            ButtonRole::Workspace => {
+                let selector = dpy.jc.select_workspace(&seat.wl);
+                let selecting = Rc::new(SelectingWorkspaceScreenshot {
+                    request: request.clone(),
+                    dpy: dpy.clone(),
+                    selector: selector.clone(),
+                });
+                selector.owner.set(Some(selecting.clone()));
+                request
+                    .phase
+                    .set(ScreenshotPhase::SelectingWorkspace(selecting));
+            }
+            ButtonRole::Window => {
+                let selector = dpy.jc.select_toplevel(&seat.wl);
+                let selecting = Rc::new(SelectingWindowScreenshot {
+                    request: request.clone(),
+                    dpy: dpy.clone(),
+                    selector: selector.clone(),
+                });
+                selector.owner.set(Some(selecting.clone()));
+                request
+                    .phase
+                    .set(ScreenshotPhase::SelectingWindow(selecting));
+            }
+            ButtonRole::Region => {
+                request.select(GuiMode::Region);
+            }
+            ButtonRole::Reject => {}
+        }
+    }
+}
+
+impl AreaSelector {
+    fn selection(&self) -> Option<(f32, f32, f32, f32)> {
+        let (x1, y1) = self.start.get()?;
+        let (x2, y2) = self.pos.get();
+        Some((x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)))
+    }
+
+    fn finish_region(&self) {
+        let Some((x1, y1, x2, y2)) = self.selection() else {
+            return;
+        };
+        self.start.set(None);
+        if x2 - x1 < 1.0 || y2 - y1 < 1.0 {
+            self.surface.overlay.data.schedule_render();
+            return;
+        }
+        log::info!("User has selected a region");
+        self.capture(CaptureArea::Region {
+            x1,
+            y1,
+            x2,
+            y2,
+            width: self.data.width.get(),
+            height: self.data.height.get(),
+        });
+    }
+
+    fn pick(&self) {
+        let (x, y) = self.pos.get();
+        log::info!("User has selected a pixel");
+        self.capture(CaptureArea::Pixel {
+            x,
+            y,
+            width: self.data.width.get(),
+            height: self.data.height.get(),
+        });
+    }
+
+    fn capture(&self, area: CaptureArea) {
+        let request = &self.surface.gui.request;
+        let selecting = match request.phase.get() {
+            ScreenshotPhase::Selecting(selecting) => selecting,
+            _ => return,
+        };
+        for gui in selecting.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        let output = &self.surface.output;
+        let dpy = &output.dpy;
+        let screenshot = dpy.jc.take_output_screenshot(&output.jay, false);
+        request.capture(dpy, screenshot, area);
+    }
+}
+
+impl GuiElement for AreaSelector {
+    fn data(&self) -> &GuiElementData {
+        &self.data
+    }
+
+    fn layout(
+        &self,
+        _ctx: &Rc<dyn GfxContext>,
+        _scale: f32,
+        max_width: f32,
+        max_height: f32,
+    ) -> (f32, f32) {
+        (max_width, max_height)
+    }
+
+    fn render_at(&self, r: &mut RendererBase, x: f32, y: f32) {
+        if self.mode != GuiMode::Region {
+            return;
+        }
+        let dim = Color::from_rgba_straight(0, 0, 0, 100);
+        let width = self.data.width.get();
+        let height = self.data.height.get();
+        let Some((x1, y1, x2, y2)) = self.selection() else {
+            r.fill_boxes2_f(&[(0.0, 0.0, width, height)], &dim, x, y);
+            return;
+        };
+        r.fill_boxes2_f(
+            &[
+                (0.0, 0.0, width, y1),
+                (0.0, y2, width, height),
+                (0.0, y1, x1, y2),
+                (x2, y1, width, y2),
+            ],
+            &dim,
+            x,
+            y,
+        );
+        let b = SELECTION_BORDER;
+        r.fill_boxes2_f(
+            &[
+                (x1 - b, y1 - b, x2 + b, y1),
+                (x1 - b, y2, x2 + b, y2 + b),
+                (x1 - b, y1, x1, y2),
+                (x2, y1, x2 + b, y2),
+            ],
+            &Color::from_gray(255),
+            x,
+            y,
+        );
+    }
+
+    fn child_at(&self, _x: f32, _y: f32) -> Option<Rc<dyn GuiElement>> {
+        None
+    }
+
+    fn hover_cursor(&self) -> KnownCursor {
+        KnownCursor::Crosshair
+    }
+
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        if state != PRESSED {
+            if button == BTN_LEFT && self.mode == GuiMode::Region {
+                self.finish_region();
+            }
+            return;
+        }
+        match button {
+            BTN_LEFT => match self.mode {
+                GuiMode::PickColor => self.pick(),
+                _ => self.start.set(Some(self.pos.get())),
+            },
+            BTN_RIGHT => {
+                log::info!("User has aborted the selection");
+                self.surface.gui.request.cancel();
+            }
+            _ => {}
+        }
+    }
+
+    fn motion(&self, _seat: &PortalSeat, x: f32, y: f32) -> bool {
+        let x = x.clamp(0.0, self.data.width.get());
+        let y = y.clamp(0.0, self.data.height.get());
+        self.pos.set((x, y));
+        self.start.get().is_some()
+    }
+}
+
+impl UsrJaySelectToplevelOwner for SelectingWindowScreenshot {
+    fn done(&self, tl: Option<Rc<UsrJayToplevel>>) {
+        let Some(tl) = tl else {
+            log::info!("User has aborted the selection");
+            self.request.cancel();
+            return;
+        };
+        match self.request.phase.get() {
+            ScreenshotPhase::SelectingWindow(s) => {
+                self.dpy.con.remove_obj(&*s.selector);
+            }
+            _ => {
+                self.dpy.con.remove_obj(&*tl);
+                return;
+            }
+        }
+        log::info!("User has selected a window");
+        let screenshot = self.dpy.jc.take_toplevel_screenshot(&tl, false);
+        self.dpy.con.remove_obj(&*tl);
+        self.request
+            .capture(&self.dpy, screenshot, CaptureArea::Full);
+    }
+}
+
+impl UsrJaySelectWorkspaceOwner for SelectingWorkspaceScreenshot {
+    fn done(&self, output: u32, ws: Option<Rc<UsrJayWorkspace>>) {
+        let Some(ws) = ws else {
+            log::info!("User has aborted the selection");
+            self.request.cancel();
+            return;
+        };
+        self.dpy.con.remove_obj(&*ws);
+        match self.request.phase.get() {
+            ScreenshotPhase::SelectingWorkspace(s) => {
+                self.dpy.con.remove_obj(&*s.selector);
+            }
+            _ => return,
+        }
+        let output = match self.dpy.outputs.get(&output) {
+            Some(o) => o,
+            _ => {
+                log::warn!("Workspace does not belong to any known output");
+                self.request
+                    .fail("Workspace does not belong to any known output");
+                return;
+            }
+        };
+        log::info!("User has selected a workspace");
+        let screenshot = self.dpy.jc.take_output_screenshot(&output.jay, false);
+        self.request
+            .capture(&self.dpy, screenshot, CaptureArea::Full);
+    }
+}
+
+fn static_button(
+    surface: &Rc<ScreenshotGuiSurface>,
+    role: ButtonRole,
+    text: &str,
+) -> (Rc<Button>, ButtonRole) {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        surface: surface.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    (button, role)
+}
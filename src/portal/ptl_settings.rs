@@ -0,0 +1,124 @@
+use {
+    crate::{
+        dbus::{prelude::Variant, DbusObject, DictEntry},
+        portal::{PortalState, PORTAL_OBJECT_PATH},
+        theme::Color,
+        wire_dbus::{org, org::freedesktop::impl_::portal::settings::SettingChanged},
+    },
+    std::{borrow::Cow, cell::Cell, rc::Rc},
+};
+
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+
+const COLOR_SCHEME: &str = "color-scheme";
+const ACCENT_COLOR: &str = "accent-color";
+const CONTRAST: &str = "contrast";
+
+const CONTRAST_NO_PREFERENCE: u32 = 0;
+
+#[derive(Default)]
+pub struct PortalSettings {
+    color_scheme: Cell<u32>,
+    accent_color: Cell<Option<Color>>,
+}
+
+impl PortalSettings {
+    fn get(&self, key: &str) -> Option<Variant<'static>> {
+        let value = match key {
+            COLOR_SCHEME => Variant::U32(self.color_scheme.get()),
+            ACCENT_COLOR => {
+                let color = self.accent_color.get()?;
+                let [r, g, b] = match color.a {
+                    0.0 => [0.0; 3],
+                    a => [color.r / a, color.g / a, color.b / a],
+                };
+                Variant::Struct(vec![
+                    Variant::F64(r as f64),
+                    Variant::F64(g as f64),
+                    Variant::F64(b as f64),
+                ])
+            }
+            CONTRAST => Variant::U32(CONTRAST_NO_PREFERENCE),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn appearance(&self) -> Vec<DictEntry<Cow<'static, str>, Variant<'static>>> {
+        let mut entries = vec![];
+        for key in [COLOR_SCHEME, ACCENT_COLOR, CONTRAST] {
+            if let Some(value) = self.get(key) {
+                entries.push(DictEntry {
+                    key: key.into(),
+                    value,
+                });
+            }
+        }
+        entries
+    }
+}
+
+impl PortalState {
+    pub fn update_appearance(&self, color_scheme: u32, accent_color: Color) {
+        let mut changed = vec![];
+        if self.settings.color_scheme.replace(color_scheme) != color_scheme {
+            changed.push(COLOR_SCHEME);
+        }
+        if self.settings.accent_color.replace(Some(accent_color)) != Some(accent_color) {
+            changed.push(ACCENT_COLOR);
+        }
+        for key in changed {
+            if let Some(value) = self.settings.get(key) {
+                self.dbus.emit_signal(
+                    PORTAL_OBJECT_PATH,
+                    &SettingChanged {
+                        namespace: APPEARANCE_NAMESPACE.into(),
+                        key: key.into(),
+                        value,
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn namespace_matches(patterns: &[Cow<'_, str>], namespace: &str) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => namespace.starts_with(prefix),
+            None => pattern.is_empty() || pattern == namespace,
+        })
+}
+
+pub(super) fn add_settings_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::settings::*;
+    let state = state_.clone();
+    object.add_method::<ReadAll, _>(move |req, pr| {
+        let mut value = vec![];
+        if namespace_matches(&req.namespaces, APPEARANCE_NAMESPACE) {
+            value.push(DictEntry {
+                key: APPEARANCE_NAMESPACE.into(),
+                value: state.settings.appearance().into(),
+            });
+        }
+        pr.ok(&ReadAllReply {
+            value: value.into(),
+        });
+    });
+    let state = state_.clone();
+    object.add_method::<Read, _>(move |req, pr| {
+        let value = match &*req.namespace {
+            APPEARANCE_NAMESPACE => state.settings.get(&req.key),
+            _ => None,
+        };
+        match value {
+            Some(value) => pr.ok(&ReadReply { value }),
+            None => pr.err("Requested setting not found"),
+        }
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
@@ -5,7 +5,10 @@ use {
         fixed::Fixed,
         format::ARGB8888,
         gfx_api::{AcquireSync, GfxContext, GfxFramebuffer, ReleaseSync},
-        ifs::zwlr_layer_shell_v1::OVERLAY,
+        ifs::{
+            wl_surface::zwlr_layer_surface_v1::{BOTTOM, LEFT, RIGHT, TOP},
+            zwlr_layer_shell_v1::OVERLAY,
+        },
         portal::ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
         renderer::renderer_base::RendererBase,
         scale::Scale,
@@ -74,6 +77,13 @@ pub trait GuiElement {
         false
     }
 
+    fn motion(&self, seat: &PortalSeat, x: f32, y: f32) -> bool {
+        let _ = seat;
+        let _ = x;
+        let _ = y;
+        false
+    }
+
     fn destroy(&self) {}
 }
 
@@ -473,6 +483,7 @@ pub struct OverlayWindow {
     pub layer_surface: Rc<UsrWlrLayerSurface>,
     pub data: Rc<WindowData>,
     pub owner: CloneCell<Option<Rc<dyn OverlayWindowOwner>>>,
+    fullscreen: bool,
 }
 
 pub trait OverlayWindowOwner {
@@ -495,6 +506,8 @@ pub struct WindowData {
     pending_bufs: CopyHashMap<ZwpLinuxBufferParamsV1Id, Rc<GuiBufferPending>>,
     pub width: Cell<i32>,
     pub height: Cell<i32>,
+    pub fixed_size: Cell<Option<(i32, i32)>>,
+    pub clear_color: Cell<Color>,
     pub owner: CloneCell<Option<Rc<dyn WindowDataOwner>>>,
     pub seats: CopyHashMap<u32, Rc<GuiWindowSeatState>>,
 }
@@ -514,8 +527,10 @@ pub trait WindowDataOwner {
 
 impl WindowDataOwner for OverlayWindow {
     fn post_layout(&self) {
-        self.layer_surface
-            .set_size(self.data.width.get(), self.data.height.get());
+        if !self.fullscreen {
+            self.layer_surface
+                .set_size(self.data.width.get(), self.data.height.get());
+        }
         self.data.surface.commit();
     }
 
@@ -533,16 +548,44 @@ const NUM_BUFFERS: usize = 2;
 
 impl OverlayWindow {
     pub fn new(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::new_(output, false, 0)
+    }
+
+    /// Creates an overlay that is attached to the top edge of the output.
+    pub fn new_top(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::new_(output, false, TOP)
+    }
+
+    /// Creates an overlay that covers the entire output.
+    ///
+    /// The content is laid out with the size of the output and the window is cleared with
+    /// a transparent color.
+    pub fn new_fullscreen(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::new_(output, true, TOP | BOTTOM | LEFT | RIGHT)
+    }
+
+    fn new_(output: &Rc<PortalOutput>, fullscreen: bool, anchor: u32) -> Rc<Self> {
         let data = WindowData::new(&output.dpy);
         let layer_surface = output
             .dpy
             .ls
             .get_layer_surface(&data.surface, &output.wl, OVERLAY);
-        layer_surface.set_size(1, 1);
+        if anchor != 0 {
+            layer_surface.set_anchor(anchor);
+        }
+        if fullscreen {
+            layer_surface.set_exclusive_zone(-1);
+            layer_surface.set_size(0, 0);
+            data.fixed_size.set(Some((0, 0)));
+            data.clear_color.set(Color::TRANSPARENT);
+        } else {
+            layer_surface.set_size(1, 1);
+        }
         let slf = Rc::new(Self {
             layer_surface,
             data,
             owner: Default::default(),
+            fullscreen,
         });
         slf.data.owner.set(Some(slf.clone()));
         slf.layer_surface.owner.set(Some(slf.clone()));
@@ -569,6 +612,8 @@ impl WindowData {
             pending_bufs: Default::default(),
             width: Cell::new(0),
             height: Cell::new(0),
+            fixed_size: Cell::new(None),
+            clear_color: Cell::new(Color::from_gray(0)),
             owner: Default::default(),
             render_trigger: Default::default(),
             render_task: Cell::new(None),
@@ -599,7 +644,14 @@ impl WindowData {
             Some(c) => c,
             _ => return,
         };
-        let (mut width, mut height) = content.layout(&ctx.ctx, scale, f32::INFINITY, f32::INFINITY);
+        let (mut width, mut height) = match self.fixed_size.get() {
+            Some((width, height)) => {
+                let (width, height) = (width as f32, height as f32);
+                content.layout(&ctx.ctx, scale, width, height);
+                (width, height)
+            }
+            _ => content.layout(&ctx.ctx, scale, f32::INFINITY, f32::INFINITY),
+        };
         content.data().width.set(width);
         content.data().height.set(height);
         width = width.max(1.0);
@@ -637,7 +689,7 @@ impl WindowData {
 
         let res = buf
             .fb
-            .render_custom(self.scale.get(), Some(&Color::from_gray(0)), &mut |r| {
+            .render_custom(self.scale.get(), Some(&self.clear_color.get()), &mut |r| {
                 if let Some(content) = self.content.get() {
                     content.render_at(r, 0.0, 0.0)
                 }
@@ -814,7 +866,17 @@ impl WindowData {
             }
         }
 
-        if element_changed {
+        let mut needs_render = element_changed;
+        if let Some(element) = &new_element {
+            let (mut x, mut y) = (x, y);
+            for e in &tree[1..] {
+                x -= e.data().x.get();
+                y -= e.data().y.get();
+            }
+            needs_render |= element.motion(pseat, x, y);
+        }
+
+        if needs_render {
             self.schedule_render();
         }
 
@@ -879,7 +941,14 @@ impl UsrWpFractionalScaleOwner for WindowData {
 }
 
 impl UsrWlrLayerSurfaceOwner for OverlayWindow {
-    fn configure(&self, _ev: &Configure) {
+    fn configure(&self, ev: &Configure) {
+        if self.fullscreen {
+            let size = Some((ev.width as i32, ev.height as i32));
+            if self.data.fixed_size.replace(size) != size {
+                self.data.layout();
+                self.data.allocate_buffers();
+            }
+        }
         self.data.schedule_render();
     }
 
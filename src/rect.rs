@@ -83,7 +83,7 @@ impl Rect {
         if width < 0 || height < 0 {
             return None;
         }
-        Self::new(x1, y1, x1 + width, y1 + height)
+        Self::new(x1, y1, x1.saturating_add(width), y1.saturating_add(height))
     }
 
     #[track_caller]
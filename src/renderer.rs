@@ -65,6 +65,7 @@ pub struct Renderer<'a> {
     pub result: Option<&'a mut RenderResult>,
     pub logical_extents: Rect,
     pub pixel_extents: Rect,
+    pub opacity: f32,
 }
 
 impl Renderer<'_> {
@@ -168,7 +169,7 @@ impl Renderer<'_> {
                         ReleaseSync::None,
                     );
                 }
-                if let Some(status) = &rd.status {
+                for status in rd.status.iter().chain(rd.binding_mode.iter()) {
                     let (x, y) = self.base.scale_point(x + status.tex_x, y + status.tex_y);
                     self.base.render_texture(
                         &status.tex.texture,
@@ -214,11 +215,39 @@ impl Renderer<'_> {
                 self.base.fill_boxes(&[bounds], &color);
             }
         }
+        let rd = output.render_data.borrow();
+        if let Some(switcher) = &rd.window_switcher {
+            let c = theme.colors.bar_background.get();
+            self.base
+                .fill_boxes2(slice::from_ref(&switcher.rect), &c, x, y);
+            let c = theme.colors.focused_title_background.get();
+            self.base
+                .fill_boxes2(slice::from_ref(&switcher.selected), &c, x, y);
+            let scale = output.global.persistent.scale.get();
+            for title in &switcher.titles {
+                let (x, y) = self.base.scale_point(x + title.tex_x, y + title.tex_y);
+                self.base.render_texture(
+                    &title.tex.texture,
+                    None,
+                    x,
+                    y,
+                    None,
+                    None,
+                    scale,
+                    None,
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                );
+            }
+        }
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
         if let Some(node) = workspace.container.get() {
-            self.render_container(&node, x, y)
+            let pos = workspace.position.get();
+            let (dx, dy) = pos.translate(node.abs_x1.get(), node.abs_y1.get());
+            self.render_container(&node, x + dx, y + dy)
         }
     }
 
@@ -321,12 +350,18 @@ impl Renderer<'_> {
     }
 
     pub fn render_xwindow(&mut self, tl: &Xwindow, x: i32, y: i32, bounds: Option<&Rect>) {
+        let opacity = self.opacity;
+        self.opacity *= tl.tl_data().opacity.get();
         self.render_surface(&tl.x.surface, x, y, bounds);
+        self.opacity = opacity;
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
     pub fn render_xdg_toplevel(&mut self, tl: &XdgToplevel, x: i32, y: i32, bounds: Option<&Rect>) {
+        let opacity = self.opacity;
+        self.opacity *= tl.tl_data().opacity.get();
         self.render_xdg_surface(&tl.xdg, x, y, bounds);
+        self.opacity = opacity;
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
@@ -405,7 +440,10 @@ impl Renderer<'_> {
         } else {
             size = self.base.scale_point(size.0, size.1);
         }
-        let alpha = surface.alpha();
+        let mut alpha = surface.alpha();
+        if self.opacity < 1.0 {
+            alpha = Some(alpha.unwrap_or(1.0) * self.opacity);
+        }
         if let Some(children) = children.deref() {
             macro_rules! render {
                 ($children:expr) => {
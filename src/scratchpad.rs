@@ -0,0 +1,99 @@
+use {
+    crate::{
+        ifs::wl_seat::{collect_kb_foci, WlSeatGlobal},
+        minimize,
+        state::State,
+        tree::{Direction, Node, ToplevelNode},
+        utils::linkedlist::LinkedList,
+    },
+    std::rc::Rc,
+};
+
+/// The windows in the scratchpad.
+///
+/// Hidden windows are not attached to any workspace. Windows that are shown from the
+/// scratchpad stay in the scratchpad until they are made tiled or destroyed.
+#[derive(Default)]
+pub struct Scratchpad {
+    windows: LinkedList<Rc<dyn ToplevelNode>>,
+}
+
+impl Scratchpad {
+    pub fn clear(&self) {
+        let windows: Vec<_> = self.windows.iter().map(|tl| (*tl).clone()).collect();
+        for tl in windows {
+            tl.tl_data().scratchpad_link.take();
+        }
+    }
+}
+
+/// Hides the toplevel and moves it to the end of the scratchpad.
+pub fn move_to_scratchpad(state: &Rc<State>, tl: Rc<dyn ToplevelNode>) {
+    let data = tl.tl_data();
+    if data.is_fullscreen.get() {
+        tl.clone().tl_set_fullscreen(false);
+    }
+    let (Some(ws), Some(parent)) = (data.workspace.get(), data.parent.get()) else {
+        return;
+    };
+    let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+    parent.cnode_remove_child2(tl.tl_as_node(), true);
+    data.parent.take();
+    data.workspace.take();
+    tl.tl_set_visible(false);
+    for seat in kb_foci {
+        ws.clone().node_do_focus(&seat, Direction::Unspecified);
+    }
+    let link = &mut *data.scratchpad_link.borrow_mut();
+    match link {
+        Some(link) => state.scratchpad.windows.add_last_existing(&link.to_ref()),
+        None => *link = Some(state.scratchpad.windows.add_last(tl.clone())),
+    }
+    state.tree_changed();
+}
+
+/// Hides the focused window if it is in the scratchpad. Otherwise shows the first window
+/// of the scratchpad floating and centered on the output that contains the cursor.
+///
+/// Since hidden windows are moved to the end of the scratchpad, repeated invocations
+/// cycle through the windows. If `app_id` is set, only windows with this app-id are
+/// considered.
+pub fn toggle_scratchpad(state: &Rc<State>, seat: &Rc<WlSeatGlobal>, app_id: Option<&str>) {
+    let matches = |tl: &Rc<dyn ToplevelNode>| match app_id {
+        Some(app_id) => *tl.tl_data().app_id.borrow() == app_id,
+        None => true,
+    };
+    if let Some(tl) = seat.focused_toplevel() {
+        if tl.tl_data().scratchpad_link.borrow().is_some() && matches(&tl) {
+            move_to_scratchpad(state, tl);
+            return;
+        }
+    }
+    let tl = state
+        .scratchpad
+        .windows
+        .iter()
+        .find(|tl| matches(tl))
+        .map(|tl| (*tl).clone());
+    let Some(tl) = tl else {
+        return;
+    };
+    let output = seat.get_output();
+    if output.is_dummy {
+        return;
+    }
+    let ws = output.ensure_workspace();
+    let data = tl.tl_data();
+    if data.is_fullscreen.get() {
+        tl.clone().tl_set_fullscreen(false);
+    }
+    if let Some(parent) = data.parent.get() {
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+    }
+    minimize::unminimize(&*tl);
+    let (width, height) = data.float_size(&ws);
+    state.map_floating(tl.clone(), width, height, &ws, None);
+    if tl.node_visible() {
+        tl.node_do_focus(seat, Direction::Unspecified);
+    }
+}
@@ -4,6 +4,7 @@ use {
         gfx_api::GfxError,
         scale::Scale,
         state::State,
+        tree::Node,
         video::{
             drm::DrmError,
             gbm::{GbmBo, GbmError, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING},
@@ -22,6 +23,8 @@ pub enum ScreenshooterError {
     NoRenderContext,
     #[error("Display is empty")]
     EmptyDisplay,
+    #[error("The screenshot target is empty")]
+    EmptyTarget,
     #[error(transparent)]
     GbmError(#[from] GbmError),
     #[error(transparent)]
@@ -42,15 +45,34 @@ pub struct Screenshot {
 pub fn take_screenshot(
     state: &State,
     include_cursor: bool,
+) -> Result<Screenshot, ScreenshooterError> {
+    if state.root.extents.get().is_empty() {
+        return Err(ScreenshooterError::EmptyDisplay);
+    }
+    take_node_screenshot(
+        state,
+        state.root.deref(),
+        Scale::from_int(1),
+        include_cursor,
+    )
+}
+
+/// Renders a single node, e.g. an output or a toplevel, at the given scale.
+pub fn take_node_screenshot(
+    state: &State,
+    node: &dyn Node,
+    scale: Scale,
+    include_cursor: bool,
 ) -> Result<Screenshot, ScreenshooterError> {
     let ctx = match state.render_ctx.get() {
         Some(ctx) => ctx,
         _ => return Err(ScreenshooterError::NoRenderContext),
     };
-    let extents = state.root.extents.get();
+    let extents = node.node_absolute_position();
     if extents.is_empty() {
-        return Err(ScreenshooterError::EmptyDisplay);
+        return Err(ScreenshooterError::EmptyTarget);
     }
+    let (width, height) = scale.pixel_size(extents.width(), extents.height());
     let formats = ctx.formats();
     let mut usage = GBM_BO_USE_RENDERING;
     let modifiers = match formats.get(&XRGB8888.drm) {
@@ -65,19 +87,19 @@ pub fn take_screenshot(
     let gbm = ctx.gbm();
     let bo = gbm.create_bo(
         &state.dma_buf_ids,
-        extents.width(),
-        extents.height(),
+        width,
+        height,
         XRGB8888,
         modifiers,
         usage,
     )?;
     let fb = ctx.clone().dmabuf_fb(bo.dmabuf())?;
     fb.render_node(
-        state.root.deref(),
+        node,
         state,
-        Some(state.root.extents.get()),
+        Some(extents),
         None,
-        Scale::from_int(1),
+        scale,
         include_cursor,
         true,
         false,
@@ -96,7 +96,11 @@ impl Acceptor {
                 }
             };
             let id = s.clients.id();
-            if let Err(e) = s.clients.spawn(id, s, fd, self.caps, self.caps) {
+            let sandbox_engine = self.sandbox_engine.clone();
+            if let Err(e) = s
+                .clients
+                .spawn(id, s, fd, self.caps, self.caps, sandbox_engine)
+            {
                 log::error!("Could not spawn a client: {}", ErrorFmt(e));
                 break;
             }
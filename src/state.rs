@@ -29,6 +29,7 @@ use {
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
             ext_session_lock_v1::ExtSessionLockV1,
             ipc::{x_data_device::XIpcDeviceIds, DataOfferIds, DataSourceIds},
+            jay_appearance::JayAppearance,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_seat_events::JaySeatEvents,
@@ -36,6 +37,7 @@ use {
             wl_drm::WlDrmGlobal,
             wl_output::{OutputGlobalOpt, OutputId, PersistentOutputState},
             wl_seat::{
+                collect_kb_foci,
                 tablet::{TabletIds, TabletInit, TabletPadIds, TabletPadInit, TabletToolIds},
                 SeatIds, WlSeatGlobal,
             },
@@ -48,28 +50,42 @@ use {
             wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1,
             wp_drm_lease_device_v1::WpDrmLeaseDeviceV1Global,
             wp_linux_drm_syncobj_manager_v1::WpLinuxDrmSyncobjManagerV1Global,
+            zwlr_gamma_control_v1::ZwlrGammaControlV1,
+            zwlr_output_manager_v1::{OutputHeadState, ZwlrOutputManagerV1},
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
             zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1,
             zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global,
         },
         io_uring::IoUring,
+        json_ipc::JsonIpc,
         leaks::Tracker,
         logger::Logger,
         rect::Rect,
         renderer::{RenderResult, Renderer},
         scale::Scale,
+        scratchpad::Scratchpad,
         security_context_acceptor::SecurityContextAcceptors,
         theme::{Color, Theme},
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeIds,
-            NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, ToplevelNodeBase,
-            WorkspaceNode,
+            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeId,
+            NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, ToplevelNodeBase,
+            WorkspaceGaps, WorkspaceNode,
         },
         utils::{
-            activation_token::ActivationToken, asyncevent::AsyncEvent, bindings::Bindings,
-            clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt, fdcloser::FdCloser,
-            hash_map_ext::HashMapExt, linkedlist::LinkedList, numcell::NumCell, queue::AsyncQueue,
-            refcounted::RefCounted, run_toplevel::RunToplevel,
+            activation_token::ActivationToken,
+            asyncevent::AsyncEvent,
+            bindings::Bindings,
+            clonecell::CloneCell,
+            color_temperature::{MAX_COLOR_TEMPERATURE, MIN_COLOR_TEMPERATURE},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            fdcloser::FdCloser,
+            hash_map_ext::HashMapExt,
+            linkedlist::LinkedList,
+            numcell::NumCell,
+            queue::AsyncQueue,
+            refcounted::RefCounted,
+            run_toplevel::RunToplevel,
         },
         video::{
             dmabuf::DmaBufIds,
@@ -80,10 +96,12 @@ use {
             },
         },
         wheel::Wheel,
+        window_rules::WindowRules,
         wire::{
-            ExtForeignToplevelListV1Id, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
-            ZwpLinuxDmabufFeedbackV1Id,
+            ExtForeignToplevelListV1Id, JayAppearanceId, JayRenderCtxId, JaySeatEventsId,
+            JayWorkspaceWatcherId, ZwlrOutputManagerV1Id, ZwpLinuxDmabufFeedbackV1Id,
         },
+        workspaces,
         xkbcommon::{KeyboardStateIds, XkbContext, XkbKeymap, XkbState},
         xwayland::{self, XWaylandEvent},
     },
@@ -91,6 +109,7 @@ use {
     bstr::ByteSlice,
     jay_config::{
         video::{GfxApi, Transform},
+        xwayland::XScalingMode,
         PciId,
     },
     std::{
@@ -130,6 +149,8 @@ pub struct State {
     pub node_ids: NodeIds,
     pub root: Rc<DisplayNode>,
     pub workspaces: CopyHashMap<String, Rc<WorkspaceNode>>,
+    pub workspace_gaps: RefCell<AHashMap<String, WorkspaceGaps>>,
+    pub workspace_connectors: RefCell<AHashMap<String, ConnectorId>>,
     pub dummy_output: CloneCell<Option<Rc<OutputNode>>>,
     pub backend_events: AsyncQueue<BackendEvent>,
     pub input_device_handlers: RefCell<AHashMap<InputDeviceId, InputDeviceData>>,
@@ -147,6 +168,7 @@ pub struct State {
     pub pending_input_popup_positioning: AsyncQueue<Rc<ZwpInputPopupSurfaceV2>>,
     pub pending_toplevel_screencasts: AsyncQueue<Rc<JayScreencast>>,
     pub pending_toplevel_screencast_reallocs: AsyncQueue<Rc<JayScreencast>>,
+    pub metadata_cursor_screencasts: NumCell<usize>,
     pub dbus: Dbus,
     pub fdcloser: Arc<FdCloser>,
     pub logger: Option<Arc<Logger>>,
@@ -158,6 +180,7 @@ pub struct State {
     pub run_args: RunArgs,
     pub xwayland: XWaylandState,
     pub acceptor: CloneCell<Option<Rc<Acceptor>>>,
+    pub json_ipc: CloneCell<Option<Rc<JsonIpc>>>,
     pub serial: NumCell<Wrapping<u32>>,
     pub run_toplevel: Rc<RunToplevel>,
     pub config_dir: Option<String>,
@@ -173,6 +196,7 @@ pub struct State {
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
+    pub appearance_watchers: CopyHashMap<(ClientId, JayAppearanceId), Rc<JayAppearance>>,
     pub default_workspace_capture: Cell<bool>,
     pub default_gfx_api: Cell<GfxApi>,
     pub activation_tokens: CopyHashMap<ActivationToken, ()>,
@@ -198,6 +222,13 @@ pub struct State {
     pub tablet_ids: TabletIds,
     pub tablet_tool_ids: TabletToolIds,
     pub tablet_pad_ids: TabletPadIds,
+    pub output_managers: CopyHashMap<(ClientId, ZwlrOutputManagerV1Id), Rc<ZwlrOutputManagerV1>>,
+    pub output_heads: CopyHashMap<ConnectorId, Rc<OutputHeadState>>,
+    pub output_heads_serial: NumCell<u32>,
+    pub output_heads_changed: AsyncEvent,
+    pub window_rules: WindowRules,
+    pub scratchpad: Scratchpad,
+    pub mapped_toplevels: CopyHashMap<NodeId, Rc<dyn ToplevelNode>>,
 }
 
 // impl Drop for State {
@@ -222,6 +253,9 @@ pub struct XWaylandState {
     pub handler: RefCell<Option<SpawnedFuture<()>>>,
     pub queue: Rc<AsyncQueue<XWaylandEvent>>,
     pub ipc_device_ids: XIpcDeviceIds,
+    pub scaling_mode: Cell<XScalingMode>,
+    /// The factor between the coordinates seen by Xwayland and logical coordinates.
+    pub wire_scale: Cell<Option<i32>>,
 }
 
 pub struct IdleState {
@@ -281,6 +315,16 @@ pub struct ConnectorData {
     pub name: String,
     pub drm_dev: Option<Rc<DrmDevData>>,
     pub async_event: Rc<AsyncEvent>,
+    pub gamma_control: CloneCell<Option<Rc<ZwlrGammaControlV1>>>,
+    pub color_temperature: Cell<u32>,
+}
+
+impl ConnectorData {
+    pub fn set_color_temperature(&self, kelvin: u32) {
+        let kelvin = kelvin.clamp(MIN_COLOR_TEMPERATURE, MAX_COLOR_TEMPERATURE);
+        self.color_temperature.set(kelvin);
+        self.connector.set_color_temperature(kelvin);
+    }
 }
 
 pub struct OutputData {
@@ -376,6 +420,26 @@ impl State {
     fn output_scales_changed(&self) {
         UpdateTextTexturesVisitor.visit_display(&self.root);
         self.reload_cursors();
+        self.update_xwayland_wire_scale();
+    }
+
+    pub fn update_xwayland_wire_scale(&self) {
+        let scale = match self.xwayland.scaling_mode.get() {
+            XScalingMode::Default => None,
+            XScalingMode::Downscaled => {
+                let scale = self
+                    .scales
+                    .lock()
+                    .iter()
+                    .map(|(s, _)| s.round_up())
+                    .max()
+                    .unwrap_or(1);
+                Some(scale as i32).filter(|&s| s > 1)
+            }
+        };
+        if self.xwayland.wire_scale.replace(scale) != scale {
+            self.xwayland.queue.push(XWaylandEvent::WireScaleChanged);
+        }
     }
 
     fn cursor_sizes_changed(&self) {
@@ -445,6 +509,8 @@ impl State {
                 fn visit_output(&mut self, node: &Rc<OutputNode>) {
                     node.render_data.borrow_mut().titles.clear();
                     node.render_data.borrow_mut().status.take();
+                    node.render_data.borrow_mut().binding_mode.take();
+                    node.render_data.borrow_mut().window_switcher.take();
                     node.hardware_cursor.set(None);
                     node.node_visit_children(self);
                 }
@@ -531,6 +597,12 @@ impl State {
         }
     }
 
+    pub fn appearance_changed(&self) {
+        for watcher in self.appearance_watchers.lock().values() {
+            watcher.send_appearance(&self.theme);
+        }
+    }
+
     pub fn add_global<T: WaylandGlobal>(&self, global: &Rc<T>) {
         self.globals.add_global(self, global)
     }
@@ -544,6 +616,7 @@ impl State {
         if self.tree_changed_sent.replace(true) {
             return;
         }
+        self.damage();
         let seats = self.globals.seats.lock();
         for seat in seats.values() {
             seat.trigger_tree_changed();
@@ -634,7 +707,70 @@ impl State {
         }
     }
 
+    pub fn set_tl_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        if data.is_floating.get() == floating {
+            return;
+        }
+        let parent = match data.parent.get() {
+            Some(p) => p,
+            _ => return,
+        };
+        if !floating {
+            data.scratchpad_link.take();
+            parent.cnode_remove_child2(tl.tl_as_node(), true);
+            self.map_tiled(tl);
+        } else if let Some(ws) = data.workspace.get() {
+            parent.cnode_remove_child2(tl.tl_as_node(), true);
+            let (width, height) = data.float_size(&ws);
+            self.map_floating(tl, width, height, &ws, None);
+        }
+    }
+
+    pub fn set_tl_workspace(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, ws: &Rc<WorkspaceNode>) {
+        if tl.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let old_ws = match tl.tl_data().workspace.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        if old_ws.id == ws.id {
+            return;
+        }
+        let cn = match tl.tl_data().parent.get() {
+            Some(cn) => cn,
+            _ => return,
+        };
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        cn.cnode_remove_child2(tl.tl_as_node(), true);
+        if !ws.visible.get() {
+            for focus in kb_foci {
+                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
+            }
+        }
+        if tl.tl_data().is_floating.get() {
+            self.map_floating(
+                tl.clone(),
+                tl.tl_data().float_width.get(),
+                tl.tl_data().float_height.get(),
+                ws,
+                None,
+            );
+        } else {
+            self.map_tiled_on(tl, ws);
+        }
+    }
+
     pub fn show_workspace(&self, seat: &Rc<WlSeatGlobal>, name: &str) {
+        if let Some(current) = seat.get_output().workspace.get() {
+            if !current.is_dummy && *current.name.borrow() != name {
+                seat.set_previous_workspace(Some(Rc::new(current.name.borrow().clone())));
+            }
+        }
         let (output, ws) = match self.workspaces.get(name) {
             Some(ws) => {
                 let output = ws.output.get();
@@ -646,13 +782,15 @@ impl State {
                 (output, ws)
             }
             _ => {
-                let output = seat.get_output();
+                let output =
+                    workspaces::preferred_output(self, name).unwrap_or_else(|| seat.get_output());
                 if output.is_dummy {
                     log::warn!("Not showing workspace because seat is on dummy output");
                     return;
                 }
                 let ws = output.create_workspace(name);
                 output.show_workspace(&ws);
+                ws.clone().node_do_focus(seat, Direction::Unspecified);
                 (output, ws)
             }
         };
@@ -736,6 +874,19 @@ impl State {
         }
     }
 
+    pub fn damage_rect(&self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        for output in self.root.outputs.lock().values() {
+            let pos = output.global.pos.get();
+            if pos.intersects(&rect) {
+                let rect = rect.intersect(pos).move_(-pos.x1(), -pos.y1());
+                output.global.connector.connector.damage_rect(rect);
+            }
+        }
+    }
+
     pub fn clear(&self) {
         self.lock.lock.take();
         self.xwayland.handler.borrow_mut().take();
@@ -747,6 +898,9 @@ impl State {
             forker.clear();
         }
         self.acceptor.set(None);
+        if let Some(json_ipc) = self.json_ipc.set(None) {
+            json_ipc.clear();
+        }
         self.backend.set(Rc::new(DummyBackend)).clear();
         self.run_toplevel.clear();
         self.xwayland.handler.borrow_mut().take();
@@ -776,7 +930,10 @@ impl State {
         self.pending_toplevel_screencast_reallocs.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.appearance_watchers.clear();
         self.toplevel_lists.clear();
+        self.mapped_toplevels.clear();
+        self.output_managers.clear();
         self.security_context_acceptors.clear();
         self.slow_clients.clear();
         for h in self.input_device_handlers.borrow_mut().drain_values() {
@@ -784,6 +941,9 @@ impl State {
         }
         self.backend_events.clear();
         self.workspaces.clear();
+        self.workspace_gaps.borrow_mut().clear();
+        self.workspace_connectors.borrow_mut().clear();
+        self.scratchpad.clear();
         {
             let seats = mem::take(self.globals.seats.lock().deref_mut());
             for seat in seats.values() {
@@ -868,6 +1028,7 @@ impl State {
                 let (width, height) = target.logical_size(Transform::None);
                 Rect::new_sized(0, 0, width, height).unwrap()
             },
+            opacity: 1.0,
         };
         let mut sample_rect = SampleRect::identity();
         sample_rect.buffer_transform = transform;
@@ -896,7 +1057,7 @@ impl State {
                 }
             }
         }
-        target.render(ops, Some(&Color::SOLID_BLACK))
+        target.render(ops, Some(&Color::SOLID_BLACK), &target.full_region())
     }
 
     fn have_hardware_cursor(&self) -> bool {
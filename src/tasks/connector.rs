@@ -3,9 +3,14 @@ use {
         backend::{Connector, ConnectorEvent, ConnectorId, MonitorInfo},
         globals::GlobalName,
         ifs::wl_output::{OutputId, PersistentOutputState, WlOutputGlobal},
+        json_ipc::{self, messages::OutputChange},
         state::{ConnectorData, OutputData, State},
         tree::{move_ws_to_output, OutputNode, OutputRenderData, WsMoveConfig},
-        utils::{asyncevent::AsyncEvent, clonecell::CloneCell, hash_map_ext::HashMapExt},
+        utils::{
+            asyncevent::AsyncEvent, clonecell::CloneCell,
+            color_temperature::NEUTRAL_COLOR_TEMPERATURE, hash_map_ext::HashMapExt,
+        },
+        workspaces,
     },
     std::{
         cell::{Cell, RefCell},
@@ -30,6 +35,8 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         name: connector.kernel_id().to_string(),
         drm_dev: drm_dev.clone(),
         async_event: Rc::new(AsyncEvent::default()),
+        gamma_control: Default::default(),
+        color_temperature: Cell::new(NEUTRAL_COLOR_TEMPERATURE),
     });
     if let Some(dev) = drm_dev {
         dev.connectors.set(id, data.clone());
@@ -79,6 +86,7 @@ impl ConnectorHandler {
         }
         self.data.handler.set(None);
         self.state.connectors.remove(&self.id);
+        self.state.output_heads_changed.trigger();
     }
 
     async fn handle_connected(&self, info: MonitorInfo) {
@@ -122,6 +130,7 @@ impl ConnectorHandler {
                     transform: Default::default(),
                     scale: Default::default(),
                     pos: Cell::new((x1, 0)),
+                    vrr_mode: Default::default(),
                 });
                 self.state
                     .persistent_output_states
@@ -159,6 +168,8 @@ impl ConnectorHandler {
                 captured_inactive_workspaces: Default::default(),
                 titles: Default::default(),
                 status: None,
+                binding_mode: None,
+                window_switcher: None,
             }),
             state: self.state.clone(),
             is_dummy: false,
@@ -174,6 +185,9 @@ impl ConnectorHandler {
             screencopies: Default::default(),
         });
         on.update_rects();
+        self.data
+            .connector
+            .set_vrr_mode(desired_state.vrr_mode.get());
         self.state
             .add_output_scale(on.global.persistent.scale.get());
         let output_data = Rc::new(OutputData {
@@ -209,6 +223,10 @@ impl ConnectorHandler {
                 if ws.is_dummy {
                     continue;
                 }
+                if workspaces::preferred_connector(&self.state, &ws.name.borrow()) == Some(self.id)
+                {
+                    ws.desired_output.set(global.output_id.clone());
+                }
                 if ws.desired_output.get() == global.output_id {
                     ws_to_move.push_back(ws.clone());
                 }
@@ -227,8 +245,10 @@ impl ConnectorHandler {
         if let Some(config) = self.state.config.get() {
             config.connector_connected(self.id);
         }
+        json_ipc::output_event(&self.state, OutputChange::Connected, &on);
         self.state.add_global(&global);
         self.state.tree_changed();
+        self.state.output_heads_changed.trigger();
         'outer: loop {
             while let Some(event) = self.data.connector.event() {
                 match event {
@@ -248,6 +268,7 @@ impl ConnectorHandler {
         if let Some(config) = self.state.config.get() {
             config.connector_disconnected(self.id);
         }
+        json_ipc::output_event(&self.state, OutputChange::Disconnected, &on);
         global.clear();
         for jo in on.jay_outputs.lock().drain_values() {
             jo.send_destroyed();
@@ -259,6 +280,9 @@ impl ConnectorHandler {
         for sc in on.screencopies.lock().drain_values() {
             sc.send_failed();
         }
+        if let Some(gc) = self.data.gamma_control.get() {
+            gc.send_failed();
+        }
         global.destroyed.set(true);
         self.state.root.outputs.remove(&self.id);
         self.state.root.update_extents();
@@ -296,6 +320,7 @@ impl ConnectorHandler {
         let _ = self.state.remove_global(&*global);
         self.state.tree_changed();
         self.state.damage();
+        self.state.output_heads_changed.trigger();
     }
 
     async fn handle_non_desktop_connected(&self, monitor_info: MonitorInfo) {
@@ -1,7 +1,10 @@
-use std::{
-    cell::{Cell, RefCell},
-    cmp::Ordering,
-    ops::Mul,
+use {
+    jay_config::theme::ColorScheme,
+    std::{
+        cell::{Cell, RefCell},
+        cmp::Ordering,
+        ops::Mul,
+    },
 };
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -284,6 +287,8 @@ macro_rules! sizes {
 sizes! {
     title_height = (1, 1000, 17),
     border_width = (1, 1000, 4),
+    inner_gap = (0, 1000, 0),
+    outer_gap = (0, 1000, 0),
 }
 
 pub const DEFAULT_FONT: &str = "monospace 8";
@@ -292,6 +297,8 @@ pub struct Theme {
     pub colors: ThemeColors,
     pub sizes: ThemeSizes,
     pub font: RefCell<String>,
+    pub smart_gaps: Cell<bool>,
+    pub color_scheme: Cell<ColorScheme>,
 }
 
 impl Default for Theme {
@@ -300,6 +307,8 @@ impl Default for Theme {
             colors: Default::default(),
             sizes: Default::default(),
             font: RefCell::new(DEFAULT_FONT.to_string()),
+            smart_gaps: Cell::new(false),
+            color_scheme: Cell::new(ColorScheme::NoPreference),
         }
     }
 }
@@ -335,6 +335,7 @@ impl ContainerNode {
         // log::info!("add_child");
         self.schedule_layout();
         self.cancel_seat_ops();
+        self.workspace.get().update_container_position();
     }
 
     fn cancel_seat_ops(&self) {
@@ -344,6 +345,33 @@ impl ContainerNode {
         }
     }
 
+    /// Returns whether this container contains exactly one window, possibly nested in
+    /// other containers.
+    pub fn is_single_window(&self) -> bool {
+        if self.num_children.get() != 1 {
+            return false;
+        }
+        let Some(child) = self.children.first() else {
+            return false;
+        };
+        match child.node.clone().tl_into_node().node_into_container() {
+            Some(c) => c.is_single_window(),
+            _ => true,
+        }
+    }
+
+    /// Returns the space between the children of a split container.
+    ///
+    /// If inner gaps are enabled, the gap replaces the border.
+    fn child_spacing(&self) -> i32 {
+        let gap = self.workspace.get().inner_gap();
+        if gap > 0 {
+            gap
+        } else {
+            self.state.theme.sizes.border_width.get()
+        }
+    }
+
     pub fn on_spaces_changed(self: &Rc<Self>) {
         self.update_content_size();
         // log::info!("on_spaces_changed");
@@ -407,7 +435,7 @@ impl ContainerNode {
 
     fn perform_split_layout(self: &Rc<Self>) {
         let sum_factors = self.sum_factors.get();
-        let border_width = self.state.theme.sizes.border_width.get();
+        let spacing = self.child_spacing();
         let title_height = self.state.theme.sizes.title_height.get();
         let split = self.split.get();
         let (content_size, other_content_size) = match split {
@@ -434,7 +462,7 @@ impl ContainerNode {
             };
             let body = Rect::new_sized(x1, y1, width, height).unwrap();
             child.body.set(body);
-            pos += body_size + border_width;
+            pos += body_size + spacing;
             if split == ContainerSplit::Vertical {
                 pos += title_height + 1;
             }
@@ -468,7 +496,7 @@ impl ContainerNode {
                 };
                 body = Rect::new_sized(x1, y1, width, height).unwrap();
                 child.body.set(body);
-                pos += size + border_width;
+                pos += size + spacing;
                 if split == ContainerSplit::Vertical {
                     pos += title_height + 1;
                 }
@@ -493,12 +521,12 @@ impl ContainerNode {
     }
 
     fn update_content_size(&self) {
-        let border_width = self.state.theme.sizes.border_width.get();
+        let spacing = self.child_spacing();
         let title_height = self.state.theme.sizes.title_height.get();
         let nc = self.num_children.get();
         match self.split.get() {
             ContainerSplit::Horizontal => {
-                let new_content_size = self.width.get().sub((nc - 1) as i32 * border_width).max(0);
+                let new_content_size = self.width.get().sub((nc - 1) as i32 * spacing).max(0);
                 self.content_width.set(new_content_size);
                 self.content_height
                     .set(self.height.get().sub(title_height + 1).max(0));
@@ -507,7 +535,7 @@ impl ContainerNode {
                 let new_content_size = self
                     .height
                     .get()
-                    .sub(title_height + 1 + (nc - 1) as i32 * (border_width + title_height + 1))
+                    .sub(title_height + 1 + (nc - 1) as i32 * (spacing + title_height + 1))
                     .max(0);
                 self.content_height.set(new_content_size);
                 self.content_width.set(self.width.get());
@@ -659,6 +687,7 @@ impl ContainerNode {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
+        let has_gaps = self.workspace.get().inner_gap() > 0;
         let font = theme.font.borrow_mut();
         let cwidth = self.width.get();
         let cheight = self.height.get();
@@ -679,7 +708,7 @@ impl ContainerNode {
         let scales = self.state.scales.lock();
         for (i, child) in self.children.iter().enumerate() {
             let rect = child.title_rect.get();
-            if i > 0 {
+            if i > 0 && (mono || !has_gaps) {
                 let rect = if mono {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
                 } else if split == ContainerSplit::Horizontal {
@@ -755,6 +784,7 @@ impl ContainerNode {
                 .push(Rect::new_sized(0, th, cwidth, 1).unwrap());
         }
         rd.titles.remove_if(|_, v| v.is_empty());
+        self.state.damage();
     }
 
     fn activate_child(self: &Rc<Self>, child: &NodeRef<ContainerChild>) {
@@ -1122,7 +1152,7 @@ impl ContainerNode {
                 && kind == SeatOpKind::Move
             {
                 drop(seat_datas);
-                seat.set_tl_floating(child.node.clone(), true);
+                self.state.set_tl_floating(child.node.clone(), true);
                 return;
             }
             seat_data.op = Some(SeatOp { child, kind })
@@ -1526,6 +1556,7 @@ impl ContainingNode for ContainerNode {
         // log::info!("cnode_remove_child2");
         self.schedule_layout();
         self.cancel_seat_ops();
+        self.workspace.get().update_container_position();
     }
 
     fn cnode_accepts_child(&self, _node: &dyn Node) -> bool {
@@ -1575,9 +1606,8 @@ impl ContainingNode for ContainerNode {
         new_x2: Option<i32>,
         new_y2: Option<i32>,
     ) {
-        let theme = &self.state.theme;
-        let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
+        let th = self.state.theme.sizes.title_height.get();
+        let spacing = self.child_spacing();
         let mut left_outside = false;
         let mut right_outside = false;
         let mut top_outside = false;
@@ -1617,8 +1647,8 @@ impl ContainingNode for ContainerNode {
                 ci = 1;
             }
             let (new_delta, between) = match split {
-                ContainerSplit::Horizontal => (self.abs_x1.get(), bw),
-                ContainerSplit::Vertical => (self.abs_y1.get(), bw + th + 1),
+                ContainerSplit::Horizontal => (self.abs_x1.get(), spacing),
+                ContainerSplit::Vertical => (self.abs_y1.get(), spacing + th + 1),
             };
             let new_i1 = new_i1.map(|v| v - new_delta);
             let new_i2 = new_i2.map(|v| v - new_delta);
@@ -1717,6 +1747,7 @@ impl ToplevelNodeBase for ContainerNode {
     }
 
     fn tl_set_workspace_ext(&self, ws: &Rc<WorkspaceNode>) {
+        self.workspace.set(ws.clone());
         for child in self.children.iter() {
             child.node.clone().tl_set_workspace(ws);
         }
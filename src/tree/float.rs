@@ -450,7 +450,7 @@ impl FloatNode {
             {
                 if let Some(tl) = self.child.get() {
                     drop(cursors);
-                    seat.set_tl_floating(tl, false);
+                    self.state.set_tl_floating(tl, false);
                     return;
                 }
             }
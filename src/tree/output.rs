@@ -24,6 +24,10 @@ use {
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
         },
+        json_ipc::{
+            self,
+            messages::{OutputChange, WorkspaceChange},
+        },
         rect::Rect,
         renderer::Renderer,
         scale::Scale,
@@ -42,7 +46,7 @@ use {
         wire::{JayOutputId, JayScreencastId, ZwlrScreencopyFrameV1Id},
     },
     ahash::AHashMap,
-    jay_config::video::Transform,
+    jay_config::video::{Transform, VrrMode},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -293,6 +297,7 @@ impl OutputNode {
         rd.captured_inactive_workspaces.clear();
         rd.active_workspace = None;
         rd.status = None;
+        let old_binding_mode_tex = rd.binding_mode.take().map(|s| s.tex);
         let mut pos = 0;
         let font = self.state.theme.font.borrow_mut();
         let theme = &self.state.theme;
@@ -315,7 +320,7 @@ impl OutputNode {
             let mut title_width = th;
             'create_texture: {
                 if let Some(ctx) = self.state.render_ctx.get() {
-                    if th == 0 || ws.name.is_empty() {
+                    if th == 0 || ws.name.borrow().is_empty() {
                         break 'create_texture;
                     }
                     let tc = match active_id == Some(ws.id) {
@@ -327,14 +332,18 @@ impl OutputNode {
                         old_tex,
                         Some(texture_height),
                         &font,
-                        &ws.name,
+                        &ws.name.borrow(),
                         tc,
                         false,
                         scale,
                     ) {
                         Ok(t) => t,
                         Err(e) => {
-                            log::error!("Could not render title {}: {}", ws.name, ErrorFmt(e));
+                            log::error!(
+                                "Could not render title {}: {}",
+                                ws.name.borrow(),
+                                ErrorFmt(e)
+                            );
                             break 'create_texture;
                         }
                     };
@@ -415,7 +424,147 @@ impl OutputNode {
                 tex: title,
             });
         }
-        self.state.damage();
+        'set_binding_mode: {
+            let ctx = match self.state.render_ctx.get() {
+                Some(ctx) => ctx,
+                _ => break 'set_binding_mode,
+            };
+            let mut modes = vec![];
+            for seat in self.state.globals.seats.lock().values() {
+                if let Some(mode) = seat.binding_mode() {
+                    if !modes.contains(&mode) {
+                        modes.push(mode);
+                    }
+                }
+            }
+            if modes.is_empty() {
+                break 'set_binding_mode;
+            }
+            let text = modes
+                .iter()
+                .map(|m| m.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let tc = self.state.theme.colors.bar_text.get();
+            let title = match text::render_fitting(
+                &ctx,
+                old_binding_mode_tex,
+                Some(texture_height),
+                &font,
+                &text,
+                tc,
+                false,
+                scale,
+            ) {
+                Ok(t) => t,
+                Err(e) => {
+                    log::error!("Could not render binding mode {}: {}", text, ErrorFmt(e));
+                    break 'set_binding_mode;
+                }
+            };
+            let (mut width, _) = title.texture.size();
+            if let Some(scale) = scale {
+                width = (width as f64 / scale).round() as _;
+            }
+            let right = match &rd.status {
+                Some(status) => status.tex_x - th,
+                None => output_width - 1,
+            };
+            rd.binding_mode = Some(OutputStatus {
+                tex_x: right - width,
+                tex_y: 0,
+                tex: title,
+            });
+        }
+        let pos = self.global.pos.get();
+        'set_window_switcher: {
+            let old = rd.window_switcher.take();
+            if let Some(old) = &old {
+                self.state.damage_rect(old.rect.move_(pos.x1(), pos.y1()));
+            }
+            let mut old_textures = old.into_iter().flat_map(|s| s.titles).map(|t| t.tex);
+            let ctx = match self.state.render_ctx.get() {
+                Some(ctx) => ctx,
+                _ => break 'set_window_switcher,
+            };
+            let seats = self.state.globals.seats.lock();
+            let switcher = seats.values().find_map(|seat| {
+                let switcher = seat.focus_history().switcher().borrow();
+                let switcher = switcher.as_ref()?;
+                if switcher.output.id != self.id {
+                    return None;
+                }
+                let titles: Vec<_> = switcher
+                    .windows
+                    .iter()
+                    .map(|tl| {
+                        let data = tl.tl_data();
+                        let title = data.title.borrow();
+                        match title.is_empty() {
+                            true => data.app_id.borrow().clone(),
+                            false => title.clone(),
+                        }
+                    })
+                    .collect();
+                Some((titles, switcher.selected))
+            });
+            drop(seats);
+            let Some((titles, selected)) = switcher else {
+                break 'set_window_switcher;
+            };
+            if th == 0 {
+                break 'set_window_switcher;
+            }
+            let width = pos.width() / 2;
+            let max_rows = ((pos.height() / th) - 2).max(1) as usize;
+            let rows = titles.len().min(max_rows);
+            let first = (selected + 1).saturating_sub(rows);
+            let height = rows as i32 * th;
+            let x1 = (pos.width() - width) / 2;
+            let y1 = (pos.height() - height) / 2;
+            let mut texture_width = width;
+            if let Some(scale) = scale {
+                texture_width = (width as f64 * scale).round() as _;
+            }
+            let mut switcher = OutputWindowSwitcher {
+                rect: Rect::new_sized(x1, y1, width, height).unwrap(),
+                selected: Rect::new_sized(x1, y1 + (selected - first) as i32 * th, width, th)
+                    .unwrap(),
+                titles: vec![],
+            };
+            for (idx, title) in titles.iter().enumerate().skip(first).take(rows) {
+                let tc = match idx == selected {
+                    true => theme.colors.focused_title_text.get(),
+                    false => theme.colors.unfocused_title_text.get(),
+                };
+                let tex = match text::render(
+                    &ctx,
+                    old_textures.next(),
+                    texture_width,
+                    texture_height,
+                    &font,
+                    title,
+                    tc,
+                    scale,
+                ) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        log::error!("Could not render title {}: {}", title, ErrorFmt(e));
+                        continue;
+                    }
+                };
+                switcher.titles.push(OutputStatus {
+                    tex_x: x1,
+                    tex_y: y1 + (idx - first) as i32 * th,
+                    tex,
+                });
+            }
+            self.state
+                .damage_rect(switcher.rect.move_(pos.x1(), pos.y1()));
+            rd.window_switcher = Some(switcher);
+        }
+        self.state
+            .damage_rect(Rect::new_sized(pos.x1(), pos.y1(), pos.width(), th + 1).unwrap());
     }
 
     pub fn ensure_workspace(self: &Rc<Self>) -> Rc<WorkspaceNode> {
@@ -442,12 +591,13 @@ impl OutputNode {
             }
             collect_kb_foci2(old.clone(), &mut seats);
             if old.is_empty() {
+                json_ipc::workspace_event(&self.state, WorkspaceChange::Empty, &old);
                 for jw in old.jay_workspaces.lock().values() {
                     jw.send_destroyed();
                     jw.workspace.set(None);
                 }
                 old.clear();
-                self.state.workspaces.remove(&old.name);
+                self.state.workspaces.remove(&*old.name.borrow());
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
@@ -461,6 +611,7 @@ impl OutputNode {
         for seat in seats {
             ws.clone().node_do_focus(&seat, Direction::Unspecified);
         }
+        json_ipc::workspace_event(&self.state, WorkspaceChange::Focus, ws);
         true
     }
 
@@ -473,7 +624,7 @@ impl OutputNode {
             container: Default::default(),
             stacked: Default::default(),
             seat_state: Default::default(),
-            name: name.to_string(),
+            name: RefCell::new(name.to_string()),
             output_link: Default::default(),
             visible: Cell::new(false),
             fullscreen: Default::default(),
@@ -485,10 +636,20 @@ impl OutputNode {
             title_texture: Default::default(),
             attention_requests: Default::default(),
             render_highlight: Default::default(),
+            gaps: Cell::new(
+                self.state
+                    .workspace_gaps
+                    .borrow()
+                    .get(name)
+                    .copied()
+                    .unwrap_or_default(),
+            ),
+            minimized: Default::default(),
         });
         ws.update_has_captures();
         *ws.output_link.borrow_mut() = Some(self.workspaces.add_last(ws.clone()));
         self.state.workspaces.set(name.to_string(), ws.clone());
+        json_ipc::workspace_event(&self.state, WorkspaceChange::Init, &ws);
         if self.workspace.is_none() {
             self.show_workspace(&ws);
         }
@@ -539,6 +700,12 @@ impl OutputNode {
         self.change_extents_(&rect);
     }
 
+    pub fn set_vrr_mode(&self, mode: VrrMode) {
+        self.global.persistent.vrr_mode.set(mode);
+        self.global.connector.connector.set_vrr_mode(mode);
+        self.state.output_heads_changed.trigger();
+    }
+
     pub fn update_mode(self: &Rc<Self>, mode: Mode) {
         self.update_mode_and_transform(mode, self.global.persistent.transform.get());
     }
@@ -569,6 +736,10 @@ impl OutputNode {
             self.state.refresh_hardware_cursors();
             self.node_visit_children(&mut SurfaceSendPreferredTransformVisitor);
         }
+
+        if mode != old_mode {
+            json_ipc::output_event(&self.state, OutputChange::Mode, self);
+        }
     }
 
     fn calculate_extents(&self) -> Rect {
@@ -587,6 +758,7 @@ impl OutputNode {
         self.global.pos.set(*rect);
         self.state.root.update_extents();
         self.update_rects();
+        self.state.output_heads_changed.trigger();
         if let Some(ls) = self.lock_surface.get() {
             ls.change_extents(*rect);
         }
@@ -772,6 +944,12 @@ pub struct OutputStatus {
     pub tex: TextTexture,
 }
 
+pub struct OutputWindowSwitcher {
+    pub rect: Rect,
+    pub selected: Rect,
+    pub titles: Vec<OutputStatus>,
+}
+
 #[derive(Copy, Clone)]
 pub struct OutputWorkspaceRenderData {
     pub rect: Rect,
@@ -787,6 +965,8 @@ pub struct OutputRenderData {
     pub captured_inactive_workspaces: Vec<Rect>,
     pub titles: Vec<OutputTitle>,
     pub status: Option<OutputStatus>,
+    pub binding_mode: Option<OutputStatus>,
+    pub window_switcher: Option<OutputWindowSwitcher>,
 }
 
 impl Debug for OutputNode {
@@ -5,10 +5,11 @@ use {
             ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
             jay_screencast::JayScreencast,
-            jay_toplevel::JayToplevel,
+            jay_toplevel::{JayToplevel, APP_ID_SINCE, MINIMIZED_SINCE},
             wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId},
             wl_surface::WlSurface,
         },
+        json_ipc::{self, messages::WindowChange},
         rect::Rect,
         state::State,
         tree::{ContainingNode, Direction, Node, OutputNode, PlaceholderNode, WorkspaceNode},
@@ -16,11 +17,13 @@ use {
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
             hash_map_ext::HashMapExt,
+            linkedlist::LinkedNode,
             numcell::NumCell,
             smallmap::SmallMap,
             threshold_counter::ThresholdCounter,
             toplevel_identifier::{toplevel_identifier, ToplevelIdentifier},
         },
+        window_rules::WindowProperties,
         wire::{ExtForeignToplevelHandleV1Id, JayScreencastId, JayToplevelId},
     },
     std::{
@@ -92,6 +95,13 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
                 .clone_from(&title);
             data.placeholder.tl_title_changed();
         }
+        drop(title);
+        if data.state.mapped_toplevels.contains(&self.node_id()) {
+            if let Some(config) = data.state.config.get() {
+                config.window_title_changed(self.node_id());
+            }
+            json_ipc::window_event(&data.state, WindowChange::Title, self, data);
+        }
     }
 
     fn tl_set_parent(&self, parent: Rc<dyn ContainingNode>) {
@@ -157,6 +167,10 @@ pub trait ToplevelNodeBase: Node {
         let _ = active;
     }
 
+    fn tl_set_minimized(&self, minimized: bool) {
+        let _ = minimized;
+    }
+
     fn tl_on_activate(&self) {
         // nothing
     }
@@ -189,6 +203,10 @@ pub trait ToplevelNodeBase: Node {
     }
 
     fn tl_admits_children(&self) -> bool;
+
+    fn tl_window_properties(&self) -> Option<WindowProperties> {
+        None
+    }
 }
 
 pub struct FullscreenedData {
@@ -196,6 +214,11 @@ pub struct FullscreenedData {
     pub workspace: Rc<WorkspaceNode>,
 }
 
+pub struct MinimizedData {
+    pub workspace: Rc<WorkspaceNode>,
+    pub _link: LinkedNode<Rc<dyn ToplevelNode>>,
+}
+
 pub struct ToplevelData {
     pub self_active: Cell<bool>,
     pub client: Option<Rc<Client>>,
@@ -223,6 +246,11 @@ pub struct ToplevelData {
     pub render_highlight: NumCell<u32>,
     pub jay_toplevels: CopyHashMap<(ClientId, JayToplevelId), Rc<JayToplevel>>,
     pub jay_screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
+    pub window_rules: RefCell<Vec<u64>>,
+    pub opacity: Cell<f32>,
+    pub scratchpad_link: RefCell<Option<LinkedNode<Rc<dyn ToplevelNode>>>>,
+    pub minimized_data: RefCell<Option<MinimizedData>>,
+    pub focus_history: SmallMap<SeatId, LinkedNode<Rc<dyn ToplevelNode>>, 1>,
 }
 
 impl ToplevelData {
@@ -253,6 +281,11 @@ impl ToplevelData {
             render_highlight: Default::default(),
             jay_toplevels: Default::default(),
             jay_screencasts: Default::default(),
+            window_rules: Default::default(),
+            opacity: Cell::new(1.0),
+            scratchpad_link: Default::default(),
+            minimized_data: Default::default(),
+            focus_history: Default::default(),
         }
     }
 
@@ -297,6 +330,18 @@ impl ToplevelData {
             screencast.do_destroy();
         }
         self.identifier.set(toplevel_identifier());
+        self.scratchpad_link.take();
+        if self
+            .state
+            .mapped_toplevels
+            .remove(&node.node_id())
+            .is_some()
+        {
+            if let Some(config) = self.state.config.get() {
+                config.window_closed(node.node_id());
+            }
+            json_ipc::window_event(&self.state, WindowChange::Close, node, self);
+        }
         {
             let mut handles = self.handles.lock();
             for handle in handles.drain_values() {
@@ -310,15 +355,29 @@ impl ToplevelData {
         if let Some(fd) = self.fullscrceen_data.borrow_mut().take() {
             fd.placeholder.tl_destroy();
         }
+        self.minimized_data.take();
         if let Some(parent) = self.parent.take() {
             parent.cnode_remove_child(node);
         }
         self.workspace.take();
         self.seat_state.destroy_node(node);
         self.focus_node.clear();
+        self.focus_history.clear();
     }
 
     pub fn broadcast(&self, toplevel: Rc<dyn ToplevelNode>) {
+        let node_id = toplevel.node_id();
+        if self
+            .state
+            .mapped_toplevels
+            .set(node_id, toplevel.clone())
+            .is_none()
+        {
+            if let Some(config) = self.state.config.get() {
+                config.new_window(node_id);
+            }
+            json_ipc::window_event(&self.state, WindowChange::New, toplevel.tl_as_node(), self);
+        }
         let id = self.identifier.get().to_string();
         let title = self.title.borrow();
         let app_id = self.app_id.borrow();
@@ -354,6 +413,19 @@ impl ToplevelData {
             .set((handle.client.id, handle.id), handle.clone());
     }
 
+    pub fn is_minimized(&self) -> bool {
+        self.minimized_data.borrow().is_some()
+    }
+
+    pub fn send_minimized(&self) {
+        let minimized = self.is_minimized();
+        for jay_tl in self.jay_toplevels.lock().values() {
+            if jay_tl.version >= MINIMIZED_SINCE {
+                jay_tl.send_minimized(minimized);
+            }
+        }
+    }
+
     pub fn set_title(&self, title: &str) {
         *self.title.borrow_mut() = title.to_string();
         for handle in self.handles.lock().values() {
@@ -368,6 +440,11 @@ impl ToplevelData {
             handle.send_app_id(app_id);
             handle.send_done();
         }
+        for jtl in self.jay_toplevels.lock().values() {
+            if jtl.version >= APP_ID_SINCE {
+                jtl.send_app_id(app_id);
+            }
+        }
     }
 
     pub fn set_fullscreen(
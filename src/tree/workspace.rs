@@ -36,6 +36,14 @@ use {
 
 tree_id!(WorkspaceNodeId);
 
+/// Per-workspace overrides of the gap settings of the theme.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct WorkspaceGaps {
+    pub inner: Option<i32>,
+    pub outer: Option<i32>,
+    pub smart: Option<bool>,
+}
+
 pub struct WorkspaceNode {
     pub id: WorkspaceNodeId,
     pub is_dummy: bool,
@@ -44,7 +52,7 @@ pub struct WorkspaceNode {
     pub container: CloneCell<Option<Rc<ContainerNode>>>,
     pub stacked: LinkedList<Rc<dyn StackedNode>>,
     pub seat_state: NodeSeatState,
-    pub name: String,
+    pub name: RefCell<String>,
     pub output_link: RefCell<Option<LinkedNode<Rc<WorkspaceNode>>>>,
     pub visible: Cell<bool>,
     pub fullscreen: CloneCell<Option<Rc<dyn ToplevelNode>>>,
@@ -56,6 +64,8 @@ pub struct WorkspaceNode {
     pub title_texture: Cell<Option<TextTexture>>,
     pub attention_requests: ThresholdCounter,
     pub render_highlight: NumCell<u32>,
+    pub gaps: Cell<WorkspaceGaps>,
+    pub minimized: LinkedList<Rc<dyn ToplevelNode>>,
 }
 
 impl WorkspaceNode {
@@ -64,6 +74,10 @@ impl WorkspaceNode {
         *self.output_link.borrow_mut() = None;
         self.fullscreen.set(None);
         self.jay_workspaces.clear();
+        let minimized: Vec<_> = self.minimized.iter().map(|tl| (*tl).clone()).collect();
+        for tl in minimized {
+            tl.tl_data().minimized_data.take();
+        }
     }
 
     pub fn update_has_captures(&self) {
@@ -112,15 +126,73 @@ impl WorkspaceNode {
             self.discard_child_properties(&*prev);
         }
         self.pull_child_properties(&**container);
-        let pos = self.position.get();
+        struct V;
+        impl NodeVisitorBase for V {
+            fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+                node.on_spaces_changed();
+                node.node_visit_children(self);
+            }
+        }
+        container.clone().node_visit(&mut V);
+        let pos = self.container_position(container);
         container.clone().tl_change_extents(&pos);
         container.tl_set_parent(self.clone());
         container.tl_set_visible(self.container_visible());
         self.container.set(Some(container.clone()));
     }
 
+    pub fn inner_gap(&self) -> i32 {
+        match self.gaps.get().inner {
+            Some(gap) => gap,
+            _ => self.output.get().state.theme.sizes.inner_gap.get(),
+        }
+    }
+
+    pub fn outer_gap(&self) -> i32 {
+        match self.gaps.get().outer {
+            Some(gap) => gap,
+            _ => self.output.get().state.theme.sizes.outer_gap.get(),
+        }
+    }
+
+    pub fn smart_gaps(&self) -> bool {
+        match self.gaps.get().smart {
+            Some(smart) => smart,
+            _ => self.output.get().state.theme.smart_gaps.get(),
+        }
+    }
+
+    fn container_position(&self, container: &ContainerNode) -> Rect {
+        let pos = self.position.get();
+        let mut gap = self.outer_gap();
+        if gap == 0 || (self.smart_gaps() && container.is_single_window()) {
+            return pos;
+        }
+        gap = gap.min(pos.width() / 2).min(pos.height() / 2);
+        Rect::new_sized(
+            pos.x1() + gap,
+            pos.y1() + gap,
+            pos.width() - 2 * gap,
+            pos.height() - 2 * gap,
+        )
+        .unwrap()
+    }
+
+    /// Moves the container to the position determined by the current gap settings.
+    pub fn update_container_position(&self) {
+        if let Some(c) = self.container.get() {
+            let pos = self.container_position(&c);
+            if pos != c.node_absolute_position() {
+                c.tl_change_extents(&pos);
+            }
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.stacked.is_empty() && self.fullscreen.is_none() && self.container.is_none()
+        self.stacked.is_empty()
+            && self.fullscreen.is_none()
+            && self.container.is_none()
+            && self.minimized.is_empty()
     }
 
     pub fn container_visible(&self) -> bool {
@@ -130,7 +202,8 @@ impl WorkspaceNode {
     pub fn change_extents(&self, rect: &Rect) {
         self.position.set(*rect);
         if let Some(c) = self.container.get() {
-            c.tl_change_extents(rect);
+            let pos = self.container_position(&c);
+            c.tl_change_extents(&pos);
         }
     }
 
@@ -259,6 +332,13 @@ impl Node for WorkspaceNode {
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
         if let Some(n) = self.container.get() {
+            let pos = self.position.get();
+            let (x, y) = (pos.x1() + x, pos.y1() + y);
+            let pos = n.node_absolute_position();
+            if !pos.contains(x, y) {
+                return FindTreeResult::Other;
+            }
+            let (x, y) = pos.translate(x, y);
             tree.push(FoundNode {
                 node: n.clone(),
                 x,
@@ -10,6 +10,7 @@ pub mod bufio;
 pub mod cell_ext;
 pub mod clone3;
 pub mod clonecell;
+pub mod color_temperature;
 pub mod copyhashmap;
 pub mod debug_fn;
 pub mod double_click_state;
@@ -55,5 +56,6 @@ pub mod vec_ext;
 pub mod vecdeque_ext;
 pub mod vecset;
 pub mod vecstorage;
+pub mod vrr_mode_ext;
 pub mod windows;
 pub mod xrd;
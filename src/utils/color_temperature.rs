@@ -0,0 +1,34 @@
+pub const NEUTRAL_COLOR_TEMPERATURE: u32 = 6500;
+pub const MIN_COLOR_TEMPERATURE: u32 = 1000;
+pub const MAX_COLOR_TEMPERATURE: u32 = 10000;
+
+/// Returns the factors by which the red, green, and blue channels have to be
+/// multiplied to shift the white point to the given color temperature.
+///
+/// The neutral color temperature maps to `[1.0, 1.0, 1.0]`.
+pub fn white_point(kelvin: u32) -> [f64; 3] {
+    let [r, g, b] = black_body(kelvin);
+    let [nr, ng, nb] = black_body(NEUTRAL_COLOR_TEMPERATURE);
+    [(r / nr).min(1.0), (g / ng).min(1.0), (b / nb).min(1.0)]
+}
+
+// Tanner Helland's approximation of the color of a black body.
+fn black_body(kelvin: u32) -> [f64; 3] {
+    let t = kelvin.clamp(MIN_COLOR_TEMPERATURE, MAX_COLOR_TEMPERATURE) as f64 / 100.0;
+    let (r, g) = if t <= 66.0 {
+        (255.0, 99.4708025861 * t.ln() - 161.1195681661)
+    } else {
+        (
+            329.698727446 * (t - 60.0).powf(-0.1332047592),
+            288.1221695283 * (t - 60.0).powf(-0.0755148492),
+        )
+    };
+    let b = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.5177312231 * (t - 10.0).ln() - 305.0447927307
+    };
+    [r, g, b].map(|v| v.clamp(0.0, 255.0) / 255.0)
+}
@@ -0,0 +1,40 @@
+use jay_config::video::VrrMode;
+
+pub trait VrrModeExt: Sized {
+    fn to_raw(self) -> u32;
+
+    fn from_raw(raw: u32) -> Option<Self>;
+
+    fn to_str(self) -> &'static str;
+}
+
+impl VrrModeExt for VrrMode {
+    fn to_raw(self) -> u32 {
+        match self {
+            VrrMode::Never => 0,
+            VrrMode::Always => 1,
+            VrrMode::FullscreenOnly => 2,
+            VrrMode::FullscreenWithContentTypeGame => 3,
+        }
+    }
+
+    fn from_raw(raw: u32) -> Option<Self> {
+        let mode = match raw {
+            0 => VrrMode::Never,
+            1 => VrrMode::Always,
+            2 => VrrMode::FullscreenOnly,
+            3 => VrrMode::FullscreenWithContentTypeGame,
+            _ => return None,
+        };
+        Some(mode)
+    }
+
+    fn to_str(self) -> &'static str {
+        match self {
+            VrrMode::Never => "never",
+            VrrMode::Always => "always",
+            VrrMode::FullscreenOnly => "fullscreen-only",
+            VrrMode::FullscreenWithContentTypeGame => "fullscreen-with-content-type-game",
+        }
+    }
+}
@@ -238,6 +238,7 @@ const DRM_MODE_PROP_ATOMIC: u32 = 0x80000000;
 
 pub const DRM_CAP_CURSOR_WIDTH: u64 = 0x8;
 pub const DRM_CAP_CURSOR_HEIGHT: u64 = 0x9;
+pub const DRM_CAP_ATOMIC_ASYNC_PAGE_FLIP: u64 = 0x15;
 
 #[repr(C)]
 struct drm_mode_property_enum {
@@ -693,6 +694,15 @@ pub fn mode_getencoder(fd: c::c_int, encoder_id: u32) -> Result<DrmEncoderInfo,
 
 pub const DRM_DISPLAY_MODE_LEN: usize = 32;
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct drm_color_lut {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub reserved: u16,
+}
+
 #[repr(C)]
 pub struct drm_mode_modeinfo {
     pub clock: u32,
@@ -865,6 +875,7 @@ struct drm_mode_atomic {
 const DRM_IOCTL_MODE_ATOMIC: u64 = drm_iowr::<drm_mode_atomic>(0xbc);
 
 pub const DRM_MODE_PAGE_FLIP_EVENT: u32 = 0x01;
+pub const DRM_MODE_PAGE_FLIP_ASYNC: u32 = 0x02;
 pub const DRM_MODE_ATOMIC_TEST_ONLY: u32 = 0x0100;
 pub const DRM_MODE_ATOMIC_NONBLOCK: u32 = 0x0200;
 pub const DRM_MODE_ATOMIC_ALLOW_MODESET: u32 = 0x0400;
@@ -917,9 +928,9 @@ struct drm_mode_create_blob {
 
 const DRM_IOCTL_MODE_CREATEPROPBLOB: u64 = drm_iowr::<drm_mode_create_blob>(0xbd);
 
-pub fn mode_create_blob<T>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
+pub fn mode_create_blob<T: ?Sized>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
     let mut res = drm_mode_create_blob {
-        data: t as *const T as _,
+        data: t as *const T as *const u8 as _,
         length: mem::size_of_val(t) as _,
         blob_id: 0,
     };
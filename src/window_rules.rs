@@ -0,0 +1,306 @@
+use {
+    crate::{
+        ifs::wl_seat::collect_kb_foci,
+        state::State,
+        tree::{Direction, Node, OutputNode, ToplevelNode, WorkspaceNode},
+        utils::numcell::NumCell,
+        workspaces,
+    },
+    jay_config::{_private::ipc::WindowRuleData, window::WindowType},
+    regex::Regex,
+    std::{cell::RefCell, rc::Rc},
+};
+
+/// The properties of a window that window rules can match on.
+pub struct WindowProperties {
+    pub ty: WindowType,
+    pub app_id: String,
+    pub title: String,
+    pub class: Option<String>,
+    pub instance: Option<String>,
+    pub role: Option<String>,
+    pub pid: Option<i32>,
+    pub sandbox_engine: Option<String>,
+}
+
+struct WindowRule {
+    id: u64,
+    data: WindowRuleData,
+    title: Option<Regex>,
+    workspace: Option<String>,
+}
+
+#[derive(Default)]
+pub struct WindowRules {
+    ids: NumCell<u64>,
+    rules: RefCell<Vec<Rc<WindowRule>>>,
+}
+
+/// The combined actions of all rules that matched a window.
+#[derive(Default)]
+struct WindowRuleActions {
+    ids: Vec<u64>,
+    workspace: Option<String>,
+    output: Option<String>,
+    floating: Option<bool>,
+    floating_size: Option<(i32, i32)>,
+    floating_position: Option<(i32, i32)>,
+    fullscreen: Option<bool>,
+    opacity: Option<f32>,
+}
+
+impl WindowRule {
+    fn matches(&self, props: &WindowProperties) -> bool {
+        fn eq(rule: &Option<String>, val: Option<&str>) -> bool {
+            match rule {
+                Some(rule) => val == Some(rule.as_str()),
+                None => true,
+            }
+        }
+        let data = &self.data;
+        if let Some(ty) = data.window_type {
+            if ty != props.ty {
+                return false;
+            }
+        }
+        if let Some(pid) = data.pid {
+            if Some(pid) != props.pid {
+                return false;
+            }
+        }
+        if let Some(title) = &self.title {
+            if !title.is_match(&props.title) {
+                return false;
+            }
+        }
+        eq(&data.app_id, Some(&props.app_id))
+            && eq(&data.class, props.class.as_deref())
+            && eq(&data.instance, props.instance.as_deref())
+            && eq(&data.role, props.role.as_deref())
+            && eq(&data.sandbox_engine, props.sandbox_engine.as_deref())
+    }
+}
+
+impl WindowRules {
+    pub fn add(&self, data: WindowRuleData, workspace: Option<String>) -> Result<(), regex::Error> {
+        let title = match &data.title {
+            Some(title) => Some(Regex::new(title)?),
+            None => None,
+        };
+        self.rules.borrow_mut().push(Rc::new(WindowRule {
+            id: self.ids.fetch_add(1),
+            data,
+            title,
+            workspace,
+        }));
+        Ok(())
+    }
+
+    pub fn clear(&self) {
+        self.rules.borrow_mut().clear();
+    }
+
+    fn evaluate(
+        &self,
+        props: &WindowProperties,
+        filter: impl Fn(&WindowRule) -> bool,
+    ) -> Option<WindowRuleActions> {
+        let mut actions = WindowRuleActions::default();
+        for rule in self.rules.borrow().iter() {
+            if !filter(rule) || !rule.matches(props) {
+                continue;
+            }
+            macro_rules! merge {
+                ($($field:ident,)*) => {
+                    $(
+                        if let Some(v) = &rule.data.$field {
+                            actions.$field = Some(v.clone());
+                        }
+                    )*
+                };
+            }
+            merge! {
+                output,
+                floating,
+                floating_size,
+                floating_position,
+                fullscreen,
+                opacity,
+            }
+            if let Some(ws) = &rule.workspace {
+                actions.workspace = Some(ws.clone());
+            }
+            actions.ids.push(rule.id);
+        }
+        if actions.ids.is_empty() {
+            return None;
+        }
+        Some(actions)
+    }
+}
+
+impl WindowRuleActions {
+    fn floating(&self) -> Option<bool> {
+        if self.floating.is_some() {
+            return self.floating;
+        }
+        if self.floating_size.is_some() || self.floating_position.is_some() {
+            return Some(true);
+        }
+        None
+    }
+
+    fn output(&self, state: &State) -> Option<Rc<OutputNode>> {
+        let name = self.output.as_deref()?;
+        let outputs = state.root.outputs.lock();
+        let output = outputs
+            .values()
+            .find(|o| o.global.connector.name == name)
+            .cloned();
+        if output.is_none() {
+            log::warn!("Window rule refers to output {name} which is not connected");
+        }
+        output
+    }
+
+    fn workspace(&self, state: &State) -> Option<Rc<WorkspaceNode>> {
+        let output = self.output(state);
+        if let Some(name) = &self.workspace {
+            if let Some(ws) = state.workspaces.get(name) {
+                return Some(ws);
+            }
+            let output = output
+                .or_else(|| workspaces::preferred_output(state, name))
+                .or_else(|| {
+                    let output = state.seat_queue.last()?.get_output();
+                    (!output.is_dummy).then_some(output)
+                })
+                .or_else(|| state.root.outputs.lock().values().next().cloned())?;
+            return Some(output.create_workspace(name));
+        }
+        output.map(|o| o.ensure_workspace())
+    }
+}
+
+/// Maps a toplevel according to the window rules.
+///
+/// `map` is used to map the toplevel if no matching rule affects its placement.
+/// `wants_floating` is the floating state used if a matching rule only assigns a workspace.
+pub fn map_toplevel(
+    state: &Rc<State>,
+    tl: Rc<dyn ToplevelNode>,
+    wants_floating: bool,
+    map: impl FnOnce(),
+) {
+    let actions = tl
+        .tl_window_properties()
+        .and_then(|props| state.window_rules.evaluate(&props, |_| true));
+    let data = tl.tl_data();
+    let Some(actions) = actions else {
+        data.window_rules.borrow_mut().clear();
+        map();
+        return;
+    };
+    data.window_rules.borrow_mut().clone_from(&actions.ids);
+    let floating = actions.floating();
+    let ws = actions.workspace(state);
+    if floating.is_none() && ws.is_none() {
+        map();
+    } else {
+        let floating = floating.unwrap_or(wants_floating);
+        let ws = ws.unwrap_or_else(|| state.float_map_ws());
+        place(state, tl.clone(), &ws, floating, &actions);
+    }
+    apply_properties(state, tl, &actions);
+}
+
+/// Applies the rules that have re-evaluation enabled and that have not yet been applied to
+/// the toplevel.
+pub fn reevaluate(state: &Rc<State>, tl: Rc<dyn ToplevelNode>) {
+    let data = tl.tl_data();
+    if data.parent.is_none() {
+        return;
+    }
+    let Some(props) = tl.tl_window_properties() else {
+        return;
+    };
+    let actions = {
+        let applied = data.window_rules.borrow();
+        state
+            .window_rules
+            .evaluate(&props, |r| r.data.reevaluate && !applied.contains(&r.id))
+    };
+    let Some(actions) = actions else {
+        return;
+    };
+    data.window_rules.borrow_mut().extend(&actions.ids);
+    if !data.is_fullscreen.get() {
+        move_toplevel(state, tl.clone(), &actions);
+    }
+    apply_properties(state, tl, &actions);
+}
+
+fn move_toplevel(state: &Rc<State>, tl: Rc<dyn ToplevelNode>, actions: &WindowRuleActions) {
+    let data = tl.tl_data();
+    let floating = actions.floating();
+    let ws = actions.workspace(state);
+    if floating.is_none() && ws.is_none() {
+        return;
+    }
+    let (Some(old_ws), Some(parent)) = (data.workspace.get(), data.parent.get()) else {
+        return;
+    };
+    let ws = ws.unwrap_or_else(|| old_ws.clone());
+    let floating = floating.unwrap_or(data.is_floating.get());
+    if ws.id == old_ws.id
+        && floating == data.is_floating.get()
+        && actions.floating_size.is_none()
+        && actions.floating_position.is_none()
+    {
+        return;
+    }
+    let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+    parent.cnode_remove_child2(tl.tl_as_node(), true);
+    if !ws.visible.get() {
+        for focus in kb_foci {
+            old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
+        }
+    }
+    place(state, tl, &ws, floating, actions);
+}
+
+fn place(
+    state: &Rc<State>,
+    tl: Rc<dyn ToplevelNode>,
+    ws: &Rc<WorkspaceNode>,
+    floating: bool,
+    actions: &WindowRuleActions,
+) {
+    if floating {
+        let (width, height) = actions
+            .floating_size
+            .unwrap_or_else(|| tl.tl_data().float_size(ws));
+        let pos = actions.floating_position.map(|(x, y)| {
+            let output = ws.output.get().global.pos.get();
+            (output.x1() + x, output.y1() + y)
+        });
+        state.map_floating(tl, width, height, ws, pos);
+    } else {
+        state.map_tiled_on(tl.clone(), ws);
+        if tl.node_visible() {
+            if let Some(seat) = state.seat_queue.last() {
+                tl.node_do_focus(&seat, Direction::Unspecified);
+            }
+        }
+    }
+}
+
+fn apply_properties(state: &State, tl: Rc<dyn ToplevelNode>, actions: &WindowRuleActions) {
+    if let Some(opacity) = actions.opacity {
+        tl.tl_data().opacity.set(opacity.clamp(0.0, 1.0));
+        state.damage();
+    }
+    if let Some(fullscreen) = actions.fullscreen {
+        tl.tl_set_fullscreen(fullscreen);
+    }
+}
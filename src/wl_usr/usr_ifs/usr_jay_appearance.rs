@@ -0,0 +1,68 @@
+use {
+    crate::{
+        theme::Color,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{jay_appearance::*, JayAppearanceId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct UsrJayAppearance {
+    pub id: JayAppearanceId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayAppearanceOwner>>>,
+    pub color_scheme: Cell<u32>,
+    pub accent_color: Cell<Color>,
+}
+
+pub trait UsrJayAppearanceOwner {
+    fn done(&self, color_scheme: u32, accent_color: Color) {
+        let _ = color_scheme;
+        let _ = accent_color;
+    }
+}
+
+impl UsrJayAppearance {
+    fn color_scheme(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: ColorScheme = self.con.parse(self, parser)?;
+        self.color_scheme.set(ev.scheme);
+        Ok(())
+    }
+
+    fn accent_color(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: AccentColor = self.con.parse(self, parser)?;
+        self.accent_color
+            .set(Color::from_u32_rgba_premultiplied(ev.r, ev.g, ev.b, ev.a));
+        Ok(())
+    }
+
+    fn done(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let _ev: Done = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.done(self.color_scheme.get(), self.accent_color.get());
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    UsrJayAppearance, JayAppearance;
+
+    COLOR_SCHEME => color_scheme,
+    ACCENT_COLOR => accent_color,
+    DONE => done,
+}
+
+impl UsrObject for UsrJayAppearance {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.set(None);
+    }
+}
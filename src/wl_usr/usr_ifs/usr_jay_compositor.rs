@@ -1,6 +1,7 @@
 use {
     crate::{
         ifs::jay_compositor::Cap,
+        theme::Color,
         utils::{
             buffd::{MsgParser, MsgParserError},
             clonecell::CloneCell,
@@ -8,10 +9,13 @@ use {
         wire::{jay_compositor::*, JayCompositorId},
         wl_usr::{
             usr_ifs::{
-                usr_jay_output::UsrJayOutput, usr_jay_pointer::UsrJayPointer,
-                usr_jay_render_ctx::UsrJayRenderCtx, usr_jay_screencast::UsrJayScreencast,
+                usr_jay_appearance::UsrJayAppearance,
+                usr_jay_global_shortcuts::UsrJayGlobalShortcuts,
+                usr_jay_input_injector::UsrJayInputInjector, usr_jay_output::UsrJayOutput,
+                usr_jay_pointer::UsrJayPointer, usr_jay_render_ctx::UsrJayRenderCtx,
+                usr_jay_screencast::UsrJayScreencast, usr_jay_screenshot::UsrJayScreenshot,
                 usr_jay_select_toplevel::UsrJaySelectToplevel,
-                usr_jay_select_workspace::UsrJaySelectWorkspace,
+                usr_jay_select_workspace::UsrJaySelectWorkspace, usr_jay_toplevel::UsrJayToplevel,
                 usr_jay_workspace_watcher::UsrJayWorkspaceWatcher, usr_wl_output::UsrWlOutput,
                 usr_wl_seat::UsrWlSeat,
             },
@@ -33,6 +37,12 @@ pub struct UsrJayCompositor {
 pub struct UsrJayCompositorCaps {
     pub window_capture: Cell<bool>,
     pub select_workspace: Cell<bool>,
+    pub cursor_modes: Cell<bool>,
+    pub find_targets: Cell<bool>,
+    pub target_screenshots: Cell<bool>,
+    pub input_injection: Cell<bool>,
+    pub global_shortcuts: Cell<bool>,
+    pub appearance: Cell<bool>,
 }
 
 pub trait UsrJayCompositorOwner {
@@ -151,6 +161,132 @@ impl UsrJayCompositor {
         sc
     }
 
+    pub fn find_toplevel(&self, app_id: &str) -> Rc<UsrJaySelectToplevel> {
+        let sc = Rc::new(UsrJaySelectToplevel {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+        });
+        self.con.request(FindToplevel {
+            self_id: self.id,
+            id: sc.id,
+            app_id,
+        });
+        self.con.add_object(sc.clone());
+        sc
+    }
+
+    pub fn find_workspace(&self, name: &str) -> Rc<UsrJaySelectWorkspace> {
+        let sc = Rc::new(UsrJaySelectWorkspace {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+        });
+        self.con.request(FindWorkspace {
+            self_id: self.id,
+            id: sc.id,
+            name,
+        });
+        self.con.add_object(sc.clone());
+        sc
+    }
+
+    fn create_screenshot(&self) -> Rc<UsrJayScreenshot> {
+        let ss = Rc::new(UsrJayScreenshot {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+        });
+        self.con.add_object(ss.clone());
+        ss
+    }
+
+    pub fn take_screenshot(&self, include_cursor: bool) -> Rc<UsrJayScreenshot> {
+        let ss = self.create_screenshot();
+        self.con.request(TakeScreenshot2 {
+            self_id: self.id,
+            id: ss.id,
+            include_cursor: include_cursor as _,
+        });
+        ss
+    }
+
+    pub fn take_output_screenshot(
+        &self,
+        output: &UsrJayOutput,
+        include_cursor: bool,
+    ) -> Rc<UsrJayScreenshot> {
+        let ss = self.create_screenshot();
+        self.con.request(TakeOutputScreenshot {
+            self_id: self.id,
+            id: ss.id,
+            output: output.id,
+            include_cursor: include_cursor as _,
+        });
+        ss
+    }
+
+    pub fn take_toplevel_screenshot(
+        &self,
+        toplevel: &UsrJayToplevel,
+        include_cursor: bool,
+    ) -> Rc<UsrJayScreenshot> {
+        let ss = self.create_screenshot();
+        self.con.request(TakeToplevelScreenshot {
+            self_id: self.id,
+            id: ss.id,
+            toplevel: toplevel.id,
+            include_cursor: include_cursor as _,
+        });
+        ss
+    }
+
+    pub fn create_input_injector(&self, name: &str, capabilities: u32) -> Rc<UsrJayInputInjector> {
+        let ii = Rc::new(UsrJayInputInjector {
+            id: self.con.id(),
+            con: self.con.clone(),
+        });
+        self.con.request(CreateInputInjector {
+            self_id: self.id,
+            id: ii.id,
+            name,
+            capabilities,
+        });
+        self.con.add_object(ii.clone());
+        ii
+    }
+
+    pub fn create_global_shortcuts(&self, seat: &UsrWlSeat) -> Rc<UsrJayGlobalShortcuts> {
+        let gs = Rc::new(UsrJayGlobalShortcuts {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+        });
+        self.con.request(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: gs.id,
+            seat: seat.id,
+        });
+        self.con.add_object(gs.clone());
+        gs
+    }
+
+    pub fn watch_appearance(&self) -> Rc<UsrJayAppearance> {
+        let ja = Rc::new(UsrJayAppearance {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            color_scheme: Default::default(),
+            accent_color: Cell::new(Color::SOLID_BLACK),
+        });
+        self.con.request(WatchAppearance {
+            self_id: self.id,
+            id: ja.id,
+        });
+        self.con.add_object(ja.clone());
+        ja
+    }
+
     fn client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
         let ev: ClientId = self.con.parse(self, parser)?;
         if let Some(owner) = self.owner.get() {
@@ -174,6 +310,12 @@ impl UsrJayCompositor {
                 Cap::NONE => {}
                 Cap::WINDOW_CAPTURE => self.caps.window_capture.set(true),
                 Cap::SELECT_WORKSPACE => self.caps.select_workspace.set(true),
+                Cap::CURSOR_MODES => self.caps.cursor_modes.set(true),
+                Cap::FIND_TARGETS => self.caps.find_targets.set(true),
+                Cap::TARGET_SCREENSHOTS => self.caps.target_screenshots.set(true),
+                Cap::INPUT_INJECTION => self.caps.input_injection.set(true),
+                Cap::GLOBAL_SHORTCUTS => self.caps.global_shortcuts.set(true),
+                Cap::APPEARANCE => self.caps.appearance.set(true),
                 _ => {}
             }
         }
@@ -0,0 +1,99 @@
+use {
+    crate::{
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::rc::Rc,
+};
+
+pub struct UsrJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayGlobalShortcutsOwner>>>,
+}
+
+pub trait UsrJayGlobalShortcutsOwner {
+    fn activated(&self, shortcut: u32, time_usec: u64) {
+        let _ = shortcut;
+        let _ = time_usec;
+    }
+
+    fn deactivated(&self, shortcut: u32, time_usec: u64) {
+        let _ = shortcut;
+        let _ = time_usec;
+    }
+
+    fn recorded(&self, mods: u32, keysym: u32) {
+        let _ = mods;
+        let _ = keysym;
+    }
+}
+
+impl UsrJayGlobalShortcuts {
+    pub fn bind(&self, shortcut: u32, mods: u32, keysym: u32) {
+        self.con.request(Bind {
+            self_id: self.id,
+            shortcut,
+            mods,
+            keysym,
+        });
+    }
+
+    #[allow(dead_code)]
+    pub fn unbind(&self, shortcut: u32) {
+        self.con.request(Unbind {
+            self_id: self.id,
+            shortcut,
+        });
+    }
+
+    pub fn record(&self) {
+        self.con.request(Record { self_id: self.id });
+    }
+
+    fn activated(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Activated = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.activated(ev.shortcut, ev.time_usec);
+        }
+        Ok(())
+    }
+
+    fn deactivated(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Deactivated = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.deactivated(ev.shortcut, ev.time_usec);
+        }
+        Ok(())
+    }
+
+    fn recorded(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Recorded = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.recorded(ev.mods, ev.keysym);
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    UsrJayGlobalShortcuts, JayGlobalShortcuts;
+
+    ACTIVATED => activated,
+    DEACTIVATED => deactivated,
+    RECORDED => recorded,
+}
+
+impl UsrObject for UsrJayGlobalShortcuts {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -0,0 +1,110 @@
+use {
+    crate::{
+        fixed::Fixed,
+        wire::{jay_input_injector::*, JayInputInjectorId},
+        wl_usr::{usr_ifs::usr_jay_output::UsrJayOutput, usr_object::UsrObject, UsrCon},
+    },
+    std::rc::Rc,
+};
+
+pub struct UsrJayInputInjector {
+    pub id: JayInputInjectorId,
+    pub con: Rc<UsrCon>,
+}
+
+impl UsrJayInputInjector {
+    pub fn motion(&self, dx: f64, dy: f64) {
+        self.con.request(Motion {
+            self_id: self.id,
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+        });
+    }
+
+    pub fn motion_absolute(&self, output: &UsrJayOutput, x: f64, y: f64) {
+        self.con.request(MotionAbsolute {
+            self_id: self.id,
+            output: output.id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+    }
+
+    pub fn button(&self, button: u32, state: u32) {
+        self.con.request(Button {
+            self_id: self.id,
+            button,
+            state,
+        });
+    }
+
+    pub fn scroll(&self, dx: f64, dy: f64, stop: bool) {
+        self.con.request(Scroll {
+            self_id: self.id,
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+            stop: stop as _,
+        });
+    }
+
+    pub fn scroll_discrete(&self, axis: u32, steps: i32) {
+        self.con.request(ScrollDiscrete {
+            self_id: self.id,
+            axis,
+            steps,
+        });
+    }
+
+    pub fn key(&self, key: u32, state: u32) {
+        self.con.request(Key {
+            self_id: self.id,
+            key,
+            state,
+        });
+    }
+
+    pub fn keysym(&self, keysym: u32, state: u32) {
+        self.con.request(Keysym {
+            self_id: self.id,
+            keysym,
+            state,
+        });
+    }
+
+    pub fn touch_down(&self, output: &UsrJayOutput, id: i32, x: f64, y: f64) {
+        self.con.request(TouchDown {
+            self_id: self.id,
+            output: output.id,
+            id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+    }
+
+    pub fn touch_motion(&self, output: &UsrJayOutput, id: i32, x: f64, y: f64) {
+        self.con.request(TouchMotion {
+            self_id: self.id,
+            output: output.id,
+            id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+    }
+
+    pub fn touch_up(&self, id: i32) {
+        self.con.request(TouchUp {
+            self_id: self.id,
+            id,
+        });
+    }
+}
+
+usr_object_base! {
+    UsrJayInputInjector, JayInputInjector;
+}
+
+impl UsrObject for UsrJayInputInjector {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
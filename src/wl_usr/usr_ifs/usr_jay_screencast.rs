@@ -56,6 +56,14 @@ pub trait UsrJayScreencastOwner {
     fn config(&self, config: UsrJayScreencastServerConfig) {
         let _ = config;
     }
+
+    fn cursor_image(&self, ev: CursorImage) {
+        let _ = ev;
+    }
+
+    fn cursor_position(&self, ev: &CursorPosition) {
+        let _ = ev;
+    }
 }
 
 impl UsrJayScreencast {
@@ -107,6 +115,13 @@ impl UsrJayScreencast {
         });
     }
 
+    pub fn set_cursor_mode(&self, mode: u32) {
+        self.con.request(SetCursorMode {
+            self_id: self.id,
+            mode,
+        });
+    }
+
     pub fn configure(&self) {
         self.con.request(Configure { self_id: self.id });
     }
@@ -181,6 +196,22 @@ impl UsrJayScreencast {
         Ok(())
     }
 
+    fn cursor_image(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: CursorImage = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.cursor_image(ev);
+        }
+        Ok(())
+    }
+
+    fn cursor_position(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: CursorPosition = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.cursor_position(&ev);
+        }
+        Ok(())
+    }
+
     fn config_output(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
         let ev: ConfigOutput = self.con.parse(self, parser)?;
         self.pending_config.borrow_mut().output = Some(ev.linear_id);
@@ -242,6 +273,8 @@ usr_object_base! {
     CONFIG_USE_LINEAR_BUFFERS => config_use_linear_buffers,
     CONFIG_RUNNING => config_running,
     CONFIG_DONE => config_done,
+    CURSOR_IMAGE => cursor_image,
+    CURSOR_POSITION => cursor_position,
 }
 
 impl UsrObject for UsrJayScreencast {
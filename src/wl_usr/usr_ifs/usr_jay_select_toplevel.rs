@@ -31,6 +31,7 @@ impl UsrJaySelectToplevel {
                 id: ev.id,
                 con: self.con.clone(),
                 owner: Default::default(),
+                app_id: Default::default(),
             });
             self.con.add_object(tl.clone());
             Some(tl)
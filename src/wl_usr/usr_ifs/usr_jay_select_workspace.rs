@@ -37,6 +37,7 @@ impl UsrJaySelectWorkspace {
             id: ev.id,
             con: self.con.clone(),
             owner: Default::default(),
+            name: Default::default(),
         });
         self.con.add_object(tl.clone());
         match self.owner.get() {
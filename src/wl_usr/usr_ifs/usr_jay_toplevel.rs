@@ -7,17 +7,22 @@ use {
         wire::{jay_toplevel::*, JayToplevelId},
         wl_usr::{usr_object::UsrObject, UsrCon},
     },
-    std::rc::Rc,
+    std::{cell::RefCell, rc::Rc},
 };
 
 pub struct UsrJayToplevel {
     pub id: JayToplevelId,
     pub con: Rc<UsrCon>,
     pub owner: CloneCell<Option<Rc<dyn UsrJayToplevelOwner>>>,
+    pub app_id: RefCell<String>,
 }
 
 pub trait UsrJayToplevelOwner {
     fn destroyed(&self) {}
+
+    fn minimized(&self, minimized: bool) {
+        let _ = minimized;
+    }
 }
 
 impl UsrJayToplevel {
@@ -28,12 +33,28 @@ impl UsrJayToplevel {
         }
         Ok(())
     }
+
+    fn minimized(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Minimized = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.minimized(ev.minimized != 0);
+        }
+        Ok(())
+    }
+
+    fn app_id(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: AppId = self.con.parse(self, parser)?;
+        *self.app_id.borrow_mut() = ev.app_id.to_string();
+        Ok(())
+    }
 }
 
 usr_object_base! {
     UsrJayToplevel, JayToplevel;
 
     DESTROYED => destroyed,
+    MINIMIZED => minimized,
+    APP_ID => app_id,
 }
 
 impl UsrObject for UsrJayToplevel {
@@ -7,13 +7,14 @@ use {
         wire::{jay_workspace::*, JayWorkspaceId},
         wl_usr::{usr_object::UsrObject, UsrCon},
     },
-    std::rc::Rc,
+    std::{cell::RefCell, rc::Rc},
 };
 
 pub struct UsrJayWorkspace {
     pub id: JayWorkspaceId,
     pub con: Rc<UsrCon>,
     pub owner: CloneCell<Option<Rc<dyn UsrJayWorkspaceOwner>>>,
+    pub name: RefCell<String>,
 }
 
 pub trait UsrJayWorkspaceOwner {
@@ -49,6 +50,7 @@ impl UsrJayWorkspace {
 
     fn name(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
         let ev: Name = self.con.parse(self, parser)?;
+        *self.name.borrow_mut() = ev.name.to_string();
         if let Some(owner) = self.owner.get() {
             owner.name(&ev);
         }
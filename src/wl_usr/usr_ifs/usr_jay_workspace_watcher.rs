@@ -30,6 +30,7 @@ impl UsrJayWorkspaceWatcher {
             id: ev.id,
             con: self.con.clone(),
             owner: Default::default(),
+            name: Default::default(),
         });
         self.con.add_object(jw.clone());
         if let Some(owner) = self.owner.get() {
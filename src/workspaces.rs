@@ -0,0 +1,162 @@
+use {
+    crate::{
+        backend::ConnectorId,
+        ifs::wl_seat::WlSeatGlobal,
+        json_ipc::{self, messages::WorkspaceChange},
+        state::State,
+        tree::{move_ws_to_output, OutputNode, WorkspaceNode, WsMoveConfig},
+    },
+    std::{cmp::Ordering, mem, ops::Deref, rc::Rc},
+};
+
+/// Compares the names of two workspaces.
+///
+/// Names that are numbers are ordered numerically and before all other names.
+pub fn cmp_names(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(l), Ok(r)) => l.cmp(&r).then_with(|| a.cmp(b)),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Shows the workspace that follows or precedes the workspace shown on the output that
+/// contains the cursor.
+///
+/// Workspaces are ordered by their names. If `global` is false, only the workspaces of the
+/// output are considered. Otherwise the workspaces of all outputs are considered. The
+/// first and last workspaces wrap around.
+pub fn show_adjacent_workspace(state: &State, seat: &Rc<WlSeatGlobal>, next: bool, global: bool) {
+    let output = seat.get_output();
+    if output.is_dummy {
+        return;
+    }
+    let mut workspaces: Vec<_> = match global {
+        true => state
+            .workspaces
+            .lock()
+            .values()
+            .filter(|ws| !ws.output.get().is_dummy)
+            .cloned()
+            .collect(),
+        false => output
+            .workspaces
+            .iter()
+            .map(|ws| ws.deref().clone())
+            .collect(),
+    };
+    if workspaces.is_empty() {
+        return;
+    }
+    workspaces.sort_by(|a, b| cmp_names(&a.name.borrow(), &b.name.borrow()));
+    let len = workspaces.len();
+    let current = output
+        .workspace
+        .get()
+        .and_then(|c| workspaces.iter().position(|ws| ws.id == c.id));
+    let idx = match (current, next) {
+        (Some(idx), true) => (idx + 1) % len,
+        (Some(idx), false) => (idx + len - 1) % len,
+        (None, true) => 0,
+        (None, false) => len - 1,
+    };
+    let name = workspaces[idx].name.borrow().clone();
+    state.show_workspace(seat, &name);
+}
+
+/// Shows the workspace that was shown before the current workspace of the seat.
+///
+/// The workspace is re-created if it has been destroyed in the meantime.
+pub fn show_previous_workspace(state: &State, seat: &Rc<WlSeatGlobal>) {
+    if let Some(name) = seat.previous_workspace() {
+        state.show_workspace(seat, &name);
+    }
+}
+
+/// Renames the workspace.
+///
+/// Returns `false` if another workspace with the new name already exists.
+pub fn rename(state: &State, ws: &Rc<WorkspaceNode>, name: &str) -> bool {
+    if ws.is_dummy || *ws.name.borrow() == name {
+        return true;
+    }
+    if state.workspaces.contains(name) {
+        return false;
+    }
+    let old = mem::replace(&mut *ws.name.borrow_mut(), name.to_string());
+    state.workspaces.remove(&old);
+    state.workspaces.set(name.to_string(), ws.clone());
+    let gaps = state.workspace_gaps.borrow().get(name).copied();
+    ws.gaps.set(gaps.unwrap_or_default());
+    for jw in ws.jay_workspaces.lock().values() {
+        jw.send_name(ws);
+    }
+    ws.flush_jay_workspaces();
+    for seat in state.globals.seats.lock().values() {
+        if seat.previous_workspace().as_deref() == Some(&old) {
+            seat.set_previous_workspace(Some(Rc::new(name.to_string())));
+        }
+    }
+    ws.output.get().schedule_update_render_data();
+    state.tree_changed();
+    json_ipc::workspace_event(state, WorkspaceChange::Rename, ws);
+    true
+}
+
+/// Returns the connector that workspaces with this name should be shown on.
+pub fn preferred_connector(state: &State, name: &str) -> Option<ConnectorId> {
+    state.workspace_connectors.borrow().get(name).copied()
+}
+
+/// Returns the connected output that workspaces with this name should be created on.
+pub fn preferred_output(state: &State, name: &str) -> Option<Rc<OutputNode>> {
+    state.root.outputs.get(&preferred_connector(state, name)?)
+}
+
+/// Sets the connector that workspaces with this name should be shown on.
+///
+/// If the workspace exists and the output of the connector is connected, the workspace
+/// is moved to that output immediately.
+pub fn set_preferred_connector(state: &State, name: &str, connector: Option<ConnectorId>) {
+    let Some(connector) = connector else {
+        state.workspace_connectors.borrow_mut().remove(name);
+        return;
+    };
+    state
+        .workspace_connectors
+        .borrow_mut()
+        .insert(name.to_string(), connector);
+    let (Some(ws), Some(output)) = (
+        state.workspaces.get(name),
+        state.root.outputs.get(&connector),
+    ) else {
+        return;
+    };
+    move_to_output(state, &ws, &output);
+}
+
+/// Moves the workspace to the output.
+///
+/// The output becomes the desired output of the workspace.
+pub fn move_to_output(state: &State, ws: &Rc<WorkspaceNode>, output: &Rc<OutputNode>) {
+    if ws.is_dummy || output.is_dummy {
+        return;
+    }
+    if ws.output.get().id == output.id {
+        return;
+    }
+    let link = match &*ws.output_link.borrow() {
+        None => return,
+        Some(l) => l.to_ref(),
+    };
+    let config = WsMoveConfig {
+        make_visible_if_empty: true,
+        source_is_destroyed: false,
+    };
+    move_ws_to_output(&link, output, config);
+    ws.desired_output.set(output.global.output_id.clone());
+    state.tree_changed();
+    state.damage();
+    json_ipc::workspace_event(state, WorkspaceChange::Move, ws);
+}
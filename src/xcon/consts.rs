@@ -6,6 +6,7 @@ pub const INPUT_DEVICE_ALL: u16 = 0;
 pub const INPUT_DEVICE_ALL_MASTER: u16 = 1;
 
 pub const WINDOW_CLASS_INPUT_OUTPUT: u16 = 1;
+pub const WINDOW_CLASS_INPUT_ONLY: u16 = 2;
 
 pub const ATOM_NONE: u32 = 0;
 pub const ATOM_ANY: u32 = 0;
@@ -13,7 +13,7 @@ use {
     isnt::std_1::primitive::IsntConstPtrExt,
     std::{
         cell::{Ref, RefCell},
-        ffi::CStr,
+        ffi::{CStr, CString},
         io::Write,
         ops::Deref,
         ptr,
@@ -96,6 +96,8 @@ extern "C" {
         level: xkb_level_index_t,
         syms_out: *mut *const xkb_keysym_t,
     ) -> c::c_int;
+    fn xkb_keysym_from_name(name: *const c::c_char, flags: xkb_keysym_flags) -> xkb_keysym_t;
+    fn xkb_keysym_get_name(keysym: xkb_keysym_t, buffer: *mut c::c_char, size: usize) -> c::c_int;
     fn xkb_state_unref(state: *mut xkb_state);
     fn xkb_state_new(keymap: *mut xkb_keymap) -> *mut xkb_state;
     #[allow(dead_code)]
@@ -119,6 +121,30 @@ extern "C" {
     ) -> xkb_state_component;
 }
 
+/// Looks up a keysym by its name.
+///
+/// Exact matches are preferred over case-insensitive matches.
+pub fn keysym_from_name(name: &str) -> Option<xkb_keysym_t> {
+    let name = CString::new(name).ok()?;
+    for flags in [XKB_KEYSYM_NO_FLAGS, XKB_KEYSYM_CASE_INSENSITIVE] {
+        let sym = unsafe { xkb_keysym_from_name(name.as_ptr(), flags.raw() as _) };
+        if sym != 0 {
+            return Some(sym);
+        }
+    }
+    None
+}
+
+pub fn keysym_name(sym: xkb_keysym_t) -> String {
+    let mut buf = [0u8; 64];
+    let len = unsafe { xkb_keysym_get_name(sym, buf.as_mut_ptr().cast(), buf.len()) };
+    if len < 0 {
+        return format!("{:#x}", sym);
+    }
+    let len = (len as usize).min(buf.len() - 1);
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
 pub struct XkbContext {
     context: *mut xkb_context,
     ids: KeymapIds,
@@ -401,6 +427,34 @@ impl XkbState {
             }
         }
     }
+
+    /// Finds a key that produces the keysym in the current group.
+    ///
+    /// Returns the evdev keycode and whether shift has to be held.
+    pub fn find_keysym(&self, sym: xkb_keysym_t) -> Option<(u32, bool)> {
+        for level in 0..2 {
+            for key in 8..256 {
+                let mut res = ptr::null();
+                let syms = unsafe {
+                    let num = xkb_keymap_key_get_syms_by_level(
+                        self.map.keymap,
+                        key,
+                        self.kb_state.mods.group,
+                        level,
+                        &mut res,
+                    );
+                    if num <= 0 {
+                        continue;
+                    }
+                    std::slice::from_raw_parts(res, num as usize)
+                };
+                if syms.contains(&sym) {
+                    return Some((key - 8, level == 1));
+                }
+            }
+        }
+        None
+    }
 }
 
 impl Drop for XkbState {
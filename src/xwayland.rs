@@ -5,11 +5,15 @@ use {
     crate::{
         client::{ClientCaps, ClientError},
         compositor::DISPLAY,
+        fixed::Fixed,
         forker::{ForkerError, ForkerProxy},
         ifs::{
             ipc::{x_data_offer::XDataOffer, DataOfferId, DataSourceId, IpcLocation},
             wl_seat::SeatId,
-            wl_surface::x_surface::xwindow::{Xwindow, XwindowData},
+            wl_surface::{
+                x_surface::xwindow::{Xwindow, XwindowData},
+                WlSurface,
+            },
         },
         io_uring::IoUringError,
         state::State,
@@ -82,6 +86,8 @@ enum XWaylandError {
     CreateSelectionWindow(#[source] XconError),
     #[error("Could not watch selection changes")]
     WatchSelection(#[source] XconError),
+    #[error("Could not create the drag-and-drop window")]
+    CreateDndWindow(#[source] XconError),
     #[error("Could not enable the xfixes extension")]
     XfixesQueryVersion(#[source] XconError),
 }
@@ -173,12 +179,14 @@ async fn run(
         pid,
         ClientCaps::all(),
         ClientCaps::all(),
+        None,
         true,
     );
     let client = match client {
         Ok(c) => c,
         Err(e) => return Err(XWaylandError::SpawnClient(e)),
     };
+    client.wire_scale.set(state.xwayland.wire_scale.get());
     state.ring.readable(&Rc::new(dfdread)).await?;
     state.xwayland.queue.clear();
     {
@@ -231,8 +239,10 @@ pub enum XWaylandEvent {
     Activate(Rc<XwindowData>),
     ActivateRoot,
     Close(Rc<XwindowData>),
+    SetMinimized(Rc<XwindowData>, bool),
     #[allow(dead_code)]
     SeatChanged,
+    WireScaleChanged,
 
     IpcCancelSource {
         location: IpcLocation,
@@ -262,4 +272,35 @@ pub enum XWaylandEvent {
         offer: DataOfferId,
         mime_type: String,
     },
+
+    DndEnter {
+        seat: SeatId,
+        offer: DataOfferId,
+        surface: WlSurfaceId,
+        x: Fixed,
+        y: Fixed,
+    },
+    DndMotion {
+        seat: SeatId,
+        x: Fixed,
+        y: Fixed,
+    },
+    DndLeave {
+        seat: SeatId,
+    },
+    DndDrop {
+        seat: SeatId,
+    },
+    DndStatus {
+        seat: SeatId,
+        source: DataSourceId,
+    },
+    DndFinished {
+        seat: SeatId,
+        source: DataSourceId,
+    },
+    DndProxyTarget {
+        seat: SeatId,
+        surface: Option<Rc<WlSurface>>,
+    },
 }
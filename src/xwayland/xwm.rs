@@ -4,10 +4,12 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         client::Client,
+        fixed::Fixed,
         ifs::{
             ipc::{
                 add_data_source_mime_type, destroy_data_device, destroy_data_offer,
                 destroy_data_source, receive_data_offer,
+                wl_data_device_manager::{DND_ASK, DND_COPY, DND_MOVE, DND_NONE},
                 x_data_device::{XClipboardIpc, XIpc, XIpcDevice, XPrimarySelectionIpc},
                 x_data_offer::XDataOffer,
                 x_data_source::XDataSource,
@@ -21,6 +23,7 @@ use {
             },
         },
         io_uring::{IoUring, IoUringError},
+        minimize,
         rect::Rect,
         state::State,
         time::Time,
@@ -30,6 +33,7 @@ use {
             copyhashmap::CopyHashMap, errorfmt::ErrorFmt, hash_map_ext::HashMapExt,
             linkedlist::LinkedList, numcell::NumCell, oserror::OsError, rc_eq::rc_eq,
         },
+        window_rules,
         wire::WlSurfaceId,
         wire_xcon::{
             ChangeProperty, ChangeWindowAttributes, ClientMessage, CompositeRedirectSubwindows,
@@ -38,14 +42,15 @@ use {
             Extension, FocusIn, GetAtomName, GetGeometry, InternAtom, KillClient, MapNotify,
             MapRequest, MapWindow, PropertyNotify, ResClientIdSpec, ResQueryClientIds,
             SelectSelectionInput, SelectionNotify, SelectionRequest, SetInputFocus,
-            SetSelectionOwner, UnmapNotify, XfixesQueryVersion, XfixesSelectionNotify,
+            SetSelectionOwner, UnmapNotify, UnmapWindow, XfixesQueryVersion, XfixesSelectionNotify,
         },
         xcon::{
             consts::{
-                ATOM_ATOM, ATOM_NONE, ATOM_STRING, ATOM_WINDOW, ATOM_WM_CLASS, ATOM_WM_NAME,
-                ATOM_WM_SIZE_HINTS, ATOM_WM_TRANSIENT_FOR, COMPOSITE_REDIRECT_MANUAL,
-                CONFIG_WINDOW_HEIGHT, CONFIG_WINDOW_WIDTH, CONFIG_WINDOW_X, CONFIG_WINDOW_Y,
-                EVENT_MASK_FOCUS_CHANGE, EVENT_MASK_PROPERTY_CHANGE,
+                ATOM_ATOM, ATOM_NONE, ATOM_RESOURCE_MANAGER, ATOM_STRING, ATOM_WINDOW,
+                ATOM_WM_CLASS, ATOM_WM_NAME, ATOM_WM_SIZE_HINTS, ATOM_WM_TRANSIENT_FOR,
+                COMPOSITE_REDIRECT_MANUAL, CONFIG_WINDOW_HEIGHT, CONFIG_WINDOW_WIDTH,
+                CONFIG_WINDOW_X, CONFIG_WINDOW_Y, EVENT_MASK_FOCUS_CHANGE,
+                EVENT_MASK_PROPERTY_CHANGE, EVENT_MASK_STRUCTURE_NOTIFY,
                 EVENT_MASK_SUBSTRUCTURE_NOTIFY, EVENT_MASK_SUBSTRUCTURE_REDIRECT,
                 ICCCM_WM_HINT_INPUT, ICCCM_WM_STATE_ICONIC, ICCCM_WM_STATE_NORMAL,
                 ICCCM_WM_STATE_WITHDRAWN, INPUT_FOCUS_POINTER_ROOT, MWM_HINTS_DECORATIONS_FIELD,
@@ -53,8 +58,8 @@ use {
                 PROP_MODE_APPEND, PROP_MODE_REPLACE, RES_CLIENT_ID_MASK_LOCAL_CLIENT_PID,
                 SELECTION_CLIENT_CLOSE_MASK, SELECTION_WINDOW_DESTROY_MASK,
                 SET_SELECTION_OWNER_MASK, STACK_MODE_ABOVE, STACK_MODE_BELOW,
-                WINDOW_CLASS_INPUT_OUTPUT, _NET_WM_STATE_ADD, _NET_WM_STATE_REMOVE,
-                _NET_WM_STATE_TOGGLE,
+                WINDOW_CLASS_INPUT_ONLY, WINDOW_CLASS_INPUT_OUTPUT, _NET_WM_STATE_ADD,
+                _NET_WM_STATE_REMOVE, _NET_WM_STATE_TOGGLE,
             },
             Event, XEvent, Xcon, XconError,
         },
@@ -83,6 +88,7 @@ atoms! {
     COMPOUND_TEXT,
     DELETE,
     INCR,
+    MANAGER,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
@@ -138,6 +144,7 @@ atoms! {
     WM_WINDOW_ROLE,
     XdndActionAsk,
     XdndActionCopy,
+    XdndActionList,
     XdndActionMove,
     XdndActionPrivate,
     XdndAware,
@@ -150,6 +157,8 @@ atoms! {
     XdndSelection,
     XdndStatus,
     XdndTypeList,
+    _XSETTINGS_S0,
+    _XSETTINGS_SETTINGS,
 }
 
 struct EnhancedOffer {
@@ -170,6 +179,20 @@ struct SelectionData<T: XIpc> {
 }
 
 impl<T: XIpc> SelectionData<T> {
+    fn has_source(&self, seat: SeatId, source: DataSourceId) -> bool {
+        match self.sources.get(&seat) {
+            Some(s) => s.data.id == source,
+            _ => false,
+        }
+    }
+
+    fn has_offer(&self, seat: SeatId, offer: DataOfferId) -> bool {
+        match self.offers.get(&seat) {
+            Some(o) => o.offer.offer_id == offer,
+            _ => false,
+        }
+    }
+
     fn destroy(&self) {
         for offer in self.offers.lock().drain_values() {
             destroy_data_offer::<T>(&offer.offer);
@@ -200,6 +223,7 @@ pub struct XwmShared {
     devices: CopyHashMap<SeatId, Rc<XIpcDevice>>,
     data: SelectionData<XClipboardIpc>,
     primary_selection: SelectionData<XPrimarySelectionIpc>,
+    dnd: SelectionData<XClipboardIpc>,
     transfers: CopyHashMap<u64, SpawnedFuture<()>>,
 }
 
@@ -207,6 +231,7 @@ impl Drop for XwmShared {
     fn drop(&mut self) {
         self.data.destroy();
         self.primary_selection.destroy();
+        self.dnd.destroy();
         for device in self.devices.lock().drain_values() {
             destroy_data_device::<XClipboardIpc>(&device);
             destroy_data_device::<XPrimarySelectionIpc>(&device);
@@ -223,6 +248,7 @@ pub struct Wm {
     never_focus: AHashSet<u32>,
     root: u32,
     xwin: u32,
+    dnd_win: u32,
     client: Rc<Client>,
     windows: AHashMap<u32, Rc<XwindowData>>,
     windows_by_surface_id: AHashMap<WlSurfaceId, Rc<XwindowData>>,
@@ -242,8 +268,47 @@ pub struct Wm {
 
     map_list: LinkedList<Rc<XwindowData>>,
     num_mapped: usize,
+
+    wl_dnd: Option<WaylandToXDnd>,
+    x_dnd: Option<XToWaylandDnd>,
+
+    /// The serial of the XSETTINGS or 0 if we are not the XSETTINGS manager.
+    xsettings_serial: u32,
+}
+
+/// A drag-and-drop operation of a wayland client over an X window.
+struct WaylandToXDnd {
+    seat: SeatId,
+    offer: Rc<EnhancedOffer>,
+    /// The XdndAware window under the pointer or 0 if the window does not support XDND.
+    target: u32,
+    /// The window that receives the messages for `target`.
+    proxy: u32,
+    version: u32,
+    /// The root coordinates of the top-left corner of `target`.
+    origin: (i32, i32),
+    dropped: bool,
+}
+
+/// A drag-and-drop operation of an X client.
+///
+/// While the pointer is over a wayland surface, `dnd_win` is placed over the surface so
+/// that the X client sends its XDND messages to us.
+struct XToWaylandDnd {
+    seat: Rc<WlSeatGlobal>,
+    source_window: u32,
+    source: Option<Rc<XDataSource>>,
+    /// The wayland surface under the pointer.
+    target: Option<Rc<WlSurface>>,
+    /// The wayland surface that has received an enter event.
+    entered: Option<Rc<WlSurface>>,
+    dropped: bool,
 }
 
+const XDND_VERSION: u32 = 5;
+
+const XSETTINGS_TYPE_INTEGER: u8 = 0;
+
 struct PendingTransfer {
     mime_type: u32,
     fd: Rc<OwnedFd>,
@@ -504,6 +569,49 @@ impl Wm {
         shared.data.selection.set(atoms.CLIPBOARD);
         shared.primary_selection.win.set(clipboard_wins[1]);
         shared.primary_selection.selection.set(atoms.PRIMARY);
+        let dnd_win = {
+            let win = c.generate_id()?;
+            let cw = CreateWindow {
+                depth: 0,
+                wid: win,
+                parent: root,
+                x: 0,
+                y: 0,
+                width: 1,
+                height: 1,
+                border_width: 0,
+                class: WINDOW_CLASS_INPUT_ONLY,
+                visual: 0,
+                values: CreateWindowValues {
+                    override_redirect: Some(1),
+                    ..Default::default()
+                },
+            };
+            if let Err(e) = c.call(&cw).await {
+                return Err(XWaylandError::CreateDndWindow(e));
+            }
+            c.call(&ChangeProperty {
+                mode: PROP_MODE_REPLACE,
+                window: win,
+                property: atoms.XdndAware,
+                ty: ATOM_ATOM,
+                format: 32,
+                data: uapi::as_bytes(&XDND_VERSION),
+            });
+            let ssi = SelectSelectionInput {
+                window: win,
+                selection: atoms.XdndSelection,
+                event_mask: SET_SELECTION_OWNER_MASK
+                    | SELECTION_CLIENT_CLOSE_MASK
+                    | SELECTION_WINDOW_DESTROY_MASK,
+            };
+            if let Err(e) = c.call(&ssi).await {
+                return Err(XWaylandError::WatchSelection(e));
+            }
+            win
+        };
+        shared.dnd.win.set(dnd_win);
+        shared.dnd.selection.set(atoms.XdndSelection);
         Ok(Self {
             state: state.clone(),
             c,
@@ -511,6 +619,7 @@ impl Wm {
             never_focus,
             root,
             xwin,
+            dnd_win,
             client,
             windows: Default::default(),
             windows_by_surface_id: Default::default(),
@@ -527,6 +636,9 @@ impl Wm {
             num_stacked: 0,
             map_list: Default::default(),
             num_mapped: 0,
+            wl_dnd: None,
+            x_dnd: None,
+            xsettings_serial: 0,
         })
     }
 
@@ -554,7 +666,14 @@ impl Wm {
         for seat in removed_seats {
             self.shared.data.seat_removed(seat);
             self.shared.primary_selection.seat_removed(seat);
+            self.shared.dnd.seat_removed(seat);
             self.shared.devices.remove(&seat);
+            if self.wl_dnd.as_ref().map(|d| d.seat) == Some(seat) {
+                self.wl_dnd = None;
+            }
+            if self.x_dnd.as_ref().map(|d| d.seat.id()) == Some(seat) {
+                self.x_dnd = None;
+            }
         }
         for seat in new_seats {
             let dd = Rc::new(XIpcDevice {
@@ -571,8 +690,119 @@ impl Wm {
         self.known_seats = current_seats;
     }
 
+    async fn handle_wire_scale_changed(&mut self) {
+        let scale = self.state.xwayland.wire_scale.get();
+        if self.client.wire_scale.replace(scale) == scale {
+            return;
+        }
+        for output in self.state.root.outputs.lock().values() {
+            output
+                .global
+                .for_each_binding(self.client.id, |b| b.send_updates());
+        }
+        let windows: Vec<_> = self
+            .windows
+            .values()
+            .filter_map(|w| w.window.get())
+            .collect();
+        for window in windows {
+            self.handle_xwayland_configure(window).await;
+        }
+        self.update_scale_hints().await;
+    }
+
+    /// Advertises the scale to X clients via the `Xft.dpi` resource and via XSETTINGS.
+    async fn update_scale_hints(&mut self) {
+        let scale = self.client.wire_scale.get().unwrap_or(1);
+        if scale == 1 && self.xsettings_serial == 0 {
+            return;
+        }
+        let mut resources = vec![];
+        let _ = self
+            .c
+            .get_property::<u8>(
+                self.root,
+                ATOM_RESOURCE_MANAGER,
+                ATOM_STRING,
+                &mut resources,
+            )
+            .await;
+        let mut new_resources = vec![];
+        for line in resources.lines_with_terminator() {
+            if !line.starts_with(b"Xft.dpi:") {
+                new_resources.extend_from_slice(line);
+            }
+        }
+        if new_resources.last().is_some_and(|&c| c != b'\n') {
+            new_resources.push(b'\n');
+        }
+        if scale > 1 {
+            new_resources.extend_from_slice(format!("Xft.dpi:\t{}\n", 96 * scale).as_bytes());
+        }
+        self.c.call(&ChangeProperty {
+            mode: PROP_MODE_REPLACE,
+            window: self.root,
+            property: ATOM_RESOURCE_MANAGER,
+            ty: ATOM_STRING,
+            format: 8,
+            data: &new_resources,
+        });
+        self.xsettings_serial += 1;
+        let settings = [
+            ("Gdk/WindowScalingFactor", scale),
+            ("Gdk/UnscaledDPI", 96 * 1024),
+            ("Xft/DPI", 96 * 1024 * scale),
+        ];
+        let mut data = vec![];
+        data.push(cfg!(target_endian = "big") as u8);
+        data.extend_from_slice(&[0; 3]);
+        data.extend_from_slice(&self.xsettings_serial.to_ne_bytes());
+        data.extend_from_slice(&(settings.len() as u32).to_ne_bytes());
+        for (name, value) in settings {
+            data.extend_from_slice(&[XSETTINGS_TYPE_INTEGER, 0]);
+            data.extend_from_slice(&(name.len() as u16).to_ne_bytes());
+            data.extend_from_slice(name.as_bytes());
+            data.resize((data.len() + 3) & !3, 0);
+            data.extend_from_slice(&self.xsettings_serial.to_ne_bytes());
+            data.extend_from_slice(&value.to_ne_bytes());
+        }
+        self.c.call(&ChangeProperty {
+            mode: PROP_MODE_REPLACE,
+            window: self.xwin,
+            property: self.atoms._XSETTINGS_SETTINGS,
+            ty: self.atoms._XSETTINGS_SETTINGS,
+            format: 8,
+            data: &data,
+        });
+        if self.xsettings_serial == 1 {
+            let so = SetSelectionOwner {
+                owner: self.xwin,
+                selection: self.atoms._XSETTINGS_S0,
+                time: 0,
+            };
+            if let Err(e) = self.c.call(&so).await {
+                log::error!("Could not become the XSETTINGS manager: {}", ErrorFmt(e));
+                return;
+            }
+            let event = ClientMessage {
+                format: 32,
+                window: self.root,
+                ty: self.atoms.MANAGER,
+                data: &[0, self.atoms._XSETTINGS_S0, self.xwin, 0, 0],
+            };
+            let res = self
+                .c
+                .send_event(false, self.root, EVENT_MASK_STRUCTURE_NOTIFY, &event)
+                .await;
+            if let Err(e) = res {
+                log::error!("Could not announce the XSETTINGS manager: {}", ErrorFmt(e));
+            }
+        }
+    }
+
     pub async fn run(mut self) {
         self.seats_changed();
+        self.update_scale_hints().await;
         loop {
             select! {
                 e = self.state.xwayland.queue.pop().fuse() => self.handle_xwayland_event(e).await,
@@ -599,14 +829,25 @@ impl Wm {
             }
             XWaylandEvent::ActivateRoot => self.activate_window(None, Initiator::Wayland).await,
             XWaylandEvent::Close(window) => self.close_window(&window).await,
+            XWaylandEvent::SetMinimized(window, minimized) => {
+                if window.info.minimized.get() != minimized {
+                    self.set_minimized(&window, minimized).await;
+                }
+            }
             XWaylandEvent::SeatChanged => self.seats_changed(),
+            XWaylandEvent::WireScaleChanged => self.handle_wire_scale_changed().await,
             XWaylandEvent::IpcCancelSource {
                 location,
                 seat,
                 source,
             } => match location {
                 IpcLocation::Clipboard => {
-                    self.dd_cancel_source::<XClipboardIpc>(&self.shared.clone().data, seat, source)
+                    let shared = self.shared.clone();
+                    let sd = match shared.dnd.has_source(seat, source) {
+                        true => &shared.dnd,
+                        false => &shared.data,
+                    };
+                    self.dd_cancel_source::<XClipboardIpc>(sd, seat, source)
                 }
                 IpcLocation::PrimarySelection => self.dd_cancel_source::<XPrimarySelectionIpc>(
                     &self.shared.clone().primary_selection,
@@ -622,14 +863,13 @@ impl Wm {
                 fd,
             } => match location {
                 IpcLocation::Clipboard => {
-                    self.dd_send_source::<XClipboardIpc>(
-                        &self.shared.clone().data,
-                        seat,
-                        source,
-                        mime_type,
-                        fd,
-                    )
-                    .await
+                    let shared = self.shared.clone();
+                    let sd = match shared.dnd.has_source(seat, source) {
+                        true => &shared.dnd,
+                        false => &shared.data,
+                    };
+                    self.dd_send_source::<XClipboardIpc>(sd, seat, source, mime_type, fd)
+                        .await
                 }
                 IpcLocation::PrimarySelection => {
                     self.dd_send_source::<XPrimarySelectionIpc>(
@@ -647,6 +887,7 @@ impl Wm {
                 seat,
                 offer,
             } => match location {
+                IpcLocation::Clipboard if offer.is_dnd() => self.dnd_set_offer(seat, offer),
                 IpcLocation::Clipboard => {
                     self.dd_set_offer::<XClipboardIpc>(&self.shared.clone().data, seat, offer)
                         .await
@@ -685,13 +926,13 @@ impl Wm {
                 mime_type,
             } => match location {
                 IpcLocation::Clipboard => {
-                    self.dd_add_offer_mime_type::<XClipboardIpc>(
-                        &self.shared.clone().data,
-                        seat,
-                        offer,
-                        mime_type,
-                    )
-                    .await
+                    let shared = self.shared.clone();
+                    let sd = match shared.dnd.has_offer(seat, offer) {
+                        true => &shared.dnd,
+                        false => &shared.data,
+                    };
+                    self.dd_add_offer_mime_type::<XClipboardIpc>(sd, seat, offer, mime_type)
+                        .await
                 }
                 IpcLocation::PrimarySelection => {
                     self.dd_add_offer_mime_type::<XPrimarySelectionIpc>(
@@ -703,6 +944,23 @@ impl Wm {
                     .await
                 }
             },
+            XWaylandEvent::DndEnter {
+                seat,
+                offer,
+                surface,
+                x,
+                y,
+            } => self.handle_dnd_enter(seat, offer, surface, x, y).await,
+            XWaylandEvent::DndMotion { seat, x, y } => self.handle_dnd_motion(seat, x, y).await,
+            XWaylandEvent::DndLeave { seat } => self.handle_dnd_leave(seat).await,
+            XWaylandEvent::DndDrop { seat } => self.handle_dnd_drop(seat).await,
+            XWaylandEvent::DndStatus { seat, source } => self.handle_dnd_status(seat, source).await,
+            XWaylandEvent::DndFinished { seat, source } => {
+                self.handle_dnd_finished(seat, source).await
+            }
+            XWaylandEvent::DndProxyTarget { seat, surface } => {
+                self.handle_dnd_proxy_target(seat, surface).await
+            }
         }
     }
 
@@ -903,6 +1161,590 @@ impl Wm {
         }
     }
 
+    fn dnd_set_offer(&mut self, seat: SeatId, offer: Rc<XDataOffer>) {
+        let offer = Rc::new(EnhancedOffer {
+            offer,
+            mime_types: Default::default(),
+            active: Cell::new(false),
+        });
+        if let Some(old) = self.shared.dnd.offers.set(seat, offer) {
+            let in_use = match &self.wl_dnd {
+                Some(dnd) => rc_eq(&dnd.offer, &old),
+                _ => false,
+            };
+            if !in_use {
+                destroy_data_offer::<XClipboardIpc>(&old.offer);
+            }
+        }
+    }
+
+    fn destroy_dnd_offer(&self, seat: SeatId, offer: &Rc<EnhancedOffer>) {
+        let sd = &self.shared.dnd;
+        if let Some(cur) = sd.offers.get(&seat) {
+            if rc_eq(&cur, offer) {
+                sd.offers.remove(&seat);
+            }
+        }
+        if let Some(cur) = sd.active_offer.get() {
+            if rc_eq(&cur, offer) {
+                sd.active_offer.take();
+            }
+        }
+        destroy_data_offer::<XClipboardIpc>(&offer.offer);
+    }
+
+    fn dnd_action_to_atom(&self, action: u32) -> u32 {
+        match action {
+            DND_COPY => self.atoms.XdndActionCopy,
+            DND_MOVE => self.atoms.XdndActionMove,
+            DND_ASK => self.atoms.XdndActionAsk,
+            _ => ATOM_NONE,
+        }
+    }
+
+    fn atom_to_dnd_action(&self, atom: u32) -> u32 {
+        if atom == self.atoms.XdndActionCopy || atom == self.atoms.XdndActionPrivate {
+            DND_COPY
+        } else if atom == self.atoms.XdndActionMove {
+            DND_MOVE
+        } else if atom == self.atoms.XdndActionAsk {
+            DND_ASK
+        } else {
+            DND_NONE
+        }
+    }
+
+    async fn send_xdnd_message(&self, window: u32, destination: u32, ty: u32, data: &[u32; 5]) {
+        let event = ClientMessage {
+            format: 32,
+            window,
+            ty,
+            data,
+        };
+        if let Err(e) = self.c.send_event(false, destination, 0, &event).await {
+            log::error!("Could not send XDND message: {}", ErrorFmt(e));
+        }
+    }
+
+    /// Returns the window that receives the XDND messages of the window and the XDND
+    /// version supported by the window.
+    async fn get_xdnd_target(&self, window: u32) -> Option<(u32, u32)> {
+        let mut proxy = vec![];
+        let _ = self
+            .c
+            .get_property::<u32>(window, self.atoms.XdndProxy, ATOM_WINDOW, &mut proxy)
+            .await;
+        let proxy = proxy.first().copied().unwrap_or(window);
+        let mut version = vec![];
+        for window in [window, proxy] {
+            let _ = self
+                .c
+                .get_property::<u32>(window, self.atoms.XdndAware, ATOM_ATOM, &mut version)
+                .await;
+            if let Some(&version) = version.first() {
+                return Some((proxy, version.min(XDND_VERSION)));
+            }
+        }
+        None
+    }
+
+    async fn handle_dnd_enter(
+        &mut self,
+        seat: SeatId,
+        offer_id: DataOfferId,
+        surface: WlSurfaceId,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        let Some(offer) = self.shared.dnd.offers.get(&seat) else {
+            return;
+        };
+        if offer.offer.offer_id != offer_id || offer.offer.is_cancelled() {
+            return;
+        }
+        let Some(data) = self.windows_by_surface_id.get(&surface).cloned() else {
+            return;
+        };
+        if let Some(old) = self.wl_dnd.take() {
+            self.end_wl_dnd(old).await;
+        }
+        let extents = data.info.extents.get();
+        let mut dnd = WaylandToXDnd {
+            seat,
+            offer: offer.clone(),
+            target: 0,
+            proxy: 0,
+            version: 0,
+            origin: (extents.x1(), extents.y1()),
+            dropped: false,
+        };
+        if let Some((proxy, version)) = self.get_xdnd_target(data.window_id).await {
+            dnd.target = data.window_id;
+            dnd.proxy = proxy;
+            dnd.version = version;
+            let so = SetSelectionOwner {
+                owner: self.dnd_win,
+                selection: self.atoms.XdndSelection,
+                time: 0,
+            };
+            if let Err(e) = self.c.call(&so).await {
+                log::error!("Could not set the XdndSelection owner: {}", ErrorFmt(e));
+            }
+            let mime_types = offer.mime_types.borrow().clone();
+            self.c.call(&ChangeProperty {
+                mode: PROP_MODE_REPLACE,
+                window: self.dnd_win,
+                property: self.atoms.XdndTypeList,
+                ty: ATOM_ATOM,
+                format: 32,
+                data: uapi::as_bytes(&mime_types[..]),
+            });
+            self.shared.dnd.active_offer.set(Some(offer));
+            let mut data = [self.dnd_win, version << 24, 0, 0, 0];
+            if mime_types.len() > 3 {
+                data[1] |= 1;
+            }
+            for (dst, mt) in data[2..].iter_mut().zip(mime_types.iter()) {
+                *dst = *mt;
+            }
+            self.send_xdnd_message(dnd.target, dnd.proxy, self.atoms.XdndEnter, &data)
+                .await;
+        }
+        self.wl_dnd = Some(dnd);
+        self.send_xdnd_position(x, y).await;
+    }
+
+    async fn send_xdnd_position(&self, x: Fixed, y: Fixed) {
+        let Some(dnd) = &self.wl_dnd else {
+            return;
+        };
+        if dnd.target == 0 || dnd.dropped {
+            return;
+        }
+        let mut x = dnd.origin.0 + x.round_down();
+        let mut y = dnd.origin.1 + y.round_down();
+        logical_to_client_wire_scale!(self.client, x, y);
+        let actions = dnd.offer.offer.source_actions();
+        let action = if actions.contains(DND_COPY) {
+            DND_COPY
+        } else if actions.contains(DND_MOVE) {
+            DND_MOVE
+        } else {
+            actions & DND_ASK
+        };
+        let data = [
+            self.dnd_win,
+            0,
+            ((x as u32) << 16) | (y as u32 & 0xffff),
+            0,
+            self.dnd_action_to_atom(action),
+        ];
+        self.send_xdnd_message(dnd.target, dnd.proxy, self.atoms.XdndPosition, &data)
+            .await;
+    }
+
+    async fn handle_dnd_motion(&mut self, seat: SeatId, x: Fixed, y: Fixed) {
+        if self.wl_dnd.as_ref().map(|d| d.seat) == Some(seat) {
+            self.send_xdnd_position(x, y).await;
+        }
+    }
+
+    async fn handle_dnd_leave(&mut self, seat: SeatId) {
+        let Some(dnd) = &self.wl_dnd else {
+            return;
+        };
+        if dnd.seat != seat || dnd.dropped {
+            return;
+        }
+        if let Some(dnd) = self.wl_dnd.take() {
+            self.end_wl_dnd(dnd).await;
+        }
+    }
+
+    async fn handle_dnd_drop(&mut self, seat: SeatId) {
+        let Some(dnd) = &mut self.wl_dnd else {
+            return;
+        };
+        if dnd.seat != seat || dnd.target == 0 || dnd.offer.offer.is_cancelled() {
+            return;
+        }
+        dnd.dropped = true;
+        let data = [self.dnd_win, 0, 0, 0, 0];
+        let (target, proxy) = (dnd.target, dnd.proxy);
+        self.send_xdnd_message(target, proxy, self.atoms.XdndDrop, &data)
+            .await;
+    }
+
+    async fn end_wl_dnd(&mut self, dnd: WaylandToXDnd) {
+        if dnd.target != 0 && !dnd.dropped {
+            let data = [self.dnd_win, 0, 0, 0, 0];
+            self.send_xdnd_message(dnd.target, dnd.proxy, self.atoms.XdndLeave, &data)
+                .await;
+        }
+        self.destroy_dnd_offer(dnd.seat, &dnd.offer);
+    }
+
+    async fn handle_xdnd_status(&mut self, event: &ClientMessage<'_>) {
+        let Some(dnd) = &self.wl_dnd else {
+            return;
+        };
+        if dnd.dropped || event.data[0] != dnd.target {
+            return;
+        }
+        let accepted = event.data[1] & 1 != 0;
+        let action = self.atom_to_dnd_action(event.data[4]);
+        dnd.offer.offer.dnd_status(accepted, action);
+    }
+
+    async fn handle_xdnd_selection_request(&mut self, event: &SelectionRequest) {
+        let Some(dnd) = &self.wl_dnd else {
+            return;
+        };
+        if event.target == self.atoms.TARGETS {
+            return;
+        }
+        let offer = dnd.offer.clone();
+        match self.atom_to_mime_type(event.target).await {
+            Ok(mt) => offer.offer.dnd_request_mime_type(&mt),
+            Err(e) => log::error!("Could not get mime type name: {}", ErrorFmt(e)),
+        }
+    }
+
+    async fn handle_xdnd_finished(&mut self, event: &ClientMessage<'_>) {
+        let Some(dnd) = &self.wl_dnd else {
+            return;
+        };
+        if !dnd.dropped || event.data[0] != dnd.target {
+            return;
+        }
+        let success = dnd.version < 5 || event.data[1] & 1 != 0;
+        if let Some(dnd) = self.wl_dnd.take() {
+            if success {
+                dnd.offer.offer.dnd_finished();
+            }
+            self.destroy_dnd_offer(dnd.seat, &dnd.offer);
+        }
+    }
+
+    async fn handle_xdnd_selection_owner(&mut self, event: &XfixesSelectionNotify) {
+        if event.owner == self.dnd_win {
+            return;
+        }
+        self.end_x_dnd().await;
+        if event.owner == 0 {
+            return;
+        }
+        let seat = self
+            .state
+            .globals
+            .seats
+            .lock()
+            .values()
+            .find(|seat| {
+                let client = seat.pointer_node().and_then(|n| n.node_client_id());
+                client == Some(self.client.id)
+            })
+            .cloned();
+        let Some(seat) = seat else {
+            return;
+        };
+        seat.start_x_dnd();
+        self.x_dnd = Some(XToWaylandDnd {
+            seat,
+            source_window: event.owner,
+            source: None,
+            target: None,
+            entered: None,
+            dropped: false,
+        });
+    }
+
+    async fn end_x_dnd(&mut self) {
+        let Some(mut dnd) = self.x_dnd.take() else {
+            return;
+        };
+        Self::end_x_dnd_source(&self.shared, &mut dnd);
+        dnd.seat.end_x_dnd();
+        if dnd.target.is_some() {
+            if let Err(e) = self
+                .c
+                .call(&UnmapWindow {
+                    window: self.dnd_win,
+                })
+                .await
+            {
+                log::error!("Could not unmap the XDND proxy window: {}", ErrorFmt(e));
+            }
+        }
+    }
+
+    fn end_x_dnd_source(shared: &XwmShared, dnd: &mut XToWaylandDnd) {
+        let Some(source) = dnd.source.take() else {
+            return;
+        };
+        if let Some(surface) = dnd.entered.take() {
+            if !dnd.dropped {
+                source.dnd_leave(&surface);
+            }
+        }
+        dnd.dropped = false;
+        let sd = &shared.dnd;
+        let seat = dnd.seat.id();
+        if sd.has_source(seat, source.data.id) {
+            sd.sources.remove(&seat);
+        }
+        source.end_dnd();
+    }
+
+    async fn handle_dnd_proxy_target(&mut self, seat: SeatId, surface: Option<Rc<WlSurface>>) {
+        let Some(dnd) = &mut self.x_dnd else {
+            return;
+        };
+        if dnd.seat.id() != seat {
+            return;
+        }
+        dnd.target = surface.clone();
+        let res = match surface {
+            Some(surface) => {
+                let rect = surface.buffer_abs_pos.get();
+                let (mut x, mut y) = rect.position();
+                let (mut width, mut height) = rect.size();
+                logical_to_client_wire_scale!(self.client, x, y, width, height);
+                let cw = ConfigureWindow {
+                    window: self.dnd_win,
+                    values: ConfigureWindowValues {
+                        x: Some(x),
+                        y: Some(y),
+                        width: Some(width.max(1) as u32),
+                        height: Some(height.max(1) as u32),
+                        stack_mode: Some(STACK_MODE_ABOVE),
+                        ..Default::default()
+                    },
+                };
+                self.c.call(&cw);
+                self.c
+                    .call(&MapWindow {
+                        window: self.dnd_win,
+                    })
+                    .await
+            }
+            None => {
+                self.c
+                    .call(&UnmapWindow {
+                        window: self.dnd_win,
+                    })
+                    .await
+            }
+        };
+        if let Err(e) = res {
+            log::error!("Could not update the XDND proxy window: {}", ErrorFmt(e));
+        }
+    }
+
+    async fn handle_xdnd_enter(&mut self, event: &ClientMessage<'_>) {
+        let Some(dnd) = &mut self.x_dnd else {
+            return;
+        };
+        let seat = dnd.seat.clone();
+        Self::end_x_dnd_source(&self.shared, dnd);
+        let source_window = event.data[0];
+        let mut types = vec![];
+        if event.data[1] & 1 != 0 {
+            let res = self
+                .c
+                .get_property::<u32>(
+                    source_window,
+                    self.atoms.XdndTypeList,
+                    ATOM_ATOM,
+                    &mut types,
+                )
+                .await;
+            if let Err(e) = res {
+                log::error!("Could not get the XdndTypeList: {}", ErrorFmt(e));
+            }
+        } else {
+            types.extend(event.data[2..].iter().copied().filter(|&t| t != ATOM_NONE));
+        }
+        let mut action_atoms = vec![];
+        let _ = self
+            .c
+            .get_property::<u32>(
+                source_window,
+                self.atoms.XdndActionList,
+                ATOM_ATOM,
+                &mut action_atoms,
+            )
+            .await;
+        let mut actions = action_atoms
+            .iter()
+            .fold(DND_NONE, |a, &atom| a | self.atom_to_dnd_action(atom));
+        if actions == DND_NONE {
+            actions = DND_COPY | DND_MOVE;
+        }
+        let Some(device) = self.shared.devices.get(&seat.id()) else {
+            return;
+        };
+        let source = Rc::new(XDataSource {
+            state: self.state.clone(),
+            device,
+            data: SourceData::new(&self.client),
+            location: IpcLocation::Clipboard,
+        });
+        for atom in types {
+            match self.atom_to_mime_type(atom).await {
+                Ok(mt) => add_data_source_mime_type::<XClipboardIpc>(&source, &mt),
+                Err(e) => log::error!("Could not get mime type name: {}", ErrorFmt(e)),
+            }
+        }
+        if let Err(e) = source.start_dnd(actions) {
+            log::error!("Could not start drag-and-drop: {}", ErrorFmt(e));
+            return;
+        }
+        self.shared.dnd.sources.set(seat.id(), source.clone());
+        if let Some(dnd) = &mut self.x_dnd {
+            dnd.source_window = source_window;
+            dnd.source = Some(source);
+        }
+    }
+
+    async fn handle_xdnd_position(&mut self, event: &ClientMessage<'_>) {
+        let Some(dnd) = &mut self.x_dnd else {
+            return;
+        };
+        let Some(source) = dnd.source.clone() else {
+            return;
+        };
+        if dnd.dropped || event.data[0] != dnd.source_window {
+            return;
+        }
+        let mut x = (event.data[2] >> 16) as i16 as i32;
+        let mut y = event.data[2] as i16 as i32;
+        client_wire_scale_to_logical!(self.client, x, y);
+        let entered = dnd.entered.as_ref().map(|s| s.node_id());
+        let target = dnd.target.as_ref().map(|s| s.node_id());
+        if entered != target {
+            if let Some(surface) = dnd.entered.take() {
+                source.dnd_leave(&surface);
+            }
+            if let Some(surface) = &dnd.target {
+                let (x, y) = surface.buffer_abs_pos.get().translate(x, y);
+                source.dnd_enter(surface, Fixed::from_int(x), Fixed::from_int(y));
+                dnd.entered = Some(surface.clone());
+            }
+        } else if let Some(surface) = &dnd.entered {
+            let (x, y) = surface.buffer_abs_pos.get().translate(x, y);
+            source.dnd_motion(surface, Fixed::from_int(x), Fixed::from_int(y));
+        }
+        self.send_xdnd_status().await;
+    }
+
+    async fn send_xdnd_status(&self) {
+        let Some(dnd) = &self.x_dnd else {
+            return;
+        };
+        let Some(source) = &dnd.source else {
+            return;
+        };
+        if dnd.dropped {
+            return;
+        }
+        let accepted = dnd.entered.is_some() && source.can_drop();
+        let action = match accepted {
+            true => self.dnd_action_to_atom(source.selected_action()),
+            false => ATOM_NONE,
+        };
+        // Always ask for XdndPosition messages since the proxy window can cover multiple
+        // wayland surfaces.
+        let data = [self.dnd_win, accepted as u32 | 2, 0, 0, action];
+        self.send_xdnd_message(
+            dnd.source_window,
+            dnd.source_window,
+            self.atoms.XdndStatus,
+            &data,
+        )
+        .await;
+    }
+
+    async fn handle_dnd_status(&mut self, seat: SeatId, source: DataSourceId) {
+        if self.shared.dnd.has_source(seat, source) {
+            self.send_xdnd_status().await;
+        }
+    }
+
+    async fn handle_xdnd_leave(&mut self, event: &ClientMessage<'_>) {
+        let Some(dnd) = &mut self.x_dnd else {
+            return;
+        };
+        if dnd.dropped || event.data[0] != dnd.source_window {
+            return;
+        }
+        Self::end_x_dnd_source(&self.shared, dnd);
+    }
+
+    async fn handle_xdnd_drop(&mut self, event: &ClientMessage<'_>) {
+        let Some(dnd) = &mut self.x_dnd else {
+            return;
+        };
+        let Some(source) = dnd.source.clone() else {
+            return;
+        };
+        if dnd.dropped || event.data[0] != dnd.source_window {
+            return;
+        }
+        let accepted = match dnd.entered.take() {
+            Some(surface) => source.dnd_drop(&surface),
+            None => false,
+        };
+        if accepted {
+            dnd.dropped = true;
+            return;
+        }
+        let source_window = dnd.source_window;
+        Self::end_x_dnd_source(&self.shared, dnd);
+        let data = [self.dnd_win, 0, ATOM_NONE, 0, 0];
+        self.send_xdnd_message(source_window, source_window, self.atoms.XdndFinished, &data)
+            .await;
+    }
+
+    async fn handle_dnd_finished(&mut self, seat: SeatId, source: DataSourceId) {
+        let Some(dnd) = &mut self.x_dnd else {
+            return;
+        };
+        if !dnd.dropped || !self.shared.dnd.has_source(seat, source) {
+            return;
+        }
+        let Some(src) = dnd.source.clone() else {
+            return;
+        };
+        let source_window = dnd.source_window;
+        Self::end_x_dnd_source(&self.shared, dnd);
+        let data = [
+            self.dnd_win,
+            1,
+            self.dnd_action_to_atom(src.selected_action()),
+            0,
+            0,
+        ];
+        self.send_xdnd_message(source_window, source_window, self.atoms.XdndFinished, &data)
+            .await;
+    }
+
+    async fn handle_xdnd_message(&mut self, event: &ClientMessage<'_>) {
+        if event.ty == self.atoms.XdndEnter {
+            self.handle_xdnd_enter(event).await;
+        } else if event.ty == self.atoms.XdndPosition {
+            self.handle_xdnd_position(event).await;
+        } else if event.ty == self.atoms.XdndLeave {
+            self.handle_xdnd_leave(event).await;
+        } else if event.ty == self.atoms.XdndDrop {
+            self.handle_xdnd_drop(event).await;
+        } else if event.ty == self.atoms.XdndStatus {
+            self.handle_xdnd_status(event).await;
+        } else if event.ty == self.atoms.XdndFinished {
+            self.handle_xdnd_finished(event).await;
+        }
+    }
+
     async fn handle_xwayland_configure(&mut self, window: Rc<Xwindow>) {
         if window.data.destroyed.get() {
             return;
@@ -913,13 +1755,16 @@ impl Wm {
     async fn send_configure(&mut self, window: Rc<Xwindow>) {
         let extents = window.data.info.extents.get();
         // log::info!("xwin {} send_configure {:?}", window.data.window_id, extents);
+        let (mut x, mut y) = extents.position();
+        let (mut width, mut height) = extents.size();
+        logical_to_client_wire_scale!(self.client, x, y, width, height);
         let cw = ConfigureWindow {
             window: window.data.window_id,
             values: ConfigureWindowValues {
-                x: Some(extents.x1()),
-                y: Some(extents.y1()),
-                width: Some(extents.width() as u32),
-                height: Some(extents.height() as u32),
+                x: Some(x),
+                y: Some(y),
+                width: Some(width as u32),
+                height: Some(height as u32),
                 border_width: Some(0),
                 ..Default::default()
             },
@@ -1161,6 +2006,9 @@ impl Wm {
         }
         *data.info.title.borrow_mut() = Some(title);
         data.title_changed();
+        if let Some(window) = data.window.get() {
+            window_rules::reevaluate(&self.state, window);
+        }
     }
 
     async fn unexpected_type(&self, window: u32, prop: &str, ty: u32) {
@@ -1557,6 +2405,9 @@ impl Wm {
         } else if event.selection == self.atoms.CLIPBOARD {
             self.handle_xfixes_selection_notify_(&shared.data, &event)
                 .await
+        } else if event.selection == self.atoms.XdndSelection {
+            self.handle_xdnd_selection_owner(&event).await;
+            Ok(())
         } else {
             Ok(())
         }
@@ -1610,6 +2461,9 @@ impl Wm {
                 .await
         } else if event.selection == self.atoms.CLIPBOARD {
             self.handle_selection_request_(&shared.data, &event).await
+        } else if event.selection == self.atoms.XdndSelection {
+            self.handle_xdnd_selection_request(&event).await;
+            self.handle_selection_request_(&shared.dnd, &event).await
         } else {
             log::warn!("Unknown selection request");
             Ok(())
@@ -1712,6 +2566,8 @@ impl Wm {
                 .await
         } else if event.selection == self.atoms.CLIPBOARD {
             self.handle_selection_notify_(&shared.data, &event).await
+        } else if event.selection == self.atoms.XdndSelection {
+            self.handle_selection_notify_(&shared.dnd, &event).await
         } else {
             Ok(())
         }
@@ -1997,7 +2853,7 @@ impl Wm {
 
     async fn handle_create_notify(&mut self, event: &Event) -> Result<(), XWaylandError> {
         let event: CreateNotify = event.parse()?;
-        if event.window == self.xwin {
+        if event.window == self.xwin || event.window == self.dnd_win {
             return Ok(());
         }
         let data = Rc::new(XwindowData::new(&self.state, &event, &self.client));
@@ -2045,6 +2901,8 @@ impl Wm {
             self.handle_net_wm_moveresize(&event).await?;
         } else if event.ty == self.atoms.WL_SURFACE_SERIAL {
             self.handle_wl_surface_serial(&event).await?;
+        } else if event.window == self.dnd_win {
+            self.handle_xdnd_message(&event).await;
         }
         Ok(())
     }
@@ -2129,15 +2987,17 @@ impl Wm {
         self.set_net_client_list().await;
         let pending = data.info.pending_extents.get();
         if pending.width() > 0 && pending.height() > 0 {
-            let dummy = Rect::new_sized(0, 0, 1, 1).unwrap();
-            for rect in [dummy, pending] {
+            let (mut x, mut y) = pending.position();
+            let (mut width, mut height) = pending.size();
+            logical_to_client_wire_scale!(self.client, x, y, width, height);
+            for (x, y, width, height) in [(0, 0, 1, 1), (x, y, width, height)] {
                 let cw = ConfigureWindow {
                     window: data.window_id,
                     values: ConfigureWindowValues {
-                        x: Some(rect.x1()),
-                        y: Some(rect.y1()),
-                        width: Some(rect.width() as _),
-                        height: Some(rect.height() as _),
+                        x: Some(x),
+                        y: Some(y),
+                        width: Some(width as _),
+                        height: Some(height as _),
                         ..Default::default()
                     },
                 };
@@ -2210,13 +3070,10 @@ impl Wm {
         };
         self.update_override_redirect(data, event.override_redirect);
         if data.info.override_redirect.get() {
-            let extents = Rect::new_sized(
-                event.x as _,
-                event.y as _,
-                event.width as _,
-                event.height as _,
-            )
-            .unwrap();
+            let (mut x, mut y) = (event.x as i32, event.y as i32);
+            let (mut width, mut height) = (event.width as i32, event.height as i32);
+            client_wire_scale_to_logical!(self.client, x, y, width, height);
+            let extents = Rect::new_sized(x, y, width, height).unwrap();
             if let Some(window) = data.window.get() {
                 window.tl_change_extents(&extents);
                 self.state.tree_changed();
@@ -2243,17 +3100,20 @@ impl Wm {
         let mut y1 = de.y1();
         let mut width = de.width();
         let mut height = de.height();
+        let (mut ex, mut ey) = (event.x as i32, event.y as i32);
+        let (mut ewidth, mut eheight) = (event.width as i32, event.height as i32);
+        client_wire_scale_to_logical!(self.client, ex, ey, ewidth, eheight);
         if event.value_mask.contains(CONFIG_WINDOW_X) {
-            x1 = event.x as _;
+            x1 = ex;
         }
         if event.value_mask.contains(CONFIG_WINDOW_Y) {
-            y1 = event.y as _;
+            y1 = ey;
         }
         if event.value_mask.contains(CONFIG_WINDOW_WIDTH) {
-            width = event.width as _;
+            width = ewidth;
         }
         if event.value_mask.contains(CONFIG_WINDOW_HEIGHT) {
-            height = event.height as _;
+            height = eheight;
         }
         data.info
             .pending_extents
@@ -2282,7 +3142,10 @@ impl Wm {
             _ => return Ok(()),
         };
         let minimize = match event.data[0] {
-            ICCCM_WM_STATE_NORMAL => false,
+            ICCCM_WM_STATE_NORMAL => {
+                self.handle_restore_requested(data).await;
+                false
+            }
             ICCCM_WM_STATE_ICONIC => self.handle_minimize_requested(data).await,
             _ => return Ok(()),
         };
@@ -2293,7 +3156,8 @@ impl Wm {
 
     async fn handle_minimize_requested(&self, data: &Rc<XwindowData>) -> bool {
         if let Some(w) = data.window.get() {
-            if w.toplevel_data.active_surfaces.active() {
+            minimize::minimize(&self.state, w.clone());
+            if !w.toplevel_data.is_minimized() {
                 self.set_wm_state(data, ICCCM_WM_STATE_NORMAL).await;
                 return false;
             }
@@ -2302,6 +3166,13 @@ impl Wm {
         true
     }
 
+    async fn handle_restore_requested(&self, data: &Rc<XwindowData>) {
+        if let Some(w) = data.window.get() {
+            minimize::restore(&self.state, w);
+        }
+        self.set_wm_state(data, ICCCM_WM_STATE_NORMAL).await;
+    }
+
     async fn handle_net_startup_info(
         &mut self,
         event: &ClientMessage<'_>,
@@ -2405,6 +3276,8 @@ impl Wm {
         if minimized != data.info.minimized.get() {
             if minimized {
                 minimized = self.handle_minimize_requested(data).await;
+            } else {
+                self.handle_restore_requested(data).await;
             }
         }
         if fullscreen != data.info.fullscreen.get() {
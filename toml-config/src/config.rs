@@ -11,19 +11,24 @@ use {
     crate::{
         config::{
             context::Context,
-            parsers::config::{ConfigParser, ConfigParserError},
+            parsers::{
+                action::{ActionParser, ActionParserError},
+                config::{ConfigParser, ConfigParserError},
+            },
         },
-        toml::{self},
+        toml::{self, toml_value::Value},
     },
     ahash::AHashMap,
     jay_config::{
-        input::{acceleration::AccelProfile, SwitchEvent},
+        input::{acceleration::AccelProfile, pointer::PointerTrigger, SwitchEvent},
         keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
         logging::LogLevel,
         status::MessageFormat,
-        theme::Color,
-        video::{GfxApi, Transform},
-        Axis, Direction, Workspace,
+        theme::{Color, ColorScheme},
+        video::{GfxApi, Transform, VrrMode},
+        window::WindowType,
+        xwayland::XScalingMode,
+        Axis, Direction, ResizeAmount, Workspace,
     },
     std::{
         error::Error,
@@ -52,6 +57,17 @@ pub enum SimpleCommand {
     ToggleSplit,
     Forward(bool),
     EnableWindowManagement(bool),
+    MoveToScratchpad,
+    ToggleScratchpad,
+    Minimize,
+    RestoreMinimized,
+    ExitMode,
+    FocusLast,
+    FocusNextMru,
+    FocusPrevMru,
+    WorkspaceNext,
+    WorkspacePrev,
+    WorkspaceBackAndForth,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +129,9 @@ pub enum Action {
     SwitchToVt {
         num: u32,
     },
+    ToggleScratchpad {
+        app_id: Option<String>,
+    },
     UnsetEnv {
         env: Vec<String>,
     },
@@ -123,6 +142,31 @@ pub enum Action {
     SetRepeatRate {
         rate: RepeatRate,
     },
+    Resize {
+        direction: Direction,
+        amount: ResizeAmount,
+    },
+    MoveFloating {
+        dx: i32,
+        dy: i32,
+    },
+    SetFloatingGeometry {
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    },
+    EnterMode {
+        name: String,
+        once: bool,
+    },
+    ShowAdjacentWorkspace {
+        next: bool,
+        global: bool,
+    },
+    RenameWorkspace {
+        name: String,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -144,7 +188,11 @@ pub struct Theme {
     pub highlight_color: Option<Color>,
     pub border_width: Option<i32>,
     pub title_height: Option<i32>,
+    pub inner_gap: Option<i32>,
+    pub outer_gap: Option<i32>,
+    pub smart_gaps: Option<bool>,
     pub font: Option<String>,
+    pub color_scheme: Option<ColorScheme>,
 }
 
 #[derive(Debug, Clone)]
@@ -206,6 +254,47 @@ pub struct Output {
     pub scale: Option<f64>,
     pub transform: Option<Transform>,
     pub mode: Option<Mode>,
+    pub tearing: Option<bool>,
+    pub vrr: Option<VrrMode>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WindowMatch {
+    pub app_id: Option<String>,
+    pub title: Option<String>,
+    pub class: Option<String>,
+    pub instance: Option<String>,
+    pub role: Option<String>,
+    pub pid: Option<i32>,
+    pub sandbox_engine: Option<String>,
+    pub window_type: Option<WindowType>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WindowRule {
+    pub match_: WindowMatch,
+    pub workspace: Option<String>,
+    pub output: Option<String>,
+    pub floating: Option<bool>,
+    pub position: Option<(i32, i32)>,
+    pub size: Option<(i32, i32)>,
+    pub fullscreen: Option<bool>,
+    pub opacity: Option<f32>,
+    pub reevaluate: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Xwayland {
+    pub scaling_mode: Option<XScalingMode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigWorkspace {
+    pub name: String,
+    pub inner_gap: Option<i32>,
+    pub outer_gap: Option<i32>,
+    pub smart_gaps: Option<bool>,
+    pub connector: Option<ConnectorMatch>,
 }
 
 #[derive(Debug, Clone)]
@@ -293,11 +382,27 @@ pub struct Shortcut {
     pub latch: Option<Action>,
 }
 
+#[derive(Debug, Clone)]
+pub struct PointerShortcut {
+    pub mods: Modifiers,
+    pub trigger: PointerTrigger,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct BindingMode {
+    pub name: String,
+    pub shortcuts: Vec<Shortcut>,
+    pub pointer_shortcuts: Vec<PointerShortcut>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub keymap: Option<ConfigKeymap>,
     pub repeat_rate: Option<RepeatRate>,
     pub shortcuts: Vec<Shortcut>,
+    pub pointer_shortcuts: Vec<PointerShortcut>,
+    pub binding_modes: Vec<BindingMode>,
     pub on_graphics_initialized: Option<Action>,
     pub on_idle: Option<Action>,
     pub status: Option<Status>,
@@ -318,6 +423,9 @@ pub struct Config {
     pub explicit_sync_enabled: Option<bool>,
     pub focus_follows_mouse: bool,
     pub window_management_key: Option<ModifiedKeySym>,
+    pub window_rules: Vec<WindowRule>,
+    pub workspaces: Vec<ConfigWorkspace>,
+    pub xwayland: Option<Xwayland>,
 }
 
 #[derive(Debug, Error)]
@@ -326,6 +434,10 @@ pub enum ConfigError {
     Toml(#[from] toml_parser::ParserError),
     #[error("Could not interpret the toml as a config document")]
     Parser(#[from] ConfigParserError),
+    #[error("Could not interpret the toml as an action")]
+    Action(#[from] ActionParserError),
+    #[error("The input does not contain an action")]
+    MissingAction,
 }
 
 pub fn parse_config<F>(input: &[u8], handle_error: F) -> Option<Config>
@@ -374,8 +486,75 @@ where
     Some(config)
 }
 
+/// Parses an action in the syntax of the `action` field of shortcuts.
+///
+/// Simple commands can also be written without quotes, e.g. `focus-left`.
+pub fn parse_action<F>(input: &str, handle_error: F) -> Option<Action>
+where
+    F: FnOnce(&dyn Error),
+{
+    let input = input.trim();
+    let bare = input.len() > 0
+        && input
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-');
+    let input = match bare {
+        true => format!("action = \"{input}\""),
+        false => format!("action = {input}"),
+    };
+    let cx = Context {
+        input: input.as_bytes(),
+        used: Default::default(),
+    };
+    macro_rules! fatal {
+        ($e:expr) => {{
+            let e = ConfigError::from($e.value);
+            let e = cx.error2($e.span, e);
+            handle_error(&e);
+            return None;
+        }};
+    }
+    let toml = match toml_parser::parse(input.as_bytes(), &cx) {
+        Ok(t) => t,
+        Err(e) => fatal!(e),
+    };
+    let action = match &toml.value {
+        Value::Table(t) => t.get("action"),
+        _ => None,
+    };
+    let Some(action) = action else {
+        handle_error(&cx.error2(toml.span, ConfigError::MissingAction));
+        return None;
+    };
+    match action.parse(&mut ActionParser(&cx)) {
+        Ok(a) => Some(a),
+        Err(e) => fatal!(e),
+    }
+}
+
 #[test]
 fn default_config_parses() {
     let input = include_bytes!("default-config.toml");
     parse_config(input, |_| ()).unwrap();
 }
+
+#[test]
+fn actions_parse() {
+    let actions = [
+        "focus-left",
+        "\"focus-left\"",
+        "{ type = \"exec\", exec = \"alacritty\" }",
+        "[\"split-horizontal\", { type = \"move-to-workspace\", name = \"2\" }]",
+    ];
+    for action in actions {
+        assert!(parse_action(action, |_| ()).is_some(), "{action}");
+    }
+    for action in [
+        "",
+        "unknown-action",
+        "{ type = \"exec\" }",
+        "focus-left = 1",
+    ] {
+        assert!(parse_action(action, |_| ()).is_none(), "{action}");
+    }
+}
@@ -7,7 +7,9 @@ use {
 };
 
 pub mod action;
+mod binding_mode;
 mod color;
+mod color_scheme;
 pub mod config;
 mod connector;
 mod connector_match;
@@ -25,10 +27,15 @@ mod mode;
 pub mod modified_keysym;
 mod output;
 mod output_match;
+pub mod pointer_trigger;
 mod repeat_rate;
 pub mod shortcuts;
 mod status;
 mod theme;
+mod window_rule;
+mod window_rule_match;
+mod workspace;
+mod xwayland;
 
 #[derive(Debug, Error)]
 pub enum StringParserError {
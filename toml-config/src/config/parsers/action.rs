@@ -2,7 +2,7 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{arr, bol, n32, opt, str, val, Extractor, ExtractorError},
+            extractor::{arr, bol, fltorint, n32, opt, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 connector::{ConnectorParser, ConnectorParserError},
@@ -34,6 +34,7 @@ use {
     jay_config::{
         get_workspace,
         Axis::{Horizontal, Vertical},
+        Direction, ResizeAmount,
     },
     thiserror::Error,
 };
@@ -80,6 +81,8 @@ pub enum ActionParserError {
     MoveToOutput(#[source] OutputMatchParserError),
     #[error("Could not parse a set-repeat-rate action")]
     RepeatRate(#[source] RepeatRateParserError),
+    #[error("Exactly one of `px` and `percent` must be specified")]
+    ResizeAmount,
 }
 
 pub struct ActionParser<'a>(pub &'a Context<'a>);
@@ -113,6 +116,17 @@ impl ActionParser<'_> {
             "consume" => Forward(false),
             "enable-window-management" => EnableWindowManagement(true),
             "disable-window-management" => EnableWindowManagement(false),
+            "move-to-scratchpad" => MoveToScratchpad,
+            "toggle-scratchpad" => ToggleScratchpad,
+            "minimize" => Minimize,
+            "restore-minimized" => RestoreMinimized,
+            "exit-mode" => ExitMode,
+            "focus-last" => FocusLast,
+            "focus-next-mru" => FocusNextMru,
+            "focus-prev-mru" => FocusPrevMru,
+            "workspace-next" => WorkspaceNext,
+            "workspace-prev" => WorkspacePrev,
+            "workspace-back-and-forth" => WorkspaceBackAndForth,
             _ => {
                 return Err(ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span))
             }
@@ -303,6 +317,74 @@ impl ActionParser<'_> {
         })
     }
 
+    fn parse_toggle_scratchpad(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let app_id = ext.extract(opt(str("app-id")))?;
+        Ok(Action::ToggleScratchpad {
+            app_id: app_id.despan_into(),
+        })
+    }
+
+    fn parse_resize(
+        &mut self,
+        span: Span,
+        ext: &mut Extractor<'_>,
+        direction: Direction,
+    ) -> ParseResult<Self> {
+        let (px, percent) = ext.extract((opt(s32("px")), opt(fltorint("percent"))))?;
+        let amount = match (px, percent) {
+            (Some(px), None) => ResizeAmount::Px(px.value),
+            (None, Some(percent)) => ResizeAmount::Percent(percent.value),
+            _ => return Err(ActionParserError::ResizeAmount.spanned(span)),
+        };
+        Ok(Action::Resize { direction, amount })
+    }
+
+    fn parse_move_floating(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (dx, dy) = ext.extract((opt(s32("dx")), opt(s32("dy"))))?;
+        Ok(Action::MoveFloating {
+            dx: dx.despan().unwrap_or(0),
+            dy: dy.despan().unwrap_or(0),
+        })
+    }
+
+    fn parse_set_floating_geometry(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (x, y, width, height) =
+            ext.extract((s32("x"), s32("y"), s32("width"), s32("height")))?;
+        Ok(Action::SetFloatingGeometry {
+            x: x.value,
+            y: y.value,
+            width: width.value,
+            height: height.value,
+        })
+    }
+
+    fn parse_enter_mode(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (name, once) = ext.extract((str("name"), opt(bol("once"))))?;
+        Ok(Action::EnterMode {
+            name: name.value.to_string(),
+            once: once.despan().unwrap_or(false),
+        })
+    }
+
+    fn parse_show_adjacent_workspace(
+        &mut self,
+        ext: &mut Extractor<'_>,
+        next: bool,
+    ) -> ParseResult<Self> {
+        let global = ext.extract(opt(bol("global")))?;
+        Ok(Action::ShowAdjacentWorkspace {
+            next,
+            global: global.despan().unwrap_or(false),
+        })
+    }
+
+    fn parse_rename_workspace(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let name = ext.extract(str("name"))?;
+        Ok(Action::RenameWorkspace {
+            name: name.value.to_string(),
+        })
+    }
+
     fn parse_set_repeat_rate(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
         let rate = ext
             .extract(val("rate"))?
@@ -361,6 +443,17 @@ impl<'a> Parser for ActionParser<'a> {
             "configure-idle" => self.parse_configure_idle(&mut ext),
             "move-to-output" => self.parse_move_to_output(&mut ext),
             "set-repeat-rate" => self.parse_set_repeat_rate(&mut ext),
+            "toggle-scratchpad" => self.parse_toggle_scratchpad(&mut ext),
+            "resize-left" => self.parse_resize(span, &mut ext, Direction::Left),
+            "resize-down" => self.parse_resize(span, &mut ext, Direction::Down),
+            "resize-up" => self.parse_resize(span, &mut ext, Direction::Up),
+            "resize-right" => self.parse_resize(span, &mut ext, Direction::Right),
+            "move-floating" => self.parse_move_floating(&mut ext),
+            "set-floating-geometry" => self.parse_set_floating_geometry(&mut ext),
+            "enter-mode" => self.parse_enter_mode(&mut ext),
+            "workspace-next" => self.parse_show_adjacent_workspace(&mut ext, true),
+            "workspace-prev" => self.parse_show_adjacent_workspace(&mut ext, false),
+            "rename-workspace" => self.parse_rename_workspace(&mut ext),
             v => {
                 ext.ignore_unused();
                 return Err(ActionParserError::UnknownType(v.to_string()).spanned(ty.span));
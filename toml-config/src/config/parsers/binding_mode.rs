@@ -0,0 +1,109 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::shortcuts::{ComplexShortcutsParser, ShortcutsParser, ShortcutsParserError},
+            spanned::SpannedErrorExt,
+            BindingMode,
+        },
+        toml::{
+            toml_span::{Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    std::collections::HashSet,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum BindingModeParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Could not parse the shortcuts")]
+    ParseShortcuts(#[source] ShortcutsParserError),
+}
+
+pub struct BindingModeParser<'a> {
+    pub cx: &'a Context<'a>,
+    pub name: &'a str,
+}
+
+impl Parser for BindingModeParser<'_> {
+    type Value = BindingMode;
+    type Error = BindingModeParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.cx, span, table);
+        let (shortcuts_val, complex_shortcuts_val) =
+            ext.extract((opt(val("shortcuts")), opt(val("complex-shortcuts"))))?;
+        let mut used_keys = HashSet::new();
+        let mut shortcuts = vec![];
+        let mut pointer_shortcuts = vec![];
+        if let Some(value) = shortcuts_val {
+            value
+                .parse(&mut ShortcutsParser {
+                    cx: self.cx,
+                    used_keys: &mut used_keys,
+                    shortcuts: &mut shortcuts,
+                    pointer_shortcuts: &mut pointer_shortcuts,
+                })
+                .map_spanned_err(BindingModeParserError::ParseShortcuts)?;
+        }
+        if let Some(value) = complex_shortcuts_val {
+            value
+                .parse(&mut ComplexShortcutsParser {
+                    cx: self.cx,
+                    used_keys: &mut used_keys,
+                    shortcuts: &mut shortcuts,
+                })
+                .map_spanned_err(BindingModeParserError::ParseShortcuts)?;
+        }
+        Ok(BindingMode {
+            name: self.name.to_string(),
+            shortcuts,
+            pointer_shortcuts,
+        })
+    }
+}
+
+pub struct BindingModesParser<'a>(pub &'a Context<'a>);
+
+impl Parser for BindingModesParser<'_> {
+    type Value = Vec<BindingMode>;
+    type Error = BindingModeParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = vec![];
+        for (name, value) in table.iter() {
+            match value.parse(&mut BindingModeParser {
+                cx: self.0,
+                name: &name.value,
+            }) {
+                Ok(m) => res.push(m),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse binding mode {}: {}",
+                        name.value,
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,35 @@
+use {
+    crate::{
+        config::parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        toml::toml_span::{Span, SpannedExt},
+    },
+    jay_config::theme::ColorScheme,
+    thiserror::Error,
+};
+
+pub struct ColorSchemeParser;
+
+#[derive(Debug, Error)]
+pub enum ColorSchemeParserError {
+    #[error(transparent)]
+    DataType(#[from] UnexpectedDataType),
+    #[error("Unknown color scheme {0}")]
+    Unknown(String),
+}
+
+impl Parser for ColorSchemeParser {
+    type Value = ColorScheme;
+    type Error = ColorSchemeParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        use ColorScheme::*;
+        let scheme = match string {
+            "no-preference" => NoPreference,
+            "dark" => Dark,
+            "light" => Light,
+            _ => return Err(ColorSchemeParserError::Unknown(string.to_string()).spanned(span)),
+        };
+        Ok(scheme)
+    }
+}
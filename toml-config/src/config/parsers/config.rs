@@ -6,6 +6,7 @@ use {
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
+                binding_mode::BindingModesParser,
                 connector::ConnectorsParser,
                 drm_device::DrmDevicesParser,
                 drm_device_match::DrmDeviceMatchParser,
@@ -23,6 +24,9 @@ use {
                 },
                 status::StatusParser,
                 theme::ThemeParser,
+                window_rule::WindowRulesParser,
+                workspace::WorkspacesParser,
+                xwayland::XwaylandParser,
             },
             spanned::SpannedErrorExt,
             Action, Config, Theme,
@@ -106,6 +110,10 @@ impl Parser for ConfigParser<'_> {
                 complex_shortcuts_val,
                 focus_follows_mouse,
                 window_management_key_val,
+                window_rules_val,
+                workspaces_val,
+                xwayland_val,
+                binding_modes_val,
             ),
         ) = ext.extract((
             (
@@ -138,6 +146,10 @@ impl Parser for ConfigParser<'_> {
                 opt(val("complex-shortcuts")),
                 recover(opt(bol("focus-follows-mouse"))),
                 recover(opt(str("window-management-key"))),
+                opt(val("window-rules")),
+                opt(val("workspaces")),
+                opt(val("xwayland")),
+                opt(val("modes")),
             ),
         ))?;
         let mut keymap = None;
@@ -154,12 +166,14 @@ impl Parser for ConfigParser<'_> {
         }
         let mut used_keys = HashSet::new();
         let mut shortcuts = vec![];
+        let mut pointer_shortcuts = vec![];
         if let Some(value) = shortcuts_val {
             value
                 .parse(&mut ShortcutsParser {
                     cx: self.0,
                     used_keys: &mut used_keys,
                     shortcuts: &mut shortcuts,
+                    pointer_shortcuts: &mut pointer_shortcuts,
                 })
                 .map_spanned_err(ConfigParserError::ParseShortcuts)?;
         }
@@ -172,9 +186,16 @@ impl Parser for ConfigParser<'_> {
                 })
                 .map_spanned_err(ConfigParserError::ParseShortcuts)?;
         }
-        if shortcuts.is_empty() {
+        if shortcuts.is_empty() && pointer_shortcuts.is_empty() {
             log::warn!("Config defines no shortcuts");
         }
+        let mut binding_modes = vec![];
+        if let Some(value) = binding_modes_val {
+            match value.parse(&mut BindingModesParser(self.0)) {
+                Ok(v) => binding_modes = v,
+                Err(e) => log::warn!("Could not parse the binding modes: {}", self.0.error(e)),
+            }
+        }
         let on_graphics_initialized =
             self.parse_action("on-graphics-initialized", on_graphics_init_val);
         let on_idle = self.parse_action("on-idle", on_idle_val);
@@ -302,10 +323,39 @@ impl Parser for ConfigParser<'_> {
                 window_management_key = Some(key);
             }
         }
+        let mut window_rules = vec![];
+        if let Some(value) = window_rules_val {
+            match value.parse(&mut WindowRulesParser(self.0)) {
+                Ok(v) => window_rules = v,
+                Err(e) => {
+                    log::warn!("Could not parse the window rules: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut workspaces = vec![];
+        if let Some(value) = workspaces_val {
+            match value.parse(&mut WorkspacesParser(self.0)) {
+                Ok(v) => workspaces = v,
+                Err(e) => {
+                    log::warn!("Could not parse the workspaces: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut xwayland = None;
+        if let Some(value) = xwayland_val {
+            match value.parse(&mut XwaylandParser(self.0)) {
+                Ok(v) => xwayland = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the Xwayland settings: {}", self.0.error(e));
+                }
+            }
+        }
         Ok(Config {
             keymap,
             repeat_rate,
             shortcuts,
+            pointer_shortcuts,
+            binding_modes,
             on_graphics_initialized,
             on_idle,
             status,
@@ -326,6 +376,9 @@ impl Parser for ConfigParser<'_> {
             idle,
             focus_follows_mouse: focus_follows_mouse.despan().unwrap_or(true),
             window_management_key,
+            window_rules,
+            workspaces,
+            xwayland,
         })
     }
 }
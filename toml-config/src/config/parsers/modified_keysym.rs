@@ -87,7 +87,7 @@ impl Parser for ModifiersParser {
     }
 }
 
-fn parse_mod(part: &str) -> Option<Modifiers> {
+pub fn parse_mod(part: &str) -> Option<Modifiers> {
     let modifier = match part {
         "shift" => SHIFT,
         "lock" => LOCK,
@@ -2,7 +2,7 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{fltorint, opt, recover, s32, str, val, Extractor, ExtractorError},
+            extractor::{bol, fltorint, opt, recover, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 mode::ModeParser,
@@ -16,7 +16,7 @@ use {
         },
     },
     indexmap::IndexMap,
-    jay_config::video::Transform,
+    jay_config::video::{Transform, VrrMode},
     thiserror::Error,
 };
 
@@ -46,7 +46,7 @@ impl<'a> Parser for OutputParser<'a> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.cx, span, table);
-        let (name, match_val, x, y, scale, transform, mode) = ext.extract((
+        let (name, match_val, x, y, scale, transform, mode, tearing, vrr) = ext.extract((
             opt(str("name")),
             val("match"),
             recover(opt(s32("x"))),
@@ -54,6 +54,8 @@ impl<'a> Parser for OutputParser<'a> {
             recover(opt(fltorint("scale"))),
             recover(opt(str("transform"))),
             opt(val("mode")),
+            recover(opt(bol("tearing"))),
+            recover(opt(str("vrr"))),
         ))?;
         let transform = match transform {
             None => None,
@@ -72,6 +74,19 @@ impl<'a> Parser for OutputParser<'a> {
                 }
             },
         };
+        let vrr = match vrr {
+            None => None,
+            Some(v) => match v.value {
+                "never" => Some(VrrMode::Never),
+                "always" => Some(VrrMode::Always),
+                "fullscreen-only" => Some(VrrMode::FullscreenOnly),
+                "fullscreen-with-content-type-game" => Some(VrrMode::FullscreenWithContentTypeGame),
+                _ => {
+                    log::warn!("Unknown VRR mode {}: {}", v.value, self.cx.error3(v.span));
+                    None
+                }
+            },
+        };
         let mode = match mode {
             Some(mode) => match mode.parse(&mut ModeParser(self.cx)) {
                 Ok(m) => Some(m),
@@ -104,6 +119,8 @@ impl<'a> Parser for OutputParser<'a> {
             scale: scale.despan(),
             transform,
             mode,
+            tearing: tearing.despan(),
+            vrr,
         })
     }
 }
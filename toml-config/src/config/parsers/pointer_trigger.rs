@@ -0,0 +1,112 @@
+use {
+    crate::{
+        config::{
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::modified_keysym::parse_mod,
+        },
+        toml::toml_span::{Span, SpannedExt},
+    },
+    jay_config::{
+        input::pointer::{
+            Button, PinchDirection, PointerTrigger, BTN_BACK, BTN_EXTRA, BTN_FORWARD, BTN_LEFT,
+            BTN_MIDDLE, BTN_RIGHT, BTN_SIDE, BTN_TASK,
+        },
+        keyboard::mods::{Modifiers, RELEASE},
+        Direction,
+    },
+    thiserror::Error,
+};
+
+const TRIGGERS: [&str; 5] = ["button", "scroll", "swipe", "pinch", "hold"];
+
+#[derive(Debug, Error)]
+pub enum PointerTriggerParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown modifier {0}")]
+    UnknownModifier(String),
+    #[error("Unknown pointer trigger {0}")]
+    UnknownTrigger(String),
+}
+
+/// Returns whether the string describes a pointer trigger instead of a key.
+pub fn is_pointer_trigger(string: &str) -> bool {
+    string.split("-").any(|p| TRIGGERS.contains(&p))
+}
+
+pub struct PointerTriggerParser;
+
+impl Parser for PointerTriggerParser {
+    type Value = (Modifiers, PointerTrigger);
+    type Error = PointerTriggerParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let parts: Vec<_> = string.split("-").collect();
+        let idx = parts
+            .iter()
+            .position(|p| TRIGGERS.contains(p))
+            .unwrap_or(parts.len());
+        let mut modifiers = Modifiers(0);
+        for part in &parts[..idx] {
+            let Some(modifier) = parse_mod(part) else {
+                return Err(
+                    PointerTriggerParserError::UnknownModifier(part.to_string()).spanned(span)
+                );
+            };
+            modifiers |= modifier;
+        }
+        let direction = |d: &str| match d {
+            "left" => Some(Direction::Left),
+            "down" => Some(Direction::Down),
+            "up" => Some(Direction::Up),
+            "right" => Some(Direction::Right),
+            _ => None,
+        };
+        let fingers = |f: &str| f.parse::<u32>().ok();
+        let trigger = match &parts[idx..] {
+            ["button", b] => {
+                let button = match *b {
+                    "left" => Some(BTN_LEFT),
+                    "right" => Some(BTN_RIGHT),
+                    "middle" => Some(BTN_MIDDLE),
+                    "side" => Some(BTN_SIDE),
+                    "extra" => Some(BTN_EXTRA),
+                    "forward" => Some(BTN_FORWARD),
+                    "back" => Some(BTN_BACK),
+                    "task" => Some(BTN_TASK),
+                    _ => b.parse().ok().map(Button),
+                };
+                button.map(PointerTrigger::Button)
+            }
+            ["scroll", d] => direction(d).map(PointerTrigger::Scroll),
+            ["swipe", f, d] => match (fingers(f), direction(d)) {
+                (Some(fingers), Some(direction)) => {
+                    Some(PointerTrigger::Swipe { fingers, direction })
+                }
+                _ => None,
+            },
+            ["pinch", f, d] => {
+                let direction = match *d {
+                    "in" => Some(PinchDirection::In),
+                    "out" => Some(PinchDirection::Out),
+                    _ => None,
+                };
+                match (fingers(f), direction) {
+                    (Some(fingers), Some(direction)) => {
+                        Some(PointerTrigger::Pinch { fingers, direction })
+                    }
+                    _ => None,
+                }
+            }
+            ["hold", f] => fingers(f).map(|fingers| PointerTrigger::Hold { fingers }),
+            _ => None,
+        };
+        match trigger {
+            Some(t) => Ok((Modifiers(modifiers.0 & !RELEASE.0), t)),
+            None => {
+                Err(PointerTriggerParserError::UnknownTrigger(parts[idx..].join("-")).spanned(span))
+            }
+        }
+    }
+}
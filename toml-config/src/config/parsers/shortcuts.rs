@@ -9,9 +9,10 @@ use {
                 modified_keysym::{
                     ModifiedKeysymParser, ModifiedKeysymParserError, ModifiersParser,
                 },
+                pointer_trigger::{is_pointer_trigger, PointerTriggerParser},
             },
             spanned::SpannedErrorExt,
-            Action, Shortcut, SimpleCommand,
+            Action, PointerShortcut, Shortcut, SimpleCommand,
         },
         toml::{
             toml_span::{Span, Spanned, SpannedExt},
@@ -19,7 +20,10 @@ use {
         },
     },
     indexmap::IndexMap,
-    jay_config::keyboard::{mods::Modifiers, ModifiedKeySym},
+    jay_config::{
+        input::pointer::PointerTrigger,
+        keyboard::{mods::Modifiers, ModifiedKeySym},
+    },
     std::collections::HashSet,
     thiserror::Error,
 };
@@ -42,6 +46,7 @@ pub struct ShortcutsParser<'a, 'b> {
     pub cx: &'a Context<'a>,
     pub used_keys: &'b mut HashSet<Spanned<ModifiedKeySym>>,
     pub shortcuts: &'b mut Vec<Shortcut>,
+    pub pointer_shortcuts: &'b mut Vec<PointerShortcut>,
 }
 
 impl Parser for ShortcutsParser<'_, '_> {
@@ -55,6 +60,20 @@ impl Parser for ShortcutsParser<'_, '_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         for (key, value) in table.iter() {
+            if is_pointer_trigger(&key.value) {
+                let Some((mods, trigger)) = parse_pointer_trigger(self.cx, key) else {
+                    continue;
+                };
+                let Some(action) = parse_action(self.cx, &key.value, value) else {
+                    continue;
+                };
+                self.pointer_shortcuts.push(PointerShortcut {
+                    mods,
+                    trigger,
+                    action,
+                });
+                continue;
+            }
             let Some(keysym) = parse_modified_keysym(self.cx, key) else {
                 continue;
             };
@@ -192,6 +211,23 @@ pub fn parse_modified_keysym_str(
     }
 }
 
+fn parse_pointer_trigger(
+    cx: &Context<'_>,
+    key: &Spanned<String>,
+) -> Option<(Modifiers, PointerTrigger)> {
+    match PointerTriggerParser.parse_string(key.span, &key.value) {
+        Ok(t) => Some(t),
+        Err(e) => {
+            log::warn!(
+                "Could not parse pointer trigger {}: {}",
+                key.value,
+                cx.error(e)
+            );
+            None
+        }
+    }
+}
+
 fn log_used(
     cx: &Context<'_>,
     used: &mut HashSet<Spanned<ModifiedKeySym>>,
@@ -2,9 +2,9 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{opt, recover, s32, str, val, Extractor, ExtractorError},
+            extractor::{bol, opt, recover, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
-            parsers::color::ColorParser,
+            parsers::{color::ColorParser, color_scheme::ColorSchemeParser},
             Theme,
         },
         toml::{
@@ -60,6 +60,7 @@ impl Parser for ThemeParser<'_> {
                 title_height,
                 font,
             ),
+            (inner_gap, outer_gap, smart_gaps, color_scheme),
         ) = ext.extract((
             (
                 opt(val("attention-requested-bg-color")),
@@ -83,6 +84,12 @@ impl Parser for ThemeParser<'_> {
                 recover(opt(s32("title-height"))),
                 recover(opt(str("font"))),
             ),
+            (
+                recover(opt(s32("inner-gap"))),
+                recover(opt(s32("outer-gap"))),
+                recover(opt(bol("smart-gaps"))),
+                opt(val("color-scheme")),
+            ),
         ))?;
         macro_rules! color {
             ($e:expr) => {
@@ -98,6 +105,15 @@ impl Parser for ThemeParser<'_> {
                 }
             };
         }
+        let mut scheme = None;
+        if let Some(value) = color_scheme {
+            match value.parse(&mut ColorSchemeParser) {
+                Ok(v) => scheme = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the color scheme: {}", self.0.error(e));
+                }
+            }
+        }
         Ok(Theme {
             attention_requested_bg_color: color!(attention_requested_bg_color),
             bg_color: color!(bg_color),
@@ -116,7 +132,11 @@ impl Parser for ThemeParser<'_> {
             highlight_color: color!(highlight_color),
             border_width: border_width.despan(),
             title_height: title_height.despan(),
+            inner_gap: inner_gap.despan(),
+            outer_gap: outer_gap.despan(),
+            smart_gaps: smart_gaps.despan(),
             font: font.map(|f| f.value.to_string()),
+            color_scheme: scheme,
         })
     }
 }
@@ -0,0 +1,118 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, fltorint, opt, recover, s32, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::window_rule_match::{WindowMatchParser, WindowMatchParserError},
+            WindowRule,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowRuleParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    Match(#[from] WindowMatchParserError),
+}
+
+pub struct WindowRuleParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WindowRuleParser<'a> {
+    type Value = WindowRule;
+    type Error = WindowRuleParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            (match_val, workspace, output, floating, x, y, width, height, fullscreen, opacity),
+            (reevaluate,),
+        ) = ext.extract((
+            (
+                val("match"),
+                recover(opt(str("workspace"))),
+                recover(opt(str("output"))),
+                recover(opt(bol("floating"))),
+                recover(opt(s32("x"))),
+                recover(opt(s32("y"))),
+                recover(opt(s32("width"))),
+                recover(opt(s32("height"))),
+                recover(opt(bol("fullscreen"))),
+                recover(opt(fltorint("opacity"))),
+            ),
+            (recover(opt(bol("reevaluate"))),),
+        ))?;
+        let pair = |a: Option<Spanned<i32>>, b: Option<Spanned<i32>>, names: &str| match (a, b) {
+            (Some(a), Some(b)) => Some((a.value, b.value)),
+            (None, None) => None,
+            (Some(v), None) | (None, Some(v)) => {
+                log::warn!(
+                    "Window rules must specify either both or none of {names}: {}",
+                    self.0.error3(v.span)
+                );
+                None
+            }
+        };
+        Ok(WindowRule {
+            match_: match_val.parse_map(&mut WindowMatchParser(self.0))?,
+            workspace: workspace.despan_into(),
+            output: output.despan_into(),
+            floating: floating.despan(),
+            position: pair(x, y, "`x` and `y`"),
+            size: pair(width, height, "`width` and `height`"),
+            fullscreen: fullscreen.despan(),
+            opacity: opacity.despan().map(|o| o as f32),
+            reevaluate: reevaluate.despan().unwrap_or(false),
+        })
+    }
+}
+
+pub struct WindowRulesParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WindowRulesParser<'a> {
+    type Value = Vec<WindowRule>;
+    type Error = WindowRuleParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table, DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut WindowRuleParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse window rule: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        log::warn!(
+            "`window-rules` value should be an array: {}",
+            self.0.error3(span)
+        );
+        WindowRuleParser(self.0)
+            .parse_table(span, table)
+            .map(|v| vec![v])
+    }
+}
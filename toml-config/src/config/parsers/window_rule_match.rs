@@ -0,0 +1,77 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, s32, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            WindowMatch,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::window::WindowType,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowMatchParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown window type {0}")]
+    UnknownWindowType(String),
+}
+
+pub struct WindowMatchParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WindowMatchParser<'a> {
+    type Value = WindowMatch;
+    type Error = WindowMatchParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (app_id, title, class, instance, role, pid, sandbox_engine, window_type) = ext
+            .extract((
+                opt(str("app-id")),
+                opt(str("title")),
+                opt(str("class")),
+                opt(str("instance")),
+                opt(str("role")),
+                opt(s32("pid")),
+                opt(str("sandbox-engine")),
+                opt(str("window-type")),
+            ))?;
+        let window_type = match window_type {
+            None => None,
+            Some(t) => match t.value {
+                "xdg-toplevel" => Some(WindowType::XdgToplevel),
+                "x-window" => Some(WindowType::XWindow),
+                _ => {
+                    return Err(
+                        WindowMatchParserError::UnknownWindowType(t.value.to_string())
+                            .spanned(t.span),
+                    )
+                }
+            },
+        };
+        Ok(WindowMatch {
+            app_id: app_id.despan_into(),
+            title: title.despan_into(),
+            class: class.despan_into(),
+            instance: instance.despan_into(),
+            role: role.despan_into(),
+            pid: pid.despan(),
+            sandbox_engine: sandbox_engine.despan_into(),
+            window_type,
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, opt, recover, s32, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::connector_match::ConnectorMatchParser,
+            ConfigWorkspace,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WorkspaceParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct WorkspaceParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WorkspaceParser<'a> {
+    type Value = ConfigWorkspace;
+    type Error = WorkspaceParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (name, inner_gap, outer_gap, smart_gaps, connector) = ext.extract((
+            str("name"),
+            recover(opt(s32("inner-gap"))),
+            recover(opt(s32("outer-gap"))),
+            recover(opt(bol("smart-gaps"))),
+            opt(val("connector")),
+        ))?;
+        let connector = match connector {
+            Some(c) => match c.parse(&mut ConnectorMatchParser(self.0)) {
+                Ok(c) => Some(c),
+                Err(e) => {
+                    log::warn!("Could not parse connector match: {}", self.0.error(e));
+                    None
+                }
+            },
+            None => None,
+        };
+        Ok(ConfigWorkspace {
+            name: name.value.to_string(),
+            inner_gap: inner_gap.despan(),
+            outer_gap: outer_gap.despan(),
+            smart_gaps: smart_gaps.despan(),
+            connector,
+        })
+    }
+}
+
+pub struct WorkspacesParser<'a>(pub &'a Context<'a>);
+
+impl<'a> Parser for WorkspacesParser<'a> {
+    type Value = Vec<ConfigWorkspace>;
+    type Error = WorkspaceParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table, DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut WorkspaceParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse workspace: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        log::warn!(
+            "`workspaces` value should be an array: {}",
+            self.0.error3(span)
+        );
+        WorkspaceParser(self.0)
+            .parse_table(span, table)
+            .map(|v| vec![v])
+    }
+}
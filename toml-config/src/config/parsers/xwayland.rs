@@ -0,0 +1,56 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            Xwayland,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::xwayland::XScalingMode,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum XwaylandParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown scaling mode {0}")]
+    UnknownScalingMode(String),
+}
+
+pub struct XwaylandParser<'a>(pub &'a Context<'a>);
+
+impl Parser for XwaylandParser<'_> {
+    type Value = Xwayland;
+    type Error = XwaylandParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (scaling_mode,) = ext.extract((opt(str("scaling-mode")),))?;
+        let scaling_mode = match scaling_mode {
+            None => None,
+            Some(m) => match m.value {
+                "default" => Some(XScalingMode::Default),
+                "downscaled" => Some(XScalingMode::Downscaled),
+                _ => {
+                    return Err(XwaylandParserError::UnknownScalingMode(m.value.to_string())
+                        .spanned(m.span))
+                }
+            },
+        };
+        Ok(Xwayland { scaling_mode })
+    }
+}
@@ -5,9 +5,9 @@ mod toml;
 
 use {
     crate::config::{
-        parse_config, Action, Config, ConfigConnector, ConfigDrmDevice, ConfigKeymap,
-        ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch, Output, OutputMatch, Shortcut,
-        SimpleCommand, Status, Theme,
+        parse_action, parse_config, Action, Config, ConfigConnector, ConfigDrmDevice, ConfigKeymap,
+        ConfigWorkspace, ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch, Output,
+        OutputMatch, PointerShortcut, Shortcut, SimpleCommand, Status, Theme, WindowRule,
     },
     ahash::{AHashMap, AHashSet},
     error_reporter::Report,
@@ -16,22 +16,28 @@ use {
         exec::{set_env, unset_env, Command},
         get_workspace,
         input::{
-            capability::CAP_SWITCH, get_seat, input_devices, on_input_device_removed,
-            on_new_input_device, FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent,
+            capability::CAP_SWITCH, get_binding_mode, get_seat, input_devices,
+            on_input_device_removed, on_new_input_device, pointer::PointerTrigger, BindingMode,
+            FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent,
         },
         is_reload,
-        keyboard::{Keymap, ModifiedKeySym},
+        keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
         logging::set_log_level,
-        on_devices_enumerated, on_idle, quit, reload, set_default_workspace_capture,
-        set_explicit_sync_enabled, set_idle,
+        on_devices_enumerated, on_idle, on_ipc_command, quit, reload,
+        set_default_workspace_capture, set_explicit_sync_enabled, set_idle,
         status::{set_i3bar_separator, set_status, set_status_command, unset_status_command},
         switch_to_vt,
-        theme::{reset_colors, reset_font, reset_sizes, set_font},
+        theme::{
+            reset_colors, reset_font, reset_sizes, set_color_scheme, set_font, set_smart_gaps,
+            ColorScheme,
+        },
         video::{
             connectors, drm_devices, on_connector_connected, on_connector_disconnected,
             on_graphics_initialized, on_new_connector, on_new_drm_device,
             set_direct_scanout_enabled, set_gfx_api, Connector, DrmDevice,
         },
+        window::clear_window_rules,
+        xwayland::set_x_scaling_mode,
     },
     std::{cell::RefCell, io::ErrorKind, path::PathBuf, rc::Rc},
 };
@@ -92,6 +98,23 @@ impl Action {
                 SimpleCommand::EnableWindowManagement(bool) => {
                     B::new(move || s.set_window_management_enabled(bool))
                 }
+                SimpleCommand::MoveToScratchpad => B::new(move || s.move_to_scratchpad()),
+                SimpleCommand::ToggleScratchpad => B::new(move || s.toggle_scratchpad(None)),
+                SimpleCommand::Minimize => B::new(move || s.minimize()),
+                SimpleCommand::RestoreMinimized => B::new(move || s.restore_minimized()),
+                SimpleCommand::ExitMode => B::new(move || {
+                    if let Some(mode) = s.binding_mode() {
+                        mode.deactivate(s);
+                    }
+                }),
+                SimpleCommand::FocusLast => B::new(move || s.focus_last()),
+                SimpleCommand::FocusNextMru => B::new(move || s.focus_next_mru()),
+                SimpleCommand::FocusPrevMru => B::new(move || s.focus_prev_mru()),
+                SimpleCommand::WorkspaceNext => B::new(move || s.show_next_workspace(false)),
+                SimpleCommand::WorkspacePrev => B::new(move || s.show_prev_workspace(false)),
+                SimpleCommand::WorkspaceBackAndForth => {
+                    B::new(move || s.workspace_back_and_forth())
+                }
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -103,6 +126,9 @@ impl Action {
             }
             Action::Exec { exec } => B::new(move || create_command(&exec).spawn()),
             Action::SwitchToVt { num } => B::new(move || switch_to_vt(num)),
+            Action::ToggleScratchpad { app_id } => {
+                B::new(move || s.toggle_scratchpad(app_id.as_deref()))
+            }
             Action::ShowWorkspace { name } => {
                 let workspace = get_workspace(&name);
                 B::new(move || s.show_workspace(workspace))
@@ -206,6 +232,26 @@ impl Action {
             Action::SetRepeatRate { rate } => {
                 B::new(move || s.set_repeat_rate(rate.rate, rate.delay))
             }
+            Action::Resize { direction, amount } => B::new(move || s.resize(direction, amount)),
+            Action::MoveFloating { dx, dy } => B::new(move || s.move_floating(dx, dy)),
+            Action::SetFloatingGeometry {
+                x,
+                y,
+                width,
+                height,
+            } => B::new(move || s.set_floating_geometry(x, y, width, height)),
+            Action::EnterMode { name, once } => {
+                let mode = get_binding_mode(&name);
+                B::new(move || match once {
+                    true => mode.activate_once(s),
+                    false => mode.activate(s),
+                })
+            }
+            Action::ShowAdjacentWorkspace { next, global } => B::new(move || match next {
+                true => s.show_next_workspace(global),
+                false => s.show_prev_workspace(global),
+            }),
+            Action::RenameWorkspace { name } => B::new(move || s.get_workspace().rename(&name)),
         }
     }
 }
@@ -519,6 +565,17 @@ impl ConnectorMatch {
     }
 }
 
+impl ConfigWorkspace {
+    fn apply_connector(&self) {
+        let Some(m) = &self.connector else {
+            return;
+        };
+        if let Some(c) = connectors().into_iter().find(|c| m.matches(*c)) {
+            get_workspace(&self.name).set_preferred_connector(Some(c));
+        }
+    }
+}
+
 impl ConfigConnector {
     fn apply(&self, c: Connector) {
         c.set_enabled(self.enabled);
@@ -537,6 +594,12 @@ impl Output {
         if let Some(transform) = self.transform {
             c.set_transform(transform);
         }
+        if let Some(tearing) = self.tearing {
+            c.set_tearing_enabled(tearing);
+        }
+        if let Some(vrr) = self.vrr {
+            c.set_vrr_mode(vrr);
+        }
         if let Some(mode) = &self.mode {
             let modes = c.modes();
             let m = modes.iter().find(|m| {
@@ -582,13 +645,32 @@ type SwitchActions = Vec<(InputMatch, AHashMap<SwitchEvent, Box<dyn Fn()>>)>;
 
 impl State {
     fn unbind_all(&self) {
+        let seat = self.persistent.seat;
+        if let Some(mode) = seat.binding_mode() {
+            mode.deactivate(seat);
+        }
         let mut binds = self.persistent.binds.borrow_mut();
-        for bind in binds.drain() {
-            self.persistent.seat.unbind(bind);
+        for (mode, bind) in binds.drain() {
+            match mode {
+                None => seat.unbind(bind),
+                Some(mode) => mode.unbind(seat, bind),
+            }
+        }
+        let mut pointer_binds = self.persistent.pointer_binds.borrow_mut();
+        for (mode, mods, trigger) in pointer_binds.drain() {
+            match mode {
+                None => seat.unbind_pointer(mods, trigger),
+                Some(mode) => mode.unbind_pointer(seat, mods, trigger),
+            }
         }
     }
 
-    fn apply_shortcuts(self: &Rc<Self>, shortcuts: impl IntoIterator<Item = Shortcut>) {
+    fn apply_shortcuts(
+        self: &Rc<Self>,
+        mode: Option<BindingMode>,
+        shortcuts: impl IntoIterator<Item = Shortcut>,
+    ) {
+        let seat = self.persistent.seat;
         let mut binds = self.persistent.binds.borrow_mut();
         for shortcut in shortcuts {
             if let Action::SimpleCommand {
@@ -596,25 +678,61 @@ impl State {
             } = shortcut.action
             {
                 if shortcut.latch.is_none() {
-                    self.persistent.seat.unbind(shortcut.keysym);
-                    binds.remove(&shortcut.keysym);
+                    match mode {
+                        None => seat.unbind(shortcut.keysym),
+                        Some(mode) => mode.unbind(seat, shortcut.keysym),
+                    }
+                    binds.remove(&(mode, shortcut.keysym));
                     continue;
                 }
             }
             let mut f = shortcut.action.into_fn(self);
             if let Some(l) = shortcut.latch {
                 let l = l.into_rc_fn(self);
-                let s = self.persistent.seat;
                 f = Box::new(move || {
                     f();
                     let l = l.clone();
-                    s.latch(move || l());
+                    seat.latch(move || l());
                 });
             }
-            self.persistent
-                .seat
-                .bind_masked(shortcut.mask, shortcut.keysym, f);
-            binds.insert(shortcut.keysym);
+            match mode {
+                None => seat.bind_masked(shortcut.mask, shortcut.keysym, f),
+                Some(mode) => mode.bind_masked(seat, shortcut.mask, shortcut.keysym, f),
+            }
+            binds.insert((mode, shortcut.keysym));
+        }
+    }
+
+    fn apply_pointer_shortcuts(
+        self: &Rc<Self>,
+        mode: Option<BindingMode>,
+        shortcuts: impl IntoIterator<Item = PointerShortcut>,
+    ) {
+        let seat = self.persistent.seat;
+        let mut binds = self.persistent.pointer_binds.borrow_mut();
+        for shortcut in shortcuts {
+            let PointerShortcut {
+                mods,
+                trigger,
+                action,
+            } = shortcut;
+            if let Action::SimpleCommand {
+                cmd: SimpleCommand::None,
+            } = action
+            {
+                match mode {
+                    None => seat.unbind_pointer(mods, trigger),
+                    Some(mode) => mode.unbind_pointer(seat, mods, trigger),
+                }
+                binds.remove(&(mode, mods, trigger));
+                continue;
+            }
+            let f = action.into_fn(self);
+            match mode {
+                None => seat.bind_pointer(mods, trigger, f),
+                Some(mode) => mode.bind_pointer(seat, mods, trigger, f),
+            }
+            binds.insert((mode, mods, trigger));
         }
     }
 
@@ -698,9 +816,17 @@ impl State {
         }
         size!(BORDER_WIDTH, border_width);
         size!(TITLE_HEIGHT, title_height);
+        size!(INNER_GAP, inner_gap);
+        size!(OUTER_GAP, outer_gap);
+        if let Some(smart_gaps) = theme.smart_gaps {
+            set_smart_gaps(smart_gaps);
+        }
         if let Some(font) = &theme.font {
             set_font(font);
         }
+        if let Some(scheme) = theme.color_scheme {
+            set_color_scheme(scheme);
+        }
     }
 
     fn handle_switch_device(self: &Rc<Self>, dev: InputDevice, actions: &Rc<SwitchActions>) {
@@ -786,7 +912,8 @@ struct PersistentState {
     seen_outputs: RefCell<AHashSet<OutputId>>,
     default: Config,
     seat: Seat,
-    binds: RefCell<AHashSet<ModifiedKeySym>>,
+    binds: RefCell<AHashSet<(Option<BindingMode>, ModifiedKeySym)>>,
+    pointer_binds: RefCell<AHashSet<(Option<BindingMode>, Modifiers, PointerTrigger)>>,
 }
 
 fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
@@ -886,8 +1013,28 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         None => on_idle(|| ()),
         Some(a) => on_idle(a.into_fn(&state)),
     }
+    on_ipc_command({
+        let state = state.clone();
+        move |command| {
+            let mut error = None;
+            let action = parse_action(command, |e| error = Some(Report::new(e).to_string()));
+            match action {
+                Some(action) => {
+                    action.into_fn(&state)();
+                    Ok(())
+                }
+                None => Err(error.unwrap_or_default()),
+            }
+        }
+    });
     state.unbind_all();
-    state.apply_shortcuts(config.shortcuts);
+    state.apply_shortcuts(None, config.shortcuts);
+    state.apply_pointer_shortcuts(None, config.pointer_shortcuts);
+    for mode in config.binding_modes {
+        let binding_mode = get_binding_mode(&mode.name);
+        state.apply_shortcuts(Some(binding_mode), mode.shortcuts);
+        state.apply_pointer_shortcuts(Some(binding_mode), mode.pointer_shortcuts);
+    }
     if let Some(keymap) = config.keymap {
         state.set_keymap(&keymap);
     }
@@ -896,10 +1043,18 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             .seat
             .set_repeat_rate(repeat_rate.rate, repeat_rate.delay);
     }
-    on_new_connector(move |c| {
-        for connector in &config.connectors {
-            if connector.match_.matches(c) {
-                connector.apply(c);
+    on_new_connector({
+        let workspaces = config.workspaces.clone();
+        move |c| {
+            for connector in &config.connectors {
+                if connector.match_.matches(c) {
+                    connector.apply(c);
+                }
+            }
+            for ws in &workspaces {
+                if matches!(&ws.connector, Some(m) if m.matches(c)) {
+                    ws.apply_connector();
+                }
             }
         }
     });
@@ -959,6 +1114,8 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     reset_colors();
     reset_font();
     reset_sizes();
+    set_smart_gaps(false);
+    set_color_scheme(ColorScheme::NoPreference);
     state.apply_theme(&config.theme);
     if let Some(api) = config.gfx_api {
         set_gfx_api(api);
@@ -969,6 +1126,8 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     if let Some(ese) = config.explicit_sync_enabled {
         set_explicit_sync_enabled(ese);
     }
+    let x_scaling_mode = config.xwayland.as_ref().and_then(|x| x.scaling_mode);
+    set_x_scaling_mode(x_scaling_mode.unwrap_or_default());
     on_new_drm_device({
         let state = state.clone();
         move |d| {
@@ -1017,6 +1176,69 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             .seat
             .set_window_management_key(window_management_key);
     }
+    clear_window_rules();
+    for rule in &config.window_rules {
+        create_window_rule(rule).add();
+    }
+    for ws in &config.workspaces {
+        let workspace = get_workspace(&ws.name);
+        workspace.set_inner_gap(ws.inner_gap);
+        workspace.set_outer_gap(ws.outer_gap);
+        workspace.set_smart_gaps(ws.smart_gaps);
+        ws.apply_connector();
+    }
+}
+
+fn create_window_rule(rule: &WindowRule) -> jay_config::window::WindowRule {
+    let mut res = jay_config::window::WindowRule::new();
+    let m = &rule.match_;
+    if let Some(app_id) = &m.app_id {
+        res.app_id(app_id);
+    }
+    if let Some(title) = &m.title {
+        res.title(title);
+    }
+    if let Some(class) = &m.class {
+        res.class(class);
+    }
+    if let Some(instance) = &m.instance {
+        res.instance(instance);
+    }
+    if let Some(role) = &m.role {
+        res.role(role);
+    }
+    if let Some(pid) = m.pid {
+        res.pid(pid);
+    }
+    if let Some(engine) = &m.sandbox_engine {
+        res.sandbox_engine(engine);
+    }
+    if let Some(ty) = m.window_type {
+        res.window_type(ty);
+    }
+    if let Some(ws) = &rule.workspace {
+        res.workspace(get_workspace(ws));
+    }
+    if let Some(output) = &rule.output {
+        res.output(output);
+    }
+    if let Some(floating) = rule.floating {
+        res.floating(floating);
+    }
+    if let Some((x, y)) = rule.position {
+        res.floating_position(x, y);
+    }
+    if let Some((width, height)) = rule.size {
+        res.floating_size(width, height);
+    }
+    if let Some(fullscreen) = rule.fullscreen {
+        res.fullscreen(fullscreen);
+    }
+    if let Some(opacity) = rule.opacity {
+        res.opacity(opacity);
+    }
+    res.reevaluate(rule.reevaluate);
+    res
 }
 
 fn create_command(exec: &Exec) -> Command {
@@ -1044,6 +1266,7 @@ pub fn configure() {
         default: default.unwrap(),
         seat: default_seat(),
         binds: Default::default(),
+        pointer_binds: Default::default(),
     });
     load_config(true, &persistent);
 }